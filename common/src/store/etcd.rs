@@ -14,10 +14,32 @@ use etcd_client::{
     Client, Compare, CompareOp, EventType, GetOptions, KeyValue, SortOrder, SortTarget, Txn, TxnOp,
     WatchStream, Watcher,
 };
+use lazy_static::lazy_static;
+use prometheus::{register_histogram_vec, HistogramVec};
 use serde_json::Value;
 use snafu::ResultExt;
+use std::future::Future;
 use tokio::sync::mpsc::{channel, Receiver, Sender};
 
+lazy_static! {
+    static ref STORE_OP_DURATION: HistogramVec = register_histogram_vec!(
+        "store_operation_duration_seconds",
+        "Latency of the etcd store operations",
+        &["operation"]
+    )
+    .expect("store operation duration metric must be valid");
+}
+
+/// Await the given etcd `operation` request, recording how long it took to complete.
+async fn timed<F: Future<Output = O>, O>(operation: &str, request: F) -> O {
+    let start = std::time::Instant::now();
+    let result = request.await;
+    STORE_OP_DURATION
+        .with_label_values(&[operation])
+        .observe(start.elapsed().as_secs_f64());
+    result
+}
+
 /// etcd client
 #[derive(Clone)]
 pub struct Etcd {
@@ -102,14 +124,15 @@ impl Store for Etcd {
         if let Some((lease_id, lock_key)) = self.lease_lock()? {
             let cmp = Compare::lease(lock_key.clone(), CompareOp::Equal, lease_id);
             let put = TxnOp::put(key.to_string(), vec_value, None);
-            let resp = self
-                .client
-                .txn(Txn::new().when([cmp]).and_then([put]))
-                .await
-                .context(Put {
-                    key: key.to_string(),
-                    value: serde_json::to_string(value).context(SerialiseValue)?,
-                })?;
+            let resp = timed(
+                "put",
+                self.client.txn(Txn::new().when([cmp]).and_then([put])),
+            )
+            .await
+            .context(Put {
+                key: key.to_string(),
+                value: serde_json::to_string(value).context(SerialiseValue)?,
+            })?;
             if !resp.succeeded() {
                 return Err(StoreError::FailedLock {
                     reason: format!(
@@ -119,8 +142,7 @@ impl Store for Etcd {
                 });
             }
         } else {
-            self.client
-                .put(key.to_string(), vec_value, None)
+            timed("put", self.client.put(key.to_string(), vec_value, None))
                 .await
                 .context(Put {
                     key: key.to_string(),
@@ -133,9 +155,11 @@ impl Store for Etcd {
 
     /// 'Get' the value for the given key from etcd.
     async fn get_kv<K: StoreKey>(&mut self, key: &K) -> Result<Value, StoreError> {
-        let resp = self.client.get(key.to_string(), None).await.context(Get {
-            key: key.to_string(),
-        })?;
+        let resp = timed("get", self.client.get(key.to_string(), None))
+            .await
+            .context(Get {
+                key: key.to_string(),
+            })?;
         match resp.kvs().first() {
             Some(kv) => Ok(
                 serde_json::from_slice(kv.value()).context(DeserialiseValue {
@@ -153,13 +177,14 @@ impl Store for Etcd {
         if let Some((lease_id, lock_key)) = self.lease_lock()? {
             let cmp = Compare::lease(lock_key.clone(), CompareOp::Equal, lease_id);
             let del = TxnOp::delete(key.to_string(), None);
-            let resp = self
-                .client
-                .txn(Txn::new().when([cmp]).and_then([del]))
-                .await
-                .context(Delete {
-                    key: key.to_string(),
-                })?;
+            let resp = timed(
+                "delete",
+                self.client.txn(Txn::new().when([cmp]).and_then([del])),
+            )
+            .await
+            .context(Delete {
+                key: key.to_string(),
+            })?;
             if !resp.succeeded() {
                 return Err(StoreError::FailedLock {
                     reason: format!(
@@ -169,8 +194,7 @@ impl Store for Etcd {
                 });
             }
         } else {
-            self.client
-                .delete(key.to_string(), None)
+            timed("delete", self.client.delete(key.to_string(), None))
                 .await
                 .context(Delete {
                     key: key.to_string(),
@@ -206,14 +230,15 @@ impl Store for Etcd {
         if let Some((lease_id, lock_key)) = self.lease_lock()? {
             let cmp = Compare::lease(lock_key.clone(), CompareOp::Equal, lease_id);
             let put = TxnOp::put(key.to_string(), vec_value, None);
-            let resp = self
-                .client
-                .txn(Txn::new().when([cmp]).and_then([put]))
-                .await
-                .context(Put {
-                    key: object.key().key(),
-                    value: serde_json::to_string(object).context(SerialiseValue)?,
-                })?;
+            let resp = timed(
+                "put",
+                self.client.txn(Txn::new().when([cmp]).and_then([put])),
+            )
+            .await
+            .context(Put {
+                key: object.key().key(),
+                value: serde_json::to_string(object).context(SerialiseValue)?,
+            })?;
             if !resp.succeeded() {
                 return Err(StoreError::FailedLock {
                     reason: format!(
@@ -223,19 +248,19 @@ impl Store for Etcd {
                 });
             }
         } else {
-            self.client.put(key, vec_value, None).await.context(Put {
-                key: object.key().key(),
-                value: serde_json::to_string(object).context(SerialiseValue)?,
-            })?;
+            timed("put", self.client.put(key, vec_value, None))
+                .await
+                .context(Put {
+                    key: object.key().key(),
+                    value: serde_json::to_string(object).context(SerialiseValue)?,
+                })?;
         };
 
         Ok(())
     }
 
     async fn get_obj<O: StorableObject>(&mut self, key: &O::Key) -> Result<O, StoreError> {
-        let resp = self
-            .client
-            .get(key.key(), None)
+        let resp = timed("get", self.client.get(key.key(), None))
             .await
             .context(Get { key: key.key() })?;
         match resp.kvs().first() {
@@ -253,11 +278,13 @@ impl Store for Etcd {
         &mut self,
         key_prefix: &str,
     ) -> Result<Vec<(String, Value)>, StoreError> {
-        let resp = self
-            .client
-            .get(key_prefix, Some(GetOptions::new().with_prefix()))
-            .await
-            .context(GetPrefix { prefix: key_prefix })?;
+        let resp = timed(
+            "get_prefix",
+            self.client
+                .get(key_prefix, Some(GetOptions::new().with_prefix())),
+        )
+        .await
+        .context(GetPrefix { prefix: key_prefix })?;
         let result = resp
             .kvs()
             .iter()
@@ -283,9 +310,9 @@ impl Store for Etcd {
             return Err(StoreError::PagedMinimum);
         }
 
-        let resp = self
-            .client
-            .get(
+        let resp = timed(
+            "get_paged",
+            self.client.get(
                 key_prefix,
                 Some(
                     GetOptions::new()
@@ -294,9 +321,10 @@ impl Store for Etcd {
                         .with_sort(SortTarget::Key, SortOrder::Ascend)
                         .with_limit(limit),
                 ),
-            )
-            .await
-            .context(GetPrefix { prefix: key_prefix })?;
+            ),
+        )
+        .await
+        .context(GetPrefix { prefix: key_prefix })?;
 
         let result = resp
             .kvs()
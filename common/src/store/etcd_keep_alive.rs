@@ -129,33 +129,27 @@ impl EtcdSingletonLock {
         let lease_resp = client
             .lease_grant(*LeaseTtl::from(lease_ttl), None)
             .await
-            .expect("Should init the lease");
+            .map_err(|e| StoreError::FailedLock {
+                reason: format!("Failed to grant the lease, error: '{}'", e),
+            })?;
         tracing::info!(
             lease.id = lease_resp.id(),
             lease.ttl = lease_resp.ttl(),
             "Granted new lease",
         );
-        let lock_resp = tokio::time::timeout(
-            lease_ttl,
-            client.lock(
-                lock_owner_key_prefix.as_str(),
-                Some(LockOptions::new().with_lease(lease_resp.id())),
-            ),
-        )
-        .await
-        .map_err(|_| StoreError::Timeout {
-            operation: format!("etcd lock '{}'", lock_owner_key_prefix),
-            timeout: lease_ttl,
-        })?
-        .map_err(|e| StoreError::FailedLock {
-            reason: e.to_string(),
-        })?;
-
-        let lock_key =
-            String::from_utf8(lock_resp.key().to_vec()).map_err(|e| StoreError::FailedLock {
-                reason: format!("Invalid etcd lock key, error: '{}'", e),
-            })?;
         let lease_id = lease_resp.id();
+        let lock_key =
+            match Self::lock(&mut client, &lock_owner_key_prefix, lease_id, lease_ttl).await {
+                Ok(lock_key) => lock_key,
+                Err(error) => {
+                    // the lock waiter is bound to the lease, so revoking it stops us from waiting
+                    // in the background, or releases the lock if we got it at the last moment
+                    if let Err(error) = client.lease_revoke(lease_id).await {
+                        tracing::warn!(lease.id = lease_id, %error, "Failed to revoke the lease");
+                    }
+                    return Err(error);
+                }
+            };
 
         let lease_info = LeaseLockInfo::new(lease_id, &lock_key, &client);
 
@@ -163,19 +157,20 @@ impl EtcdSingletonLock {
             client,
             state: Some(LeaseKeeperState::Locked(Locked {
                 lock_key: lock_key.clone(),
-                lease_id: lease_resp.id(),
+                lease_id,
             })),
             lease_ttl,
             lease_id,
             lease_info: lease_info.clone(),
             service_name: service_kind,
         };
-        keeper
-            .set_owner_lease(lease_resp.id(), &lock_key)
-            .await
-            .map_err(|e| StoreError::FailedLock {
-                reason: e.to_string(),
-            })?;
+        if let Err(error) = keeper.set_owner_lease(lease_id, &lock_key).await {
+            // release the lock straight away rather than when the lease expires
+            lease_info.revoke().await.ok();
+            return Err(StoreError::FailedLock {
+                reason: error.to_string(),
+            });
+        }
 
         tokio::spawn(async move {
             keeper.keep_lock_alive_forever().await;
@@ -184,6 +179,35 @@ impl EtcdSingletonLock {
         Ok(lease_info)
     }
 
+    /// Wait for up to `lease_ttl` to lock the `lock_owner_key_prefix` lock with the given lease,
+    /// returning the key of the lock.
+    async fn lock(
+        client: &mut Client,
+        lock_owner_key_prefix: &str,
+        lease_id: i64,
+        lease_ttl: Duration,
+    ) -> Result<String, StoreError> {
+        let lock_resp = tokio::time::timeout(
+            lease_ttl,
+            client.lock(
+                lock_owner_key_prefix,
+                Some(LockOptions::new().with_lease(lease_id)),
+            ),
+        )
+        .await
+        .map_err(|_| StoreError::Timeout {
+            operation: format!("etcd lock '{}'", lock_owner_key_prefix),
+            timeout: lease_ttl,
+        })?
+        .map_err(|e| StoreError::FailedLock {
+            reason: e.to_string(),
+        })?;
+
+        String::from_utf8(lock_resp.key().to_vec()).map_err(|e| StoreError::FailedLock {
+            reason: format!("Invalid etcd lock key, error: '{}'", e),
+        })
+    }
+
     fn lease_ttl(&self) -> LeaseTtl {
         LeaseTtl::from(self.lease_ttl)
     }
@@ -17,7 +17,7 @@ use dyn_clonable::clonable;
 use serde::{de::StdError, Deserialize, Serialize};
 
 use std::{fmt::Debug, num::TryFromIntError, str::FromStr, time::Duration};
-use strum_macros::{AsRefStr, ToString};
+use strum_macros::{AsRefStr, EnumString, ToString};
 use tokio::task::JoinError;
 use tonic::Code;
 
@@ -108,7 +108,7 @@ pub trait Message {
 }
 
 /// All the different variants of Resources
-#[derive(Serialize, Deserialize, Debug, Clone, AsRefStr, ToString, Eq, PartialEq)]
+#[derive(Serialize, Deserialize, Debug, Clone, AsRefStr, EnumString, ToString, Eq, PartialEq)]
 pub enum ResourceKind {
     /// Unknown or unspecified resource
     Unknown,
@@ -142,6 +142,10 @@ pub enum ResourceKind {
     NvmeSubsystem,
     /// Nvme Controller Path
     NvmePath,
+    /// Volume Snapshot
+    VolumeSnapshot,
+    /// Volume SwitchOver
+    SwitchOver,
 }
 
 /// Error type which is returned over the transport for any operation.
@@ -24,9 +24,13 @@ impl_message!(Deregister);
 
 impl_vector_request!(Nodes, Node);
 impl_message!(GetNodes);
+impl_message!(SetRebuildBandwidth);
 
 impl_message!(CreatePool);
 impl_message!(DestroyPool);
+impl_message!(ExpandPool);
+impl_message!(LabelPool);
+impl_message!(UnlabelPool);
 impl_vector_request!(Pools, Pool);
 impl_message!(GetPools);
 
@@ -38,6 +42,11 @@ impl_message!(CreateReplica);
 impl_message!(DestroyReplica);
 impl_message!(ShareReplica);
 impl_message!(UnshareReplica);
+impl_message!(ResizeReplica);
+impl_message!(CreateReplicaSnapshot);
+impl_message!(DestroyReplicaSnapshot);
+impl_message!(CreateSnapshotClone);
+impl_message!(CreateNexusSnapshot);
 
 impl_vector_request!(Nexuses, Nexus);
 impl_message!(GetNexuses);
@@ -45,6 +54,8 @@ impl_message!(CreateNexus);
 impl_message!(DestroyNexus);
 impl_message!(ShareNexus);
 impl_message!(UnshareNexus);
+impl_message!(ResizeNexus);
+impl_message!(SetNexusQos);
 impl_message!(RemoveNexusChild);
 impl_message!(AddNexusChild);
 impl_message!(FaultNexusChild);
@@ -61,6 +72,12 @@ impl_message!(DestroyVolume);
 impl_message!(AddVolumeNexus);
 impl_message!(RemoveVolumeNexus);
 impl_message!(SetVolumeReplica);
+impl_message!(ResizeVolume);
+impl_message!(SetVolumeQos);
+impl_vector_request!(VolumeSnapshots, VolumeSnapshot);
+impl_message!(CreateVolumeSnapshot);
+impl_message!(DestroyVolumeSnapshot);
+impl_message!(GetVolumeSnapshots);
 
 impl_message!(JsonGrpcRequest, JsonGrpc);
 
@@ -71,6 +88,10 @@ impl_vector_request!(Watches, Watch);
 impl_message!(CreateWatch);
 impl_message!(GetWatches);
 impl_message!(DeleteWatch);
+impl_message!(WatchEvents);
 
 impl_message!(GetSpecs);
 impl_message!(GetStates);
+impl_message!(GetHaHistory);
+impl_message!(GetRebuildHistory);
+impl_message!(GetRebuildQueue);
@@ -169,6 +169,11 @@ pub enum StorableObjectType {
     StoreLeaseLock,
     StoreLeaseOwner,
     SwitchOver,
+    VolumeSnapshot,
+    FailoverHistory,
+    PathFailureHistory,
+    RebuildHistory,
+    TargetMove,
 }
 
 /// Returns the key prefix that should is used for the keys, when running from within the cluster.
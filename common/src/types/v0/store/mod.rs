@@ -5,8 +5,10 @@ pub mod nexus_child;
 pub mod nexus_persistence;
 pub mod node;
 pub mod pool;
+pub mod rebuild;
 pub mod registry;
 pub mod replica;
+pub mod snapshot;
 pub mod switchover;
 pub mod volume;
 pub mod watch;
@@ -227,6 +227,7 @@ impl SpecTransaction<NexusOperation> for NexusSpec {
                 }
                 NexusOperation::AddChild(uri) => self.children.push(uri),
                 NexusOperation::RemoveChild(uri) => self.children.retain(|c| c != &uri),
+                NexusOperation::Resize(size) => self.size = size,
             }
         }
         self.clear_op();
@@ -260,6 +261,7 @@ pub enum NexusOperation {
     Unshare,
     AddChild(NexusChild),
     RemoveChild(NexusChild),
+    Resize(u64),
 }
 
 /// Key used by the store to uniquely identify a NexusSpec structure.
@@ -3,7 +3,7 @@ use crate::{
     types::v0::{
         openapi::models,
         store::definitions::{ObjectKey, StorableObject, StorableObjectType},
-        transport::{self, HostNqn, NodeId},
+        transport::{self, HostNqn, NodeId, VolumeId},
     },
     IntoOption,
 };
@@ -35,6 +35,33 @@ impl CordonedState {
     }
 }
 
+/// Progress of the evacuation of the replicas of a volume from a draining node.
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
+pub struct VolumeEvacuation {
+    /// The volume.
+    pub volume: VolumeId,
+    /// Number of replicas of the volume which are still on the node.
+    pub replicas: u8,
+    /// Rebuild progress (%) of the replacement replica, if one is being rebuilt.
+    pub rebuild_progress: Option<u8>,
+    /// The volume is not healthy, and so its replicas are not moved off the node, as that could
+    /// compromise it further. The drain completes without them.
+    #[serde(default)]
+    pub degraded: bool,
+}
+impl VolumeEvacuation {
+    /// Create a new `Self` for the given volume which still has the given number of replicas
+    /// on the node.
+    pub fn new(volume: VolumeId, replicas: usize) -> Self {
+        Self {
+            volume,
+            replicas: replicas.min(u8::MAX as usize) as u8,
+            rebuild_progress: None,
+            degraded: false,
+        }
+    }
+}
+
 /// Data relating to a draining or drained node, including non-drain cordon labels.
 #[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
 pub struct DrainState {
@@ -42,6 +69,13 @@ pub struct DrainState {
     pub cordonlabels: Vec<String>,
     /// Labels used to cordon a node specifically for a drain.
     pub drainlabels: Vec<String>,
+    /// The volume replicas are also moved off the node, and not only the volume targets.
+    #[serde(default)]
+    pub evacuate: bool,
+    /// Progress of the evacuation of the volumes which still have replicas on the node.
+    /// Once drained, only the degraded volumes which were skipped are left.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub evacuations: Vec<VolumeEvacuation>,
 }
 
 impl DrainState {
@@ -50,6 +84,8 @@ impl DrainState {
         Self {
             cordonlabels,
             drainlabels,
+            evacuate: false,
+            evacuations: vec![],
         }
     }
     /// Remove a label from a DrainState object.
@@ -201,6 +237,14 @@ impl NodeSpec {
     pub fn set_endpoint(&mut self, endpoint: std::net::SocketAddr) {
         self.endpoint = endpoint;
     }
+    /// Set the node label with the given key to the given value.
+    pub fn set_label(&mut self, key: String, value: String) {
+        self.labels.insert(key, value);
+    }
+    /// Remove the node label with the given key, returning its value if it was present.
+    pub fn remove_label(&mut self, key: &str) -> Option<String> {
+        self.labels.remove(key)
+    }
 
     /// Ensure the state is consistent with the labels.
     pub fn resolve(&mut self) {
@@ -243,32 +287,39 @@ impl NodeSpec {
     }
 
     /// Drain node by applying the drain label.
-    pub fn set_drain(&mut self, label: String) {
+    /// If `evacuate` is set the volume replicas are also moved off the node, in which case a
+    /// drained node which was not evacuated goes back to draining.
+    pub fn set_drain(&mut self, label: String, evacuate: bool) {
         // the the node has the label, return with an error
         match &mut self.cordon_drain_state {
             Some(ds) => match ds {
                 CordonDrainState::Cordoned(state) => {
                     // set state to draining and add label
-                    self.cordon_drain_state =
-                        Some(CordonDrainState::Draining(state.into_drain(&label)))
+                    let mut state = state.into_drain(&label);
+                    state.evacuate = evacuate;
+                    self.cordon_drain_state = Some(CordonDrainState::Draining(state))
                 }
                 CordonDrainState::Draining(state) => {
                     // add the label
                     state.add_drain_label(&label);
+                    state.evacuate |= evacuate;
                 }
                 CordonDrainState::Drained(state) => {
                     // add the label
                     state.add_drain_label(&label);
+                    if evacuate && !state.evacuate {
+                        state.evacuate = true;
+                        self.cordon_drain_state = Some(CordonDrainState::Draining(state.clone()));
+                    }
                 }
             },
             None => {
                 //add the label and set the state to draining
                 let cordonlabels = Vec::<String>::new();
                 let drainlabels = vec![label];
-                self.cordon_drain_state = Some(CordonDrainState::Draining(DrainState::new(
-                    cordonlabels,
-                    drainlabels,
-                )));
+                let mut state = DrainState::new(cordonlabels, drainlabels);
+                state.evacuate = evacuate;
+                self.cordon_drain_state = Some(CordonDrainState::Draining(state));
             }
         }
     }
@@ -276,14 +327,22 @@ impl NodeSpec {
     /// Move state from Draining to Drained, no change to the labels.
     pub fn set_drained(&mut self) {
         if let Some(CordonDrainState::Draining(state)) = &mut self.cordon_drain_state {
-            self.cordon_drain_state = Some(CordonDrainState::Drained(DrainState::new(
-                state.cordonlabels.clone(),
-                state.drainlabels.clone(),
-            )));
+            let mut drained =
+                DrainState::new(state.cordonlabels.clone(), state.drainlabels.clone());
+            drained.evacuate = state.evacuate;
+            drained.evacuations = state.evacuations.clone();
+            self.cordon_drain_state = Some(CordonDrainState::Drained(drained));
             self.resolve();
         }
     }
 
+    /// Update the evacuation progress of a draining node.
+    pub fn set_evacuations(&mut self, evacuations: Vec<VolumeEvacuation>) {
+        if let Some(CordonDrainState::Draining(state)) = &mut self.cordon_drain_state {
+            state.evacuations = evacuations;
+        }
+    }
+
     /// Uncordon node by removing the corresponding label.
     pub fn uncordon(&mut self, label: String) {
         match &mut self.cordon_drain_state {
@@ -335,6 +394,22 @@ impl NodeSpec {
             None => false,
         }
     }
+    /// Returns true if the node is in the draining state and its volume replicas are also being
+    /// moved off the node.
+    pub fn is_evacuating(&self) -> bool {
+        match &self.cordon_drain_state {
+            Some(CordonDrainState::Draining(state)) => state.evacuate,
+            _ => false,
+        }
+    }
+    /// Returns the evacuation progress of the node, if it's draining or drained.
+    pub fn evacuations(&self) -> &[VolumeEvacuation] {
+        match &self.cordon_drain_state {
+            Some(CordonDrainState::Draining(state)) => &state.evacuations,
+            Some(CordonDrainState::Drained(state)) => &state.evacuations,
+            _ => &[],
+        }
+    }
     /// Returns true if the node is in the drained state.
     pub fn is_drained(&self) -> bool {
         match &self.cordon_drain_state {
@@ -351,6 +426,10 @@ impl From<NodeSpec> for models::NodeSpec {
             src.id,
             src.cordon_drain_state.into_opt(),
             src.node_nqn.into_opt(),
+            match src.labels.is_empty() {
+                true => None,
+                false => Some(src.labels),
+            },
         )
     }
 }
@@ -365,23 +444,32 @@ impl From<CordonDrainState> for models::CordonDrainState {
                 models::CordonDrainState::cordonedstate(cs)
             }
             CordonDrainState::Draining(state) => {
-                let ds = models::DrainState {
-                    cordonlabels: state.cordonlabels,
-                    drainlabels: state.drainlabels,
-                };
-                models::CordonDrainState::drainingstate(ds)
+                models::CordonDrainState::drainingstate(state.into())
             }
             CordonDrainState::Drained(state) => {
-                let ds = models::DrainState {
-                    cordonlabels: state.cordonlabels,
-                    drainlabels: state.drainlabels,
-                };
-                models::CordonDrainState::drainedstate(ds)
+                models::CordonDrainState::drainedstate(state.into())
             }
         }
     }
 }
 
+impl From<DrainState> for models::DrainState {
+    fn from(src: DrainState) -> Self {
+        Self {
+            cordonlabels: src.cordonlabels,
+            drainlabels: src.drainlabels,
+            evacuate: Some(src.evacuate),
+            evacuations: Some(src.evacuations.into_iter().map(Into::into).collect()),
+        }
+    }
+}
+
+impl From<VolumeEvacuation> for models::VolumeEvacuation {
+    fn from(src: VolumeEvacuation) -> Self {
+        Self::new_all(src.volume, src.replicas, src.rebuild_progress, src.degraded)
+    }
+}
+
 /// Key used by the store to uniquely identify a NodeSpec structure.
 pub struct NodeSpecKey(NodeId);
 
@@ -121,6 +121,9 @@ impl SpecTransaction<PoolOperation> for PoolSpec {
                 PoolOperation::Create => {
                     self.status = SpecStatus::Created(transport::PoolStatus::Online);
                 }
+                PoolOperation::Expand(disks) => {
+                    self.disks = disks;
+                }
             }
         }
         self.clear_op();
@@ -149,6 +152,7 @@ impl SpecTransaction<PoolOperation> for PoolSpec {
 pub enum PoolOperation {
     Create,
     Destroy,
+    Expand(Vec<PoolDeviceUri>),
 }
 
 impl PartialEq<transport::PoolState> for PoolSpec {
@@ -0,0 +1,183 @@
+use crate::types::v0::{
+    store::definitions::{ObjectKey, StorableObject, StorableObjectType},
+    transport::{NexusId, ReplicaId, VolumeId},
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+/// Maximum number of records kept by the rebuild history of each volume.
+pub const REBUILD_HISTORY_CAPACITY: usize = 32;
+
+/// Timestamp of the rebuild events.
+pub type RebuildTime = DateTime<Utc>;
+
+/// Why a volume replica was rebuilt.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Eq, PartialEq)]
+pub enum RebuildReason {
+    /// The replica count of the volume was increased.
+    ReplicaIncrease,
+    /// A missing or faulted replica was replaced by a new replica.
+    Replacement,
+    /// A replica was moved onto a less used pool.
+    Rebalance,
+    /// The rebuild was not started by the control-plane, eg: the io-engine retried a child.
+    Unknown,
+}
+
+/// Outcome of a volume replica rebuild.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Eq, PartialEq)]
+pub enum RebuildOutcome {
+    /// The rebuild is still in progress.
+    Running,
+    /// The replica was fully rebuilt and is now healthy.
+    Successful,
+    /// The replica faulted or was removed before it was fully rebuilt.
+    Failed,
+}
+
+/// A rebuild of a volume replica by the volume target.
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+pub struct RebuildRecord {
+    /// The volume which the replica belongs to.
+    pub volume: VolumeId,
+    /// The volume target nexus which rebuilds the replica.
+    pub nexus: NexusId,
+    /// The replica being rebuilt.
+    pub replica: ReplicaId,
+    /// Why the replica was rebuilt.
+    pub reason: RebuildReason,
+    /// When the rebuild was started, or first observed if not started by the control-plane.
+    pub started: RebuildTime,
+    /// When the rebuild finished, if it did.
+    pub finished: Option<RebuildTime>,
+    /// The number of bytes rebuilt, as estimated from the last observed rebuild progress.
+    pub bytes: u64,
+    /// The outcome of the rebuild.
+    pub outcome: RebuildOutcome,
+    /// Current rebuild progress (%), only for running rebuilds.
+    /// This is not persisted as it's retrieved from the nexus state.
+    #[serde(default, skip_serializing)]
+    pub progress: Option<u8>,
+    /// Estimated completion time, only for running rebuilds.
+    /// This is not persisted as it's calculated from the progress.
+    #[serde(default, skip_serializing)]
+    pub eta: Option<RebuildTime>,
+}
+
+impl RebuildRecord {
+    /// Create a new running rebuild record for a rebuild which is starting now.
+    pub fn new(
+        volume: VolumeId,
+        nexus: NexusId,
+        replica: ReplicaId,
+        reason: RebuildReason,
+    ) -> Self {
+        Self {
+            volume,
+            nexus,
+            replica,
+            reason,
+            started: Utc::now(),
+            finished: None,
+            bytes: 0,
+            outcome: RebuildOutcome::Running,
+            progress: None,
+            eta: None,
+        }
+    }
+
+    /// Check if the rebuild is still in progress.
+    pub fn running(&self) -> bool {
+        self.outcome == RebuildOutcome::Running
+    }
+
+    /// Mark the rebuild as finished now, with the given outcome.
+    /// The bytes rebuilt are the ones last observed through `set_progress`.
+    pub fn finish(&mut self, outcome: RebuildOutcome) {
+        self.outcome = outcome;
+        self.progress = None;
+        self.eta = None;
+        self.finished = Some(Utc::now());
+    }
+
+    /// Set the current progress of a running rebuild of a replica with the given size, updating
+    /// the bytes rebuilt so far and the estimated completion time.
+    pub fn set_progress(&mut self, progress: u8, size: u64) {
+        let progress = progress.min(100);
+        self.progress = Some(progress);
+        self.bytes = size / 100 * progress as u64;
+        if progress > 0 {
+            let elapsed = Utc::now() - self.started;
+            self.eta = Some(self.started + elapsed * 100 / progress as i32);
+        }
+    }
+}
+
+/// Bounded, time-ordered history of the replica rebuilds of a volume.
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+pub struct RebuildHistory {
+    /// The volume.
+    pub volume: VolumeId,
+    /// Replica rebuilds, oldest first.
+    pub records: VecDeque<RebuildRecord>,
+}
+
+impl RebuildHistory {
+    /// Create a new empty history for the given volume.
+    pub fn new(volume: VolumeId) -> Self {
+        Self {
+            volume,
+            records: VecDeque::new(),
+        }
+    }
+
+    /// Append a record, evicting the oldest finished one if the history is full.
+    pub fn push(&mut self, record: RebuildRecord) {
+        if self.records.len() >= REBUILD_HISTORY_CAPACITY {
+            match self.records.iter().position(|record| !record.running()) {
+                Some(index) => {
+                    self.records.remove(index);
+                }
+                None => {
+                    self.records.pop_front();
+                }
+            }
+        }
+        self.records.push_back(record);
+    }
+
+    /// Get the running rebuild of the given replica, if any.
+    pub fn running_mut(&mut self, replica: &ReplicaId) -> Option<&mut RebuildRecord> {
+        self.records
+            .iter_mut()
+            .find(|record| record.running() && &record.replica == replica)
+    }
+}
+
+/// Persistent Store key for `RebuildHistory`.
+pub struct RebuildHistoryKey(VolumeId);
+
+impl RebuildHistoryKey {
+    pub fn new(id: VolumeId) -> Self {
+        Self(id)
+    }
+}
+
+impl ObjectKey for RebuildHistoryKey {
+    fn key_type(&self) -> StorableObjectType {
+        StorableObjectType::RebuildHistory
+    }
+
+    fn key_uuid(&self) -> String {
+        self.0.to_string()
+    }
+}
+
+impl StorableObject for RebuildHistory {
+    type Key = RebuildHistoryKey;
+
+    fn key(&self) -> Self::Key {
+        RebuildHistoryKey(self.volume.clone())
+    }
+}
@@ -95,6 +95,7 @@ impl StorableObject for CoreRegistryConfig {
 #[derive(Serialize, Deserialize, Debug, Clone, strum_macros::Display)]
 pub enum ControlPlaneService {
     CoreAgent,
+    HaClusterAgent,
 }
 
 /// Key used by the store lock api to identify the lock.
@@ -248,6 +248,9 @@ impl SpecTransaction<ReplicaOperation> for ReplicaSpec {
                 ReplicaOperation::OwnerUpdate(owners) => {
                     self.owners = owners;
                 }
+                ReplicaOperation::Resize(size) => {
+                    self.size = size;
+                }
             }
         }
         self.clear_op();
@@ -279,6 +282,7 @@ pub enum ReplicaOperation {
     Share(ReplicaShareProtocol, Vec<HostNqn>),
     Unshare,
     OwnerUpdate(ReplicaOwners),
+    Resize(u64),
 }
 
 /// Key used by the store to uniquely identify a ReplicaSpec structure.
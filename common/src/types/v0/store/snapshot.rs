@@ -0,0 +1,202 @@
+//! Definition of volume snapshot types that can be saved to the persistent store.
+
+use crate::types::v0::{
+    openapi::models,
+    store::{
+        definitions::{ObjectKey, StorableObject, StorableObjectType},
+        AsOperationSequencer, OperationSequence, SpecStatus, SpecTransaction,
+    },
+    transport::{CreateVolumeSnapshot, PoolId, ReplicaId, SnapshotId, VolumeEncryption, VolumeId},
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Defines the timestamp of a snapshot.
+pub type SnapshotTime = DateTime<Utc>;
+
+/// State of the Volume Snapshot Spec.
+pub type VolumeSnapshotSpecStatus = SpecStatus<()>;
+
+/// User specification of a volume snapshot.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct VolumeSnapshotSpec {
+    /// Uuid of the snapshot.
+    pub uuid: SnapshotId,
+    /// Uuid of the volume from which the snapshot was taken.
+    pub source_id: VolumeId,
+    /// The status that the snapshot should eventually achieve.
+    pub status: VolumeSnapshotSpecStatus,
+    /// Update in progress.
+    #[serde(skip)]
+    pub sequencer: OperationSequence,
+    /// Record of the operation in progress.
+    pub operation: Option<VolumeSnapshotOperationState>,
+    /// Time at which the snapshot was taken.
+    #[serde(default)]
+    pub timestamp: Option<SnapshotTime>,
+    /// Size of the source volume at the time the snapshot was taken.
+    #[serde(default)]
+    pub size: u64,
+    /// The replica snapshots which make up this volume snapshot.
+    #[serde(default)]
+    pub replica_snapshots: Vec<ReplicaSnapshot>,
+    /// The encryption at rest of the snapshot data, as used by the source volume.
+    #[serde(default)]
+    pub encryption: Option<VolumeEncryption>,
+}
+
+impl VolumeSnapshotSpec {
+    /// Get the snapshot uuid.
+    pub fn uuid(&self) -> &SnapshotId {
+        &self.uuid
+    }
+    /// Get the uuid of the source volume.
+    pub fn source_id(&self) -> &VolumeId {
+        &self.source_id
+    }
+}
+
+/// A snapshot of a single volume replica.
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+pub struct ReplicaSnapshot {
+    /// Uuid of the replica snapshot.
+    pub uuid: SnapshotId,
+    /// Uuid of the replica from which the snapshot was taken.
+    pub source_id: ReplicaId,
+    /// The pool where the replica snapshot lives.
+    pub pool_id: PoolId,
+}
+
+impl ReplicaSnapshot {
+    /// Create a new `Self` from the given arguments.
+    pub fn new(uuid: SnapshotId, source_id: ReplicaId, pool_id: PoolId) -> Self {
+        Self {
+            uuid,
+            source_id,
+            pool_id,
+        }
+    }
+}
+
+/// Operation State for a VolumeSnapshot resource.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct VolumeSnapshotOperationState {
+    /// Record of the operation.
+    pub operation: VolumeSnapshotOperation,
+    /// Result of the operation.
+    pub result: Option<bool>,
+}
+
+/// Available VolumeSnapshot Operations.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum VolumeSnapshotOperation {
+    Create,
+    Destroy,
+}
+
+impl SpecTransaction<VolumeSnapshotOperation> for VolumeSnapshotSpec {
+    fn pending_op(&self) -> bool {
+        self.operation.is_some()
+    }
+
+    fn commit_op(&mut self) {
+        if let Some(op) = self.operation.clone() {
+            match op.operation {
+                VolumeSnapshotOperation::Create => {
+                    self.status = SpecStatus::Created(());
+                }
+                VolumeSnapshotOperation::Destroy => {
+                    self.status = SpecStatus::Deleted;
+                }
+            }
+        }
+        self.clear_op();
+    }
+
+    fn clear_op(&mut self) {
+        self.operation = None;
+    }
+
+    fn start_op(&mut self, operation: VolumeSnapshotOperation) {
+        self.operation = Some(VolumeSnapshotOperationState {
+            operation,
+            result: None,
+        })
+    }
+
+    fn set_op_result(&mut self, result: bool) {
+        if let Some(op) = &mut self.operation {
+            op.result = Some(result);
+        }
+    }
+}
+
+impl AsOperationSequencer for VolumeSnapshotSpec {
+    fn as_ref(&self) -> &OperationSequence {
+        &self.sequencer
+    }
+
+    fn as_mut(&mut self) -> &mut OperationSequence {
+        &mut self.sequencer
+    }
+}
+
+/// Key used by the store to uniquely identify a VolumeSnapshotSpec structure.
+pub struct VolumeSnapshotKey(SnapshotId);
+
+impl From<&SnapshotId> for VolumeSnapshotKey {
+    fn from(id: &SnapshotId) -> Self {
+        Self(id.clone())
+    }
+}
+
+impl ObjectKey for VolumeSnapshotKey {
+    fn key_type(&self) -> StorableObjectType {
+        StorableObjectType::VolumeSnapshot
+    }
+
+    fn key_uuid(&self) -> String {
+        self.0.to_string()
+    }
+}
+
+impl StorableObject for VolumeSnapshotSpec {
+    type Key = VolumeSnapshotKey;
+
+    fn key(&self) -> Self::Key {
+        VolumeSnapshotKey(self.uuid.clone())
+    }
+}
+
+impl From<&CreateVolumeSnapshot> for VolumeSnapshotSpec {
+    fn from(request: &CreateVolumeSnapshot) -> Self {
+        Self {
+            uuid: request.snap_id.clone(),
+            source_id: request.source_id.clone(),
+            status: VolumeSnapshotSpecStatus::Creating,
+            sequencer: OperationSequence::new(request.snap_id.clone()),
+            operation: None,
+            timestamp: None,
+            size: 0,
+            replica_snapshots: vec![],
+            encryption: None,
+        }
+    }
+}
+impl PartialEq<CreateVolumeSnapshot> for VolumeSnapshotSpec {
+    fn eq(&self, other: &CreateVolumeSnapshot) -> bool {
+        self.uuid == other.snap_id && self.source_id == other.source_id
+    }
+}
+
+impl From<VolumeSnapshotSpec> for models::VolumeSnapshotSpec {
+    fn from(src: VolumeSnapshotSpec) -> Self {
+        Self::new_all(
+            src.uuid,
+            src.source_id,
+            src.status,
+            src.size,
+            src.timestamp.map(|t| t.to_rfc3339()),
+        )
+    }
+}
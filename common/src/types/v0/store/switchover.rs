@@ -3,11 +3,14 @@ use crate::types::v0::{
         definitions::{ObjectKey, StorableObject, StorableObjectType},
         SpecTransaction,
     },
-    transport::{NodeId, VolumeId},
+    transport::{NodeId, SwitchOverStage, VolumeId},
 };
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use std::{collections::HashMap, net::SocketAddr};
+use std::{
+    collections::{HashMap, VecDeque},
+    net::SocketAddr,
+};
 
 /// Defines operation for SwitchOverSpec.
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -66,6 +69,9 @@ pub struct SwitchOverSpec {
     pub reuse_existing: bool,
     /// Publish context of the volume.
     pub publish_context: Option<HashMap<String, String>>,
+    /// Timings of the stages which have been started so far.
+    #[serde(default)]
+    pub stage_timings: Vec<StageTiming>,
 }
 
 impl SwitchOverSpec {
@@ -173,3 +179,337 @@ impl SpecTransaction<Operation> for SwitchOverSpec {
         }
     }
 }
+
+/// Maximum number of records kept by each history.
+pub const HISTORY_CAPACITY: usize = 32;
+
+/// How long the path failure history of a node is kept after its last reported path failure.
+pub const PATH_FAILURE_HISTORY_TTL: std::time::Duration =
+    std::time::Duration::from_secs(7 * 24 * 60 * 60);
+
+/// Timing of a single switchover stage.
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+pub struct StageTiming {
+    /// The stage.
+    pub stage: SwitchOverStage,
+    /// When the first attempt of the stage was started.
+    pub started: SwitchOverTime,
+    /// When the stage completed successfully, if it did.
+    pub finished: Option<SwitchOverTime>,
+    /// Number of attempts made for the stage.
+    pub attempts: u64,
+}
+
+impl StageTiming {
+    /// Create a new `Self` for a stage which is starting now.
+    pub fn new(stage: SwitchOverStage) -> Self {
+        Self {
+            stage,
+            started: Utc::now(),
+            finished: None,
+            attempts: 0,
+        }
+    }
+}
+
+/// Outcome of a finished switchover, kept for post-mortem analysis.
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+pub struct SwitchOverRecord {
+    /// Volume for which the switchover was executed.
+    pub volume: VolumeId,
+    /// The nodename of the node which reported the failed path.
+    pub node_name: NodeId,
+    /// Failed nexus path.
+    pub existing_nqn: String,
+    /// New nexus path, if one was published.
+    pub new_path: Option<String>,
+    /// When the switchover was initialized.
+    pub started: SwitchOverTime,
+    /// When the switchover finished.
+    pub finished: SwitchOverTime,
+    /// Either `Successful` or `Errored`.
+    pub outcome: SwitchOverStage,
+    /// Error message of the last failed attempt, for errored switchovers.
+    pub error: Option<String>,
+    /// Timings of each stage.
+    pub stages: Vec<StageTiming>,
+}
+
+/// A failed NVMe path reported by a node.
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+pub struct PathFailureRecord {
+    /// The nodename of the node which reported the failed path.
+    pub node_name: NodeId,
+    /// NQN of the NVMe target to which the path was connected.
+    pub nqn: String,
+    /// When the failure was reported.
+    pub timestamp: SwitchOverTime,
+}
+
+/// Bounded, time-ordered history of the switchover outcomes of a volume.
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+pub struct SwitchOverHistory {
+    /// The volume.
+    pub volume: VolumeId,
+    /// Switchover outcomes, oldest first.
+    pub records: VecDeque<SwitchOverRecord>,
+}
+
+impl SwitchOverHistory {
+    /// Create a new empty history for the given volume.
+    pub fn new(volume: VolumeId) -> Self {
+        Self {
+            volume,
+            records: VecDeque::new(),
+        }
+    }
+
+    /// Append a record, evicting the oldest one if the history is full.
+    pub fn push(&mut self, record: SwitchOverRecord) {
+        if self.records.len() >= HISTORY_CAPACITY {
+            self.records.pop_front();
+        }
+        self.records.push_back(record);
+    }
+}
+
+/// Persistent Store key for `SwitchOverHistory`.
+pub struct SwitchOverHistoryKey(VolumeId);
+
+impl SwitchOverHistoryKey {
+    pub fn new(id: VolumeId) -> Self {
+        Self(id)
+    }
+}
+
+impl ObjectKey for SwitchOverHistoryKey {
+    fn key_type(&self) -> StorableObjectType {
+        StorableObjectType::FailoverHistory
+    }
+
+    fn key_uuid(&self) -> String {
+        self.0.to_string()
+    }
+}
+
+impl StorableObject for SwitchOverHistory {
+    type Key = SwitchOverHistoryKey;
+
+    fn key(&self) -> Self::Key {
+        SwitchOverHistoryKey(self.volume.clone())
+    }
+}
+
+/// Bounded, time-ordered history of the failed NVMe paths reported by a node.
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+pub struct PathFailureHistory {
+    /// The nodename of the node.
+    pub node_name: NodeId,
+    /// Reported path failures, oldest first.
+    pub records: VecDeque<PathFailureRecord>,
+}
+
+impl PathFailureHistory {
+    /// Create a new empty history for the given node.
+    pub fn new(node_name: NodeId) -> Self {
+        Self {
+            node_name,
+            records: VecDeque::new(),
+        }
+    }
+
+    /// Check if the node has not reported any path failure for longer than the
+    /// `PATH_FAILURE_HISTORY_TTL`, and so the history is no longer of any use.
+    pub fn expired(&self, now: SwitchOverTime) -> bool {
+        let ttl = chrono::Duration::from_std(PATH_FAILURE_HISTORY_TTL)
+            .unwrap_or_else(|_| chrono::Duration::max_value());
+        self.records
+            .back()
+            .map_or(true, |last| now - last.timestamp > ttl)
+    }
+
+    /// Append a record, evicting the oldest one if the history is full.
+    pub fn push(&mut self, record: PathFailureRecord) {
+        if self.records.len() >= HISTORY_CAPACITY {
+            self.records.pop_front();
+        }
+        self.records.push_back(record);
+    }
+}
+
+/// Persistent Store key for `PathFailureHistory`.
+pub struct PathFailureHistoryKey(NodeId);
+
+impl PathFailureHistoryKey {
+    pub fn new(id: NodeId) -> Self {
+        Self(id)
+    }
+}
+
+impl ObjectKey for PathFailureHistoryKey {
+    fn key_type(&self) -> StorableObjectType {
+        StorableObjectType::PathFailureHistory
+    }
+
+    fn key_uuid(&self) -> String {
+        self.0.to_string()
+    }
+}
+
+impl StorableObject for PathFailureHistory {
+    type Key = PathFailureHistoryKey;
+
+    fn key(&self) -> Self::Key {
+        PathFailureHistoryKey(self.node_name.clone())
+    }
+}
+
+/// A planned move of the target of a volume.
+/// It's persisted until the move completes, so that the path of the volume remains reserved
+/// against switchovers and the move is resumed should the cluster-agent restart meanwhile.
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+pub struct TargetMoveSpec {
+    /// The volume whose target is being moved.
+    pub volume: VolumeId,
+    /// NQN of the volume target, reserved while the move is in progress.
+    pub nqn: String,
+    /// The node where the new target should be created, if any.
+    pub target_node: Option<NodeId>,
+    /// The frontend nodes along with the endpoints of their node-agents.
+    pub frontends: Vec<(NodeId, SocketAddr)>,
+    /// New nexus path, once the new target has been created.
+    pub new_path: Option<String>,
+    /// Publish context of the volume, once the new target has been created.
+    pub publish_context: Option<HashMap<String, String>>,
+    /// The frontend nodes which have been connected to the new target so far.
+    pub connected: Vec<NodeId>,
+    /// When the move was requested.
+    pub timestamp: SwitchOverTime,
+}
+
+impl TargetMoveSpec {
+    /// Create a new move of the target of the given volume, which is yet to be started.
+    pub fn new(
+        volume: VolumeId,
+        nqn: String,
+        target_node: Option<NodeId>,
+        frontends: Vec<(NodeId, SocketAddr)>,
+    ) -> Self {
+        Self {
+            volume,
+            nqn,
+            target_node,
+            frontends,
+            new_path: None,
+            publish_context: None,
+            connected: vec![],
+            timestamp: Utc::now(),
+        }
+    }
+}
+
+/// Persistent Store key for `TargetMoveSpec`.
+pub struct TargetMoveKey(VolumeId);
+
+impl TargetMoveKey {
+    pub fn new(id: VolumeId) -> Self {
+        Self(id)
+    }
+}
+
+impl ObjectKey for TargetMoveKey {
+    fn key_type(&self) -> StorableObjectType {
+        StorableObjectType::TargetMove
+    }
+
+    fn key_uuid(&self) -> String {
+        self.0.to_string()
+    }
+}
+
+impl StorableObject for TargetMoveSpec {
+    type Key = TargetMoveKey;
+
+    fn key(&self) -> Self::Key {
+        TargetMoveKey(self.volume.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn path_failure(node: &NodeId, timestamp: SwitchOverTime) -> PathFailureRecord {
+        PathFailureRecord {
+            node_name: node.clone(),
+            nqn: "nqn".to_string(),
+            timestamp,
+        }
+    }
+
+    fn switchover(volume: &VolumeId, finished: SwitchOverTime) -> SwitchOverRecord {
+        SwitchOverRecord {
+            volume: volume.clone(),
+            node_name: "node".into(),
+            existing_nqn: "nqn".to_string(),
+            new_path: None,
+            started: finished,
+            finished,
+            outcome: SwitchOverStage::Successful,
+            error: None,
+            stages: vec![],
+        }
+    }
+
+    #[test]
+    fn history_capacity() {
+        let volume = VolumeId::new();
+        let mut history = SwitchOverHistory::new(volume.clone());
+        let start = Utc::now();
+        let records = (0 .. HISTORY_CAPACITY as i64 + 2)
+            .map(|i| switchover(&volume, start + chrono::Duration::seconds(i)))
+            .collect::<Vec<_>>();
+        records
+            .iter()
+            .cloned()
+            .for_each(|record| history.push(record));
+        // the oldest records are evicted first
+        assert_eq!(history.records.len(), HISTORY_CAPACITY);
+        assert_eq!(history.records.front(), records.get(2));
+        assert_eq!(history.records.back(), records.last());
+
+        let node = NodeId::from("node");
+        let mut history = PathFailureHistory::new(node.clone());
+        let records = (0 .. HISTORY_CAPACITY as i64 + 1)
+            .map(|i| path_failure(&node, start + chrono::Duration::seconds(i)))
+            .collect::<Vec<_>>();
+        records
+            .iter()
+            .cloned()
+            .for_each(|record| history.push(record));
+        assert_eq!(history.records.len(), HISTORY_CAPACITY);
+        assert_eq!(history.records.front(), records.get(1));
+        assert_eq!(history.records.back(), records.last());
+    }
+
+    #[test]
+    fn path_failure_history_expiry() {
+        let node = NodeId::from("node");
+        let now = Utc::now();
+        let ttl = chrono::Duration::from_std(PATH_FAILURE_HISTORY_TTL).unwrap();
+
+        let mut history = PathFailureHistory::new(node.clone());
+        assert!(history.expired(now), "An empty history is of no use");
+
+        history.push(path_failure(
+            &node,
+            now - ttl - chrono::Duration::seconds(1),
+        ));
+        assert!(history.expired(now));
+
+        // only the last reported failure counts
+        history.push(path_failure(&node, now - chrono::Duration::seconds(1)));
+        assert!(!history.expired(now));
+        assert!(history.expired(now + ttl));
+    }
+}
@@ -9,7 +9,8 @@ use crate::{
         },
         transport::{
             self, CreateVolume, HostNqn, NexusId, NexusNvmfConfig, NodeId, ReplicaId, Topology,
-            VolumeId, VolumeLabels, VolumePolicy, VolumeShareProtocol, VolumeStatus,
+            VolumeAccessMode, VolumeContentSource, VolumeEncryption, VolumeId, VolumeLabels,
+            VolumePolicy, VolumeQos, VolumeShareProtocol, VolumeStatus,
         },
     },
     IntoOption,
@@ -40,11 +41,68 @@ impl ObjectKey for VolumeStateKey {
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
 pub struct FrontendConfig {
     host_acl: Vec<InitiatorAC>,
+    /// The frontend nodes which the target is currently published to.
+    #[serde(default)]
+    nodes: Vec<String>,
+    /// The mode with which the target is accessed from the frontend nodes.
+    #[serde(default)]
+    access_mode: VolumeAccessMode,
 }
 impl FrontendConfig {
     /// Create new `Self` based on the host access list.
     pub fn from_acls(host_acl: Vec<InitiatorAC>) -> Self {
-        Self { host_acl }
+        Self {
+            host_acl,
+            nodes: vec![],
+            access_mode: VolumeAccessMode::default(),
+        }
+    }
+    /// Create new `Self` based on the published frontend nodes, the host access list and the
+    /// mode with which the frontend nodes access the target.
+    pub fn new(
+        nodes: Vec<String>,
+        host_acl: Vec<InitiatorAC>,
+        access_mode: VolumeAccessMode,
+    ) -> Self {
+        Self {
+            host_acl,
+            nodes,
+            access_mode,
+        }
+    }
+    /// Get the mode with which the target is accessed from the frontend nodes.
+    pub fn access_mode(&self) -> VolumeAccessMode {
+        self.access_mode
+    }
+    /// Get the frontend nodes which the target is published to.
+    /// Targets published before the nodes were tracked fall back to the host access list.
+    pub fn nodes(&self) -> Vec<String> {
+        match self.nodes.is_empty() {
+            true => self.node_names(),
+            false => self.nodes.clone(),
+        }
+    }
+    /// Check if the target is published to the given frontend node.
+    pub fn node_published(&self, nodename: &str) -> bool {
+        self.nodes().iter().any(|n| n == nodename)
+    }
+    /// Check if access to the target is restricted by the host access list.
+    pub fn host_acl_enabled(&self) -> bool {
+        !self.host_acl.is_empty()
+    }
+    /// Add a frontend node, along with its initiator if access is restricted.
+    pub fn add_node(&mut self, nodename: String, initiator: Option<InitiatorAC>) {
+        self.nodes = self.nodes();
+        if let Some(initiator) = initiator {
+            self.host_acl.push(initiator);
+        }
+        self.nodes.push(nodename);
+    }
+    /// Remove a frontend node, along with its initiator.
+    pub fn remove_node(&mut self, nodename: &str) {
+        self.nodes = self.nodes();
+        self.nodes.retain(|n| n != nodename);
+        self.host_acl.retain(|h| h.node_name() != nodename);
     }
     /// Check if the nodename is allowed.
     pub fn nodename_allowed(&self, nodename: &str) -> bool {
@@ -120,7 +178,18 @@ impl VolumeTarget {
 }
 impl From<VolumeTarget> for models::VolumeTarget {
     fn from(src: VolumeTarget) -> Self {
-        Self::new_all(src.node, src.protocol.into_opt())
+        Self::new_all(src.node, src.protocol.into_opt(), None, None)
+    }
+}
+impl From<TargetConfig> for models::VolumeTarget {
+    fn from(src: TargetConfig) -> Self {
+        let frontend_nodes = src.frontend.nodes();
+        Self::new_all(
+            src.target.node,
+            src.target.protocol.into_opt(),
+            frontend_nodes,
+            src.frontend.access_mode.into_opt(),
+        )
     }
 }
 
@@ -157,6 +226,21 @@ pub struct VolumeSpec {
     /// The publish context of the volume.
     #[serde(default)]
     pub publish_context: Option<HashMap<String, String>>,
+    /// The source from which the volume content was populated, if any.
+    #[serde(default)]
+    pub content_source: Option<VolumeContentSource>,
+    /// The encryption at rest of the volume data, if any.
+    #[serde(default)]
+    pub encryption: Option<VolumeEncryption>,
+    /// The replica which is being moved off its pool to rebalance the pool usage, if any.
+    /// An extra replica is added to the volume while the rebalance is in progress, which is
+    /// not accounted for in `num_replicas`, see `VolumeSpec::effective_num_replicas`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rebalance: Option<ReplicaId>,
+    /// The previous target of a planned target move, which keeps serving the frontend nodes
+    /// until they're connected to the new target, and is shut down afterwards.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub moved_target: Option<NexusId>,
 }
 
 /// The volume's Nvmf Configuration.
@@ -242,15 +326,25 @@ impl VolumeSpec {
                 .unwrap_or_default(),
         }
     }
-    /// Desired volume replica count if during `SetReplica` operation
-    /// or otherwise the current num_replicas.
+    /// Desired volume replica count if during `SetReplica` or a rebalance operation
+    /// or otherwise the current effective replica count.
     pub fn desired_num_replicas(&self) -> u8 {
         match &self.operation {
             Some(operation) => match operation.operation {
                 VolumeOperation::SetReplica(count) => count,
-                _ => self.num_replicas,
+                VolumeOperation::StartRebalance(_) => self.num_replicas.saturating_add(1),
+                VolumeOperation::CompleteRebalance(_) => self.num_replicas,
+                _ => self.effective_num_replicas(),
             },
-            _ => self.num_replicas,
+            _ => self.effective_num_replicas(),
+        }
+    }
+    /// Number of replicas which the volume currently needs, ie: the replica count plus the
+    /// extra replica which is added while a replica is moved off its pool.
+    pub fn effective_num_replicas(&self) -> u8 {
+        match self.rebalance {
+            Some(_) => self.num_replicas.saturating_add(1),
+            None => self.num_replicas,
         }
     }
     /// Get the last known target configuration.
@@ -317,8 +411,18 @@ impl SpecTransaction<VolumeOperation> for VolumeSpec {
                         target.protocol = None
                     }
                 }
-                VolumeOperation::SetReplica(count) => self.num_replicas = count,
+                VolumeOperation::SetReplica(count) => {
+                    self.num_replicas = count;
+                    self.rebalance = None;
+                }
+                VolumeOperation::StartRebalance(replica) => {
+                    self.rebalance = Some(replica);
+                }
+                VolumeOperation::CompleteRebalance(_) => {
+                    self.rebalance = None;
+                }
                 VolumeOperation::RemoveUnusedReplica(_) => {}
+                VolumeOperation::Resize(size) => self.size = size,
                 VolumeOperation::PublishOld(args) => {
                     let (node, nexus, protocol) = (args.node, args.nexus, args.protocol);
                     let target = VolumeTarget::new(node, nexus, protocol);
@@ -337,9 +441,24 @@ impl SpecTransaction<VolumeOperation> for VolumeSpec {
                 VolumeOperation::Republish(args) => {
                     self.last_nexus_id = None;
                     self.target_config = Some(args.config);
+                    self.moved_target = args.moved_target;
                 }
                 VolumeOperation::Unpublish => {
                     self.deactivate_target();
+                    self.moved_target = None;
+                }
+                VolumeOperation::AddFrontendNode(node, initiator) => {
+                    if let Some(cfg) = self.target_config.as_mut() {
+                        cfg.frontend.add_node(node, initiator);
+                    }
+                }
+                VolumeOperation::RemoveFrontendNode(node) => {
+                    if let Some(cfg) = self.target_config.as_mut() {
+                        cfg.frontend.remove_node(&node);
+                    }
+                }
+                VolumeOperation::SetQos(qos) => {
+                    self.policy.qos = if qos.limited() { Some(qos) } else { None };
                 }
             }
         }
@@ -379,6 +498,12 @@ pub enum VolumeOperation {
     Republish(RepublishOperation),
     Unpublish,
     RemoveUnusedReplica(ReplicaId),
+    Resize(u64),
+    AddFrontendNode(String, Option<InitiatorAC>),
+    RemoveFrontendNode(String),
+    SetQos(VolumeQos),
+    StartRebalance(ReplicaId),
+    CompleteRebalance(ReplicaId),
 }
 
 #[test]
@@ -408,6 +533,58 @@ fn volume_op_deserializer() {
     }
 }
 
+#[test]
+fn volume_rebalance_transitions() {
+    let mut spec = VolumeSpec::from(&CreateVolume {
+        uuid: VolumeId::new(),
+        replicas: 2,
+        ..Default::default()
+    });
+    let replica = ReplicaId::new();
+    assert_eq!(spec.effective_num_replicas(), 2);
+    assert_eq!(spec.desired_num_replicas(), 2);
+
+    // an extra replica is needed while the rebalance is in progress, but the replica count of
+    // the volume stays the same
+    spec.start_op(VolumeOperation::StartRebalance(replica.clone()));
+    assert_eq!(spec.desired_num_replicas(), 3);
+    spec.commit_op();
+    assert_eq!(spec.rebalance, Some(replica.clone()));
+    assert_eq!(spec.num_replicas, 2);
+    assert_eq!(spec.effective_num_replicas(), 3);
+    assert_eq!(spec.desired_num_replicas(), 3);
+
+    // a failed completion is rolled back, leaving the rebalance in progress
+    spec.start_op(VolumeOperation::CompleteRebalance(replica.clone()));
+    assert_eq!(spec.desired_num_replicas(), 2);
+    spec.set_op_result(false);
+    spec.clear_op();
+    assert_eq!(spec.rebalance, Some(replica.clone()));
+    assert_eq!(spec.effective_num_replicas(), 3);
+
+    spec.start_op(VolumeOperation::CompleteRebalance(replica.clone()));
+    spec.commit_op();
+    assert_eq!(spec.rebalance, None);
+    assert_eq!(spec.num_replicas, 2);
+    assert_eq!(spec.effective_num_replicas(), 2);
+
+    // a failed start leaves the volume as it was
+    spec.start_op(VolumeOperation::StartRebalance(replica.clone()));
+    spec.set_op_result(false);
+    spec.clear_op();
+    assert_eq!(spec.rebalance, None);
+    assert_eq!(spec.effective_num_replicas(), 2);
+
+    // changing the replica count takes over from a rebalance in progress
+    spec.start_op(VolumeOperation::StartRebalance(replica));
+    spec.commit_op();
+    spec.start_op(VolumeOperation::SetReplica(3));
+    assert_eq!(spec.desired_num_replicas(), 3);
+    spec.commit_op();
+    assert_eq!(spec.rebalance, None);
+    assert_eq!(spec.effective_num_replicas(), 3);
+}
+
 /// The `PublishOperation` which is easier to manage and update.
 #[derive(serde_tuple::Serialize_tuple, serde_tuple::Deserialize_tuple, Debug, Clone, PartialEq)]
 pub struct OldPublishOperation {
@@ -444,11 +621,23 @@ impl PublishOperation {
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct RepublishOperation {
     config: TargetConfig,
+    /// The current target, if its shutdown is deferred until the frontend nodes are connected
+    /// to the new target.
+    #[serde(default)]
+    moved_target: Option<NexusId>,
 }
 impl RepublishOperation {
     /// Return new `Self` from the given parameters.
     pub fn new(config: TargetConfig) -> Self {
-        Self { config }
+        Self {
+            config,
+            moved_target: None,
+        }
+    }
+    /// Defer the shutdown of the given current target.
+    pub fn with_moved_target(mut self, moved_target: Option<NexusId>) -> Self {
+        self.moved_target = moved_target;
+        self
     }
     /// Get the share protocol.
     pub fn protocol(&self) -> VolumeShareProtocol {
@@ -471,6 +660,20 @@ impl From<VolumeOperation> for models::volume_spec_operation::Operation {
             VolumeOperation::RemoveUnusedReplica(_) => {
                 models::volume_spec_operation::Operation::RemoveUnusedReplica
             }
+            VolumeOperation::Resize(_) => models::volume_spec_operation::Operation::Resize,
+            VolumeOperation::AddFrontendNode(..) => {
+                models::volume_spec_operation::Operation::AddFrontendNode
+            }
+            VolumeOperation::RemoveFrontendNode(_) => {
+                models::volume_spec_operation::Operation::RemoveFrontendNode
+            }
+            VolumeOperation::SetQos(_) => models::volume_spec_operation::Operation::SetQos,
+            VolumeOperation::StartRebalance(_) => {
+                models::volume_spec_operation::Operation::StartRebalance
+            }
+            VolumeOperation::CompleteRebalance(_) => {
+                models::volume_spec_operation::Operation::CompleteRebalance
+            }
         }
     }
 }
@@ -521,6 +724,10 @@ impl From<&CreateVolume> for VolumeSpec {
             thin: request.thin,
             target_config: None,
             publish_context: None,
+            content_source: request.content_source.clone(),
+            encryption: request.encryption.clone(),
+            rebalance: None,
+            moved_target: None,
         }
     }
 }
@@ -558,7 +765,7 @@ impl PartialEq<transport::VolumeState> for VolumeSpec {
 
 impl From<VolumeSpec> for models::VolumeSpec {
     fn from(src: VolumeSpec) -> Self {
-        let target = src.target().cloned();
+        let target = src.active_config().cloned();
         Self::new_all(
             src.labels,
             src.num_replicas,
@@ -570,6 +777,8 @@ impl From<VolumeSpec> for models::VolumeSpec {
             src.topology.into_opt(),
             src.policy,
             src.thin,
+            src.content_source.into_opt(),
+            src.encryption.into_opt(),
         )
     }
 }
@@ -1,4 +1,5 @@
 use super::*;
+use crate::types::v0::store::switchover::SwitchOverTime;
 use serde::{Deserialize, Serialize};
 use std::{collections::HashMap, net::SocketAddr};
 
@@ -126,6 +127,8 @@ pub struct ReplacePath {
     target_nqn: String,
     new_path: String,
     publish_context: Option<HashMap<String, String>>,
+    /// The path being replaced is healthy, as the target is moved on purpose.
+    planned: bool,
 }
 
 impl ReplacePath {
@@ -140,9 +143,16 @@ impl ReplacePath {
             target_nqn,
             new_path,
             publish_context,
+            planned: false,
         }
     }
 
+    /// Mark the replacement as planned, ie the path being replaced is healthy.
+    pub fn with_planned(mut self, planned: bool) -> Self {
+        self.planned = planned;
+        self
+    }
+
     /// Get failed nexus path.
     pub fn target(&self) -> &str {
         &self.target_nqn
@@ -157,4 +167,169 @@ impl ReplacePath {
     pub fn publish_context(&self) -> Option<HashMap<String, String>> {
         self.publish_context.clone()
     }
+
+    /// Check if the replacement is planned.
+    pub fn planned(&self) -> bool {
+        self.planned
+    }
+}
+
+/// Stage of a volume switchover request.
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, Eq, PartialEq)]
+pub enum SwitchOverStage {
+    /// Initialize switchover request.
+    Init,
+    /// Shutdown original/old volume target. Create new nexus for existing vol obj.
+    RepublishVolume,
+    /// Send updated path of volume to node-agent.
+    ReplacePath,
+    /// Delete original/old volume target.
+    DeleteTarget,
+    /// Marks switchover process as Complete.
+    Successful,
+    /// Represent failed switchover request.
+    Errored,
+}
+
+impl From<SwitchOverStage> for models::SwitchOverStage {
+    fn from(src: SwitchOverStage) -> Self {
+        match src {
+            SwitchOverStage::Init => Self::Init,
+            SwitchOverStage::RepublishVolume => Self::RepublishVolume,
+            SwitchOverStage::ReplacePath => Self::ReplacePath,
+            SwitchOverStage::DeleteTarget => Self::DeleteTarget,
+            SwitchOverStage::Successful => Self::Successful,
+            SwitchOverStage::Errored => Self::Errored,
+        }
+    }
+}
+
+/// A volume switchover request, either in-flight or recently failed.
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+pub struct SwitchOver {
+    /// Volume for which the switchover is executed.
+    pub volume_id: VolumeId,
+    /// The nodename of the request's originator node.
+    pub node_name: NodeId,
+    /// Current stage of the switchover.
+    pub stage: SwitchOverStage,
+    /// Timestamp when the switchover request was initialized.
+    pub timestamp: SwitchOverTime,
+    /// Number of failed attempts in the current stage.
+    pub retry_count: u64,
+    /// Failed nexus path of the volume.
+    pub existing_nqn: String,
+    /// New nexus path of the volume.
+    pub new_path: Option<String>,
+    /// Error message of the last failed attempt.
+    pub error: Option<String>,
+}
+
+impl From<SwitchOver> for models::SwitchOver {
+    fn from(src: SwitchOver) -> Self {
+        Self::new_all(
+            src.volume_id,
+            src.node_name,
+            src.stage,
+            src.timestamp.to_rfc3339(),
+            src.retry_count,
+            src.existing_nqn,
+            src.new_path,
+            src.error,
+        )
+    }
+}
+
+/// List of volume switchovers.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct SwitchOvers(pub Vec<SwitchOver>);
+
+impl SwitchOvers {
+    /// Get the inner list of switchovers.
+    pub fn into_inner(self) -> Vec<SwitchOver> {
+        self.0
+    }
+}
+
+/// Get the in-flight and recently failed switchovers, optionally filtered by volume.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct GetSwitchOvers {
+    /// Only return switchovers of this volume.
+    volume_id: Option<VolumeId>,
+}
+
+impl GetSwitchOvers {
+    /// Creates a new request for all switchovers or those of the given volume only.
+    pub fn new(volume_id: Option<VolumeId>) -> Self {
+        Self { volume_id }
+    }
+
+    /// Get the volume filter.
+    pub fn volume_id(&self) -> Option<&VolumeId> {
+        self.volume_id.as_ref()
+    }
+}
+
+/// Cancel the in-flight switchover of a volume.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CancelSwitchOver {
+    volume_id: VolumeId,
+}
+
+impl CancelSwitchOver {
+    /// Creates a new request to cancel the switchover of the given volume.
+    pub fn new(volume_id: VolumeId) -> Self {
+        Self { volume_id }
+    }
+
+    /// Get the volume id.
+    pub fn volume_id(&self) -> &VolumeId {
+        &self.volume_id
+    }
+}
+
+/// Retry the failed switchover of a volume from the beginning.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct RetrySwitchOver {
+    volume_id: VolumeId,
+}
+
+impl RetrySwitchOver {
+    /// Creates a new request to retry the failed switchover of the given volume.
+    pub fn new(volume_id: VolumeId) -> Self {
+        Self { volume_id }
+    }
+
+    /// Get the volume id.
+    pub fn volume_id(&self) -> &VolumeId {
+        &self.volume_id
+    }
+}
+
+/// Move the target of a published volume to another node, without failing the existing path.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct MoveVolumeTarget {
+    volume_id: VolumeId,
+    /// The node where the new target should be created, if any.
+    target_node: Option<NodeId>,
+}
+
+impl MoveVolumeTarget {
+    /// Creates a new request to move the target of the given volume.
+    pub fn new(volume_id: VolumeId, target_node: Option<NodeId>) -> Self {
+        Self {
+            volume_id,
+            target_node,
+        }
+    }
+
+    /// Get the volume id.
+    pub fn volume_id(&self) -> &VolumeId {
+        &self.volume_id
+    }
+
+    /// Get the requested target node.
+    pub fn target_node(&self) -> Option<&NodeId> {
+        self.target_node.as_ref()
+    }
 }
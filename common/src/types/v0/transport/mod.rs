@@ -8,6 +8,7 @@ pub mod node;
 pub mod nvme_nqn;
 pub mod pool;
 pub mod replica;
+pub mod snapshot;
 pub mod spec;
 pub mod state;
 pub mod volume;
@@ -23,6 +24,7 @@ pub use node::*;
 pub use nvme_nqn::{NvmeNqn as HostNqn, NvmeNqnParseError as HostNqnParseError, *};
 pub use pool::*;
 pub use replica::*;
+pub use snapshot::*;
 pub use spec::*;
 pub use state::*;
 pub use volume::*;
@@ -61,6 +63,8 @@ pub enum MessageIdVs {
     GetNodes,
     /// Cordon a node.
     CordonNode,
+    /// Set the rebuild bandwidth limit of a node.
+    SetRebuildBandwidth,
     /// Pool Service
     ///
     /// Get pools with filter.
@@ -69,6 +73,12 @@ pub enum MessageIdVs {
     CreatePool,
     /// Destroy Pool.
     DestroyPool,
+    /// Expand Pool.
+    ExpandPool,
+    /// Label Pool.
+    LabelPool,
+    /// Unlabel Pool.
+    UnlabelPool,
     /// Get replicas with filter.
     GetReplicas,
     /// Create Replica.
@@ -79,6 +89,16 @@ pub enum MessageIdVs {
     ShareReplica,
     /// Unshare Replica.
     UnshareReplica,
+    /// Resize Replica.
+    ResizeReplica,
+    /// Create Replica Snapshot.
+    CreateReplicaSnapshot,
+    /// Destroy Replica Snapshot.
+    DestroyReplicaSnapshot,
+    /// Create Replica from a Snapshot.
+    CreateSnapshotClone,
+    /// Create Nexus Snapshot.
+    CreateNexusSnapshot,
     /// Volume Service
     ///
     /// Get nexuses with filter.
@@ -91,6 +111,10 @@ pub enum MessageIdVs {
     ShareNexus,
     /// Unshare Nexus.
     UnshareNexus,
+    /// Resize Nexus.
+    ResizeNexus,
+    /// Set Nexus QoS limits.
+    SetNexusQos,
     /// Remove a child from its parent nexus.
     RemoveNexusChild,
     /// Add a child to a nexus.
@@ -119,6 +143,16 @@ pub enum MessageIdVs {
     RemoveVolumeNexus,
     /// Set replica count.
     SetVolumeReplica,
+    /// Resize Volume.
+    ResizeVolume,
+    /// Set Volume QoS limits.
+    SetVolumeQos,
+    /// Create Volume Snapshot.
+    CreateVolumeSnapshot,
+    /// Delete Volume Snapshot.
+    DestroyVolumeSnapshot,
+    /// Get Volume Snapshots.
+    GetVolumeSnapshots,
     /// Generic JSON gRPC message.
     JsonGrpc,
     /// Get block devices.
@@ -129,14 +163,30 @@ pub enum MessageIdVs {
     GetWatches,
     /// Delete Resource Watch.
     DeleteWatch,
+    /// Stream resource change events.
+    WatchEvents,
     /// Get Specs.
     GetSpecs,
     /// Get States.
     GetStates,
+    /// Get the HA history of path failures and switchovers.
+    GetHaHistory,
+    /// Get the history of the volume replica rebuilds.
+    GetRebuildHistory,
+    /// Get the queue of the pending volume replica rebuilds.
+    GetRebuildQueue,
     /// High Availability Agents.
     RegisterHaNode,
     /// Report failed NVMe paths.
     ReportFailedPaths,
+    /// Get volume switchovers.
+    GetSwitchOvers,
+    /// Cancel a volume switchover.
+    CancelSwitchOver,
+    /// Retry a failed volume switchover.
+    RetrySwitchOver,
+    /// Move the target of a volume.
+    MoveVolumeTarget,
     /// Shutdown Nexus
     ShutdownNexus,
     /// Replace Path
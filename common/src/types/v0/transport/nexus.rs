@@ -188,6 +188,9 @@ pub struct CreateNexus {
     pub owner: Option<VolumeId>,
     /// Nexus Nvmf Configuration
     pub config: Option<NexusNvmfConfig>,
+    /// QoS limits of the nexus, if any
+    #[serde(default)]
+    pub qos: Option<VolumeQos>,
 }
 
 /// NVMe reservation types.
@@ -389,8 +392,14 @@ impl CreateNexus {
             managed,
             owner: owner.cloned(),
             config,
+            qos: None,
         }
     }
+    /// Create the nexus with the given `qos` limits, if they limit anything at all.
+    pub fn with_qos(mut self, qos: Option<&VolumeQos>) -> Self {
+        self.qos = qos.filter(|qos| qos.limited()).cloned();
+        self
+    }
     /// Name of the nexus.
     /// When part of a volume, it's set to its `VolumeId`. Otherwise it's set to its `NexusId`.
     pub fn name(&self) -> String {
@@ -585,3 +594,47 @@ pub struct UnshareNexus {
     /// uuid of the nexus
     pub uuid: NexusId,
 }
+
+/// Resize Nexus Request
+#[derive(Serialize, Deserialize, Default, Debug, Clone, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ResizeNexus {
+    /// id of the io-engine instance
+    pub node: NodeId,
+    /// uuid of the nexus
+    pub uuid: NexusId,
+    /// the new size of the nexus in bytes
+    pub requested_size: u64,
+}
+impl ResizeNexus {
+    /// Create new `Self` to resize the given nexus to the `requested_size`.
+    pub fn new(node: &NodeId, uuid: &NexusId, requested_size: u64) -> Self {
+        Self {
+            node: node.clone(),
+            uuid: uuid.clone(),
+            requested_size,
+        }
+    }
+}
+
+/// Set Nexus QoS Request
+#[derive(Serialize, Deserialize, Default, Debug, Clone, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SetNexusQos {
+    /// id of the io-engine instance
+    pub node: NodeId,
+    /// uuid of the nexus
+    pub uuid: NexusId,
+    /// the QoS limits which replace the existing ones
+    pub qos: VolumeQos,
+}
+impl SetNexusQos {
+    /// Create new `Self` to set the `qos` limits on the given nexus.
+    pub fn new(node: &NodeId, uuid: &NexusId, qos: &VolumeQos) -> Self {
+        Self {
+            node: node.clone(),
+            uuid: uuid.clone(),
+            qos: qos.clone(),
+        }
+    }
+}
@@ -57,6 +57,25 @@ impl GetNodes {
     }
 }
 
+/// Set the bandwidth limit of the replica rebuilds on a node
+#[derive(Serialize, Deserialize, Default, Debug, Clone, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SetRebuildBandwidth {
+    /// id of the io-engine instance
+    pub node: NodeId,
+    /// bandwidth limit in MB/s shared by all the rebuilds on the node, 0 meaning unlimited
+    pub mbps: u64,
+}
+impl SetRebuildBandwidth {
+    /// Create new `Self` to limit the rebuilds on the given node to `mbps`.
+    pub fn new(node: &NodeId, mbps: u64) -> Self {
+        Self {
+            node: node.clone(),
+            mbps,
+        }
+    }
+}
+
 /// Node information
 #[derive(Serialize, Deserialize, Default, Debug, Clone, PartialEq)]
 #[serde(rename_all = "camelCase")]
@@ -281,3 +281,53 @@ pub struct DestroyPool {
     /// id of the pool
     pub id: PoolId,
 }
+
+/// Label Pool Request
+/// The value of an existing label is only changed if `overwrite` is set.
+#[derive(Serialize, Deserialize, Default, Debug, Clone, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct LabelPool {
+    /// id of the pool
+    pub id: PoolId,
+    /// key of the label
+    pub key: String,
+    /// value of the label
+    pub value: String,
+    /// overwrite the value of the label if the pool already has it
+    pub overwrite: bool,
+}
+
+/// Unlabel Pool Request
+#[derive(Serialize, Deserialize, Default, Debug, Clone, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct UnlabelPool {
+    /// id of the pool
+    pub id: PoolId,
+    /// key of the label
+    pub key: String,
+}
+
+/// Expand Pool Request
+/// The pool claims any disks which it does not yet claim and grows into any extra capacity
+/// which may have become available on the disks it already claims.
+#[derive(Serialize, Deserialize, Default, Debug, Clone, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ExpandPool {
+    /// id of the io-engine instance
+    pub node: NodeId,
+    /// id of the pool
+    pub id: PoolId,
+    /// disk device paths or URIs to be claimed by the pool, including the existing ones
+    pub disks: Vec<PoolDeviceUri>,
+}
+
+impl ExpandPool {
+    /// Create new `Self` from the given parameters
+    pub fn new(node: &NodeId, id: &PoolId, disks: &[PoolDeviceUri]) -> Self {
+        Self {
+            node: node.clone(),
+            id: id.clone(),
+            disks: disks.to_vec(),
+        }
+    }
+}
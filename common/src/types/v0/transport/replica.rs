@@ -167,6 +167,9 @@ pub struct CreateReplica {
     pub owners: ReplicaOwners,
     /// Host nqn's allowed to connect to the target.
     pub allowed_hosts: Vec<HostNqn>,
+    /// Encryption at rest of the replica data.
+    #[serde(default)]
+    pub encryption: Option<VolumeEncryption>,
 }
 
 /// Replica owners which is a volume or none and a list of nexuses
@@ -421,6 +424,37 @@ pub struct UnshareReplica {
     pub name: Option<ReplicaName>,
 }
 
+/// Resize Replica Request
+#[derive(Serialize, Deserialize, Default, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ResizeReplica {
+    /// id of the io-engine instance
+    pub node: NodeId,
+    /// id of the pool
+    pub pool_id: PoolId,
+    /// uuid of the pool
+    pub pool_uuid: Option<PoolUuid>,
+    /// uuid of the replica
+    pub uuid: ReplicaId,
+    /// name of the replica
+    pub name: Option<ReplicaName>,
+    /// the new size of the replica in bytes
+    pub requested_size: u64,
+}
+impl ResizeReplica {
+    /// Create new `Self` to resize the given replica to the `requested_size`.
+    pub fn new(replica: &Replica, requested_size: u64) -> Self {
+        Self {
+            node: replica.node.clone(),
+            pool_id: replica.pool_id.clone(),
+            pool_uuid: replica.pool_uuid.clone(),
+            uuid: replica.uuid.clone(),
+            name: replica.name.clone().into(),
+            requested_size,
+        }
+    }
+}
+
 /// The protocol used to share the replica.
 #[derive(Serialize, Deserialize, Debug, Clone, Copy, EnumString, ToString, Eq, PartialEq)]
 #[strum(serialize_all = "camelCase")]
@@ -0,0 +1,244 @@
+use super::*;
+
+use crate::types::v0::store::snapshot::{SnapshotTime, VolumeSnapshotSpec};
+use serde::{Deserialize, Serialize};
+use std::fmt::Debug;
+
+rpc_impl_string_uuid!(SnapshotId, "UUID of a snapshot");
+
+/// Volume Snapshot
+///
+/// Volume Snapshot information.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct VolumeSnapshot {
+    /// Desired specification of the snapshot.
+    spec: VolumeSnapshotSpec,
+    /// Runtime state of the snapshot.
+    state: VolumeSnapshotState,
+}
+
+impl VolumeSnapshot {
+    /// Construct a new volume snapshot.
+    pub fn new(spec: VolumeSnapshotSpec, state: VolumeSnapshotState) -> Self {
+        Self { spec, state }
+    }
+    /// Get the snapshot spec.
+    pub fn spec(&self) -> &VolumeSnapshotSpec {
+        &self.spec
+    }
+    /// Get the snapshot state.
+    pub fn state(&self) -> &VolumeSnapshotState {
+        &self.state
+    }
+    /// Get the snapshot's uuid.
+    pub fn uuid(&self) -> &SnapshotId {
+        &self.spec.uuid
+    }
+    /// Get the uuid of the source volume.
+    pub fn source_id(&self) -> &VolumeId {
+        &self.spec.source_id
+    }
+}
+
+impl From<VolumeSnapshot> for models::VolumeSnapshot {
+    fn from(snapshot: VolumeSnapshot) -> Self {
+        Self::new_all(snapshot.spec, snapshot.state)
+    }
+}
+
+/// Runtime state of a volume snapshot.
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct VolumeSnapshotState {
+    /// Uuid of the snapshot.
+    pub uuid: SnapshotId,
+    /// Uuid of the volume from which the snapshot was taken.
+    pub source_id: VolumeId,
+    /// Time at which the snapshot was taken.
+    pub timestamp: Option<SnapshotTime>,
+    /// Size of the source volume at the time the snapshot was taken.
+    pub size: u64,
+    /// The snapshot can be used as a source for new volumes.
+    pub ready_as_source: bool,
+}
+
+impl From<&VolumeSnapshotSpec> for VolumeSnapshotState {
+    fn from(spec: &VolumeSnapshotSpec) -> Self {
+        Self {
+            uuid: spec.uuid.clone(),
+            source_id: spec.source_id.clone(),
+            timestamp: spec.timestamp,
+            size: spec.size,
+            ready_as_source: spec.status.created() && !spec.replica_snapshots.is_empty(),
+        }
+    }
+}
+
+impl From<VolumeSnapshotState> for models::VolumeSnapshotState {
+    fn from(src: VolumeSnapshotState) -> Self {
+        Self::new_all(
+            src.uuid,
+            src.source_id,
+            src.size,
+            src.ready_as_source,
+            src.timestamp.map(|t| t.to_rfc3339()),
+        )
+    }
+}
+
+/// Create volume snapshot request.
+#[derive(Serialize, Deserialize, Default, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateVolumeSnapshot {
+    /// The uuid of the source volume.
+    pub source_id: VolumeId,
+    /// The uuid of the snapshot.
+    pub snap_id: SnapshotId,
+}
+impl CreateVolumeSnapshot {
+    /// Create new `Self` from the given volume and snapshot ids.
+    pub fn new(source_id: &VolumeId, snap_id: SnapshotId) -> Self {
+        Self {
+            source_id: source_id.clone(),
+            snap_id,
+        }
+    }
+}
+
+/// Delete volume snapshot request.
+#[derive(Serialize, Deserialize, Default, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct DestroyVolumeSnapshot {
+    /// The uuid of the source volume, if known.
+    pub source_id: Option<VolumeId>,
+    /// The uuid of the snapshot.
+    pub snap_id: SnapshotId,
+}
+impl DestroyVolumeSnapshot {
+    /// Create new `Self` from the given volume and snapshot ids.
+    pub fn new(source_id: Option<VolumeId>, snap_id: SnapshotId) -> Self {
+        Self { source_id, snap_id }
+    }
+}
+
+/// Get volume snapshots request.
+#[derive(Serialize, Deserialize, Default, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct GetVolumeSnapshots {
+    /// Retrieve only the snapshots of this volume.
+    pub source_id: Option<VolumeId>,
+    /// Retrieve only the snapshot with this uuid.
+    pub snap_id: Option<SnapshotId>,
+}
+impl GetVolumeSnapshots {
+    /// Create new `Self` from the given optional volume and snapshot ids.
+    pub fn new(source_id: Option<VolumeId>, snap_id: Option<SnapshotId>) -> Self {
+        Self { source_id, snap_id }
+    }
+}
+
+/// Create a snapshot of a replica on an io-engine node.
+#[derive(Serialize, Deserialize, Default, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateReplicaSnapshot {
+    /// The id of the io-engine instance.
+    pub node: NodeId,
+    /// The uuid of the replica to snapshot.
+    pub replica_id: ReplicaId,
+    /// The uuid of the replica snapshot.
+    pub snap_id: SnapshotId,
+    /// The name of the replica snapshot.
+    pub snap_name: String,
+    /// The id of the entity which owns the snapshot, eg: the volume.
+    pub entity_id: String,
+    /// The transaction id, used to group snapshots taken as part of the same request.
+    pub txn_id: String,
+}
+
+/// Create a snapshot of the replicas of a nexus on an io-engine node.
+/// The nexus pauses its I/O while the replica snapshots are taken, making them crash-consistent.
+#[derive(Serialize, Deserialize, Default, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateNexusSnapshot {
+    /// The id of the io-engine instance.
+    pub node: NodeId,
+    /// The uuid of the nexus.
+    pub nexus: NexusId,
+    /// The replicas to snapshot and the uuid of their snapshots.
+    pub replicas: Vec<NexusSnapshotReplica>,
+    /// The name of the replica snapshots.
+    pub snap_name: String,
+    /// The id of the entity which owns the snapshot, eg: the volume.
+    pub entity_id: String,
+    /// The transaction id, used to group snapshots taken as part of the same request.
+    pub txn_id: String,
+}
+
+/// A replica to snapshot as part of a `CreateNexusSnapshot` request.
+#[derive(Serialize, Deserialize, Default, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct NexusSnapshotReplica {
+    /// The uuid of the replica to snapshot.
+    pub replica_id: ReplicaId,
+    /// The uuid of the replica snapshot.
+    pub snap_id: SnapshotId,
+}
+
+/// Outcome of a `CreateNexusSnapshot` request.
+#[derive(Serialize, Deserialize, Default, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct NexusSnapshotStatus {
+    /// The replicas which have been snapshotted.
+    pub replicas_done: Vec<ReplicaId>,
+    /// The replicas which failed to be snapshotted, with the io-engine status code.
+    pub replicas_failed: Vec<(ReplicaId, u32)>,
+    /// The replicas which were skipped, eg: because they're not healthy.
+    pub replicas_skipped: Vec<ReplicaId>,
+}
+
+/// Snapshot of a replica, as reported by an io-engine node.
+#[derive(Serialize, Deserialize, Default, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplicaSnapshotInfo {
+    /// The id of the io-engine instance.
+    pub node: NodeId,
+    /// The uuid of the replica snapshot.
+    pub snap_id: SnapshotId,
+    /// The uuid of the source replica.
+    pub replica_id: ReplicaId,
+    /// The size of the snapshot in bytes.
+    pub size: u64,
+}
+
+/// Destroy a replica snapshot on an io-engine node.
+#[derive(Serialize, Deserialize, Default, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct DestroyReplicaSnapshot {
+    /// The id of the io-engine instance.
+    pub node: NodeId,
+    /// The pool where the replica snapshot lives.
+    pub pool_id: PoolId,
+    /// The uuid of the replica snapshot.
+    pub snap_id: SnapshotId,
+}
+
+/// Create a new replica from a replica snapshot on an io-engine node.
+/// The replica is created on the same pool as the replica snapshot.
+#[derive(Serialize, Deserialize, Default, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateSnapshotClone {
+    /// The uuid of the replica snapshot to clone.
+    pub snap_id: SnapshotId,
+    /// The definition of the replica clone.
+    pub replica: CreateReplica,
+}
+impl CreateSnapshotClone {
+    /// Create new `Self` from the given replica snapshot and replica definition.
+    pub fn new(snap_id: &SnapshotId, replica: CreateReplica) -> Self {
+        Self {
+            snap_id: snap_id.clone(),
+            replica,
+        }
+    }
+}
@@ -2,7 +2,13 @@ use serde::{Deserialize, Serialize};
 use std::fmt::Debug;
 
 use super::*;
-use crate::types::v0::store::{nexus, pool, replica, volume};
+use crate::types::v0::store::{
+    nexus, pool,
+    rebuild::{RebuildOutcome, RebuildReason, RebuildRecord},
+    replica,
+    switchover::{PathFailureRecord, StageTiming, SwitchOverRecord},
+    volume,
+};
 
 /// Retrieve all specs from core agent
 #[derive(Serialize, Deserialize, Default, Debug, Clone)]
@@ -28,3 +34,205 @@ impl From<Specs> for models::Specs {
         Self::new(src.nexuses, src.pools, src.replicas, src.volumes)
     }
 }
+
+/// Retrieve the persisted HA history of path failures and switchover outcomes.
+#[derive(Serialize, Deserialize, Default, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GetHaHistory {
+    /// Only return the history of this volume.
+    volume_id: Option<VolumeId>,
+    /// Only return the history of this node.
+    node_id: Option<NodeId>,
+}
+
+impl GetHaHistory {
+    /// Creates a new request for the history, optionally filtered by volume and/or node.
+    pub fn new(volume_id: Option<VolumeId>, node_id: Option<NodeId>) -> Self {
+        Self { volume_id, node_id }
+    }
+
+    /// Get the volume filter.
+    pub fn volume_id(&self) -> Option<&VolumeId> {
+        self.volume_id.as_ref()
+    }
+
+    /// Get the node filter.
+    pub fn node_id(&self) -> Option<&NodeId> {
+        self.node_id.as_ref()
+    }
+}
+
+/// Persisted HA history, each list ordered by time (oldest first).
+#[derive(Serialize, Deserialize, Default, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct HaHistory {
+    /// NVMe path failures reported by the nodes.
+    pub path_failures: Vec<PathFailureRecord>,
+    /// Outcomes of the finished switchovers.
+    pub switchovers: Vec<SwitchOverRecord>,
+}
+
+impl From<HaHistory> for models::HaHistory {
+    fn from(src: HaHistory) -> Self {
+        Self::new(src.path_failures, src.switchovers)
+    }
+}
+
+impl From<PathFailureRecord> for models::PathFailureRecord {
+    fn from(src: PathFailureRecord) -> Self {
+        Self::new(src.node_name, src.nqn, src.timestamp.to_rfc3339())
+    }
+}
+
+impl From<StageTiming> for models::StageTiming {
+    fn from(src: StageTiming) -> Self {
+        Self::new_all(
+            src.stage,
+            src.started.to_rfc3339(),
+            src.finished.map(|finished| finished.to_rfc3339()),
+            src.attempts,
+        )
+    }
+}
+
+impl From<SwitchOverRecord> for models::SwitchOverRecord {
+    fn from(src: SwitchOverRecord) -> Self {
+        Self::new_all(
+            src.volume,
+            src.node_name,
+            src.existing_nqn,
+            src.new_path,
+            src.started.to_rfc3339(),
+            src.finished.to_rfc3339(),
+            src.outcome,
+            src.error,
+            src.stages,
+        )
+    }
+}
+
+/// Retrieve the persisted history of the volume replica rebuilds.
+#[derive(Serialize, Deserialize, Default, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GetRebuildHistory {
+    /// Only return the history of this volume.
+    volume_id: Option<VolumeId>,
+}
+
+impl GetRebuildHistory {
+    /// Creates a new request for the history, optionally filtered by volume.
+    pub fn new(volume_id: Option<VolumeId>) -> Self {
+        Self { volume_id }
+    }
+
+    /// Get the volume filter.
+    pub fn volume_id(&self) -> Option<&VolumeId> {
+        self.volume_id.as_ref()
+    }
+}
+
+/// Running and finished volume replica rebuilds, ordered by their start time (oldest first).
+#[derive(Serialize, Deserialize, Default, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct VolumeRebuilds {
+    /// The replica rebuilds.
+    pub rebuilds: Vec<RebuildRecord>,
+}
+
+impl From<VolumeRebuilds> for Vec<models::RebuildRecord> {
+    fn from(src: VolumeRebuilds) -> Self {
+        src.rebuilds.into_iter().map(Into::into).collect()
+    }
+}
+
+impl From<RebuildReason> for models::RebuildReason {
+    fn from(src: RebuildReason) -> Self {
+        match src {
+            RebuildReason::ReplicaIncrease => Self::ReplicaIncrease,
+            RebuildReason::Replacement => Self::Replacement,
+            RebuildReason::Rebalance => Self::Rebalance,
+            RebuildReason::Unknown => Self::Unknown,
+        }
+    }
+}
+
+impl From<RebuildOutcome> for models::RebuildOutcome {
+    fn from(src: RebuildOutcome) -> Self {
+        match src {
+            RebuildOutcome::Running => Self::Running,
+            RebuildOutcome::Successful => Self::Successful,
+            RebuildOutcome::Failed => Self::Failed,
+        }
+    }
+}
+
+impl From<RebuildRecord> for models::RebuildRecord {
+    fn from(src: RebuildRecord) -> Self {
+        Self::new_all(
+            src.volume,
+            src.nexus,
+            src.replica,
+            src.reason,
+            src.started.to_rfc3339(),
+            src.finished.map(|finished| finished.to_rfc3339()),
+            src.bytes,
+            src.outcome,
+            src.progress,
+            src.eta.map(|eta| eta.to_rfc3339()),
+        )
+    }
+}
+
+/// Retrieve the queue of the volume replica rebuilds which are yet to be started.
+#[derive(Serialize, Deserialize, Default, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GetRebuildQueue {}
+
+/// A volume whose target is missing replicas, waiting for them to be rebuilt.
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct QueuedRebuild {
+    /// The volume.
+    pub volume: VolumeId,
+    /// The rebuild priority of the volume.
+    pub priority: u32,
+    /// Number of healthy replicas of the volume target.
+    pub healthy_replicas: u8,
+    /// Number of replicas which are missing from the volume target.
+    pub missing_replicas: u8,
+}
+
+impl QueuedRebuild {
+    /// Order in which the rebuilds are started: volumes with fewer healthy replicas first, and
+    /// then volumes with a higher rebuild priority first.
+    pub fn rebuild_order(&self, other: &Self) -> std::cmp::Ordering {
+        self.healthy_replicas
+            .cmp(&other.healthy_replicas)
+            .then_with(|| other.priority.cmp(&self.priority))
+    }
+}
+
+/// Volume replica rebuilds waiting to be started, in the order in which they are started.
+#[derive(Serialize, Deserialize, Default, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct RebuildQueue {
+    /// The queued rebuilds.
+    pub rebuilds: Vec<QueuedRebuild>,
+}
+
+impl From<RebuildQueue> for Vec<models::QueuedRebuild> {
+    fn from(src: RebuildQueue) -> Self {
+        src.rebuilds.into_iter().map(Into::into).collect()
+    }
+}
+
+impl From<QueuedRebuild> for models::QueuedRebuild {
+    fn from(src: QueuedRebuild) -> Self {
+        Self::new_all(
+            src.volume,
+            src.priority,
+            src.healthy_replicas,
+            src.missing_replicas,
+        )
+    }
+}
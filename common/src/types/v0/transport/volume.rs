@@ -120,6 +120,46 @@ impl From<models::VolumeShareProtocol> for NexusShareProtocol {
     }
 }
 
+/// The mode with which the volume is accessed from its frontend nodes.
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, Eq, PartialEq)]
+pub enum VolumeAccessMode {
+    /// Read and written from a single frontend node.
+    SingleNodeWriter,
+    /// Read and written by multiple writers on a single frontend node.
+    SingleNodeMultiWriter,
+    /// Only read from, possibly from multiple frontend nodes.
+    MultiNodeReaderOnly,
+}
+impl VolumeAccessMode {
+    /// Check if the volume may be accessed from multiple frontend nodes.
+    pub fn multi_node(&self) -> bool {
+        matches!(self, Self::MultiNodeReaderOnly)
+    }
+}
+impl Default for VolumeAccessMode {
+    fn default() -> Self {
+        Self::SingleNodeWriter
+    }
+}
+impl From<VolumeAccessMode> for models::VolumeAccessMode {
+    fn from(src: VolumeAccessMode) -> Self {
+        match src {
+            VolumeAccessMode::SingleNodeWriter => Self::SingleNodeWriter,
+            VolumeAccessMode::SingleNodeMultiWriter => Self::SingleNodeMultiWriter,
+            VolumeAccessMode::MultiNodeReaderOnly => Self::MultiNodeReaderOnly,
+        }
+    }
+}
+impl From<models::VolumeAccessMode> for VolumeAccessMode {
+    fn from(src: models::VolumeAccessMode) -> Self {
+        match src {
+            models::VolumeAccessMode::SingleNodeWriter => Self::SingleNodeWriter,
+            models::VolumeAccessMode::SingleNodeMultiWriter => Self::SingleNodeMultiWriter,
+            models::VolumeAccessMode::MultiNodeReaderOnly => Self::MultiNodeReaderOnly,
+        }
+    }
+}
+
 /// Volume State information.
 /// Currently it's the same as the nexus.
 pub type VolumeStatus = NexusStatus;
@@ -139,12 +179,16 @@ impl From<VolumeStatus> for models::VolumeStatus {
 /// Volume placement topology using resource labels.
 #[derive(Serialize, Deserialize, Default, Debug, Clone, Eq, PartialEq)]
 pub struct LabelledTopology {
-    /// Exclusive labels.
+    /// Exclusive labels, which the resources must not have.
+    /// A label with an empty value excludes the resources having the label key with any value.
     #[serde(default)]
     pub exclusion: ::std::collections::HashMap<String, String>,
-    /// Inclusive labels.
+    /// Inclusive labels, which the resources must have.
     #[serde(default)]
     pub inclusion: ::std::collections::HashMap<String, String>,
+    /// Label keys whose values must differ between the resources used by the volume replicas.
+    #[serde(default)]
+    pub spread: Vec<String>,
 }
 
 impl From<models::LabelledTopology> for LabelledTopology {
@@ -152,12 +196,13 @@ impl From<models::LabelledTopology> for LabelledTopology {
         Self {
             exclusion: src.exclusion,
             inclusion: src.inclusion,
+            spread: src.spread.unwrap_or_default(),
         }
     }
 }
 impl From<LabelledTopology> for models::LabelledTopology {
     fn from(src: LabelledTopology) -> Self {
-        Self::new(src.exclusion, src.inclusion)
+        Self::new_all(src.exclusion, src.inclusion, src.spread)
     }
 }
 
@@ -175,6 +220,18 @@ impl Topology {
     pub fn explicit(&self) -> Option<&ExplicitNodeTopology> {
         self.node.as_ref().and_then(|n| n.explicit())
     }
+    /// Check if the node or pool topology spreads the replicas using label keys.
+    pub fn spread(&self) -> bool {
+        let node_spread = match &self.node {
+            Some(NodeTopology::Labelled(topology)) => !topology.spread.is_empty(),
+            _ => false,
+        };
+        let pool_spread = match &self.pool {
+            Some(PoolTopology::Labelled(topology)) => !topology.spread.is_empty(),
+            None => false,
+        };
+        node_spread || pool_spread
+    }
 }
 impl From<Topology> for models::Topology {
     fn from(src: Topology) -> Self {
@@ -190,12 +247,12 @@ impl From<models::Topology> for Topology {
     }
 }
 
-/// Excludes resources with the same $label name, eg:
-/// "Zone" would not allow for resources with the same "Zone" value
-/// to be used for a certain operation, eg:
-/// A node with "Zone: A" would not be paired up with a node with "Zone: A",
-/// but it could be paired up with a node with "Zone: B"
-/// exclusive label NAME in the form "NAME", and not "NAME: VALUE"
+/// Excludes resources with the $label or $label:$value eg:
+/// if label is "Zone: A":
+/// A resource with "Zone: A" would not be used, but a resource with "Zone: B" could be
+/// if label is "Zone":
+/// A resource with any "Zone" value would not be used
+/// exclusive label key value in the form "NAME: VALUE"
 pub type ExclusiveLabel = String;
 
 /// Includes resources with the same $label or $label:$value eg:
@@ -295,11 +352,22 @@ pub struct VolumePolicy {
     /// The server will attempt to heal the volume by itself.
     /// The client should not attempt to do the same if this is enabled.
     pub self_heal: bool,
+    /// QoS limits enforced on the volume target.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub qos: Option<VolumeQos>,
+    /// Priority of the replica rebuilds of the volume, higher first.
+    /// Volumes with fewer healthy replicas are still rebuilt before volumes of higher priority.
+    #[serde(default)]
+    pub rebuild_priority: u32,
 }
 
 impl Default for VolumePolicy {
     fn default() -> Self {
-        Self { self_heal: true }
+        Self {
+            self_heal: true,
+            qos: None,
+            rebuild_priority: 0,
+        }
     }
 }
 
@@ -307,12 +375,61 @@ impl From<models::VolumePolicy> for VolumePolicy {
     fn from(src: models::VolumePolicy) -> Self {
         Self {
             self_heal: src.self_heal,
+            qos: src.qos.map(From::from),
+            rebuild_priority: src.rebuild_priority.unwrap_or_default(),
         }
     }
 }
 impl From<VolumePolicy> for models::VolumePolicy {
     fn from(src: VolumePolicy) -> Self {
-        Self::new_all(src.self_heal)
+        Self::new_all(
+            src.self_heal,
+            src.qos.map(From::from),
+            Some(src.rebuild_priority),
+        )
+    }
+}
+
+/// Volume QoS limits, enforced on the volume target.
+/// A limit which is not set does not restrict the volume.
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Eq, PartialEq)]
+pub struct VolumeQos {
+    /// Maximum number of read operations per second.
+    pub read_iops: Option<u64>,
+    /// Maximum number of write operations per second.
+    pub write_iops: Option<u64>,
+    /// Maximum read bandwidth, in MiB per second.
+    pub read_mbps: Option<u64>,
+    /// Maximum write bandwidth, in MiB per second.
+    pub write_mbps: Option<u64>,
+}
+impl VolumeQos {
+    /// Check if any of the limits is set.
+    pub fn limited(&self) -> bool {
+        [
+            self.read_iops,
+            self.write_iops,
+            self.read_mbps,
+            self.write_mbps,
+        ]
+        .iter()
+        .any(|limit| limit.unwrap_or_default() > 0)
+    }
+}
+
+impl From<models::VolumeQos> for VolumeQos {
+    fn from(src: models::VolumeQos) -> Self {
+        Self {
+            read_iops: src.read_iops,
+            write_iops: src.write_iops,
+            read_mbps: src.read_mbps,
+            write_mbps: src.write_mbps,
+        }
+    }
+}
+impl From<VolumeQos> for models::VolumeQos {
+    fn from(src: VolumeQos) -> Self {
+        Self::new_all(src.read_iops, src.write_iops, src.read_mbps, src.write_mbps)
     }
 }
 
@@ -350,6 +467,70 @@ pub struct CreateVolume {
     pub labels: Option<VolumeLabels>,
     /// The flag indicating whether the volume should be thin provisioned.
     pub thin: bool,
+    /// The source from which the volume content should be populated.
+    #[serde(default)]
+    pub content_source: Option<VolumeContentSource>,
+    /// The encryption at rest of the volume data.
+    #[serde(default)]
+    pub encryption: Option<VolumeEncryption>,
+}
+
+/// The source of a new volume's content.
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum VolumeContentSource {
+    /// Clone an existing volume.
+    Volume(VolumeId),
+    /// Restore an existing volume snapshot.
+    Snapshot(SnapshotId),
+}
+impl From<VolumeContentSource> for models::VolumeContentSource {
+    fn from(src: VolumeContentSource) -> Self {
+        match src {
+            VolumeContentSource::Volume(id) => {
+                Self::new_all(models::VolumeContentSourceType::Volume, id)
+            }
+            VolumeContentSource::Snapshot(id) => {
+                Self::new_all(models::VolumeContentSourceType::Snapshot, id)
+            }
+        }
+    }
+}
+impl From<models::VolumeContentSource> for VolumeContentSource {
+    fn from(src: models::VolumeContentSource) -> Self {
+        match src.source_type {
+            models::VolumeContentSourceType::Volume => Self::Volume(src.uuid.into()),
+            models::VolumeContentSourceType::Snapshot => Self::Snapshot(src.uuid.into()),
+        }
+    }
+}
+
+/// The encryption at rest of a volume's data.
+/// Only a reference to the key is kept, the key itself must be made available to the io-engine
+/// under this reference, eg: through a kubernetes secret.
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct VolumeEncryption {
+    /// The reference to the key used to encrypt the volume data.
+    pub key_ref: String,
+}
+impl VolumeEncryption {
+    /// Create new `Self` with the given key reference.
+    pub fn new(key_ref: impl Into<String>) -> Self {
+        Self {
+            key_ref: key_ref.into(),
+        }
+    }
+}
+impl From<VolumeEncryption> for models::VolumeEncryption {
+    fn from(src: VolumeEncryption) -> Self {
+        Self::new_all(src.key_ref)
+    }
+}
+impl From<models::VolumeEncryption> for VolumeEncryption {
+    fn from(src: models::VolumeEncryption) -> Self {
+        Self::new(src.key_ref)
+    }
 }
 
 /// Volume label information.
@@ -403,6 +584,10 @@ pub struct PublishVolume {
     pub publish_context: HashMap<String, String>,
     /// Hosts allowed to access nexus.
     pub frontend_nodes: Vec<String>,
+    /// The mode with which the volume is accessed from the frontend nodes.
+    /// Only multi-node access modes allow publishing the volume to other frontend nodes.
+    #[serde(default)]
+    pub access_mode: VolumeAccessMode,
 }
 impl PublishVolume {
     /// Create new `PublishVolume` based on the provided arguments.
@@ -412,6 +597,7 @@ impl PublishVolume {
         share: Option<VolumeShareProtocol>,
         publish_context: HashMap<String, String>,
         frontend_nodes: Vec<String>,
+        access_mode: VolumeAccessMode,
     ) -> Self {
         Self {
             uuid,
@@ -419,6 +605,7 @@ impl PublishVolume {
             share,
             publish_context,
             frontend_nodes,
+            access_mode,
         }
     }
 }
@@ -439,6 +626,11 @@ pub struct RepublishVolume {
     pub share: VolumeShareProtocol,
     /// Allows reusing of the current target.
     pub reuse_existing: bool,
+    /// Keep the current target serving until the frontend nodes are connected to the new one,
+    /// rather than shutting it down before the new target is created.
+    /// The current target is then shut down when the shutdown targets are destroyed.
+    #[serde(default)]
+    pub defer_shutdown: bool,
 }
 impl RepublishVolume {
     /// Create new `RepublishVolume` based on the provided arguments.
@@ -455,8 +647,15 @@ impl RepublishVolume {
             frontend_node,
             share,
             reuse_existing,
+            defer_shutdown: false,
         }
     }
+
+    /// Defer the shutdown of the current target, ie: for a planned target move.
+    pub fn with_deferred_shutdown(mut self, defer_shutdown: bool) -> Self {
+        self.defer_shutdown = defer_shutdown;
+        self
+    }
 }
 
 /// Unpublish a volume from any node where it may be published.
@@ -470,6 +669,10 @@ pub struct UnpublishVolume {
     /// the nexus. Note: this option should be used only when we know the node will not become
     /// accessible again and it is safe to do so.
     force: bool,
+    /// Only unpublish the volume from this frontend node.
+    /// If the volume is also published to other frontend nodes then the target is kept for them.
+    #[serde(default)]
+    frontend_node: Option<NodeId>,
 }
 impl UnpublishVolume {
     /// Create a new `UnpublishVolume` for the given uuid.
@@ -477,12 +680,22 @@ impl UnpublishVolume {
         Self {
             uuid: uuid.clone(),
             force,
+            frontend_node: None,
         }
     }
+    /// Get `Self` which only unpublishes the volume from the given frontend node.
+    pub fn with_frontend_node(mut self, frontend_node: Option<NodeId>) -> Self {
+        self.frontend_node = frontend_node;
+        self
+    }
     /// It's a force `Self`.
     pub fn force(&self) -> bool {
         self.force
     }
+    /// Get the frontend node to unpublish the volume from, if any.
+    pub fn frontend_node(&self) -> Option<&NodeId> {
+        self.frontend_node.as_ref()
+    }
 }
 
 /// Share Volume request.
@@ -536,6 +749,41 @@ impl SetVolumeReplica {
     }
 }
 
+/// Resize volume request.
+#[derive(Serialize, Deserialize, Default, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ResizeVolume {
+    /// The uuid of the volume.
+    pub uuid: VolumeId,
+    /// The new size of the volume in bytes.
+    pub requested_size: u64,
+}
+impl ResizeVolume {
+    /// Create new `Self` based on the provided arguments.
+    pub fn new(uuid: VolumeId, requested_size: u64) -> Self {
+        Self {
+            uuid,
+            requested_size,
+        }
+    }
+}
+
+/// Set the QoS limits of a volume.
+#[derive(Serialize, Deserialize, Default, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SetVolumeQos {
+    /// The uuid of the volume.
+    pub uuid: VolumeId,
+    /// The QoS limits which replace the existing ones.
+    pub qos: VolumeQos,
+}
+impl SetVolumeQos {
+    /// Create new `Self` based on the provided arguments.
+    pub fn new(uuid: VolumeId, qos: VolumeQos) -> Self {
+        Self { uuid, qos }
+    }
+}
+
 /// Delete volume request.
 #[derive(Serialize, Deserialize, Default, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
@@ -566,12 +814,27 @@ pub struct ReplicaTopology {
     pool: Option<PoolId>,
     /// The status of the replica.
     status: ReplicaStatus,
+    /// Current rebuild progress (%) of the replica, if it's being rebuilt.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    rebuild_progress: Option<u8>,
 }
 
 impl ReplicaTopology {
     /// Create a new instance of ReplicaTopology.
     pub fn new(node: Option<NodeId>, pool: Option<PoolId>, status: ReplicaStatus) -> Self {
-        Self { node, pool, status }
+        Self {
+            node,
+            pool,
+            status,
+            rebuild_progress: None,
+        }
+    }
+
+    /// Set the rebuild progress of the replica.
+    #[must_use]
+    pub fn with_rebuild_progress(mut self, rebuild_progress: Option<u8>) -> Self {
+        self.rebuild_progress = rebuild_progress;
+        self
     }
 
     /// Get the ReplicaTopology node ID.
@@ -588,6 +851,11 @@ impl ReplicaTopology {
     pub fn status(&self) -> &ReplicaStatus {
         &self.status
     }
+
+    /// Get the rebuild progress of the replica, if it's being rebuilt.
+    pub fn rebuild_progress(&self) -> Option<u8> {
+        self.rebuild_progress
+    }
 }
 
 impl From<&ReplicaTopology> for models::ReplicaTopology {
@@ -596,6 +864,7 @@ impl From<&ReplicaTopology> for models::ReplicaTopology {
             replica_topology.node.clone().map(Into::into),
             replica_topology.pool.clone().map(Into::into),
             replica_topology.status.clone(),
+            replica_topology.rebuild_progress,
         )
     }
 }
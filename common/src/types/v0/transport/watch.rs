@@ -1,7 +1,9 @@
 use super::*;
 
+use crate::transport_api::ResourceKind;
 use serde::{Deserialize, Serialize};
-use std::{convert::TryFrom, fmt::Debug};
+use std::{collections::HashMap, convert::TryFrom, fmt::Debug};
+use strum_macros::{EnumString, ToString};
 
 ///
 /// Watch Agent
@@ -134,3 +136,128 @@ impl Default for WatchCallback {
         Self::Uri(Default::default())
     }
 }
+
+/// Stream the create/update/delete events of the control-plane resources.
+/// Only events matching all of the given filters are streamed.
+#[derive(Serialize, Deserialize, Default, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct WatchEvents {
+    /// only stream events for these resource kinds, or all kinds if empty
+    pub kinds: Vec<ResourceKind>,
+    /// only stream events for resources which carry all of these labels
+    pub labels: HashMap<String, String>,
+}
+impl WatchEvents {
+    /// Create a new `Self` which streams events for all resources.
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Only stream events for the given resource kinds.
+    pub fn with_kinds(mut self, kinds: Vec<ResourceKind>) -> Self {
+        self.kinds = kinds;
+        self
+    }
+    /// Only stream events for resources which carry all of the given labels.
+    pub fn with_labels(mut self, labels: HashMap<String, String>) -> Self {
+        self.labels = labels;
+        self
+    }
+    /// Check if the given `ResourceEvent` matches the filters.
+    pub fn matches(&self, event: &ResourceEvent) -> bool {
+        let kind_match = self.kinds.is_empty() || self.kinds.contains(&event.kind);
+        let label_match = self
+            .labels
+            .iter()
+            .all(|(key, value)| event.labels.get(key) == Some(value));
+        kind_match && label_match
+    }
+}
+
+/// The action which triggered a `ResourceEvent`.
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, EnumString, ToString, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+#[strum(serialize_all = "camelCase")]
+pub enum ResourceEventAction {
+    /// the resource has been created
+    Created,
+    /// the resource has been updated
+    Updated,
+    /// the resource has been deleted
+    Deleted,
+}
+
+/// A change event for a control-plane resource.
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ResourceEvent {
+    /// kind of the resource
+    pub kind: ResourceKind,
+    /// id of the resource
+    pub id: String,
+    /// what happened to the resource
+    pub action: ResourceEventAction,
+    /// labels of the resource at the time of the event
+    pub labels: HashMap<String, String>,
+}
+impl ResourceEvent {
+    /// Create a new `Self` with no labels.
+    pub fn new(kind: ResourceKind, id: impl Into<String>, action: ResourceEventAction) -> Self {
+        Self {
+            kind,
+            id: id.into(),
+            action,
+            labels: HashMap::new(),
+        }
+    }
+    /// Add the resource labels to the event.
+    pub fn with_labels(mut self, labels: HashMap<String, String>) -> Self {
+        self.labels = labels;
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn labels(labels: &[(&str, &str)]) -> HashMap<String, String> {
+        labels
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn watch_events_matches() {
+        let event = ResourceEvent::new(ResourceKind::Volume, "vol-1", ResourceEventAction::Created)
+            .with_labels(labels(&[("app", "db"), ("tier", "gold")]));
+
+        // no filters match everything
+        assert!(WatchEvents::new().matches(&event));
+
+        let kinds = WatchEvents::new().with_kinds(vec![ResourceKind::Pool, ResourceKind::Volume]);
+        assert!(kinds.matches(&event));
+        let kinds = WatchEvents::new().with_kinds(vec![ResourceKind::Pool]);
+        assert!(!kinds.matches(&event));
+
+        // all of the labels must be carried by the resource, with the same values
+        let filter = WatchEvents::new().with_labels(labels(&[("app", "db")]));
+        assert!(filter.matches(&event));
+        let filter = WatchEvents::new().with_labels(labels(&[("app", "db"), ("tier", "gold")]));
+        assert!(filter.matches(&event));
+        let filter = WatchEvents::new().with_labels(labels(&[("app", "web")]));
+        assert!(!filter.matches(&event));
+        let filter = WatchEvents::new().with_labels(labels(&[("app", "db"), ("zone", "a")]));
+        assert!(!filter.matches(&event));
+
+        // both the kinds and the labels must match
+        let filter = WatchEvents::new()
+            .with_kinds(vec![ResourceKind::Pool])
+            .with_labels(labels(&[("app", "db")]));
+        assert!(!filter.matches(&event));
+        let filter = WatchEvents::new()
+            .with_kinds(vec![ResourceKind::Volume])
+            .with_labels(labels(&[("app", "db")]));
+        assert!(filter.matches(&event));
+    }
+}
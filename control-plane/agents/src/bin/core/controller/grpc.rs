@@ -30,6 +30,7 @@ use rpc::{
 };
 use snafu::ResultExt;
 use std::{
+    future::Future,
     ops::{Deref, DerefMut},
     str::FromStr,
     sync::Arc,
@@ -52,6 +53,8 @@ pub(crate) struct GrpcContext {
     comms_timeouts: NodeCommsTimeout,
     /// The api version to be used for calls.
     api_version: ApiVersion,
+    /// The request which this context was created for, if any.
+    request: Option<MessageId>,
 }
 
 impl GrpcContext {
@@ -75,6 +78,7 @@ impl GrpcContext {
             })?;
 
         let timeout = request
+            .clone()
             .map(|r| timeout_grpc(r, comms_timeouts.opts().clone()))
             .unwrap_or_else(|| comms_timeouts.request());
 
@@ -89,6 +93,7 @@ impl GrpcContext {
             endpoint,
             comms_timeouts: comms_timeouts.clone(),
             api_version,
+            request,
         })
     }
     /// Override the timeout config in the context for the given request.
@@ -133,6 +138,9 @@ pub(crate) type NexusClient = rpc::v1::nexus::nexus_rpc_client::NexusRpcClient<C
 /// The V1 PoolClient.
 pub(crate) type PoolClient = rpc::v1::pool::pool_rpc_client::PoolRpcClient<Channel>;
 
+/// The V1 SnapshotClient.
+pub(crate) type SnapshotClient = rpc::v1::snapshot::snapshot_rpc_client::SnapshotRpcClient<Channel>;
+
 /// A collection of all collects for the V1 dataplane interface.
 #[derive(Clone, Debug)]
 pub(crate) struct MayaClientV1 {
@@ -140,6 +148,7 @@ pub(crate) struct MayaClientV1 {
     replica: ReplicaClient,
     nexus: NexusClient,
     pool: PoolClient,
+    snapshot: SnapshotClient,
 }
 
 impl MayaClientV1 {
@@ -155,6 +164,10 @@ impl MayaClientV1 {
     pub(crate) fn pool(&self) -> PoolClient {
         self.pool.clone()
     }
+    /// Get the v1 snapshot client.
+    pub(crate) fn snapshot(&self) -> SnapshotClient {
+        self.snapshot.clone()
+    }
 }
 
 /// Wrapper over all gRPC Clients types
@@ -261,6 +274,22 @@ impl GrpcClient {
                         endpoint: context.endpoint.uri().to_string(),
                     })?),
                 }?;
+                let snapshot = match tokio::time::timeout(
+                    context.comms_timeouts.connect(),
+                    SnapshotClient::connect(context.endpoint.clone()),
+                )
+                .await
+                {
+                    Err(_) => Err(SvcError::GrpcConnectTimeout {
+                        node_id: context.node.to_string(),
+                        endpoint: context.endpoint.uri().to_string(),
+                        timeout: context.comms_timeouts.connect(),
+                    }),
+                    Ok(client) => Ok(client.context(GrpcConnect {
+                        node_id: context.node.to_string(),
+                        endpoint: context.endpoint.uri().to_string(),
+                    })?),
+                }?;
                 Ok(Self {
                     context: context.clone(),
                     io_engine_v0: None,
@@ -269,6 +298,7 @@ impl GrpcClient {
                         replica,
                         nexus,
                         pool,
+                        snapshot,
                     }),
                 })
             }
@@ -621,6 +651,24 @@ impl GrpcClientLocked {
     pub(crate) fn api_version(&self) -> ApiVersion {
         self.context.api_version.clone()
     }
+    /// Await the given gRPC `future`, recording its latency and outcome against the node and
+    /// request of this client's context.
+    pub(crate) async fn observed<F, R>(&self, future: F) -> Result<R, SvcError>
+    where
+        F: Future<Output = Result<R, SvcError>>,
+    {
+        let start = std::time::Instant::now();
+        let result = future.await;
+        if let Some(request) = &self.context.request {
+            crate::metrics::observe_node_request(
+                &self.context.node,
+                request,
+                result.is_err(),
+                start.elapsed(),
+            );
+        }
+        result
+    }
 }
 
 impl Deref for GrpcClientLocked {
@@ -0,0 +1,65 @@
+use crate::controller::{
+    reconciler::poller::ReconcilerWorker,
+    task_poller::{PollContext, PollResult, PollTimer, TaskPoller},
+};
+
+use common_lib::types::v0::store::{
+    definitions::{ObjectKey, StorableObject, StorableObjectType},
+    switchover::{PathFailureHistory, SwitchOverHistory},
+};
+
+/// HA history garbage collector.
+/// The HA cluster-agent records the switchover outcomes of the volumes and the path failures
+/// reported by the nodes, but it never deletes them. The switchover history is deleted once its
+/// volume no longer exists, and the path failure history once its node has not reported any path
+/// failure for longer than the `PATH_FAILURE_HISTORY_TTL`.
+#[derive(Debug)]
+pub(super) struct HaHistoryGarbageCollector {
+    counter: PollTimer,
+}
+impl HaHistoryGarbageCollector {
+    /// Return a new `Self`
+    pub(super) fn new() -> Self {
+        Self {
+            counter: ReconcilerWorker::garbage_collection_period(),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl TaskPoller for HaHistoryGarbageCollector {
+    async fn poll(&mut self, context: &PollContext) -> PollResult {
+        let registry = context.registry();
+        let mut results = vec![];
+
+        let switchovers = registry
+            .load_obj_prefix::<SwitchOverHistory>(StorableObjectType::FailoverHistory)
+            .await?;
+        for history in switchovers
+            .into_iter()
+            .filter(|history| context.specs().volume_rsc(&history.volume).is_none())
+        {
+            if let Err(error) = registry.delete_kv(&history.key().key()).await {
+                results.push(PollResult::Err(error));
+            }
+        }
+
+        let now = chrono::Utc::now();
+        let path_failures = registry
+            .load_obj_prefix::<PathFailureHistory>(StorableObjectType::PathFailureHistory)
+            .await?;
+        for history in path_failures
+            .into_iter()
+            .filter(|history| history.expired(now))
+        {
+            if let Err(error) = registry.delete_kv(&history.key().key()).await {
+                results.push(PollResult::Err(error));
+            }
+        }
+        Self::squash_results(results)
+    }
+
+    async fn poll_timer(&mut self, _context: &PollContext) -> bool {
+        self.counter.poll()
+    }
+}
@@ -1,3 +1,4 @@
+mod ha_history;
 pub(crate) mod nexus;
 mod node;
 mod persistent_store;
@@ -12,6 +13,7 @@ use crate::controller::task_poller::{
 };
 use poller::ReconcilerWorker;
 use std::fmt::Debug;
+pub(crate) use volume::{ReplicaRebalanceConfig, TargetRebalanceConfig};
 
 use crate::controller::registry::Registry;
 
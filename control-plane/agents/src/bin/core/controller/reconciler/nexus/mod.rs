@@ -19,7 +19,9 @@ use common_lib::{
             nexus::{NexusSpec, ReplicaUri},
             nexus_child::NexusChild,
         },
-        transport::{CreateNexus, NexusShareProtocol, NodeStatus, ShareNexus, UnshareNexus},
+        transport::{
+            ChildUri, CreateNexus, NexusShareProtocol, NodeStatus, ShareNexus, UnshareNexus,
+        },
     },
 };
 use garbage_collector::GarbageCollector;
@@ -146,6 +148,16 @@ pub(super) async fn faulted_children_remover(
                 nexus_spec_clone.warn_span(|| {
                     tracing::warn!("Attempting to remove faulted child '{}'", child.uri)
                 });
+                let keep_replica =
+                    keep_faulted_replica(&nexus_spec_clone, &child.uri, context).await;
+                if keep_replica {
+                    nexus_spec_clone.info_span(|| {
+                        tracing::info!(
+                            child.uri = %child.uri.as_str(),
+                            "Keeping the replica of the faulted child for a partial rebuild as its node is offline"
+                        )
+                    });
+                }
                 if let Err(error) = context
                     .specs()
                     .remove_nexus_child_by_uri(
@@ -153,7 +165,7 @@ pub(super) async fn faulted_children_remover(
                         nexus,
                         &nexus_state,
                         &child.uri,
-                        true,
+                        !keep_replica,
                     )
                     .await
                 {
@@ -181,6 +193,30 @@ pub(super) async fn faulted_children_remover(
     PollResult::Ok(PollerState::Idle)
 }
 
+/// Check if the replica of the given faulted child should be kept rather than destroyed, so that
+/// it may be re-added to the nexus for a partial rebuild.
+/// This is only the case for volume replicas whose node is offline, until they have been faulted
+/// for longer than the faulted child wait period, as a node which is briefly offline (eg: a node
+/// reboot) should not trigger a full rebuild of its replicas.
+async fn keep_faulted_replica(nexus: &NexusSpec, uri: &ChildUri, context: &PollContext) -> bool {
+    if context.registry().faulted_child_wait_period().is_none() || nexus.owner.is_none() {
+        return false;
+    }
+    let replica = match nexus.children.iter().find(|c| &c.uri() == uri) {
+        Some(NexusChild::Replica(replica)) => replica.uuid().clone(),
+        _ => return false,
+    };
+    if context.specs().replica_rsc(&replica).is_none() {
+        return false;
+    }
+    if context.registry().replica_node_online(&replica).await {
+        // the replica itself has faulted, so it must be fully rebuilt anyway
+        context.registry().forget_faulted_replica(&replica);
+        return false;
+    }
+    !context.registry().faulted_replica_expired(&replica)
+}
+
 /// Find and removes unknown children from the given nexus
 /// If the child is a replica it also disowns and destroys it
 #[tracing::instrument(skip(nexus, context), level = "trace", fields(nexus.uuid = %nexus.uuid(), request.reconcile = true))]
@@ -366,7 +402,16 @@ pub(super) async fn missing_nexus_recreate(
             return PollResult::Ok(PollerState::Idle);
         }
 
-        match node.create_nexus(&CreateNexus::from(&nexus)).await {
+        // restore the QoS limits of the owning volume, if any
+        let qos = nexus
+            .owner
+            .as_ref()
+            .and_then(|owner| context.specs().volume_clone(owner).ok())
+            .and_then(|volume| volume.policy.qos);
+        match node
+            .create_nexus(&CreateNexus::from(&nexus).with_qos(qos.as_ref()))
+            .await
+        {
             Ok(_) => {
                 nexus.info_span(|| tracing::info!("Nexus successfully recreated"));
                 PollResult::Ok(PollerState::Idle)
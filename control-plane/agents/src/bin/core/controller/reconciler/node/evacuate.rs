@@ -0,0 +1,156 @@
+use crate::controller::{
+    reconciler::PollContext,
+    resources::{operations_helper::OperationSequenceGuard, ResourceMutex, TraceStrLog},
+};
+use agents::errors::SvcError;
+use common_lib::types::v0::{
+    store::{
+        node::{NodeSpec, VolumeEvacuation},
+        rebuild::RebuildReason,
+        volume::VolumeSpec,
+    },
+    transport::{NodeId, ReplicaId, VolumeId, VolumeState, VolumeStatus},
+};
+
+/// Move the volume replicas off the draining node, one replica of each volume at a time.
+/// As when rebalancing the pools (see the `ReplicaRebalancer`), a replacement replica is added
+/// on another node and the replica on the draining node is only removed once the replacement
+/// has been rebuilt by the volume target. The unpublished volumes have no target to rebuild the
+/// replacement, so it's created and the replica on the draining node is removed straight away,
+/// leaving the rebuild to when the volume is published.
+/// Degraded volumes are skipped and reported, rather than holding up the drain.
+/// The evacuation progress of each volume is kept in the drain state of the node.
+/// Returns true once there are no volume replicas left on the node, other than the ones of the
+/// skipped volumes.
+pub(super) async fn evacuate_node(context: &PollContext, node_spec: &NodeSpec) -> bool {
+    let node_id = node_spec.id();
+    let mut evacuations = vec![];
+
+    for mut volume in context.specs().volumes_rsc() {
+        let replicas = node_replicas(context, volume.uuid(), node_id);
+        if replicas.is_empty() {
+            continue;
+        }
+        let evacuation = match evacuate_volume(context, &mut volume, &replicas).await {
+            Ok(evacuation) => evacuation,
+            Err(error) => {
+                tracing::error!(
+                    error=%error,
+                    volume.uuid = volume.uuid().as_str(),
+                    node.id = node_id.as_str(),
+                    "Failed to move the volume replica off the node"
+                );
+                VolumeEvacuation::new(volume.uuid().clone(), replicas.len())
+            }
+        };
+        evacuations.push(evacuation);
+    }
+
+    let evacuated = evacuations.iter().all(|evacuation| evacuation.degraded);
+    if evacuations.as_slice() != node_spec.evacuations() {
+        if let Err(error) = context
+            .specs()
+            .set_node_evacuations(context.registry(), node_id, evacuations)
+            .await
+        {
+            tracing::error!(
+                error=%error,
+                node.id = node_id.as_str(),
+                "Failed to update the node evacuation progress"
+            );
+        }
+    }
+    evacuated
+}
+
+/// Get the replicas of the given volume which live on the given node.
+fn node_replicas(context: &PollContext, volume: &VolumeId, node: &NodeId) -> Vec<ReplicaId> {
+    context
+        .specs()
+        .volume_replicas(volume)
+        .into_iter()
+        .filter_map(|replica| {
+            let (uuid, pool) = {
+                let replica = replica.lock();
+                (replica.uuid.clone(), replica.pool.pool_name().clone())
+            };
+            match context.specs().pool(&pool) {
+                Ok(pool) if &pool.node == node => Some(uuid),
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+/// Start moving one of the given replicas of the volume off the draining node, unless one is
+/// already being moved.
+/// Returns the evacuation progress of the volume.
+async fn evacuate_volume(
+    context: &PollContext,
+    volume_spec: &mut ResourceMutex<VolumeSpec>,
+    replicas: &[ReplicaId],
+) -> Result<VolumeEvacuation, SvcError> {
+    let mut evacuation = VolumeEvacuation::new(volume_spec.uuid().clone(), replicas.len());
+    let mut volume = match volume_spec.operation_guard() {
+        Ok(guard) => guard,
+        Err(_) => return Ok(evacuation),
+    };
+    let state = context.registry().volume_state(volume.uuid()).await?;
+
+    // a replica is already being moved, and it's removed once its replacement is rebuilt
+    if let Some(replica) = &volume.as_ref().rebalance {
+        if replicas.contains(replica) {
+            evacuation.rebuild_progress = replacement_progress(context, &state).await;
+        }
+        return Ok(evacuation);
+    }
+    if !volume.as_ref().status.created() {
+        return Ok(evacuation);
+    }
+    if state.status != VolumeStatus::Online {
+        // moving a replica off the node could compromise the volume further
+        evacuation.degraded = true;
+        return Ok(evacuation);
+    }
+
+    let replica = &replicas[0];
+    if state.target.is_none() {
+        volume.info(&format!(
+            "Moving replica '{replica}' of the unpublished volume off the draining node"
+        ));
+        context
+            .specs()
+            .move_unpublished_volume_replica(&mut volume, context.registry(), replica)
+            .await?;
+        evacuation.replicas = evacuation.replicas.saturating_sub(1);
+        return Ok(evacuation);
+    }
+
+    // the replacement replica will be rebuilt, so don't go over the maximum number of rebuilds
+    if let Err(error) = context.registry().rebuild_allowed().await {
+        tracing::debug!(%error, "Not moving the volume replica off the draining node yet");
+        return Ok(evacuation);
+    }
+
+    volume.info(&format!("Moving replica '{replica}' off the draining node"));
+    context
+        .specs()
+        .start_volume_rebalance(&mut volume, context.registry(), replica)
+        .await?;
+    Ok(evacuation)
+}
+
+/// Get the rebuild progress of the replacement replica which is added to the volume while one
+/// of its replicas is being moved, if it's being rebuilt.
+async fn replacement_progress(context: &PollContext, state: &VolumeState) -> Option<u8> {
+    let history = context.registry().rebuild_history(&state.uuid).await.ok()?;
+    let replacement = history
+        .records
+        .iter()
+        .rev()
+        .find(|record| record.running() && record.reason == RebuildReason::Rebalance)?;
+    state
+        .replica_topology
+        .get(&replacement.replica)?
+        .rebuild_progress()
+}
@@ -1,11 +1,15 @@
+mod evacuate;
 mod nexus;
+mod rebuild;
 
 use crate::controller::{
-    reconciler::node::nexus::NodeNexusReconciler,
+    reconciler::node::{nexus::NodeNexusReconciler, rebuild::NodeRebuildReconciler},
     task_poller::{PollContext, PollPeriods, PollResult, PollTimer, TaskPoller},
 };
 
-/// Node reconciler loop which moves nexuses from draining nodes.
+/// Node reconciler loop which:
+/// 1. moves nexuses, and replicas if evacuating, from draining nodes
+/// 2. applies the rebuild bandwidth limit to the nodes
 #[derive(Debug)]
 pub(crate) struct NodeReconciler {
     counter: PollTimer,
@@ -16,7 +20,10 @@ impl NodeReconciler {
     pub(crate) fn from(period: PollPeriods) -> Self {
         NodeReconciler {
             counter: PollTimer::from(period),
-            poll_targets: vec![Box::new(NodeNexusReconciler::new())],
+            poll_targets: vec![
+                Box::new(NodeNexusReconciler::new()),
+                Box::new(NodeRebuildReconciler::new()),
+            ],
         }
     }
     /// Return new `Self` with the default period.
@@ -1,5 +1,5 @@
 use crate::controller::{
-    reconciler::{PollContext, TaskPoller},
+    reconciler::{node::evacuate::evacuate_node, PollContext, TaskPoller},
     resources::{
         operations::ResourcePublishing, operations_helper::OperationSequenceGuard, OperationGuard,
         ResourceMutex,
@@ -104,7 +104,7 @@ async fn check_and_drain_node(context: &PollContext, node_spec: &NodeSpec) -> Po
                     };
                     let frontend_node = config
                         .frontend()
-                        .node_names()
+                        .nodes()
                         .first()
                         .unwrap_or(&String::default())
                         .clone();
@@ -144,8 +144,11 @@ async fn check_and_drain_node(context: &PollContext, node_spec: &NodeSpec) -> Po
             }
         };
     }
+    // When evacuating, the volume replicas must be moved off the node as well
+    let evacuated = !node_spec.is_evacuating() || evacuate_node(context, node_spec).await;
+
     // Change the node state to "drained"
-    if !move_failures {
+    if !move_failures && evacuated {
         if let Err(e) = context
             .specs()
             .set_node_drained(context.registry(), node_spec.id())
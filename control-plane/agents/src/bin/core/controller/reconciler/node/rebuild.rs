@@ -0,0 +1,107 @@
+use crate::controller::{
+    reconciler::{PollContext, TaskPoller},
+    task_poller::{PollResult, PollTimer, PollerState},
+    wrapper::ClientOps,
+};
+use common_lib::types::v0::transport::{NodeId, NodeStatus, SetRebuildBandwidth};
+use std::collections::HashMap;
+
+/// Node rebuild bandwidth reconciler.
+/// Applies the rebuild bandwidth limit to the online io-engine nodes. The limit is applied once
+/// per io-engine instance, and again when the node comes back online, as it's not persisted by
+/// the io-engine.
+#[derive(Debug)]
+pub(super) struct NodeRebuildReconciler {
+    counter: PollTimer,
+    /// The nodes which have the limit applied, along with their io-engine instance.
+    applied: HashMap<NodeId, Option<uuid::Uuid>>,
+}
+impl NodeRebuildReconciler {
+    /// Return a new `Self`.
+    pub(super) fn new() -> Self {
+        Self {
+            counter: PollTimer::from(6),
+            applied: HashMap::new(),
+        }
+    }
+    /// Check if the limit must be applied to the given node, ie: if it's online and the limit has
+    /// not been applied to its current io-engine instance yet.
+    /// An offline node has to have the limit applied again once it's back online.
+    fn pending(&mut self, node: &NodeId, online: bool, instance: &Option<uuid::Uuid>) -> bool {
+        if !online {
+            self.applied.remove(node);
+            return false;
+        }
+        self.applied.get(node) != Some(instance)
+    }
+}
+
+#[async_trait::async_trait]
+impl TaskPoller for NodeRebuildReconciler {
+    async fn poll(&mut self, context: &PollContext) -> PollResult {
+        let mbps = match context.registry().node_rebuild_mbps() {
+            Some(mbps) => mbps,
+            None => return PollResult::Ok(PollerState::Idle),
+        };
+
+        let mut results = vec![];
+        for node in context.registry().node_states().await {
+            let online = node.status() == &NodeStatus::Online;
+            if !self.pending(node.id(), online, node.instance_uuid()) {
+                continue;
+            }
+            let wrapper = match context.registry().node_wrapper(node.id()).await {
+                Ok(wrapper) => wrapper,
+                Err(error) => {
+                    results.push(PollResult::Err(error));
+                    continue;
+                }
+            };
+            let request = SetRebuildBandwidth::new(node.id(), mbps);
+            match wrapper.set_rebuild_bandwidth(&request).await {
+                Ok(_) => {
+                    tracing::info!(node.id = %node.id(), mbps, "Set the rebuild bandwidth limit");
+                    self.applied
+                        .insert(node.id().clone(), *node.instance_uuid());
+                }
+                Err(error) => {
+                    tracing::warn!(
+                        node.id = %node.id(),
+                        %error,
+                        "Failed to set the rebuild bandwidth limit"
+                    );
+                    results.push(PollResult::Err(error));
+                }
+            }
+        }
+        Self::squash_results(results)
+    }
+
+    async fn poll_timer(&mut self, _context: &PollContext) -> bool {
+        self.counter.poll()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pending_nodes() {
+        let mut reconciler = NodeRebuildReconciler::new();
+        let node = NodeId::from("node");
+        let instance = Some(uuid::Uuid::new_v4());
+
+        assert!(reconciler.pending(&node, true, &instance));
+        reconciler.applied.insert(node.clone(), instance);
+        assert!(!reconciler.pending(&node, true, &instance));
+
+        // the limit is not persisted by the io-engine, so a new instance needs it again
+        let restarted = Some(uuid::Uuid::new_v4());
+        assert!(reconciler.pending(&node, true, &restarted));
+
+        // as does a node which comes back online
+        assert!(!reconciler.pending(&node, false, &instance));
+        assert!(reconciler.pending(&node, true, &instance));
+    }
+}
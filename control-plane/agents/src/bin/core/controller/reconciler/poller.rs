@@ -1,5 +1,8 @@
 use crate::controller::{
-    reconciler::{nexus, node, persistent_store::PersistentStoreReconciler, pool, replica, volume},
+    reconciler::{
+        ha_history::HaHistoryGarbageCollector, nexus, node,
+        persistent_store::PersistentStoreReconciler, pool, replica, volume,
+    },
     registry::Registry,
     task_poller::{
         squash_results, PollContext, PollEvent, PollResult, PollTimer, PollTriggerEvent,
@@ -33,6 +36,7 @@ impl ReconcilerWorker {
             Box::new(PersistentStoreReconciler::new()),
             Box::new(replica::ReplicaReconciler::new()),
             Box::new(node::NodeReconciler::new()),
+            Box::new(HaHistoryGarbageCollector::new()),
         ];
 
         // if events are sent before the worker is started they may fill up the buffer
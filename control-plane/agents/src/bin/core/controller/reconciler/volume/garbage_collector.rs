@@ -140,7 +140,7 @@ async fn destroy_volume(
 
 /// Given a volume
 /// When any of its nexuses are no longer used
-/// Then they should be disowned
+/// Then they should be disowned, unless it's the previous target of a planned move
 /// And they should eventually be destroyed
 #[tracing::instrument(level = "debug", skip(context, volume), fields(volume.uuid = %volume.uuid(), request.reconcile = true))]
 async fn disown_unused_nexuses(
@@ -154,6 +154,8 @@ async fn disown_unused_nexuses(
 
         match volume.as_ref().target() {
             Some(target) if target.nexus() == nexus.uid() || nexus_clone.is_shutdown() => continue,
+            // the previous target of a planned move is shut down once the move completes
+            Some(_) if volume.as_ref().moved_target.as_ref() == Some(nexus.uid()) => continue,
             _ => {}
         };
 
@@ -180,6 +182,8 @@ async fn disown_unused_nexuses(
 /// Given a published volume
 /// When some of its replicas are not healthy, not online and not used by a nexus
 /// Then they should be disowned
+/// Unless their node is offline, as they are then kept for a partial rebuild until the faulted
+/// child wait period has elapsed
 #[tracing::instrument(level = "debug", skip(context, volume), fields(volume.uuid = %volume.uuid(), request.reconcile = true))]
 async fn disown_unused_replicas(
     volume: &mut OperationGuardArc<VolumeSpec>,
@@ -225,6 +229,13 @@ async fn disown_unused_replicas(
             && !replica_in_target
             && !is_replica_healthy(context, &mut nexus_info, replica.as_ref(), volume.as_ref())
                 .await?
+            && (context
+                .registry()
+                .replica_node_online(&replica.as_ref().uuid)
+                .await
+                || context
+                    .registry()
+                    .faulted_replica_expired(&replica.as_ref().uuid))
         {
             volume.warn_span(|| tracing::warn!(replica.uuid = %replica.as_ref().uuid, "Attempting to disown unused replica"));
 
@@ -238,6 +249,9 @@ async fn disown_unused_replicas(
                 .await
             {
                 Ok(_) => {
+                    context
+                        .registry()
+                        .forget_faulted_replica(&replica.as_ref().uuid);
                     volume.info_span(|| tracing::info!(replica.uuid = %replica.as_ref().uuid, "Successfully disowned unused replica"));
                 }
                 Err(error) => {
@@ -11,13 +11,16 @@ use common_lib::{
     transport_api::ErrorChain,
     types::v0::{
         store::{nexus::NexusSpec, volume::VolumeSpec},
-        transport::{Nexus, VolumeState, VolumeStatus},
+        transport::{Nexus, QueuedRebuild, VolumeState, VolumeStatus},
     },
 };
 
 use std::cmp::Ordering;
 
 /// Volume HotSpare reconciler
+/// The volumes with fewer healthy replicas, and then those with a higher rebuild priority, are
+/// reconciled first, so they get to start their rebuilds before the maximum number of rebuilds
+/// is reached.
 #[derive(Debug)]
 pub(super) struct HotSpareReconciler {}
 impl HotSpareReconciler {
@@ -31,14 +34,30 @@ impl HotSpareReconciler {
 impl TaskPoller for HotSpareReconciler {
     async fn poll(&mut self, context: &PollContext) -> PollResult {
         let mut results = vec![];
-        let volumes = context.specs().volumes_rsc();
-        for mut volume in volumes {
+        let mut volumes = vec![];
+        for volume in context.specs().volumes_rsc() {
+            let spec = volume.lock().clone();
+            let order = context.registry().volume_rebuild_order(&spec).await.ok();
+            volumes.push((order, volume));
+        }
+        sort_by_rebuild_order(&mut volumes);
+        for (_, mut volume) in volumes {
             results.push(hot_spare_reconcile(&mut volume, context).await);
         }
         Self::squash_results(results)
     }
 }
 
+/// Sort the volumes in the order in which their rebuilds are started.
+/// The volumes whose rebuild order is not known, eg: because their state can't be retrieved, are
+/// reconciled last.
+fn sort_by_rebuild_order<T>(volumes: &mut [(Option<QueuedRebuild>, T)]) {
+    volumes.sort_by(|(a, _), (b, _)| match (a, b) {
+        (Some(a), Some(b)) => a.rebuild_order(b),
+        _ => b.is_some().cmp(&a.is_some()),
+    });
+}
+
 #[tracing::instrument(level = "debug", skip(context, volume_spec), fields(volume.uuid = %volume_spec.uuid(), request.reconcile = true))]
 async fn hot_spare_reconcile(
     volume_spec: &mut ResourceMutex<VolumeSpec>,
@@ -156,7 +175,7 @@ async fn nexus_replica_count_reconciler(
 
     let vol_spec_clone = volume.as_ref();
     let nexus_spec_clone = nexus.as_ref();
-    let volume_replicas = vol_spec_clone.num_replicas as usize;
+    let volume_replicas = vol_spec_clone.effective_num_replicas() as usize;
     let nexus_replica_children =
         nexus_spec_clone
             .children
@@ -194,7 +213,7 @@ async fn nexus_replica_count_reconciler_traced(
     context: &PollContext,
 ) -> PollResult {
     let vol_spec_clone = volume.as_ref();
-    let volume_replicas = vol_spec_clone.num_replicas as usize;
+    let volume_replicas = vol_spec_clone.effective_num_replicas() as usize;
 
     match nexus_replica_children.cmp(&volume_replicas) {
         Ordering::Less => {
@@ -241,7 +260,7 @@ async fn volume_replica_count_reconciler(
     volume: &mut OperationGuardArc<VolumeSpec>,
     context: &PollContext,
 ) -> PollResult {
-    let required_replica_count = volume.as_ref().num_replicas as usize;
+    let required_replica_count = volume.as_ref().effective_num_replicas() as usize;
 
     let current_replicas = context.specs().volume_replicas(volume.uuid());
     let current_replica_count = current_replicas.len();
@@ -259,7 +278,7 @@ async fn volume_replica_count_reconciler_traced(
     volume: &mut OperationGuardArc<VolumeSpec>,
     context: &PollContext,
 ) -> PollResult {
-    let required_replica_count = volume.as_ref().num_replicas as usize;
+    let required_replica_count = volume.as_ref().effective_num_replicas() as usize;
 
     let current_replicas = context.specs().volume_replicas(volume.uuid());
     let mut current_replica_count = current_replicas.len();
@@ -340,3 +359,35 @@ async fn volume_replica_count_reconciler_traced(
         PollerState::Busy
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common_lib::types::v0::transport::VolumeId;
+
+    fn queued(name: &str, healthy: u8, priority: u32) -> (Option<QueuedRebuild>, &str) {
+        let rebuild = QueuedRebuild {
+            volume: VolumeId::new(),
+            priority,
+            healthy_replicas: healthy,
+            missing_replicas: 1,
+        };
+        (Some(rebuild), name)
+    }
+
+    #[test]
+    fn rebuild_order() {
+        let mut volumes = vec![
+            (None, "unknown"),
+            queued("healthy", 2, 10),
+            queued("low", 1, 0),
+            queued("high", 1, 5),
+            queued("degraded", 0, 0),
+        ];
+        sort_by_rebuild_order(&mut volumes);
+
+        // fewer healthy replicas first, then a higher priority, then the unknown volumes
+        let order = volumes.iter().map(|(_, name)| *name).collect::<Vec<_>>();
+        assert_eq!(order, vec!["degraded", "high", "low", "healthy", "unknown"]);
+    }
+}
@@ -1,17 +1,27 @@
 mod garbage_collector;
 mod hot_spare;
 mod nexus;
+mod rebuild_history;
+mod replica_rebalance;
+mod target_rebalance;
+
+pub(crate) use replica_rebalance::ReplicaRebalanceConfig;
+pub(crate) use target_rebalance::TargetRebalanceConfig;
 
 use crate::controller::task_poller::{PollContext, PollPeriods, PollResult, PollTimer, TaskPoller};
 
 use crate::controller::reconciler::volume::{
     garbage_collector::GarbageCollector, hot_spare::HotSpareReconciler,
-    nexus::VolumeNexusReconciler,
+    nexus::VolumeNexusReconciler, rebuild_history::RebuildMonitor,
+    replica_rebalance::ReplicaRebalancer, target_rebalance::TargetRebalancer,
 };
 
 /// Volume Reconciler loop which:
 /// 1. does the replica replacement
 /// 2. volume garbage collection.
+/// 3. volume target rebalancing across the nodes.
+/// 4. volume replica rebalancing across the pools.
+/// 5. volume replica rebuild history monitoring.
 #[derive(Debug)]
 pub(crate) struct VolumeReconciler {
     counter: PollTimer,
@@ -26,6 +36,9 @@ impl VolumeReconciler {
                 Box::new(HotSpareReconciler::new()),
                 Box::new(GarbageCollector::new()),
                 Box::new(VolumeNexusReconciler::new()),
+                Box::new(TargetRebalancer::new()),
+                Box::new(ReplicaRebalancer::new()),
+                Box::new(RebuildMonitor::new()),
             ],
         }
     }
@@ -0,0 +1,141 @@
+use crate::controller::{
+    reconciler::{PollContext, TaskPoller},
+    resources::{operations_helper::OperationSequenceGuard, ResourceMutex},
+    task_poller::{PollResult, PollTimer, PollerState},
+};
+
+use common_lib::types::v0::{
+    store::{
+        rebuild::{RebuildOutcome, RebuildReason, RebuildRecord},
+        volume::VolumeSpec,
+    },
+    transport::ChildState,
+};
+
+/// Volume replica rebuild monitor.
+/// Keeps the rebuild history of the volumes up to date: the running rebuilds are finished once
+/// the replica is healthy, or once it faulted or was removed from the volume target; and the
+/// rebuilds which were not started by the control-plane are recorded as they are observed.
+#[derive(Debug)]
+pub(super) struct RebuildMonitor {
+    counter: PollTimer,
+}
+impl RebuildMonitor {
+    /// Return a new `Self`
+    pub(super) fn new() -> Self {
+        Self {
+            counter: PollTimer::from(6),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl TaskPoller for RebuildMonitor {
+    async fn poll(&mut self, context: &PollContext) -> PollResult {
+        let mut results = vec![];
+        for mut volume in context.specs().volumes_rsc() {
+            results.push(monitor_rebuilds(&mut volume, context).await);
+        }
+
+        // the volumes no longer exist, and so neither should their history
+        let histories = context.registry().rebuild_histories().await?;
+        for history in histories {
+            if context.specs().volume_rsc(&history.volume).is_some() {
+                continue;
+            }
+            if let Err(error) = context
+                .registry()
+                .delete_rebuild_history(&history.volume)
+                .await
+            {
+                results.push(PollResult::Err(error));
+            }
+        }
+        Self::squash_results(results)
+    }
+
+    async fn poll_timer(&mut self, _context: &PollContext) -> bool {
+        self.counter.poll()
+    }
+}
+
+/// Update the rebuild history of the volume with the current state of its target children.
+#[tracing::instrument(level = "trace", skip(volume_spec, context), fields(volume.uuid = %volume_spec.uuid(), request.reconcile = true))]
+async fn monitor_rebuilds(
+    volume_spec: &mut ResourceMutex<VolumeSpec>,
+    context: &PollContext,
+) -> PollResult {
+    let volume = match volume_spec.operation_guard() {
+        Ok(guard) => guard,
+        Err(_) => return PollResult::Ok(PollerState::Busy),
+    };
+    // the history is only read with the guard held, as the volume operations record the
+    // rebuilds they start
+    let mut history = context.registry().rebuild_history(volume.uuid()).await?;
+    let original = history.clone();
+    let state = context.registry().volume_state(volume.uuid()).await?;
+
+    let nexus = state.target.as_ref();
+    let replica_uris = nexus
+        .and_then(|nexus| context.specs().nexus_rsc(&nexus.uuid))
+        .map(|nexus| {
+            nexus
+                .lock()
+                .children
+                .iter()
+                .filter_map(|child| child.as_replica())
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+
+    for record in history.records.iter_mut().filter(|record| record.running()) {
+        let child = nexus
+            .filter(|nexus| nexus.uuid == record.nexus)
+            .and_then(|nexus| {
+                let uri = replica_uris.iter().find(|r| r.uuid() == &record.replica)?;
+                nexus.children.iter().find(|c| &c.uri == uri.uri())
+            });
+        match child {
+            Some(child)
+                if child.state == ChildState::Online && child.rebuild_progress.is_none() =>
+            {
+                record.finish(RebuildOutcome::Successful);
+            }
+            Some(child) if !child.state.faulted() => {
+                if let Some(progress) = child.rebuild_progress {
+                    record.set_progress(progress, state.size);
+                }
+            }
+            _ => record.finish(RebuildOutcome::Failed),
+        }
+    }
+
+    if let Some(nexus) = nexus {
+        for child in nexus
+            .children
+            .iter()
+            .filter(|c| c.rebuild_progress.is_some())
+        {
+            let replica = match replica_uris.iter().find(|r| r.uri() == &child.uri) {
+                Some(replica) => replica.uuid().clone(),
+                None => continue,
+            };
+            if history.running_mut(&replica).is_none() {
+                history.push(RebuildRecord::new(
+                    volume.uuid().clone(),
+                    nexus.uuid.clone(),
+                    replica,
+                    RebuildReason::Unknown,
+                ));
+            }
+        }
+    }
+
+    if history != original {
+        context
+            .registry()
+            .update_rebuild_history(&original, history)
+            .await?;
+    }
+    PollResult::Ok(PollerState::Idle)
+}
@@ -0,0 +1,243 @@
+use crate::controller::{
+    reconciler::{PollContext, TaskPoller},
+    resources::{operations_helper::OperationSequenceGuard, ResourceMutex, TraceStrLog},
+    scheduling::{volume::AddVolumeReplica, ResourceFilter},
+    task_poller::{PollResult, PollTimer, PollerState},
+};
+
+use agents::errors::SvcError;
+use common_lib::types::v0::{
+    store::volume::VolumeSpec,
+    transport::{PoolState, ReplicaId, VolumeStatus},
+};
+
+/// Configuration of the volume replica rebalancing across the pools.
+#[derive(Debug, Clone)]
+pub(crate) struct ReplicaRebalanceConfig {
+    /// Minimum difference, in percentage points, between the usage of the most used pool of a
+    /// volume and the usage of the pool where a new replica would be placed, for a replica to
+    /// be moved.
+    /// The replicas are not rebalanced if 0.
+    imbalance: u8,
+}
+impl ReplicaRebalanceConfig {
+    /// Return a new `Self` with the given imbalance threshold.
+    pub(crate) fn new(imbalance: u8) -> Self {
+        Self {
+            imbalance: imbalance.min(100),
+        }
+    }
+}
+
+/// Volume replica rebalancer.
+/// Replicas are placed on the least used pools when they are created, but when new pools are
+/// added the existing ones remain as full as they were. Periodically move a replica of the healthy
+/// volumes off their most used pool by adding a new replica on a less used pool, waiting for it
+/// to be rebuilt and only then removing the replica from the most used pool.
+/// The number of replicas being moved at a time is constrained by the maximum number of rebuilds.
+#[derive(Debug)]
+pub(super) struct ReplicaRebalancer {
+    counter: PollTimer,
+}
+impl ReplicaRebalancer {
+    /// Return a new `Self`
+    pub(super) fn new() -> Self {
+        Self {
+            counter: PollTimer::from(6),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl TaskPoller for ReplicaRebalancer {
+    async fn poll(&mut self, context: &PollContext) -> PollResult {
+        let config = context.registry().replica_rebalance().clone();
+        let mut results = vec![];
+        let mut started = false;
+        for mut volume in context.specs().volumes_rsc() {
+            if volume.lock().rebalance.is_some() {
+                results.push(complete_rebalance(&mut volume, context).await);
+            } else if config.imbalance > 0 && !started {
+                // the rebuild count is only refreshed with the nexus state, so start a single
+                // rebalance at a time to stay within the maximum number of rebuilds
+                match start_rebalance(&mut volume, &config, context).await {
+                    Ok(result) => started = result,
+                    Err(error) => results.push(PollResult::Err(error)),
+                }
+            }
+        }
+        if started {
+            results.push(PollResult::Ok(PollerState::Busy));
+        }
+        Self::squash_results(results)
+    }
+
+    async fn poll_timer(&mut self, _context: &PollContext) -> bool {
+        self.counter.poll()
+    }
+}
+
+/// Get the percentage of the pool capacity which is used.
+fn pool_usage(pool: &PoolState) -> u8 {
+    pool_usage_with(pool, 0)
+}
+
+/// Get the percentage of the pool capacity which would be used once `extra` bytes are allocated.
+fn pool_usage_with(pool: &PoolState, extra: u64) -> u8 {
+    pool.used
+        .saturating_add(extra)
+        .saturating_mul(100)
+        .checked_div(pool.capacity)
+        .unwrap_or(100)
+        .min(100) as u8
+}
+
+/// Get the volume replica which lives on the most used pool, along with the pool usage.
+async fn most_used_replica(volume: &VolumeSpec, context: &PollContext) -> Option<(ReplicaId, u8)> {
+    let mut most_used: Option<(ReplicaId, u8)> = None;
+    for replica in context.specs().volume_replicas(&volume.uuid) {
+        let (replica, pool) = {
+            let replica = replica.lock();
+            (replica.uuid.clone(), replica.pool.pool_name().clone())
+        };
+        let usage = match context.registry().get_pool_state(&pool).await {
+            Ok(pool) => pool_usage(&pool),
+            Err(_) => continue,
+        };
+        if most_used.as_ref().map_or(true, |(_, most)| usage > *most) {
+            most_used = Some((replica, usage));
+        }
+    }
+    most_used
+}
+
+/// Start moving the replica of the volume which lives on its most used pool, if the pool where
+/// a new replica would be placed is used less enough.
+/// Returns true if the rebalance was started.
+#[tracing::instrument(level = "debug", skip(volume_spec, config, context), fields(volume.uuid = %volume_spec.uuid(), request.reconcile = true))]
+async fn start_rebalance(
+    volume_spec: &mut ResourceMutex<VolumeSpec>,
+    config: &ReplicaRebalanceConfig,
+    context: &PollContext,
+) -> Result<bool, SvcError> {
+    let mut volume = match volume_spec.operation_guard() {
+        Ok(guard) => guard,
+        Err(_) => return Ok(false),
+    };
+    let spec = volume.as_ref().clone();
+    if !spec.policy.self_heal || !spec.status.created() || spec.rebalance.is_some() {
+        return Ok(false);
+    }
+
+    // only healthy published volumes, as the new replica must be rebuilt by the volume target
+    let state = context.registry().volume_state(&spec.uuid).await?;
+    if state.status != VolumeStatus::Online || state.target.is_none() {
+        return Ok(false);
+    }
+
+    let (replica, usage) = match most_used_replica(&spec, context).await {
+        Some(most_used) => most_used,
+        None => return Ok(false),
+    };
+    let candidate = AddVolumeReplica::builder_with_defaults(&spec, context.registry())
+        .await
+        .collect()
+        .into_iter()
+        .next();
+    // account for the new replica, otherwise the replica could be moved back and forth
+    let candidate_usage = match candidate {
+        Some(candidate) => pool_usage_with(candidate.pool.state(), spec.size),
+        None => return Ok(false),
+    };
+    if usage < candidate_usage.saturating_add(config.imbalance) {
+        return Ok(false);
+    }
+
+    // the new replica will be rebuilt, so don't go over the maximum number of rebuilds
+    if let Err(error) = context.registry().rebuild_allowed().await {
+        tracing::debug!(%error, "Not rebalancing the volume replicas");
+        return Ok(false);
+    }
+
+    volume.info(&format!(
+        "Moving replica '{replica}' off a pool with {usage}% usage to rebalance the pools"
+    ));
+    match context
+        .specs()
+        .start_volume_rebalance(&mut volume, context.registry(), &replica)
+        .await
+    {
+        Ok(_) => Ok(true),
+        Err(error) => {
+            tracing::warn!(%error, "Failed to start moving the volume replica");
+            Ok(false)
+        }
+    }
+}
+
+/// Once the new replica has been rebuilt, remove the replica which is being moved.
+/// If the volume has been unpublished meanwhile, the rebalance is completed or rolled back
+/// without a target, depending on whether the new replica had been rebuilt.
+#[tracing::instrument(level = "debug", skip(volume_spec, context), fields(volume.uuid = %volume_spec.uuid(), request.reconcile = true))]
+async fn complete_rebalance(
+    volume_spec: &mut ResourceMutex<VolumeSpec>,
+    context: &PollContext,
+) -> PollResult {
+    let mut volume = match volume_spec.operation_guard() {
+        Ok(guard) => guard,
+        Err(_) => return PollResult::Ok(PollerState::Busy),
+    };
+    let replica = match &volume.as_ref().rebalance {
+        Some(replica) => replica.clone(),
+        None => return PollResult::Ok(PollerState::Idle),
+    };
+
+    // wait until the new replica has been rebuilt
+    let state = context.registry().volume_state(volume.uuid()).await?;
+    if state.target.is_some() && state.status != VolumeStatus::Online {
+        return PollResult::Ok(PollerState::Busy);
+    }
+
+    context
+        .specs()
+        .complete_volume_rebalance(&mut volume, context.registry(), &replica)
+        .await?;
+    if context.specs().replica_rsc(&replica).is_some() {
+        volume.info(&format!(
+            "Rolled back moving replica '{replica}' as the volume was unpublished"
+        ));
+    } else {
+        volume.info(&format!(
+            "Successfully moved replica '{replica}' to rebalance the pools"
+        ));
+    }
+    PollResult::Ok(PollerState::Idle)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common_lib::types::v0::transport::PoolStatus;
+
+    fn pool(capacity: u64, used: u64) -> PoolState {
+        PoolState {
+            node: "node".into(),
+            id: "pool".into(),
+            disks: vec![],
+            status: PoolStatus::Online,
+            capacity,
+            used,
+        }
+    }
+
+    #[test]
+    fn pool_usage_percentage() {
+        assert_eq!(pool_usage(&pool(100, 0)), 0);
+        assert_eq!(pool_usage(&pool(100, 40)), 40);
+        // a new replica is accounted for on the candidate pool
+        assert_eq!(pool_usage_with(&pool(100, 0), 40), 40);
+        assert_eq!(pool_usage_with(&pool(100, 80), 40), 100);
+        // a pool without capacity is never a candidate
+        assert_eq!(pool_usage(&pool(0, 0)), 100);
+    }
+}
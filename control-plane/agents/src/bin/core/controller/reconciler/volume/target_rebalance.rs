@@ -0,0 +1,329 @@
+use crate::controller::{
+    reconciler::{PollContext, TaskPoller},
+    resources::ResourceMutex,
+    scheduling::{nexus::NexusTargetNode, ResourceFilter},
+    task_poller::{PollResult, PollTimer, PollerState},
+};
+
+use agents::errors::SvcError;
+use common_lib::types::v0::{
+    store::volume::VolumeSpec,
+    transport::{MoveVolumeTarget, NodeId, VolumeId, VolumeShareProtocol, VolumeStatus},
+};
+use grpc::operations::ha_node::{client::ClusterAgentClient, traits::ClusterAgentOperations};
+
+use parking_lot::Mutex;
+use std::{collections::HashMap, sync::Arc};
+use tonic::transport::Uri;
+use utils::HA_FAILOVER_LABEL;
+
+/// Configuration of the volume target rebalancing across the io-engine nodes.
+#[derive(Debug, Clone)]
+pub(crate) struct TargetRebalanceConfig {
+    /// Maximum number of targets which may be moved concurrently.
+    /// The targets are not rebalanced if 0.
+    max_moves: u32,
+    /// Minimum difference between the number of targets on a node and on the least loaded
+    /// candidate node for a target to be moved.
+    /// This is at least 2 as otherwise a target could be moved back and forth.
+    imbalance: u32,
+    /// The HA cluster agent which carries out the planned target moves.
+    /// The targets are not rebalanced if not configured.
+    cluster_agent: Option<Uri>,
+}
+impl TargetRebalanceConfig {
+    /// Return a new `Self` with the given move budget, imbalance threshold and HA cluster agent.
+    pub(crate) fn new(max_moves: u32, imbalance: u32, cluster_agent: Option<Uri>) -> Self {
+        Self {
+            max_moves,
+            imbalance: imbalance.max(2),
+            cluster_agent,
+        }
+    }
+}
+
+/// Volume target rebalancer.
+/// Targets are placed on the least loaded node when a volume is first published, but over time
+/// they may end up clustered on a few nodes. Periodically move the targets of HA-capable volumes
+/// from the most loaded nodes onto the less loaded ones, through planned target moves of the HA
+/// cluster agent which connect every frontend node to the new target before the old one is
+/// shut down.
+/// The moves are carried out in the background, so a slow switchover of the frontend nodes does
+/// not hold up the other reconcilers.
+#[derive(Debug)]
+pub(super) struct TargetRebalancer {
+    counter: PollTimer,
+    in_flight: InFlightMoves,
+}
+impl TargetRebalancer {
+    /// Return a new `Self`
+    pub(super) fn new() -> Self {
+        Self {
+            counter: PollTimer::from(6),
+            in_flight: Default::default(),
+        }
+    }
+}
+
+/// The target moves which are in flight, along with the nodes each target is moved from and to.
+#[derive(Debug, Default, Clone)]
+struct InFlightMoves {
+    moves: Arc<Mutex<HashMap<VolumeId, (NodeId, NodeId)>>>,
+}
+impl InFlightMoves {
+    /// Get the number of moves which may still be started without going over the limit.
+    fn budget(&self, max_moves: u32) -> usize {
+        (max_moves as usize).saturating_sub(self.moves.lock().len())
+    }
+    /// Check if the target of the volume is being moved.
+    fn contains(&self, volume: &VolumeId) -> bool {
+        self.moves.lock().contains_key(volume)
+    }
+    /// Record the move of the volume target, returning false if it's already being moved.
+    fn start(&self, volume: &VolumeId, from: &NodeId, to: &NodeId) -> bool {
+        let mut moves = self.moves.lock();
+        if moves.contains_key(volume) {
+            return false;
+        }
+        moves.insert(volume.clone(), (from.clone(), to.clone()));
+        true
+    }
+    /// Remove the move of the volume target, once it has completed or failed.
+    fn complete(&self, volume: &VolumeId) {
+        self.moves.lock().remove(volume);
+    }
+    /// Count the targets which are being moved against the nodes they're moved to, as their
+    /// specs still point to the nodes they're moved from until the moves complete.
+    fn count(&self, targets: &mut HashMap<NodeId, u32>, volume: &VolumeId, node: &NodeId) {
+        let node = match self.moves.lock().get(volume) {
+            Some((from, to)) if from == node => to.clone(),
+            _ => node.clone(),
+        };
+        *targets.entry(node).or_default() += 1;
+    }
+}
+
+#[async_trait::async_trait]
+impl TaskPoller for TargetRebalancer {
+    async fn poll(&mut self, context: &PollContext) -> PollResult {
+        let config = context.registry().target_rebalance().clone();
+        let cluster_agent = match &config.cluster_agent {
+            Some(cluster_agent) if config.max_moves > 0 => cluster_agent.clone(),
+            _ => return PollResult::Ok(PollerState::Idle),
+        };
+
+        let volumes = context.specs().volumes_rsc();
+        let mut targets = node_targets(context, &volumes, &self.in_flight).await;
+        let mut budget = self.in_flight.budget(config.max_moves);
+        let mut client = None;
+        let mut moves = 0;
+        for volume in volumes {
+            if budget == 0 {
+                return PollResult::Ok(PollerState::Busy);
+            }
+            if self.in_flight.contains(&volume.immutable_ref().uuid) {
+                continue;
+            }
+            let candidate = match rebalance_candidate(&volume, &targets, &config, context).await? {
+                Some(candidate) => candidate,
+                None => continue,
+            };
+            let client = match &mut client {
+                Some(client) => client,
+                None => client.insert(Arc::new(
+                    ClusterAgentClient::new(cluster_agent.clone(), None).await,
+                )),
+            };
+            if start_move(&volume, candidate, &mut targets, &self.in_flight, client) {
+                budget -= 1;
+                moves += 1;
+            }
+        }
+        match moves {
+            0 => PollResult::Ok(PollerState::Idle),
+            _ => PollResult::Ok(PollerState::Busy),
+        }
+    }
+
+    async fn poll_timer(&mut self, _context: &PollContext) -> bool {
+        self.counter.poll()
+    }
+}
+
+/// Get the number of published volume targets on each online node, including the targets which
+/// are being moved onto it.
+async fn node_targets(
+    context: &PollContext,
+    volumes: &[ResourceMutex<VolumeSpec>],
+    in_flight: &InFlightMoves,
+) -> HashMap<NodeId, u32> {
+    let mut targets = HashMap::new();
+    for node in context.registry().node_wrappers().await {
+        let node = node.read().await;
+        if node.is_online() {
+            targets.insert(node.id().clone(), 0);
+        }
+    }
+    for volume in volumes {
+        let volume = volume.lock();
+        if let Some(target) = volume.target() {
+            in_flight.count(&mut targets, &volume.uuid, target.node());
+        }
+    }
+    targets
+}
+
+/// A volume target may only be moved if the frontend nodes can be switched over to the new
+/// target by the HA agents, ie when it's shared over nvmf to known frontend nodes and the
+/// failover has not been disabled for the volume.
+fn ha_capable(volume: &VolumeSpec) -> bool {
+    let config = match volume.active_config() {
+        Some(config) => config,
+        None => return false,
+    };
+    let failover = volume
+        .labels
+        .as_ref()
+        .and_then(|labels| labels.get(HA_FAILOVER_LABEL))
+        .map(|failover| failover != "false")
+        .unwrap_or(true);
+    config.target().protocol() == Some(&VolumeShareProtocol::Nvmf)
+        && !config.frontend().nodes().is_empty()
+        && failover
+}
+
+/// Get the least loaded candidate node for the volume target, if its current node is loaded
+/// enough over it.
+#[tracing::instrument(level = "debug", skip(volume_spec, targets, config, context), fields(volume.uuid = %volume_spec.immutable_ref().uuid, request.reconcile = true))]
+async fn rebalance_candidate(
+    volume_spec: &ResourceMutex<VolumeSpec>,
+    targets: &HashMap<NodeId, u32>,
+    config: &TargetRebalanceConfig,
+    context: &PollContext,
+) -> Result<Option<NodeId>, SvcError> {
+    let spec = volume_spec.lock().clone();
+    if !spec.status.created() || spec.operation.is_some() || !ha_capable(&spec) {
+        return Ok(None);
+    }
+    let current = match spec.target() {
+        Some(target) => target.node().clone(),
+        None => return Ok(None),
+    };
+    let current_targets = targets.get(&current).cloned().unwrap_or_default();
+
+    let candidate = NexusTargetNode::builder_with_defaults(&spec, context.registry())
+        .await
+        .collect()
+        .into_iter()
+        .filter_map(|item| {
+            let node = item.node_wrapper().id().clone();
+            targets.get(&node).map(|count| (node, *count))
+        })
+        .min_by_key(|(_, count)| *count);
+    let candidate = match candidate {
+        Some((node, count)) if current_targets >= count + config.imbalance => node,
+        _ => return Ok(None),
+    };
+
+    // only move the targets of healthy volumes, as a degraded one would need to rebuild
+    if context.registry().volume_state(&spec.uuid).await?.status != VolumeStatus::Online {
+        return Ok(None);
+    }
+    Ok(Some(candidate))
+}
+
+/// Start moving the volume target onto the candidate node in the background, through a planned
+/// target move of the HA cluster agent which connects every frontend node of the volume to the
+/// new target before the old one is shut down. Returns true if the move was started.
+#[tracing::instrument(level = "debug", skip(volume_spec, targets, in_flight, client), fields(volume.uuid = %volume_spec.immutable_ref().uuid, request.reconcile = true))]
+fn start_move(
+    volume_spec: &ResourceMutex<VolumeSpec>,
+    candidate: NodeId,
+    targets: &mut HashMap<NodeId, u32>,
+    in_flight: &InFlightMoves,
+    client: &Arc<ClusterAgentClient>,
+) -> bool {
+    let volume = volume_spec.lock().clone();
+    let current = match volume.target() {
+        Some(target) => target.node().clone(),
+        None => return false,
+    };
+    if !in_flight.start(&volume.uuid, &current, &candidate) {
+        return false;
+    }
+    tracing::info!(
+        node.id = %current,
+        target.node = %candidate,
+        "Moving volume target to rebalance the targets across the nodes"
+    );
+    if let Some(count) = targets.get_mut(&current) {
+        *count = count.saturating_sub(1);
+    }
+    *targets.entry(candidate.clone()).or_default() += 1;
+
+    let in_flight = in_flight.clone();
+    let client = client.clone();
+    tokio::spawn(async move {
+        let request = MoveVolumeTarget::new(volume.uuid.clone(), Some(candidate.clone()));
+        if let Err(error) = client.move_volume_target(&request, None).await {
+            tracing::warn!(
+                volume.uuid = %volume.uuid,
+                node.id = %current,
+                target.node = %candidate,
+                %error,
+                "Failed to move the volume target"
+            );
+        }
+        in_flight.complete(&volume.uuid);
+    });
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn in_flight_moves() {
+        let in_flight = InFlightMoves::default();
+        let (volume, other) = (VolumeId::new(), VolumeId::new());
+        let (node_a, node_b): (NodeId, NodeId) = ("a".into(), "b".into());
+        assert_eq!(in_flight.budget(2), 2);
+
+        assert!(in_flight.start(&volume, &node_a, &node_b));
+        // the same target can't be moved twice at a time
+        assert!(!in_flight.start(&volume, &node_b, &node_a));
+        assert!(in_flight.contains(&volume));
+        assert!(!in_flight.contains(&other));
+        // the moves which are still in flight are counted against the limit, across runs
+        assert_eq!(in_flight.budget(2), 1);
+        assert!(in_flight.start(&other, &node_a, &node_b));
+        assert_eq!(in_flight.budget(2), 0);
+        assert_eq!(in_flight.budget(1), 0);
+
+        in_flight.complete(&volume);
+        assert!(!in_flight.contains(&volume));
+        assert_eq!(in_flight.budget(2), 1);
+        in_flight.complete(&other);
+        assert_eq!(in_flight.budget(2), 2);
+    }
+
+    #[test]
+    fn in_flight_targets_count() {
+        let in_flight = InFlightMoves::default();
+        let (moving, moved, idle) = (VolumeId::new(), VolumeId::new(), VolumeId::new());
+        let (node_a, node_b): (NodeId, NodeId) = ("a".into(), "b".into());
+        assert!(in_flight.start(&moving, &node_a, &node_b));
+        assert!(in_flight.start(&moved, &node_a, &node_b));
+
+        let mut targets = HashMap::new();
+        // the spec still points to the old node while the target is being moved
+        in_flight.count(&mut targets, &moving, &node_a);
+        // the spec already points to the new node but the move hasn't been completed yet
+        in_flight.count(&mut targets, &moved, &node_b);
+        in_flight.count(&mut targets, &idle, &node_a);
+
+        assert_eq!(targets.get(&node_a), Some(&1));
+        assert_eq!(targets.get(&node_b), Some(&2));
+    }
+}
@@ -14,21 +14,27 @@
 //! Each instance also contains the known nexus, pools and replicas that live in
 //! said instance.
 use super::{resources::operations_helper::*, wrapper::NodeWrapper};
-use crate::controller::{
-    reconciler::ReconcilerControl,
-    task_poller::{PollEvent, PollTriggerEvent},
-    wrapper::InternalOps,
+use crate::{
+    controller::{
+        reconciler::{ReconcilerControl, ReplicaRebalanceConfig, TargetRebalanceConfig},
+        scheduling::PoolScoringConfig,
+        task_poller::{PollEvent, PollTriggerEvent},
+        wrapper::InternalOps,
+    },
+    volume::RebuildHistories,
 };
 use agents::errors::SvcError;
 use common_lib::{
     store::etcd::Etcd,
     types::v0::{
         store::{
-            definitions::{StorableObject, Store, StoreError, StoreKey},
+            definitions::{
+                key_prefix_obj, StorableObject, StorableObjectType, Store, StoreError, StoreKey,
+            },
             registry::{ControlPlaneService, CoreRegistryConfig, NodeRegistration},
             volume::InitiatorAC,
         },
-        transport::{HostNqn, NodeId},
+        transport::{HostNqn, NodeId, ReplicaId, ResourceEvent},
     },
     HostAccessControl,
 };
@@ -38,7 +44,7 @@ use std::{
     ops::{Deref, DerefMut},
     sync::Arc,
 };
-use tokio::sync::{Mutex, RwLock};
+use tokio::sync::{broadcast, Mutex, RwLock};
 
 /// Registry containing all io-engine instances (aka nodes).
 #[derive(Clone, Debug)]
@@ -82,8 +88,28 @@ pub(crate) struct RegistryInner<S: Store> {
     max_rebuilds: Option<NumRebuilds>,
     /// Enablement of host access control.
     host_acl: Vec<HostAccessControl>,
+    /// weights used to score the pools for replica placement.
+    pool_scoring: PoolScoringConfig,
+    /// configuration of the volume target rebalancing.
+    target_rebalance: TargetRebalanceConfig,
+    /// configuration of the volume replica rebalancing.
+    replica_rebalance: ReplicaRebalanceConfig,
+    /// period for which a faulted replica whose node is offline is kept for a partial rebuild.
+    faulted_child_wait_period: Option<std::time::Duration>,
+    /// replicas kept for a partial rebuild and when they were found to be faulted.
+    faulted_replicas: parking_lot::Mutex<HashMap<ReplicaId, std::time::Instant>>,
+    /// in-memory copy of the persisted volume rebuild histories.
+    rebuild_histories: parking_lot::Mutex<RebuildHistories>,
+    /// bandwidth limit in MB/s of the rebuilds on each node.
+    node_rebuild_mbps: Option<u64>,
+    /// broadcast channel for the resource change events.
+    events: broadcast::Sender<ResourceEvent>,
 }
 
+/// Capacity of the resource events broadcast channel.
+/// Slow subscribers which fall behind by more than this miss events and have their stream ended.
+const RESOURCE_EVENTS_CAPACITY: usize = 1024;
+
 impl Registry {
     /// Create a new registry with the `cache_period` to reload the cache, the
     /// `store_url` to connect to, a `store_timeout` for store operations
@@ -99,6 +125,11 @@ impl Registry {
         reconcile_idle_period: std::time::Duration,
         max_rebuilds: Option<NumRebuilds>,
         host_acl: Vec<HostAccessControl>,
+        pool_scoring: PoolScoringConfig,
+        target_rebalance: TargetRebalanceConfig,
+        replica_rebalance: ReplicaRebalanceConfig,
+        faulted_child_wait_period: Option<std::time::Duration>,
+        node_rebuild_mbps: Option<u64>,
     ) -> Self {
         let store_endpoint = Self::format_store_endpoint(&store_url);
         tracing::info!("Connecting to persistent store at {}", store_endpoint);
@@ -123,6 +154,14 @@ impl Registry {
                 config: Self::get_config_or_panic(store).await,
                 max_rebuilds,
                 host_acl,
+                pool_scoring,
+                target_rebalance,
+                replica_rebalance,
+                faulted_child_wait_period,
+                faulted_replicas: Default::default(),
+                rebuild_histories: Default::default(),
+                node_rebuild_mbps,
+                events: broadcast::channel(RESOURCE_EVENTS_CAPACITY).0,
             }),
         };
         registry.init().await;
@@ -176,6 +215,10 @@ impl Registry {
     pub(crate) fn specs(&self) -> &ResourceSpecsLocked {
         &self.specs
     }
+    /// Get a reference to the in-memory copy of the volume rebuild histories.
+    pub(crate) fn rebuild_history_cache(&self) -> &parking_lot::Mutex<RebuildHistories> {
+        &self.rebuild_histories
+    }
 
     /// Serialized write to the persistent store.
     pub(crate) async fn store_obj<O: StorableObject>(&self, object: &O) -> Result<(), SvcError> {
@@ -215,6 +258,38 @@ impl Registry {
         }
     }
 
+    /// Serialized read of all objects of the given type from the persistent store.
+    pub(crate) async fn load_obj_prefix<O: StorableObject>(
+        &self,
+        obj_type: StorableObjectType,
+    ) -> Result<Vec<O>, SvcError> {
+        let store = self.store.clone();
+        let prefix = key_prefix_obj(obj_type);
+        match tokio::time::timeout(self.store_timeout, async move {
+            let mut store = store.lock().await;
+            Self::op_with_threshold(async move { store.get_values_prefix(&prefix).await }).await
+        })
+        .await
+        {
+            Ok(values) => values?
+                .into_iter()
+                .map(|(_, value)| {
+                    serde_json::from_value(value.clone()).map_err(|source| {
+                        SvcError::from(StoreError::DeserialiseValue {
+                            value: value.to_string(),
+                            source,
+                        })
+                    })
+                })
+                .collect(),
+            Err(_) => Err(StoreError::Timeout {
+                operation: "GetPrefix".to_string(),
+                timeout: self.store_timeout,
+            }
+            .into()),
+        }
+    }
+
     /// Serialized delete to the persistent store.
     pub(crate) async fn delete_kv<K: StoreKey>(&self, key: &K) -> Result<(), SvcError> {
         let store = self.store.clone();
@@ -300,6 +375,20 @@ impl Registry {
         self.reconciler.notify(PollEvent::Triggered(event)).await
     }
 
+    /// Publish a resource change event to all event subscribers.
+    pub(crate) fn notify_event(&self, event: ResourceEvent) {
+        // an error only means there are no subscribers at the moment
+        self.events.send(event).ok();
+    }
+    /// Get a sender for the resource change events.
+    pub(crate) fn events_sender(&self) -> broadcast::Sender<ResourceEvent> {
+        self.events.clone()
+    }
+    /// Subscribe to the resource change events.
+    pub(crate) fn subscribe_events(&self) -> broadcast::Receiver<ResourceEvent> {
+        self.events.subscribe()
+    }
+
     /// Poll each node for resource updates.
     async fn poller(&self) {
         loop {
@@ -323,18 +412,98 @@ impl Registry {
         }
     }
 
+    /// Get the maximum number of system-wide rebuilds, if one was specified.
+    pub(crate) fn max_rebuilds(&self) -> Option<NumRebuilds> {
+        self.max_rebuilds
+    }
+
+    /// Get the configuration used to score the pools for replica placement.
+    pub(crate) fn pool_scoring(&self) -> &PoolScoringConfig {
+        &self.pool_scoring
+    }
+
+    /// Get the configuration of the volume target rebalancing.
+    pub(crate) fn target_rebalance(&self) -> &TargetRebalanceConfig {
+        &self.target_rebalance
+    }
+
+    /// Get the configuration of the volume replica rebalancing.
+    pub(crate) fn replica_rebalance(&self) -> &ReplicaRebalanceConfig {
+        &self.replica_rebalance
+    }
+
+    /// Get the period for which a faulted replica whose node is offline is kept for a partial
+    /// rebuild, rather than being replaced, if one was specified.
+    pub(crate) fn faulted_child_wait_period(&self) -> Option<std::time::Duration> {
+        self.faulted_child_wait_period
+    }
+
+    /// Get the bandwidth limit in MB/s of the rebuilds on each node, if one was specified.
+    pub(crate) fn node_rebuild_mbps(&self) -> Option<u64> {
+        self.node_rebuild_mbps
+    }
+
+    /// Keep track of a faulted replica which is kept for a partial rebuild.
+    /// The time it was first found to be faulted is kept, even if it's tracked more than once.
+    pub(crate) fn track_faulted_replica(&self, replica: &ReplicaId) {
+        self.faulted_replicas
+            .lock()
+            .entry(replica.clone())
+            .or_insert_with(std::time::Instant::now);
+    }
+
+    /// Check if the faulted replica has waited for longer than the faulted child wait period.
+    /// Replicas which are not tracked yet (eg: following a restart) start being tracked now.
+    pub(crate) fn faulted_replica_expired(&self, replica: &ReplicaId) -> bool {
+        let period = match self.faulted_child_wait_period {
+            Some(period) => period,
+            None => return true,
+        };
+        self.track_faulted_replica(replica);
+        self.faulted_replicas
+            .lock()
+            .get(replica)
+            .map_or(true, |faulted| faulted.elapsed() > period)
+    }
+
+    /// Stop tracking a faulted replica, eg: because it was re-added or destroyed.
+    pub(crate) fn forget_faulted_replica(&self, replica: &ReplicaId) {
+        self.faulted_replicas.lock().remove(replica);
+    }
+
+    /// Check if the node of the given replica is online.
+    /// The node is considered offline if it can't be found, eg: the pool spec no longer exists.
+    pub(crate) async fn replica_node_online(&self, replica: &ReplicaId) -> bool {
+        let pool = match self.specs().replica_rsc(replica) {
+            Some(spec) => spec.lock().pool.pool_name().clone(),
+            None => return false,
+        };
+        let node = match self.specs().pool(&pool) {
+            Ok(pool) => pool.node,
+            Err(_) => return false,
+        };
+        match self.node_wrapper(&node).await {
+            Ok(node) => node.read().await.is_online(),
+            Err(_) => false,
+        }
+    }
+
+    /// Get the number of rebuilds currently in progress across all nodes.
+    pub(crate) async fn num_rebuilds(&self) -> NumRebuilds {
+        let mut num_rebuilds = 0;
+        for (_id, node_wrapper) in self.nodes.read().await.iter() {
+            num_rebuilds += node_wrapper.read().await.num_rebuilds();
+        }
+        num_rebuilds
+    }
+
     /// Determine if a rebuild is allowed to start.
     /// Constrain the number of system-wide rebuilds to the maximum specified.
     /// If a maximum is not specified, do not limit the number of rebuilds.
     pub(crate) async fn rebuild_allowed(&self) -> Result<(), SvcError> {
         match self.max_rebuilds {
             Some(max_rebuilds) => {
-                let mut num_rebuilds = 0;
-                for (_id, node_wrapper) in self.nodes.read().await.iter() {
-                    num_rebuilds += node_wrapper.read().await.num_rebuilds();
-                }
-
-                if num_rebuilds < max_rebuilds {
+                if self.num_rebuilds().await < max_rebuilds {
                     Ok(())
                 } else {
                     Err(SvcError::MaxRebuilds { max_rebuilds })
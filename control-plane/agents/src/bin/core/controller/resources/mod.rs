@@ -11,6 +11,7 @@ mod nexus;
 mod node;
 mod pool;
 mod replica;
+mod snapshot;
 mod volume;
 
 /// The internal operations interface for all resources.
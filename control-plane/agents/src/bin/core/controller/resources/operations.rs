@@ -140,3 +140,66 @@ pub(crate) trait ResourceShutdownOperations {
         request: &Self::RemoveShutdownTargets,
     ) -> Result<(), SvcError>;
 }
+
+/// Resource Resize Operations.
+#[async_trait::async_trait]
+pub(crate) trait ResourceResize {
+    type Resize: Sync + Send;
+    type ResizeOutput: Sync + Send + Sized;
+
+    /// Resize the resource to the requested size.
+    async fn resize(
+        &mut self,
+        registry: &Registry,
+        request: &Self::Resize,
+    ) -> Result<Self::ResizeOutput, SvcError>;
+}
+
+/// Resource Label Operations.
+#[async_trait::async_trait]
+pub(crate) trait ResourceLabel {
+    type Label: Sync + Send;
+    type LabelOutput: Sync + Send + Sized;
+    type Unlabel: Sync + Send;
+    type UnlabelOutput: Sync + Send + Sized;
+
+    /// Label the resource.
+    async fn label(
+        &mut self,
+        registry: &Registry,
+        request: &Self::Label,
+    ) -> Result<Self::LabelOutput, SvcError>;
+    /// Remove a label from the resource.
+    async fn unlabel(
+        &mut self,
+        registry: &Registry,
+        request: &Self::Unlabel,
+    ) -> Result<Self::UnlabelOutput, SvcError>;
+}
+
+/// Resource QoS Operations.
+#[async_trait::async_trait]
+pub(crate) trait ResourceQos {
+    type SetQos: Sync + Send;
+    type SetQosOutput: Sync + Send + Sized;
+
+    /// Set the QoS limits of the resource.
+    async fn set_qos(
+        &mut self,
+        registry: &Registry,
+        request: &Self::SetQos,
+    ) -> Result<Self::SetQosOutput, SvcError>;
+}
+
+/// Resource Cloning Operations.
+#[async_trait::async_trait]
+pub(crate) trait ResourceCloning {
+    type CreateClone: Sync + Send;
+    type CreateCloneOutput: Sync + Send + Sized;
+
+    /// Create a clone of the resource from a snapshot.
+    async fn create_clone(
+        registry: &Registry,
+        request: &Self::CreateClone,
+    ) -> Result<Self::CreateCloneOutput, SvcError>;
+}
@@ -13,10 +13,14 @@ use common_lib::{
             node::NodeSpec,
             pool::PoolSpec,
             replica::ReplicaSpec,
+            snapshot::VolumeSnapshotSpec,
             volume::VolumeSpec,
             AsOperationSequencer, OperationMode, OperationSequence, SpecStatus, SpecTransaction,
         },
-        transport::{NexusId, NodeId, PoolId, ReplicaId, VolumeId},
+        transport::{
+            NexusId, NodeId, PoolId, ReplicaId, ResourceEvent, ResourceEventAction, SnapshotId,
+            VolumeId,
+        },
     },
 };
 
@@ -25,7 +29,7 @@ use async_trait::async_trait;
 use parking_lot::RwLock;
 use serde::de::DeserializeOwned;
 use snafu::{ResultExt, Snafu};
-use std::{fmt::Debug, ops::Deref, sync::Arc};
+use std::{collections::HashMap, fmt::Debug, ops::Deref, sync::Arc};
 
 #[derive(Debug, Snafu)]
 #[snafu(context(suffix(false)))]
@@ -119,6 +123,7 @@ pub(crate) trait GuardedOperationsHelper:
                 match stored {
                     Ok(_) => {
                         spec.commit_op();
+                        registry.notify_event(spec.event(ResourceEventAction::Created));
                         Ok(val)
                     }
                     Err(error) => {
@@ -280,6 +285,7 @@ pub(crate) trait GuardedOperationsHelper:
                         self.remove_spec(registry);
                         let mut spec = self.lock();
                         spec.commit_op();
+                        registry.notify_event(spec_clone.event(ResourceEventAction::Deleted));
                         Ok(val)
                     }
                     Err(error) => {
@@ -353,6 +359,7 @@ pub(crate) trait GuardedOperationsHelper:
                 match stored {
                     Ok(_) => {
                         self.complete_op();
+                        registry.notify_event(spec_clone.event(ResourceEventAction::Updated));
                         Ok(val)
                     }
                     Err(error) => {
@@ -622,6 +629,14 @@ pub(crate) trait SpecOperationsHelper:
     fn kind(&self) -> ResourceKind;
     /// Get the UUID as a string (for log messages)
     fn uuid_str(&self) -> String;
+    /// Get the labels to be published along with the resource change events
+    fn event_labels(&self) -> HashMap<String, String> {
+        HashMap::new()
+    }
+    /// Get the resource change event for the given action
+    fn event(&self, action: ResourceEventAction) -> ResourceEvent {
+        ResourceEvent::new(self.kind(), self.uuid_str(), action).with_labels(self.event_labels())
+    }
     /// Get the state of the object
     fn status(&self) -> SpecStatus<Self::Status>;
     /// Set the state of the object
@@ -729,6 +744,7 @@ pub(crate) struct ResourceSpecs {
     pub(crate) nexuses: ResourceMap<NexusId, NexusSpec>,
     pub(crate) pools: ResourceMap<PoolId, PoolSpec>,
     pub(crate) replicas: ResourceMap<ReplicaId, ReplicaSpec>,
+    pub(crate) snapshots: ResourceMap<SnapshotId, VolumeSnapshotSpec>,
 }
 
 impl ResourceSpecsLocked {
@@ -744,6 +760,7 @@ impl ResourceSpecsLocked {
             StorableObjectType::NexusSpec,
             StorableObjectType::PoolSpec,
             StorableObjectType::ReplicaSpec,
+            StorableObjectType::VolumeSnapshot,
         ];
         for spec in &spec_types {
             if let Err(e) = self.populate_specs(store, *spec).await {
@@ -841,6 +858,14 @@ impl ResourceSpecsLocked {
                     })?;
                 resource_specs.replicas.populate(specs);
             }
+            StorableObjectType::VolumeSnapshot => {
+                let specs = Self::deserialise_specs::<VolumeSnapshotSpec>(store_values).context(
+                    Deserialise {
+                        obj_type: StorableObjectType::VolumeSnapshot,
+                    },
+                )?;
+                resource_specs.snapshots.populate(specs);
+            }
             _ => {
                 // Not all spec types are persisted in the store.
                 unimplemented!("{} not persisted in store", spec_type);
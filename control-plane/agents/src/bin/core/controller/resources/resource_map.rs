@@ -1,7 +1,7 @@
 use super::{ResourceMutex, ResourceUid};
 use common_lib::IntoVec;
 use indexmap::{map::Values, IndexMap};
-use std::{fmt::Debug, hash::Hash};
+use std::{collections::HashSet, fmt::Debug, hash::Hash};
 
 #[derive(Debug)]
 pub(crate) struct ResourceMap<I, S: Clone> {
@@ -67,6 +67,32 @@ where
         }
     }
 
+    /// Replace the contents of the map with the given values.
+    /// Returns the keys of the resources which were added, modified or removed.
+    pub(crate) fn replace(&mut self, values: impl IntoVec<S>) -> Vec<I>
+    where
+        S: PartialEq,
+    {
+        let values = values.into_vec();
+        let mut changed = values
+            .iter()
+            .filter(|value| match self.map.get(value.uid()) {
+                Some(existing) => &*existing.lock() != *value,
+                None => true,
+            })
+            .map(|value| value.uid().clone())
+            .collect::<Vec<_>>();
+        let keys = values
+            .iter()
+            .map(|value| value.uid())
+            .collect::<HashSet<_>>();
+        changed.extend(self.map.keys().filter(|key| !keys.contains(key)).cloned());
+
+        self.clear();
+        self.populate(values);
+        changed
+    }
+
     /// Get all the resources as a vector.
     pub(crate) fn to_vec(&self) -> Vec<ResourceMutex<S>> {
         self.map.values().cloned().collect()
@@ -92,3 +118,52 @@ where
             .collect()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common_lib::types::v0::{
+        store::replica::ReplicaState,
+        transport::{Replica, ReplicaId, ReplicaStatus},
+    };
+
+    fn replica(uuid: &ReplicaId, status: ReplicaStatus) -> Replica {
+        Replica {
+            uuid: uuid.clone(),
+            status,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn replace_changed_keys() {
+        let (kept, modified, removed, added) = (
+            ReplicaId::new(),
+            ReplicaId::new(),
+            ReplicaId::new(),
+            ReplicaId::new(),
+        );
+        let mut map = ResourceMap::<ReplicaId, ReplicaState>::default();
+        let changed = map.replace(vec![
+            replica(&kept, ReplicaStatus::Online),
+            replica(&modified, ReplicaStatus::Online),
+            replica(&removed, ReplicaStatus::Online),
+        ]);
+        assert_eq!(
+            changed,
+            vec![kept.clone(), modified.clone(), removed.clone()]
+        );
+
+        let changed = map.replace(vec![
+            replica(&kept, ReplicaStatus::Online),
+            replica(&modified, ReplicaStatus::Degraded),
+            replica(&added, ReplicaStatus::Online),
+        ]);
+        assert_eq!(changed, vec![modified, added, removed]);
+        assert_eq!(map.len(), 3);
+
+        let changed = map.replace(Vec::<Replica>::new());
+        assert_eq!(changed.len(), 3);
+        assert_eq!(map.len(), 0);
+    }
+}
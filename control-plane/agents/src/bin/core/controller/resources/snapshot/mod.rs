@@ -0,0 +1,30 @@
+use super::{ResourceMutex, ResourceUid};
+use common_lib::types::v0::{store::snapshot::VolumeSnapshotSpec, transport::SnapshotId};
+
+impl ResourceMutex<VolumeSnapshotSpec> {
+    /// Get the resource uuid.
+    pub fn uuid(&mut self) -> &SnapshotId {
+        &self.immutable_ref().uuid
+    }
+}
+impl ResourceUid for VolumeSnapshotSpec {
+    type Uid = SnapshotId;
+    fn uid(&self) -> &Self::Uid {
+        &self.uuid
+    }
+}
+
+macro_rules! snapshot_log {
+    ($Self:tt, $Level:expr, $Message:tt) => {
+        match tracing::Span::current().field("snapshot.uuid") {
+            None => {
+                let _span = tracing::span!($Level, "log_event", snapshot.uuid = %$Self.uuid).entered();
+                tracing::event!($Level, snapshot.uuid = %$Self.uuid, $Message);
+            }
+            Some(_) => {
+                tracing::event!($Level, snapshot.uuid = %$Self.uuid, $Message);
+            }
+        }
+    };
+}
+crate::impl_trace_str_log!(snapshot_log, VolumeSnapshotSpec);
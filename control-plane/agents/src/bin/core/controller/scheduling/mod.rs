@@ -7,7 +7,7 @@ use crate::controller::scheduling::{
     resources::{ChildItem, NodeItem, PoolItem, ReplicaItem},
     volume::{GetSuitablePoolsContext, VolumeReplicasForNexusCtx},
 };
-use common_lib::types::v0::transport::{PoolStatus, PoolTopology};
+use common_lib::types::v0::transport::{LabelledTopology, NodeId, PoolId, PoolStatus};
 use std::{cmp::Ordering, collections::HashMap, future::Future};
 
 #[async_trait::async_trait(?Send)]
@@ -33,6 +33,10 @@ pub(crate) trait ResourceFilter: Sized {
     ) -> Self {
         unimplemented!();
     }
+    /// Rank the items in order of preference, using the given `rank` function.
+    fn rank<F: FnOnce(&Self::Request, Vec<Self::Item>) -> Vec<Self::Item>>(self, _rank: F) -> Self {
+        unimplemented!();
+    }
     fn collect(self) -> Vec<Self::Item>;
     fn group_by<K, V, F: Fn(&Self::Request, &Vec<Self::Item>) -> HashMap<K, V>>(
         self,
@@ -53,6 +57,44 @@ impl NodeFilters {
     pub(crate) fn allowed(request: &GetSuitablePoolsContext, item: &PoolItem) -> bool {
         request.allowed_nodes().is_empty() || request.allowed_nodes().contains(&item.pool.node)
     }
+    /// Should only attempt to use nodes having the inclusive labels and none of the exclusive
+    /// labels of the topology, if it has them.
+    pub(crate) fn topology(request: &GetSuitablePoolsContext, item: &PoolItem) -> bool {
+        let labelled_topology = match request.node_topology() {
+            Some(labelled_topology) => labelled_topology,
+            None => return true,
+        };
+        // The labels in Volume Node Topology should match the node labels if present,
+        // otherwise selection of any node is allowed.
+        if labelled_topology.inclusion.is_empty() && labelled_topology.exclusion.is_empty() {
+            return true;
+        }
+        match request.registry().specs().node(&item.pool.node) {
+            Ok(spec) => qualifies_labels(labelled_topology, spec.labels()),
+            Err(_) => false,
+        }
+    }
+    /// Should only attempt to use nodes whose values for the spread label keys of the topology
+    /// differ from the ones of the nodes currently used by the volume.
+    pub(crate) fn spread(request: &GetSuitablePoolsContext, item: &PoolItem) -> bool {
+        let spread = match request.node_topology() {
+            Some(labelled_topology) if !labelled_topology.spread.is_empty() => {
+                &labelled_topology.spread
+            }
+            _ => return true,
+        };
+        let specs = request.registry().specs();
+        let node_labels = |node: &NodeId| specs.node(node).ok().map(|spec| spec.labels().clone());
+        let used_labels = specs
+            .volume_data_nodes(&request.uuid)
+            .iter()
+            .filter_map(node_labels)
+            .collect::<Vec<_>>();
+        match node_labels(&item.pool.node) {
+            Some(labels) => qualifies_spread(spread, &labels, &used_labels),
+            None => false,
+        }
+    }
     /// Should only attempt to use nodes not currently used by the volume.
     pub(crate) fn unused(request: &GetSuitablePoolsContext, item: &PoolItem) -> bool {
         let registry = request.registry();
@@ -120,8 +162,10 @@ impl PoolFilters {
         }
     }
     /// Should only attempt to use pools with sufficient free space for a full rebuild.
-    /// Currently the data-plane fully rebuilds a volume, meaning a thin provisioned volume
+    /// Currently the data-plane fully rebuilds a new replica, meaning a thin provisioned volume
     /// becomes fully allocated.
+    /// Replicas which are kept for a partial rebuild while their node is briefly offline are
+    /// re-added to the volume target as they are, and so they are not subject to this filter.
     pub(crate) fn free_space_full_rebuild(
         request: &GetSuitablePoolsContext,
         item: &PoolItem,
@@ -135,37 +179,77 @@ impl PoolFilters {
     pub(crate) fn usable(_: &GetSuitablePoolsContext, item: &PoolItem) -> bool {
         item.pool.status != PoolStatus::Faulted && item.pool.status != PoolStatus::Unknown
     }
-    /// Should only attempt to use pools having specific creation label if topology has it.
+    /// Should only attempt to use pools having the inclusive labels and none of the exclusive
+    /// labels of the topology, if it has them.
     pub(crate) fn topology(request: &GetSuitablePoolsContext, item: &PoolItem) -> bool {
-        let volume_pool_topology_labels: HashMap<String, String>;
-        match request.topology.clone() {
+        let labelled_topology = match request.pool_topology() {
+            Some(labelled_topology) => labelled_topology,
             None => return true,
-            Some(topology) => match topology.pool {
-                None => return true,
-                Some(pool_topology) => match pool_topology {
-                    PoolTopology::Labelled(labelled_topology) => {
-                        // The labels in Volume Pool Topology should match the pool labels if
-                        // present, otherwise selection of any pool is allowed.
-                        if !labelled_topology.inclusion.is_empty() {
-                            volume_pool_topology_labels = labelled_topology.inclusion
-                        } else {
-                            return true;
-                        }
-                    }
-                },
-            },
         };
+        // The labels in Volume Pool Topology should match the pool labels if present,
+        // otherwise selection of any pool is allowed.
+        if labelled_topology.inclusion.is_empty() && labelled_topology.exclusion.is_empty() {
+            return true;
+        }
         // We will reach this part of code only if the volume has pool topology labels.
         match request.registry().specs().pool(&item.pool.id) {
-            Ok(spec) => match spec.labels {
-                None => false,
-                Some(label) => volume_pool_topology_labels.keys().all(|k| {
-                    label.contains_key(k) && (volume_pool_topology_labels.get(k) == label.get(k))
-                }),
-            },
+            Ok(spec) => qualifies_labels(labelled_topology, &spec.labels.unwrap_or_default()),
             Err(_) => false,
         }
     }
+    /// Should only attempt to use pools whose values for the spread label keys of the topology
+    /// differ from the ones of the pools currently used by the volume.
+    pub(crate) fn spread(request: &GetSuitablePoolsContext, item: &PoolItem) -> bool {
+        let spread = match request.pool_topology() {
+            Some(labelled_topology) if !labelled_topology.spread.is_empty() => {
+                &labelled_topology.spread
+            }
+            _ => return true,
+        };
+        let specs = request.registry().specs();
+        let pool_labels = |pool: &PoolId| specs.pool(pool).ok().map(|spec| spec.labels);
+        let used_labels = specs
+            .volume_replicas_cln(&request.uuid)
+            .iter()
+            .filter_map(|replica| pool_labels(replica.pool_name()))
+            .map(Option::unwrap_or_default)
+            .collect::<Vec<_>>();
+        match pool_labels(&item.pool.id) {
+            Some(labels) => qualifies_spread(spread, &labels.unwrap_or_default(), &used_labels),
+            None => false,
+        }
+    }
+}
+
+/// Check if the given resource labels have all of the inclusive labels and none of the exclusive
+/// labels of the topology.
+/// An exclusive label with an empty value excludes the label key with any value.
+fn qualifies_labels(topology: &LabelledTopology, labels: &HashMap<String, String>) -> bool {
+    let included = topology
+        .inclusion
+        .iter()
+        .all(|(key, value)| labels.get(key) == Some(value));
+    let excluded = topology
+        .exclusion
+        .iter()
+        .any(|(key, value)| match labels.get(key) {
+            Some(label) => value.is_empty() || label == value,
+            None => false,
+        });
+    included && !excluded
+}
+
+/// Check if the given resource labels have a value for each of the spread label keys, which is
+/// not the value of any of the resources which are already used.
+fn qualifies_spread(
+    spread: &[String],
+    labels: &HashMap<String, String>,
+    used_labels: &[HashMap<String, String>],
+) -> bool {
+    spread.iter().all(|key| match labels.get(key) {
+        Some(value) => !used_labels.iter().any(|used| used.get(key) == Some(value)),
+        None => false,
+    })
 }
 
 /// Sort the pools used for replica creation
@@ -177,6 +261,168 @@ impl PoolSorters {
     }
 }
 
+/// Configuration of the pool scoring for replica placement.
+/// Each of the criteria is scored from 0 to 100 and then weighted by its configured weight.
+/// With all weights set to 0 the pools are not scored at all and the replica count order is kept.
+#[derive(Debug, Clone)]
+pub(crate) struct PoolScoringConfig {
+    /// Weight of the pool free capacity percentage.
+    capacity_weight: u64,
+    /// Weight of the pool and node load (number of replicas and nexuses).
+    load_weight: u64,
+    /// Weight of the spread across the failure domains.
+    spread_weight: u64,
+    /// Node labels which define the failure domains, eg: zone or rack.
+    failure_domain_labels: Vec<String>,
+}
+impl PoolScoringConfig {
+    /// Return a new `Self` with the given weights and failure domain node labels.
+    pub(crate) fn new(
+        capacity_weight: u64,
+        load_weight: u64,
+        spread_weight: u64,
+        failure_domain_labels: Vec<String>,
+    ) -> Self {
+        Self {
+            capacity_weight,
+            load_weight,
+            spread_weight,
+            failure_domain_labels,
+        }
+    }
+    /// Check if any of the criteria is weighted at all.
+    fn enabled(&self) -> bool {
+        self.capacity_weight > 0
+            || self.load_weight > 0
+            || (self.spread_weight > 0 && !self.failure_domain_labels.is_empty())
+    }
+}
+
+/// The failure domains of a node, ie: the values of its failure domain labels, in the same order
+/// as the configured labels. A node without one of the labels is not part of that failure domain.
+type FailureDomains = Vec<Option<String>>;
+
+/// The score of a pool, before the spread is accounted for.
+struct PoolScore {
+    /// The weighted capacity and load score, which doesn't change while ranking.
+    base: u64,
+    /// The failure domains of the pool's node.
+    domains: FailureDomains,
+}
+
+/// Score the pools used for replica creation
+pub(crate) struct PoolScorers {}
+impl PoolScorers {
+    /// The maximum score of each criteria, before it's weighted.
+    const MAX_SCORE: u64 = 100;
+
+    /// Rank pools by their free capacity, load and the spread of the volume replicas across the
+    /// failure domains, from highest to lowest score, with the previous order breaking any ties.
+    /// The spread of a pool accounts for the pools which have already been ranked ahead of it.
+    pub(crate) fn rank(request: &GetSuitablePoolsContext, pools: Vec<PoolItem>) -> Vec<PoolItem> {
+        let config = request.registry().pool_scoring();
+        if !config.enabled() {
+            return pools;
+        }
+
+        // all of the node labels are looked up once per request, rather than once per score
+        let specs = request.registry().specs();
+        let mut node_domains = HashMap::<NodeId, FailureDomains>::new();
+        let mut domains = |node: &NodeId| -> FailureDomains {
+            node_domains
+                .entry(node.clone())
+                .or_insert_with(|| {
+                    let labels = specs
+                        .node(node)
+                        .map(|n| n.labels().clone())
+                        .unwrap_or_default();
+                    config
+                        .failure_domain_labels
+                        .iter()
+                        .map(|key| labels.get(key).cloned())
+                        .collect()
+                })
+                .clone()
+        };
+        let used = specs
+            .volume_data_nodes(&request.uuid)
+            .iter()
+            .map(&mut domains)
+            .collect::<Vec<_>>();
+        let scores = pools
+            .iter()
+            .map(|item| PoolScore {
+                base: config.capacity_weight
+                    * Self::capacity(item.pool.free_space(), item.pool.capacity)
+                    + config.load_weight
+                        * Self::load(item.pool.replicas().len() + item.node.nexus_count()),
+                domains: domains(&item.pool.node),
+            })
+            .collect::<Vec<_>>();
+
+        let mut pools = pools.into_iter().map(Some).collect::<Vec<_>>();
+        Self::rank_scores(config, &scores, used)
+            .into_iter()
+            .filter_map(|index| pools[index].take())
+            .collect()
+    }
+    /// Rank the given scores from highest to lowest, returning their indexes.
+    /// The spread is scored against the `used` failure domains, which grow with every ranked pool.
+    fn rank_scores(
+        config: &PoolScoringConfig,
+        scores: &[PoolScore],
+        mut used: Vec<FailureDomains>,
+    ) -> Vec<usize> {
+        let mut unranked = (0 .. scores.len()).collect::<Vec<_>>();
+        let mut ranked = Vec::with_capacity(scores.len());
+        while !unranked.is_empty() {
+            // pick the first of the highest scores, so ties keep the previous order
+            let mut best: Option<(usize, u64)> = None;
+            for (position, index) in unranked.iter().enumerate() {
+                let score = &scores[*index];
+                let score = score.base + config.spread_weight * Self::spread(&score.domains, &used);
+                if best.map_or(true, |(_, best_score)| score > best_score) {
+                    best = Some((position, score));
+                }
+            }
+            let (best, _) = best.unwrap_or_default();
+            let index = unranked.remove(best);
+            used.push(scores[index].domains.clone());
+            ranked.push(index);
+        }
+        ranked
+    }
+    /// Prefer pools with a larger percentage of free capacity.
+    fn capacity(free_space: u64, capacity: u64) -> u64 {
+        match capacity {
+            0 => 0,
+            capacity => free_space * Self::MAX_SCORE / capacity,
+        }
+    }
+    /// Prefer pools with fewer replicas, on nodes with fewer nexuses.
+    fn load(load: usize) -> u64 {
+        Self::MAX_SCORE / (1 + load as u64)
+    }
+    /// Prefer pools on nodes in failure domains which are not yet used.
+    fn spread(domains: &[Option<String>], used: &[FailureDomains]) -> u64 {
+        if domains.is_empty() {
+            return Self::MAX_SCORE;
+        }
+        let spread_domains = domains
+            .iter()
+            .enumerate()
+            .filter(|(label, domain)| match domain {
+                // nodes without the label are not part of any known failure domain
+                None => true,
+                Some(domain) => !used
+                    .iter()
+                    .any(|used| used.get(*label).and_then(Option::as_ref) == Some(domain)),
+            })
+            .count() as u64;
+        spread_domains * Self::MAX_SCORE / domains.len() as u64
+    }
+}
+
 /// Sort the nexus children for removal when decreasing a volume's replica count
 pub(crate) struct ChildSorters {}
 impl ChildSorters {
@@ -373,3 +619,161 @@ impl NodeSorters {
             .cmp(&b.node_wrapper().nexus_count())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn zones(zones: &[Option<&str>]) -> Vec<FailureDomains> {
+        zones
+            .iter()
+            .map(|zone| vec![zone.map(ToString::to_string)])
+            .collect()
+    }
+    fn scores(base: &[u64], zones: &[Option<&str>]) -> Vec<PoolScore> {
+        base.iter()
+            .zip(self::zones(zones))
+            .map(|(base, domains)| PoolScore {
+                base: *base,
+                domains,
+            })
+            .collect()
+    }
+    fn labels(labels: &[(&str, &str)]) -> HashMap<String, String> {
+        labels
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn topology_labels() {
+        let node = labels(&[("zone", "a"), ("rack", "1")]);
+        assert!(qualifies_labels(&LabelledTopology::default(), &node));
+
+        let topology = LabelledTopology {
+            inclusion: labels(&[("zone", "a")]),
+            ..Default::default()
+        };
+        assert!(qualifies_labels(&topology, &node));
+        assert!(!qualifies_labels(&topology, &labels(&[("zone", "b")])));
+        assert!(!qualifies_labels(&topology, &labels(&[])));
+
+        let topology = LabelledTopology {
+            exclusion: labels(&[("rack", "2")]),
+            ..Default::default()
+        };
+        assert!(qualifies_labels(&topology, &node));
+        assert!(qualifies_labels(&topology, &labels(&[])));
+        assert!(!qualifies_labels(&topology, &labels(&[("rack", "2")])));
+        // an empty value excludes the key with any value
+        let topology = LabelledTopology {
+            exclusion: labels(&[("rack", "")]),
+            ..Default::default()
+        };
+        assert!(!qualifies_labels(&topology, &node));
+        assert!(qualifies_labels(&topology, &labels(&[("zone", "a")])));
+
+        // the exclusion wins over the inclusion
+        let topology = LabelledTopology {
+            inclusion: labels(&[("zone", "a")]),
+            exclusion: labels(&[("rack", "1")]),
+            ..Default::default()
+        };
+        assert!(!qualifies_labels(&topology, &node));
+        assert!(qualifies_labels(
+            &topology,
+            &labels(&[("zone", "a"), ("rack", "2")])
+        ));
+    }
+
+    #[test]
+    fn topology_spread() {
+        let spread = vec!["zone".to_string()];
+        // nothing used yet
+        assert!(qualifies_spread(&spread, &labels(&[("zone", "a")]), &[]));
+        // resources without the key can't be spread
+        assert!(!qualifies_spread(&spread, &labels(&[]), &[]));
+
+        let used = vec![labels(&[("zone", "a"), ("rack", "1")]), labels(&[])];
+        assert!(!qualifies_spread(&spread, &labels(&[("zone", "a")]), &used));
+        assert!(qualifies_spread(&spread, &labels(&[("zone", "b")]), &used));
+        // without any spread keys every resource qualifies
+        assert!(qualifies_spread(&[], &labels(&[]), &used));
+
+        // each of the keys must be spread
+        let spread = vec!["zone".to_string(), "rack".to_string()];
+        let candidate = labels(&[("zone", "b"), ("rack", "1")]);
+        assert!(!qualifies_spread(&spread, &candidate, &used));
+        let candidate = labels(&[("zone", "b"), ("rack", "2")]);
+        assert!(qualifies_spread(&spread, &candidate, &used));
+    }
+
+    #[test]
+    fn default_weights_keep_the_order() {
+        let config = PoolScoringConfig::new(0, 0, 0, vec![]);
+        assert!(!config.enabled());
+        // the spread is not scored without any failure domain labels
+        let config = PoolScoringConfig::new(0, 0, 2, vec![]);
+        assert!(!config.enabled());
+        let config = PoolScoringConfig::new(0, 0, 2, vec!["zone".to_string()]);
+        assert!(config.enabled());
+
+        let config = PoolScoringConfig::new(0, 0, 0, vec!["zone".to_string()]);
+        let scores = scores(&[0, 0, 0], &[Some("a"), Some("a"), Some("b")]);
+        assert_eq!(
+            PoolScorers::rank_scores(&config, &scores, vec![]),
+            vec![0, 1, 2]
+        );
+    }
+
+    #[test]
+    fn capacity_and_load() {
+        assert_eq!(PoolScorers::capacity(0, 0), 0);
+        assert_eq!(PoolScorers::capacity(50, 100), 50);
+        assert_eq!(PoolScorers::capacity(100, 100), 100);
+        assert_eq!(PoolScorers::load(0), 100);
+        assert_eq!(PoolScorers::load(1), 50);
+        assert_eq!(PoolScorers::load(3), 25);
+
+        // ties keep the previous order
+        let config = PoolScoringConfig::new(1, 0, 0, vec![]);
+        let scores = scores(&[10, 30, 30, 20], &[None, None, None, None]);
+        assert_eq!(
+            PoolScorers::rank_scores(&config, &scores, vec![]),
+            vec![1, 2, 3, 0]
+        );
+    }
+
+    #[test]
+    fn spread() {
+        let used = zones(&[Some("a")]);
+        assert_eq!(PoolScorers::spread(&[Some("a".to_string())], &used), 0);
+        assert_eq!(PoolScorers::spread(&[Some("b".to_string())], &used), 100);
+        // nodes without the label are not part of any known failure domain
+        assert_eq!(PoolScorers::spread(&[None], &used), 100);
+        // each of the failure domain labels is scored separately
+        let used = vec![vec![Some("a".to_string()), Some("rack-1".to_string())]];
+        let domains = vec![Some("a".to_string()), Some("rack-2".to_string())];
+        assert_eq!(PoolScorers::spread(&domains, &used), 50);
+        // without any failure domain labels every pool is equally spread
+        assert_eq!(PoolScorers::spread(&[], &used), 100);
+
+        // pools in the zone already used by the volume come last, and each pool ranked ahead
+        // takes its zone into use as well
+        let config = PoolScoringConfig::new(0, 0, 1, vec!["zone".to_string()]);
+        let scores = scores(&[0, 0, 0, 0], &[Some("a"), Some("b"), Some("b"), Some("c")]);
+        assert_eq!(
+            PoolScorers::rank_scores(&config, &scores, zones(&[Some("a")])),
+            vec![1, 3, 0, 2]
+        );
+
+        // the spread is weighted against the other criteria
+        let config = PoolScoringConfig::new(1, 0, 2, vec!["zone".to_string()]);
+        let scores = scores(&[150, 100], &[Some("a"), Some("b")]);
+        assert_eq!(
+            PoolScorers::rank_scores(&config, &scores, zones(&[Some("a")])),
+            vec![1, 0]
+        );
+    }
+}
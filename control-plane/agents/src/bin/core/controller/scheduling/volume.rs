@@ -2,15 +2,18 @@ use crate::controller::{
     registry::Registry,
     scheduling::{
         resources::{ChildItem, PoolItem, PoolItemLister, ReplicaItem},
-        AddReplicaFilters, AddReplicaSorters, ChildSorters, NodeFilters, PoolFilters, PoolSorters,
-        ResourceFilter,
+        AddReplicaFilters, AddReplicaSorters, ChildSorters, NodeFilters, PoolFilters, PoolScorers,
+        PoolSorters, ResourceFilter,
     },
 };
 
 use agents::errors::SvcError;
 use common_lib::types::v0::{
     store::{nexus::NexusSpec, nexus_persistence::NexusInfo, volume::VolumeSpec},
-    transport::{ChildUri, CreateVolume, VolumeState},
+    transport::{
+        ChildUri, CreateVolume, LabelledTopology, NodeTopology, PoolId, PoolTopology, ReplicaId,
+        VolumeState,
+    },
 };
 
 use crate::controller::resources::ResourceMutex;
@@ -47,6 +50,28 @@ impl GetSuitablePoolsContext {
     pub(crate) fn registry(&self) -> &Registry {
         &self.registry
     }
+    /// Get the labelled node topology of the volume, if any.
+    pub(crate) fn node_topology(&self) -> Option<&LabelledTopology> {
+        match self
+            .topology
+            .as_ref()
+            .and_then(|topology| topology.node.as_ref())
+        {
+            Some(NodeTopology::Labelled(labelled_topology)) => Some(labelled_topology),
+            _ => None,
+        }
+    }
+    /// Get the labelled pool topology of the volume, if any.
+    pub(crate) fn pool_topology(&self) -> Option<&LabelledTopology> {
+        match self
+            .topology
+            .as_ref()
+            .and_then(|topology| topology.pool.as_ref())
+        {
+            Some(PoolTopology::Labelled(labelled_topology)) => Some(labelled_topology),
+            None => None,
+        }
+    }
 }
 
 impl Deref for GetSuitablePoolsContext {
@@ -92,22 +117,61 @@ impl AddVolumeReplica {
             .await
             // filter pools according to the following criteria (any order):
             // 1. exclude nodes that are cordoned
-            // 2. if allowed_nodes were specified then only pools from those nodes
-            // can be used.
+            // 2. if allowed_nodes or node topology labels were specified then only pools
+            // from those nodes can be used.
             // 3. pools should have enough free space for the
             // volume (do we need to take into account metadata?)
             // 4. ideally use only healthy(online) pools with degraded pools as a
             // fallback
             // 5. only one replica per node
+            // 6. if topology spread label keys were specified then only nodes and pools with
+            // label values not used by the volume yet
             .filter(NodeFilters::cordoned_for_pool)
             .filter(NodeFilters::online_for_pool)
             .filter(NodeFilters::allowed)
+            .filter(NodeFilters::topology)
+            .filter(NodeFilters::spread)
             .filter(NodeFilters::unused)
             .filter(PoolFilters::usable)
             .filter(PoolFilters::capacity)
             .filter(PoolFilters::free_space)
             .filter(PoolFilters::free_space_full_rebuild)
             .filter(PoolFilters::topology)
+            .filter(PoolFilters::spread)
+            // sort pools in order of preference (from least to most number of replicas)
+            .sort(PoolSorters::sort_by_replica_count)
+            // rank pools by their free capacity, load and failure domain spread, with the
+            // previous order breaking any ties
+            .rank(PoolScorers::rank)
+    }
+    /// Rules for pool selection when creating replicas for a volume from a snapshot.
+    /// A snapshot clone can only be created on the pool where the replica snapshot lives.
+    pub(crate) async fn builder_for_clone(
+        request: impl Into<GetSuitablePools>,
+        registry: &Registry,
+        source_pools: &[PoolId],
+    ) -> Self {
+        Self::builder(request, registry)
+            .await
+            // filter pools according to the following criteria (any order):
+            // 1. exclude nodes that are cordoned
+            // 2. if allowed_nodes or node topology labels were specified then only pools
+            // from those nodes can be used.
+            // 3. only pools which contain a replica snapshot of the source
+            // 4. only one replica per node
+            // 5. if topology spread label keys were specified then only nodes and pools with
+            // label values not used by the volume yet
+            // clones are thin and share the snapshot's data, so no space filters are applied
+            .filter(NodeFilters::cordoned_for_pool)
+            .filter(NodeFilters::online_for_pool)
+            .filter(NodeFilters::allowed)
+            .filter(NodeFilters::topology)
+            .filter(NodeFilters::spread)
+            .filter(NodeFilters::unused)
+            .filter(PoolFilters::usable)
+            .filter(PoolFilters::topology)
+            .filter(PoolFilters::spread)
+            .filter(|_, item| source_pools.contains(&item.pool.id))
             // sort pools in order of preference (from least to most number of replicas)
             .sort(PoolSorters::sort_by_replica_count)
     }
@@ -133,6 +197,15 @@ impl ResourceFilter for AddVolumeReplica {
         self
     }
 
+    fn rank<P: FnOnce(&Self::Request, Vec<Self::Item>) -> Vec<Self::Item>>(
+        mut self,
+        rank: P,
+    ) -> Self {
+        let request = self.context.clone();
+        self.list = rank(&request, self.list);
+        self
+    }
+
     fn collect(self) -> Vec<Self::Item> {
         self.list
     }
@@ -309,15 +382,19 @@ pub(crate) struct ReplicaRemovalCandidates {
 }
 
 impl ReplicaRemovalCandidates {
-    /// Get the next healthy replica removal candidate.
-    fn next_healthy(&mut self) -> Option<ReplicaItem> {
+    /// Check if a healthy replica may be removed without compromising the volume's redundancy.
+    fn healthy_removable(&self) -> bool {
         let replica_count = self.context.spec.desired_num_replicas();
         let healthy_online = self.healthy.iter().filter(|replica| match replica.state() {
             None => false,
             Some(state) => state.online(),
         });
         // removing too many healthy_online replicas could compromise the volume's redundancy
-        if healthy_online.into_iter().count() > replica_count as usize {
+        healthy_online.into_iter().count() > replica_count as usize
+    }
+    /// Get the next healthy replica removal candidate.
+    fn next_healthy(&mut self) -> Option<ReplicaItem> {
+        if self.healthy_removable() {
             match self.healthy.pop() {
                 Some(replica) if self.context.unused_only & replica.child_spec().is_none() => {
                     Some(replica)
@@ -337,6 +414,18 @@ impl ReplicaRemovalCandidates {
     pub(crate) fn next(&mut self) -> Option<ReplicaItem> {
         self.next_unhealthy().or_else(|| self.next_healthy())
     }
+    /// Get the given replica as the next removal candidate, if it can be removed.
+    /// As with `next`, a healthy replica is only removed if there are enough healthy replicas.
+    pub(crate) fn next_of(&mut self, replica: &ReplicaId) -> Option<ReplicaItem> {
+        let is_replica = |item: &ReplicaItem| &item.spec().uuid == replica;
+        if let Some(index) = self.unhealthy.iter().position(is_replica) {
+            return Some(self.unhealthy.remove(index));
+        }
+        match self.healthy.iter().position(is_replica) {
+            Some(index) if self.healthy_removable() => Some(self.healthy.remove(index)),
+            _ => None,
+        }
+    }
 
     fn new(context: GetChildForRemovalContext, items: Vec<ReplicaItem>) -> Self {
         let has_info = context.nexus_info.is_some();
@@ -26,6 +26,13 @@ impl Deref for ResourceStatesLocked {
     }
 }
 
+/// The nexuses and replicas whose states were added, modified or removed by an update.
+#[derive(Default, Debug)]
+pub(crate) struct ChangedStates {
+    pub(crate) nexuses: Vec<NexusId>,
+    pub(crate) replicas: Vec<ReplicaId>,
+}
+
 /// Resource States.
 #[derive(Default, Debug)]
 pub(crate) struct ResourceStates {
@@ -36,21 +43,23 @@ pub(crate) struct ResourceStates {
 
 impl ResourceStates {
     /// Update the various resource states.
+    /// Returns the nexuses and replicas whose states changed.
     pub(crate) fn update(
         &mut self,
         pools: Vec<transport::PoolState>,
         replicas: Vec<Replica>,
         nexuses: Vec<Nexus>,
-    ) {
-        self.update_replicas(replicas);
+    ) -> ChangedStates {
+        let replicas = self.update_replicas(replicas);
         self.update_pools(pools);
-        self.update_nexuses(nexuses);
+        let nexuses = self.update_nexuses(nexuses);
+        ChangedStates { nexuses, replicas }
     }
 
     /// Update nexus states.
-    pub(crate) fn update_nexuses(&mut self, nexuses: Vec<Nexus>) {
-        self.nexuses.clear();
-        self.nexuses.populate(nexuses);
+    /// Returns the IDs of the nexuses whose states changed.
+    pub(crate) fn update_nexuses(&mut self, nexuses: Vec<Nexus>) -> Vec<NexusId> {
+        self.nexuses.replace(nexuses)
     }
 
     /// Returns a vector of cloned nexus states.
@@ -91,9 +100,9 @@ impl ResourceStates {
     }
 
     /// Update replica states.
-    pub(crate) fn update_replicas(&mut self, replicas: Vec<Replica>) {
-        self.replicas.clear();
-        self.replicas.populate(replicas);
+    /// Returns the IDs of the replicas whose states changed.
+    pub(crate) fn update_replicas(&mut self, replicas: Vec<Replica>) -> Vec<ReplicaId> {
+        self.replicas.replace(replicas)
     }
 
     /// Returns a vector of cloned replica states.
@@ -119,7 +119,10 @@ pub(crate) trait TaskPoller: Send + Sync + std::fmt::Debug {
     async fn try_poll(&mut self, context: &PollContext) -> PollResult {
         tracing::trace!("Entering trace call");
         let result = if self.poll_ready(context).await {
-            self.poll(context).await
+            let start = std::time::Instant::now();
+            let result = self.poll(context).await;
+            crate::metrics::observe_reconciler_poll(self.name(), &result, start.elapsed());
+            result
         } else {
             PollResult::Ok(PollerState::Idle)
         };
@@ -127,6 +130,12 @@ pub(crate) trait TaskPoller: Send + Sync + std::fmt::Debug {
         result
     }
 
+    /// Name of the poller, used to identify it in the metrics
+    fn name(&self) -> &'static str {
+        let name = std::any::type_name::<Self>();
+        name.rsplit("::").next().unwrap_or(name)
+    }
+
     /// Force poll the poller
     async fn poll(&mut self, context: &PollContext) -> PollResult;
 
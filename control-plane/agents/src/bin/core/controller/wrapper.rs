@@ -3,7 +3,7 @@ use crate::{
     controller::{
         grpc::{GrpcClient, GrpcClientLocked},
         resources::ResourceUid,
-        states::{ResourceStates, ResourceStatesLocked},
+        states::{ChangedStates, ResourceStates, ResourceStatesLocked},
     },
     node::service::NodeCommsTimeout,
     NumRebuilds,
@@ -17,9 +17,11 @@ use agents::{
             rpc_replica_to_agent as v0_rpc_replica_to_agent, AgentToIoEngine as v0_conversion,
         },
         v1::{
+            rpc_nexus_snapshot_to_agent as v1_rpc_nexus_snapshot_to_agent,
             rpc_nexus_to_agent as v1_rpc_nexus_to_agent,
             rpc_nexus_to_child_agent as v1_rpc_nexus_to_child_agent,
             rpc_pool_to_agent as v1_rpc_pool_to_agent,
+            rpc_replica_snapshot_to_agent as v1_rpc_replica_snapshot_to_agent,
             rpc_replica_to_agent as v1_rpc_replica_to_agent, AgentToIoEngine as v1_conversion,
         },
         IoEngineToAgent,
@@ -31,11 +33,14 @@ use common_lib::{
         store,
         store::{nexus::NexusState, replica::ReplicaState},
         transport::{
-            AddNexusChild, ApiVersion, Child, CreateNexus, CreatePool, CreateReplica, DestroyNexus,
-            DestroyPool, DestroyReplica, FaultNexusChild, MessageIdVs, Nexus, NexusId, NodeId,
-            NodeState, NodeStatus, PoolId, PoolState, PoolStatus, Protocol, Register,
-            RemoveNexusChild, Replica, ReplicaId, ReplicaName, ShareNexus, ShareReplica,
-            ShutdownNexus, UnshareNexus, UnshareReplica,
+            AddNexusChild, ApiVersion, Child, CreateNexus, CreateNexusSnapshot, CreatePool,
+            CreateReplica, CreateReplicaSnapshot, CreateSnapshotClone, DestroyNexus, DestroyPool,
+            DestroyReplica, DestroyReplicaSnapshot, ExpandPool, FaultNexusChild, MessageIdVs,
+            Nexus, NexusId, NexusSnapshotStatus, NodeId, NodeState, NodeStatus, PoolId, PoolState,
+            PoolStatus, Protocol, Register, RemoveNexusChild, Replica, ReplicaId, ReplicaName,
+            ReplicaSnapshotInfo, ResizeNexus, ResizeReplica, ResourceEvent, ResourceEventAction,
+            SetNexusQos, SetRebuildBandwidth, ShareNexus, ShareReplica, ShutdownNexus,
+            UnshareNexus, UnshareReplica,
         },
     },
 };
@@ -48,6 +53,7 @@ use std::{
     ops::{Deref, DerefMut},
     sync::Arc,
 };
+use tokio::sync::broadcast;
 use tracing::{debug, trace, warn};
 
 type NodeResourceStates = (Vec<Replica>, Vec<PoolState>, Vec<Nexus>);
@@ -85,14 +91,18 @@ pub(crate) struct NodeWrapper {
     states: ResourceStatesLocked,
     /// The number of rebuilds in progress on the node.
     num_rebuilds: Arc<RwLock<NumRebuilds>>,
+    /// Publishes the nexus and replica state change events.
+    events: broadcast::Sender<ResourceEvent>,
 }
 
 impl NodeWrapper {
     /// Create a new wrapper for a `Node` with a `deadline` for its watchdog.
+    /// Changes to its nexus and replica states are published through `events`.
     pub(crate) fn new(
         node: &NodeState,
         deadline: std::time::Duration,
         comms_timeouts: NodeCommsTimeout,
+        events: broadcast::Sender<ResourceEvent>,
     ) -> Self {
         tracing::debug!("Creating new node {:?}", node);
         Self {
@@ -103,6 +113,7 @@ impl NodeWrapper {
             comms_timeouts,
             states: ResourceStatesLocked::new(),
             num_rebuilds: Arc::new(RwLock::new(0)),
+            events,
         }
     }
 
@@ -646,21 +657,47 @@ impl NodeWrapper {
     /// Update the states of the specified resource type.
     /// Whenever the nexus states are updated the number of rebuilds must be updated.
     fn update_resources(&self, resource_type: ResourceType) {
-        match resource_type {
+        let changed = match resource_type {
             ResourceType::All(pools, replicas, nexuses) => {
-                self.resources_mut().update(pools, replicas, nexuses);
+                let changed = self.resources_mut().update(pools, replicas, nexuses);
                 self.update_num_rebuilds();
+                changed
             }
             ResourceType::Nexus(nexuses) => {
-                self.resources_mut().update_nexuses(nexuses);
+                let nexuses = self.resources_mut().update_nexuses(nexuses);
                 self.update_num_rebuilds();
+                ChangedStates {
+                    nexuses,
+                    ..Default::default()
+                }
             }
             ResourceType::Pool(pools) => {
                 self.resources_mut().update_pools(pools);
+                ChangedStates::default()
             }
-            ResourceType::Replica(replicas) => {
-                self.resources_mut().update_replicas(replicas);
-            }
+            ResourceType::Replica(replicas) => ChangedStates {
+                replicas: self.resources_mut().update_replicas(replicas),
+                ..Default::default()
+            },
+        };
+        self.notify_state_changes(changed);
+    }
+
+    /// Publish an update event for each nexus and replica whose state changed.
+    fn notify_state_changes(&self, changed: ChangedStates) {
+        let nexuses = changed
+            .nexuses
+            .into_iter()
+            .map(|id| (ResourceKind::Nexus, id.to_string()));
+        let replicas = changed
+            .replicas
+            .into_iter()
+            .map(|id| (ResourceKind::Replica, id.to_string()));
+        for (kind, id) in nexuses.chain(replicas) {
+            // an error only means there are no subscribers at the moment
+            self.events
+                .send(ResourceEvent::new(kind, id, ResourceEventAction::Updated))
+                .ok();
         }
     }
 
@@ -752,14 +789,31 @@ pub(crate) trait ClientOps {
     async fn create_pool(&self, request: &CreatePool) -> Result<PoolState, SvcError>;
     /// Destroy a pool on the node via gRPC.
     async fn destroy_pool(&self, request: &DestroyPool) -> Result<(), SvcError>;
+    /// Expand a pool on the node via gRPC.
+    async fn expand_pool(&self, request: &ExpandPool) -> Result<PoolState, SvcError>;
     /// Create a replica on the pool via gRPC.
     async fn create_replica(&self, request: &CreateReplica) -> Result<Replica, SvcError>;
     /// Share a replica on the pool via gRPC.
     async fn share_replica(&self, request: &ShareReplica) -> Result<String, SvcError>;
     /// Unshare a replica on the pool via gRPC.
     async fn unshare_replica(&self, request: &UnshareReplica) -> Result<String, SvcError>;
+    /// Resize a replica on the pool via gRPC.
+    async fn resize_replica(&self, request: &ResizeReplica) -> Result<Replica, SvcError>;
     /// Destroy a replica on the pool via gRPC.
     async fn destroy_replica(&self, request: &DestroyReplica) -> Result<(), SvcError>;
+    /// Create a snapshot of a replica via gRPC.
+    async fn create_repl_snapshot(
+        &self,
+        request: &CreateReplicaSnapshot,
+    ) -> Result<ReplicaSnapshotInfo, SvcError>;
+    /// Destroy a replica snapshot via gRPC.
+    async fn destroy_repl_snapshot(&self, request: &DestroyReplicaSnapshot)
+        -> Result<(), SvcError>;
+    /// Create a replica from a replica snapshot via gRPC.
+    async fn create_snapshot_clone(
+        &self,
+        request: &CreateSnapshotClone,
+    ) -> Result<Replica, SvcError>;
 
     /// Create a nexus on a node via gRPC.
     async fn create_nexus(&self, request: &CreateNexus) -> Result<Nexus, SvcError>;
@@ -769,6 +823,17 @@ pub(crate) trait ClientOps {
     async fn share_nexus(&self, request: &ShareNexus) -> Result<String, SvcError>;
     /// Unshare a nexus on the node via gRPC.
     async fn unshare_nexus(&self, request: &UnshareNexus) -> Result<(), SvcError>;
+    /// Resize a nexus on the node via gRPC.
+    async fn resize_nexus(&self, request: &ResizeNexus) -> Result<Nexus, SvcError>;
+    /// Set the QoS limits of a nexus on the node via gRPC.
+    async fn set_nexus_qos(&self, request: &SetNexusQos) -> Result<(), SvcError>;
+    /// Snapshot the replicas of a nexus via gRPC, pausing the nexus I/O while doing so.
+    async fn create_nexus_snapshot(
+        &self,
+        request: &CreateNexusSnapshot,
+    ) -> Result<NexusSnapshotStatus, SvcError>;
+    /// Set the bandwidth limit of the rebuilds on the node via gRPC.
+    async fn set_rebuild_bandwidth(&self, request: &SetRebuildBandwidth) -> Result<(), SvcError>;
     /// Add a child to a nexus via gRPC.
     async fn add_child(&self, request: &AddNexusChild) -> Result<Child, SvcError>;
     /// Remove a child from its parent nexus via gRPC.
@@ -917,7 +982,7 @@ impl ClientOps for Arc<tokio::sync::RwLock<NodeWrapper>> {
     /// Create a pool on the node via gRPC.
     async fn create_pool(&self, request: &CreatePool) -> Result<PoolState, SvcError> {
         let dataplane = self.grpc_client_locked(request.id()).await?;
-        let create_response = dataplane.create_pool(request).await;
+        let create_response = dataplane.observed(dataplane.create_pool(request)).await;
         let mut ctx = dataplane.reconnect(GETS_TIMEOUT).await?;
         self.update_pool_states(ctx.deref_mut()).await?;
         self.update_replica_states(ctx.deref_mut()).await?;
@@ -935,7 +1000,7 @@ impl ClientOps for Arc<tokio::sync::RwLock<NodeWrapper>> {
     /// Destroy a pool on the node via gRPC.
     async fn destroy_pool(&self, request: &DestroyPool) -> Result<(), SvcError> {
         let dataplane = self.grpc_client_locked(request.id()).await?;
-        let destroy_response = dataplane.destroy_pool(request).await;
+        let destroy_response = dataplane.observed(dataplane.destroy_pool(request)).await;
         let mut ctx = dataplane.reconnect(GETS_TIMEOUT).await?;
         self.update_pool_states(ctx.deref_mut()).await?;
         match destroy_response {
@@ -948,6 +1013,14 @@ impl ClientOps for Arc<tokio::sync::RwLock<NodeWrapper>> {
             Ok(_) => Ok(()),
         }
     }
+    /// Expand a pool on the node via gRPC.
+    async fn expand_pool(&self, request: &ExpandPool) -> Result<PoolState, SvcError> {
+        let dataplane = self.grpc_client_locked(request.id()).await?;
+        let pool = dataplane.observed(dataplane.expand_pool(request)).await?;
+        let mut ctx = dataplane.reconnect(GETS_TIMEOUT).await?;
+        self.update_pool_states(ctx.deref_mut()).await?;
+        Ok(pool)
+    }
 
     /// Create a replica on the pool via gRPC.
     async fn create_replica(&self, request: &CreateReplica) -> Result<Replica, SvcError> {
@@ -958,7 +1031,7 @@ impl ClientOps for Arc<tokio::sync::RwLock<NodeWrapper>> {
             });
         }
         let dataplane = self.grpc_client_locked(request.id()).await?;
-        let replica = dataplane.create_replica(request).await;
+        let replica = dataplane.observed(dataplane.create_replica(request)).await;
         let mut ctx = dataplane.reconnect(GETS_TIMEOUT).await?;
         self.update_replica_states(ctx.deref_mut()).await?;
         self.update_pool_states(ctx.deref_mut()).await?;
@@ -984,7 +1057,7 @@ impl ClientOps for Arc<tokio::sync::RwLock<NodeWrapper>> {
     /// Share a replica on the pool via gRPC.
     async fn share_replica(&self, request: &ShareReplica) -> Result<String, SvcError> {
         let dataplane = self.grpc_client_locked(request.id()).await?;
-        let share = dataplane.share_replica(request).await?;
+        let share = dataplane.observed(dataplane.share_replica(request)).await?;
         let mut ctx = dataplane.reconnect(GETS_TIMEOUT).await?;
         self.update_replica_states(ctx.deref_mut()).await?;
         Ok(share)
@@ -993,16 +1066,30 @@ impl ClientOps for Arc<tokio::sync::RwLock<NodeWrapper>> {
     /// Unshare a replica on the pool via gRPC.
     async fn unshare_replica(&self, request: &UnshareReplica) -> Result<String, SvcError> {
         let dataplane = self.grpc_client_locked(request.id()).await?;
-        let local_uri = dataplane.unshare_replica(request).await?;
+        let local_uri = dataplane
+            .observed(dataplane.unshare_replica(request))
+            .await?;
         let mut ctx = dataplane.reconnect(GETS_TIMEOUT).await?;
         self.update_replica_states(ctx.deref_mut()).await?;
         Ok(local_uri)
     }
 
+    /// Resize a replica on the pool via gRPC.
+    async fn resize_replica(&self, request: &ResizeReplica) -> Result<Replica, SvcError> {
+        let dataplane = self.grpc_client_locked(request.id()).await?;
+        let replica = dataplane
+            .observed(dataplane.resize_replica(request))
+            .await?;
+        let mut ctx = dataplane.reconnect(GETS_TIMEOUT).await?;
+        self.update_replica_states(ctx.deref_mut()).await?;
+        self.update_pool_states(ctx.deref_mut()).await?;
+        Ok(replica)
+    }
+
     /// Destroy a replica on the pool via gRPC.
     async fn destroy_replica(&self, request: &DestroyReplica) -> Result<(), SvcError> {
         let dataplane = self.grpc_client_locked(request.id()).await?;
-        let result = dataplane.destroy_replica(request).await;
+        let result = dataplane.observed(dataplane.destroy_replica(request)).await;
         let mut ctx = dataplane.reconnect(GETS_TIMEOUT).await?;
         self.update_replica_states(ctx.deref_mut()).await?;
         // todo: remove when CAS-1107 is resolved
@@ -1027,6 +1114,72 @@ impl ClientOps for Arc<tokio::sync::RwLock<NodeWrapper>> {
         }
     }
 
+    /// Create a snapshot of a replica via gRPC.
+    async fn create_repl_snapshot(
+        &self,
+        request: &CreateReplicaSnapshot,
+    ) -> Result<ReplicaSnapshotInfo, SvcError> {
+        if request.snap_id == Default::default() {
+            return Err(SvcError::InvalidUuid {
+                uuid: request.snap_id.to_string(),
+                kind: ResourceKind::VolumeSnapshot,
+            });
+        }
+        let dataplane = self.grpc_client_locked(request.id()).await?;
+        let snapshot = dataplane
+            .observed(dataplane.create_repl_snapshot(request))
+            .await;
+        let mut ctx = dataplane.reconnect(GETS_TIMEOUT).await?;
+        self.update_pool_states(ctx.deref_mut()).await?;
+        snapshot
+    }
+
+    /// Destroy a replica snapshot via gRPC.
+    async fn destroy_repl_snapshot(
+        &self,
+        request: &DestroyReplicaSnapshot,
+    ) -> Result<(), SvcError> {
+        let dataplane = self.grpc_client_locked(request.id()).await?;
+        let result = dataplane
+            .observed(dataplane.destroy_repl_snapshot(request))
+            .await;
+        let mut ctx = dataplane.reconnect(GETS_TIMEOUT).await?;
+        self.update_pool_states(ctx.deref_mut()).await?;
+        match result {
+            Err(error) if error.tonic_code() == tonic::Code::NotFound => Ok(()),
+            result => result,
+        }
+    }
+
+    /// Create a replica from a replica snapshot via gRPC.
+    async fn create_snapshot_clone(
+        &self,
+        request: &CreateSnapshotClone,
+    ) -> Result<Replica, SvcError> {
+        if request.replica.uuid == ReplicaId::default() {
+            return Err(SvcError::InvalidUuid {
+                uuid: request.replica.uuid.to_string(),
+                kind: ResourceKind::Replica,
+            });
+        }
+        let dataplane = self.grpc_client_locked(request.id()).await?;
+        let replica = dataplane
+            .observed(dataplane.create_snapshot_clone(request))
+            .await;
+        let mut ctx = dataplane.reconnect(GETS_TIMEOUT).await?;
+        self.update_replica_states(ctx.deref_mut()).await?;
+        self.update_pool_states(ctx.deref_mut()).await?;
+        // check for idempotency
+        match replica {
+            Ok(replica) => Ok(replica),
+            Err(error) => match self.read().await.replica(&request.replica.uuid) {
+                // return Ok if the clone with the requested uuid already exists
+                Some(replica) => Ok(replica),
+                None => Err(error),
+            },
+        }
+    }
+
     /// Create a nexus on the node via gRPC.
     async fn create_nexus(&self, request: &CreateNexus) -> Result<Nexus, SvcError> {
         if request.uuid == NexusId::default() {
@@ -1036,7 +1189,7 @@ impl ClientOps for Arc<tokio::sync::RwLock<NodeWrapper>> {
             });
         }
         let dataplane = self.grpc_client_locked(request.id()).await?;
-        let nexus = dataplane.create_nexus(request).await;
+        let nexus = dataplane.observed(dataplane.create_nexus(request)).await;
         let mut ctx = dataplane.reconnect(GETS_TIMEOUT).await?;
         self.update_nexus_states(ctx.deref_mut()).await?;
         match nexus {
@@ -1063,7 +1216,7 @@ impl ClientOps for Arc<tokio::sync::RwLock<NodeWrapper>> {
     /// Destroy a nexus on the node via gRPC.
     async fn destroy_nexus(&self, request: &DestroyNexus) -> Result<(), SvcError> {
         let dataplane = self.grpc_client_locked(request.id()).await?;
-        let result = dataplane.destroy_nexus(request).await;
+        let result = dataplane.observed(dataplane.destroy_nexus(request)).await;
         let mut ctx = dataplane.reconnect(GETS_TIMEOUT).await?;
         self.update_nexus_states(ctx.deref_mut()).await?;
         match result {
@@ -1082,7 +1235,7 @@ impl ClientOps for Arc<tokio::sync::RwLock<NodeWrapper>> {
     /// Share a nexus on the node via gRPC.
     async fn share_nexus(&self, request: &ShareNexus) -> Result<String, SvcError> {
         let dataplane = self.grpc_client_locked(request.id()).await?;
-        let share = dataplane.share_nexus(request).await?;
+        let share = dataplane.observed(dataplane.share_nexus(request)).await?;
         let mut ctx = dataplane.reconnect(GETS_TIMEOUT).await?;
         self.update_nexus_states(ctx.deref_mut()).await?;
         Ok(share)
@@ -1091,16 +1244,63 @@ impl ClientOps for Arc<tokio::sync::RwLock<NodeWrapper>> {
     /// Unshare a nexus on the node via gRPC.
     async fn unshare_nexus(&self, request: &UnshareNexus) -> Result<(), SvcError> {
         let dataplane = self.grpc_client_locked(request.id()).await?;
-        let _ = dataplane.unshare_nexus(request).await?;
+        let _ = dataplane.observed(dataplane.unshare_nexus(request)).await?;
         let mut ctx = dataplane.reconnect(GETS_TIMEOUT).await?;
         self.update_nexus_states(ctx.deref_mut()).await?;
         Ok(())
     }
 
+    /// Resize a nexus on the node via gRPC.
+    async fn resize_nexus(&self, request: &ResizeNexus) -> Result<Nexus, SvcError> {
+        let dataplane = self.grpc_client_locked(request.id()).await?;
+        let nexus = dataplane.observed(dataplane.resize_nexus(request)).await?;
+        let mut ctx = dataplane.reconnect(GETS_TIMEOUT).await?;
+        self.update_nexus_states(ctx.deref_mut()).await?;
+        Ok(nexus)
+    }
+
+    /// Set the QoS limits of a nexus on the node via gRPC.
+    async fn set_nexus_qos(&self, request: &SetNexusQos) -> Result<(), SvcError> {
+        let dataplane = self.grpc_client_locked(request.id()).await?;
+        dataplane.observed(dataplane.set_nexus_qos(request)).await
+    }
+
+    /// Snapshot the replicas of a nexus via gRPC, pausing the nexus I/O while doing so.
+    async fn create_nexus_snapshot(
+        &self,
+        request: &CreateNexusSnapshot,
+    ) -> Result<NexusSnapshotStatus, SvcError> {
+        if let Some(replica) = request
+            .replicas
+            .iter()
+            .find(|r| r.snap_id == Default::default())
+        {
+            return Err(SvcError::InvalidUuid {
+                uuid: replica.snap_id.to_string(),
+                kind: ResourceKind::VolumeSnapshot,
+            });
+        }
+        let dataplane = self.grpc_client_locked(request.id()).await?;
+        let status = dataplane
+            .observed(dataplane.create_nexus_snapshot(request))
+            .await;
+        let mut ctx = dataplane.reconnect(GETS_TIMEOUT).await?;
+        self.update_pool_states(ctx.deref_mut()).await?;
+        status
+    }
+
+    /// Set the bandwidth limit of the rebuilds on the node via gRPC.
+    async fn set_rebuild_bandwidth(&self, request: &SetRebuildBandwidth) -> Result<(), SvcError> {
+        let dataplane = self.grpc_client_locked(request.id()).await?;
+        dataplane
+            .observed(dataplane.set_rebuild_bandwidth(request))
+            .await
+    }
+
     /// Add a child to a nexus via gRPC.
     async fn add_child(&self, request: &AddNexusChild) -> Result<Child, SvcError> {
         let dataplane = self.grpc_client_locked(request.id()).await?;
-        let result = dataplane.add_child(request).await;
+        let result = dataplane.observed(dataplane.add_child(request)).await;
         let mut ctx = dataplane.reconnect(GETS_TIMEOUT).await?;
         self.update_nexus_states(ctx.deref_mut()).await?;
         match result {
@@ -1124,7 +1324,7 @@ impl ClientOps for Arc<tokio::sync::RwLock<NodeWrapper>> {
     /// Remove a child from its parent nexus via gRPC.
     async fn remove_child(&self, request: &RemoveNexusChild) -> Result<(), SvcError> {
         let dataplane = self.grpc_client_locked(request.id()).await?;
-        let result = dataplane.remove_child(request).await;
+        let result = dataplane.observed(dataplane.remove_child(request)).await;
         let mut ctx = dataplane.reconnect(GETS_TIMEOUT).await?;
         self.update_nexus_states(ctx.deref_mut()).await?;
         match result {
@@ -1147,7 +1347,7 @@ impl ClientOps for Arc<tokio::sync::RwLock<NodeWrapper>> {
 
     async fn fault_child(&self, request: &FaultNexusChild) -> Result<(), SvcError> {
         let dataplane = self.grpc_client_locked(request.id()).await?;
-        let result = dataplane.fault_child(request).await;
+        let result = dataplane.observed(dataplane.fault_child(request)).await;
         let mut ctx = dataplane.reconnect(GETS_TIMEOUT).await?;
         self.update_nexus_states(ctx.deref_mut()).await?;
         match result {
@@ -1167,7 +1367,7 @@ impl ClientOps for Arc<tokio::sync::RwLock<NodeWrapper>> {
 
     async fn shutdown_nexus(&self, request: &ShutdownNexus) -> Result<(), SvcError> {
         let dataplane = self.grpc_client_locked(request.id()).await?;
-        let result = dataplane.shutdown_nexus(request).await;
+        let result = dataplane.observed(dataplane.shutdown_nexus(request)).await;
         let mut ctx = dataplane.reconnect(GETS_TIMEOUT).await?;
         self.update_nexus_states(ctx.deref_mut()).await?;
         match result {
@@ -1246,8 +1446,49 @@ impl ClientOps for GrpcClientLocked {
         }
     }
 
+    async fn expand_pool(&self, request: &ExpandPool) -> Result<PoolState, SvcError> {
+        match self.api_version() {
+            ApiVersion::V0 => Err(SvcError::InvalidApiVersion {
+                api_version: Some(ApiVersion::V0),
+            }),
+            // growing pools is not supported by the io-engine api which we're built against
+            #[cfg(not(feature = "pool-grow"))]
+            ApiVersion::V1 => Err(SvcError::Unsupported {
+                kind: ResourceKind::Pool,
+                operation: "Growing pools".to_string(),
+            }),
+            #[cfg(feature = "pool-grow")]
+            ApiVersion::V1 => {
+                let rpc_pool = self
+                    .client_v1()?
+                    .pool()
+                    .grow_pool(v1_conversion::to_rpc(request))
+                    .await
+                    .context(GrpcRequestError {
+                        resource: ResourceKind::Pool,
+                        request: "grow_pool",
+                    })?;
+                if let Some(pool) = rpc_pool.into_inner().current_pool {
+                    let pool = v1_rpc_pool_to_agent(&pool, &request.node);
+                    Ok(pool)
+                } else {
+                    Err(SvcError::Internal {
+                        details: format!(
+                            "resource: {}, request: {}, err: {}",
+                            "Pool", "grow_pool", "no pool returned"
+                        ),
+                    })
+                }
+            }
+        }
+    }
+
     async fn create_replica(&self, request: &CreateReplica) -> Result<Replica, SvcError> {
         match self.api_version() {
+            // encryption at rest is only supported by the v1 api
+            ApiVersion::V0 if request.encryption.is_some() => Err(SvcError::InvalidApiVersion {
+                api_version: Some(ApiVersion::V0),
+            }),
             ApiVersion::V0 => {
                 let rpc_replica = self
                     .client_v0()?
@@ -1260,6 +1501,15 @@ impl ClientOps for GrpcClientLocked {
                 let replica = v0_rpc_replica_to_agent(&rpc_replica.into_inner(), &request.node)?;
                 Ok(replica)
             }
+            // encryption at rest is not supported by the io-engine api which we're built against
+            ApiVersion::V1
+                if cfg!(not(feature = "replica-encryption")) && request.encryption.is_some() =>
+            {
+                Err(SvcError::Unsupported {
+                    kind: ResourceKind::Replica,
+                    operation: "Encryption at rest".to_string(),
+                })
+            }
             ApiVersion::V1 => {
                 let rpc_replica = self
                     .client_v1()?
@@ -1328,6 +1578,27 @@ impl ClientOps for GrpcClientLocked {
         }
     }
 
+    async fn resize_replica(&self, request: &ResizeReplica) -> Result<Replica, SvcError> {
+        match self.api_version() {
+            ApiVersion::V0 => Err(SvcError::InvalidApiVersion {
+                api_version: Some(ApiVersion::V0),
+            }),
+            ApiVersion::V1 => {
+                let rpc_replica = self
+                    .client_v1()?
+                    .replica()
+                    .resize_replica(v1_conversion::to_rpc(request))
+                    .await
+                    .context(GrpcRequestError {
+                        resource: ResourceKind::Replica,
+                        request: "resize_replica",
+                    })?;
+                let replica = v1_rpc_replica_to_agent(&rpc_replica.into_inner(), &request.node)?;
+                Ok(replica)
+            }
+        }
+    }
+
     async fn destroy_replica(&self, request: &DestroyReplica) -> Result<(), SvcError> {
         match self.api_version() {
             ApiVersion::V0 => {
@@ -1356,6 +1627,83 @@ impl ClientOps for GrpcClientLocked {
         }
     }
 
+    async fn create_repl_snapshot(
+        &self,
+        request: &CreateReplicaSnapshot,
+    ) -> Result<ReplicaSnapshotInfo, SvcError> {
+        match self.api_version() {
+            ApiVersion::V0 => Err(SvcError::InvalidApiVersion {
+                api_version: Some(ApiVersion::V0),
+            }),
+            ApiVersion::V1 => {
+                let response = self
+                    .client_v1()?
+                    .snapshot()
+                    .create_replica_snapshot(v1_conversion::to_rpc(request))
+                    .await
+                    .context(GrpcRequestError {
+                        resource: ResourceKind::VolumeSnapshot,
+                        request: "create_replica_snapshot",
+                    })?
+                    .into_inner();
+                match response.snapshot {
+                    Some(snapshot) => v1_rpc_replica_snapshot_to_agent(&snapshot, &request.node),
+                    None => Err(SvcError::Internal {
+                        details: "io-engine returned no replica snapshot".to_string(),
+                    }),
+                }
+            }
+        }
+    }
+
+    async fn destroy_repl_snapshot(
+        &self,
+        request: &DestroyReplicaSnapshot,
+    ) -> Result<(), SvcError> {
+        match self.api_version() {
+            ApiVersion::V0 => Err(SvcError::InvalidApiVersion {
+                api_version: Some(ApiVersion::V0),
+            }),
+            ApiVersion::V1 => {
+                let _ = self
+                    .client_v1()?
+                    .snapshot()
+                    .destroy_snapshot(v1_conversion::to_rpc(request))
+                    .await
+                    .context(GrpcRequestError {
+                        resource: ResourceKind::VolumeSnapshot,
+                        request: "destroy_snapshot",
+                    })?;
+                Ok(())
+            }
+        }
+    }
+
+    async fn create_snapshot_clone(
+        &self,
+        request: &CreateSnapshotClone,
+    ) -> Result<Replica, SvcError> {
+        match self.api_version() {
+            ApiVersion::V0 => Err(SvcError::InvalidApiVersion {
+                api_version: Some(ApiVersion::V0),
+            }),
+            ApiVersion::V1 => {
+                let rpc_replica = self
+                    .client_v1()?
+                    .snapshot()
+                    .create_snapshot_clone(v1_conversion::to_rpc(request))
+                    .await
+                    .context(GrpcRequestError {
+                        resource: ResourceKind::Replica,
+                        request: "create_snapshot_clone",
+                    })?;
+                let replica =
+                    v1_rpc_replica_to_agent(&rpc_replica.into_inner(), &request.replica.node)?;
+                Ok(replica)
+            }
+        }
+    }
+
     async fn create_nexus(&self, request: &CreateNexus) -> Result<Nexus, SvcError> {
         match self.api_version() {
             ApiVersion::V0 => {
@@ -1373,6 +1721,13 @@ impl ClientOps for GrpcClientLocked {
                 nexus.uuid = request.uuid.clone();
                 Ok(nexus)
             }
+            // nexus QoS limits are not supported by the io-engine api which we're built against
+            ApiVersion::V1 if cfg!(not(feature = "nexus-qos")) && request.qos.is_some() => {
+                Err(SvcError::Unsupported {
+                    kind: ResourceKind::Nexus,
+                    operation: "Setting nexus QoS limits".to_string(),
+                })
+            }
             ApiVersion::V1 => {
                 let rpc_nexus = self
                     .client_v1()?
@@ -1492,6 +1847,116 @@ impl ClientOps for GrpcClientLocked {
         }
     }
 
+    async fn resize_nexus(&self, request: &ResizeNexus) -> Result<Nexus, SvcError> {
+        match self.api_version() {
+            ApiVersion::V0 => Err(SvcError::InvalidApiVersion {
+                api_version: Some(ApiVersion::V0),
+            }),
+            ApiVersion::V1 => {
+                let rpc_nexus = self
+                    .client_v1()?
+                    .nexus()
+                    .resize_nexus(v1_conversion::to_rpc(request))
+                    .await
+                    .context(GrpcRequestError {
+                        resource: ResourceKind::Nexus,
+                        request: "resize_nexus",
+                    })?;
+                if let Some(nexus) = rpc_nexus.into_inner().nexus {
+                    let nexus = v1_rpc_nexus_to_agent(&nexus, &request.node)?;
+                    Ok(nexus)
+                } else {
+                    Err(SvcError::Internal {
+                        details: format!(
+                            "resource: {}, request: {}, err: {}",
+                            "Nexus", "resize_nexus", "no nexus returned"
+                        ),
+                    })
+                }
+            }
+        }
+    }
+
+    async fn set_nexus_qos(&self, request: &SetNexusQos) -> Result<(), SvcError> {
+        match self.api_version() {
+            ApiVersion::V0 => Err(SvcError::InvalidApiVersion {
+                api_version: Some(ApiVersion::V0),
+            }),
+            // nexus QoS limits are not supported by the io-engine api which we're built against
+            #[cfg(not(feature = "nexus-qos"))]
+            ApiVersion::V1 => Err(SvcError::Unsupported {
+                kind: ResourceKind::Nexus,
+                operation: "Setting nexus QoS limits".to_string(),
+            }),
+            #[cfg(feature = "nexus-qos")]
+            ApiVersion::V1 => {
+                let _ = self
+                    .client_v1()?
+                    .nexus()
+                    .set_nexus_qos(v1_conversion::to_rpc(request))
+                    .await
+                    .context(GrpcRequestError {
+                        resource: ResourceKind::Nexus,
+                        request: "set_nexus_qos",
+                    })?;
+                Ok(())
+            }
+        }
+    }
+
+    async fn create_nexus_snapshot(
+        &self,
+        request: &CreateNexusSnapshot,
+    ) -> Result<NexusSnapshotStatus, SvcError> {
+        match self.api_version() {
+            ApiVersion::V0 => Err(SvcError::InvalidApiVersion {
+                api_version: Some(ApiVersion::V0),
+            }),
+            ApiVersion::V1 => {
+                let response = self
+                    .client_v1()?
+                    .snapshot()
+                    .create_nexus_snapshot(v1_conversion::to_rpc(request))
+                    .await
+                    .context(GrpcRequestError {
+                        resource: ResourceKind::VolumeSnapshot,
+                        request: "create_nexus_snapshot",
+                    })?
+                    .into_inner();
+                v1_rpc_nexus_snapshot_to_agent(&response)
+            }
+        }
+    }
+
+    async fn set_rebuild_bandwidth(&self, request: &SetRebuildBandwidth) -> Result<(), SvcError> {
+        match self.api_version() {
+            ApiVersion::V0 => Err(SvcError::InvalidApiVersion {
+                api_version: Some(ApiVersion::V0),
+            }),
+            // rebuild bandwidth limits are not supported by the io-engine api which we're built
+            // against
+            #[cfg(not(feature = "rebuild-bandwidth"))]
+            ApiVersion::V1 => Err(SvcError::GrpcRequestError {
+                resource: ResourceKind::Node,
+                request: "set_rebuild_bandwidth".to_string(),
+                source: tonic::Status::unimplemented("Rebuild bandwidth limits are not supported"),
+            }),
+            #[cfg(feature = "rebuild-bandwidth")]
+            ApiVersion::V1 => {
+                let _ = self
+                    .client_v1()?
+                    .nexus()
+                    .set_rebuild_bandwidth(v1_conversion::to_rpc(request))
+                    .await
+                    .context(GrpcRequestError {
+                        resource: ResourceKind::Node,
+                        request: "set_rebuild_bandwidth",
+                    })?;
+                Ok(())
+            }
+        }
+    }
+
     async fn add_child(&self, request: &AddNexusChild) -> Result<Child, SvcError> {
         match self.api_version() {
             ApiVersion::V0 => {
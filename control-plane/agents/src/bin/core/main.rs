@@ -3,6 +3,8 @@
 
 /// The controller logic for all resources.
 pub(crate) mod controller;
+/// The prometheus metrics exporter.
+pub(crate) mod metrics;
 /// The nexus related operations.
 pub(crate) mod nexus;
 /// The node related operations.
@@ -88,6 +90,67 @@ pub(crate) struct CliArgs {
     /// If `None` do not limit the number of rebuilds.
     #[structopt(long)]
     max_rebuilds: Option<NumRebuilds>,
+    /// The address on which to serve the prometheus metrics (at the `/metrics` path).
+    /// If `None` the metrics are not served.
+    #[structopt(long)]
+    metrics_addr: Option<SocketAddr>,
+    /// Weight of the pool free capacity percentage when scoring pools for replica placement.
+    /// When all of the weights are 0, the pools are ordered by their number of replicas only.
+    #[structopt(long, default_value = "0")]
+    pool_capacity_weight: u64,
+    /// Weight of the pool and node load (number of replicas and nexuses) when scoring pools for
+    /// replica placement.
+    #[structopt(long, default_value = "0")]
+    pool_load_weight: u64,
+    /// Weight of the spread of a volume's replicas across the failure domains when scoring pools
+    /// for replica placement.
+    #[structopt(long, default_value = "0")]
+    pool_spread_weight: u64,
+    /// Node labels which define the failure domains (eg: topology.kubernetes.io/zone) that a
+    /// volume's replicas should be spread across.
+    #[structopt(long, use_delimiter = true)]
+    failure_domain_labels: Vec<String>,
+    /// The maximum number of volume targets which may be moved at a time to rebalance the targets
+    /// across the io-engine nodes. Only the targets of HA-capable volumes are moved.
+    /// If 0 the targets are not rebalanced.
+    #[structopt(long, default_value = "0")]
+    max_target_moves: u32,
+    /// The minimum difference between the number of targets on a node and on the least loaded
+    /// node for a target to be moved when rebalancing.
+    #[structopt(long, default_value = "2")]
+    target_imbalance: u32,
+    /// The HA cluster agent gRPC server URL, through which the volume targets are moved when
+    /// rebalancing. If not set the targets are not rebalanced.
+    #[structopt(long)]
+    ha_cluster_agent: Option<tonic::transport::Uri>,
+    /// The minimum difference, in percentage points, between the usage of the most used pool of a
+    /// volume and of the pool where a new replica would be placed for the replica to be moved.
+    /// The number of replicas moved at a time is limited by the maximum number of rebuilds.
+    /// If 0 the replicas are not rebalanced.
+    #[structopt(long, default_value = "0")]
+    pool_imbalance: u8,
+    /// The period for which a faulted volume replica whose node is offline is kept, so that it
+    /// may be re-added to the volume target for a partial rebuild when its node comes back.
+    /// If `None` such replicas are replaced straight away, which requires a full rebuild.
+    #[structopt(long)]
+    faulted_child_wait_period: Option<humantime::Duration>,
+    /// The bandwidth limit in MB/s shared by all the replica rebuilds on each io-engine node.
+    /// If `None` the rebuilds are not limited.
+    /// Requires the io-engine rebuild bandwidth api, ie: the `rebuild-bandwidth` feature.
+    #[structopt(long, parse(try_from_str = parse_rebuild_mbps))]
+    node_rebuild_mbps: Option<u64>,
+}
+
+/// Parse the rebuild bandwidth limit, which can only be applied through the io-engine rebuild
+/// bandwidth api.
+fn parse_rebuild_mbps(src: &str) -> Result<u64, String> {
+    if cfg!(not(feature = "rebuild-bandwidth")) {
+        return Err(
+            "the rebuild bandwidth can't be limited without the rebuild-bandwidth feature"
+                .to_string(),
+        );
+    }
+    src.parse::<u64>().map_err(|error| error.to_string())
 }
 impl CliArgs {
     fn args() -> Self {
@@ -123,6 +186,20 @@ async fn server(cli_args: CliArgs) {
         } else {
             cli_args.hosts_acl
         },
+        controller::scheduling::PoolScoringConfig::new(
+            cli_args.pool_capacity_weight,
+            cli_args.pool_load_weight,
+            cli_args.pool_spread_weight,
+            cli_args.failure_domain_labels,
+        ),
+        controller::reconciler::TargetRebalanceConfig::new(
+            cli_args.max_target_moves,
+            cli_args.target_imbalance,
+            cli_args.ha_cluster_agent.clone(),
+        ),
+        controller::reconciler::ReplicaRebalanceConfig::new(cli_args.pool_imbalance),
+        cli_args.faulted_child_wait_period.map(Into::into),
+        cli_args.node_rebuild_mbps,
     )
     .await;
 
@@ -142,8 +219,32 @@ async fn server(cli_args: CliArgs) {
         .configure(watch::configure)
         .configure(registry::configure);
 
+    if let Some(metrics_addr) = cli_args.metrics_addr {
+        tokio::spawn(metrics::serve(metrics_addr, registry.clone()));
+    }
+
     registry.start().await;
     service.run(cli_args.grpc_server_addr).await;
     registry.stop().await;
     opentelemetry::global::shutdown_tracer_provider();
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn node_rebuild_mbps() {
+        let args = CliArgs::from_iter_safe(["core", "--node-rebuild-mbps", "100"]);
+        if cfg!(feature = "rebuild-bandwidth") {
+            assert_eq!(args.unwrap().node_rebuild_mbps, Some(100));
+        } else {
+            args.expect_err("The rebuild bandwidth can't be limited without the feature");
+        }
+        CliArgs::from_iter_safe(["core", "--node-rebuild-mbps", "fast"]).unwrap_err();
+        assert_eq!(
+            CliArgs::from_iter_safe(["core"]).unwrap().node_rebuild_mbps,
+            None
+        );
+    }
+}
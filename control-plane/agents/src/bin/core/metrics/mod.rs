@@ -0,0 +1,190 @@
+mod server;
+
+pub(crate) use server::serve;
+
+use crate::controller::{
+    registry::Registry,
+    task_poller::{PollResult, PollerState},
+};
+use common_lib::{
+    transport_api::MessageId,
+    types::v0::transport::{NodeId, PoolStatus, VolumeStatus},
+};
+use lazy_static::lazy_static;
+use prometheus::{
+    register_histogram_vec, register_int_counter_vec, register_int_gauge, register_int_gauge_vec,
+    Encoder, HistogramVec, IntCounterVec, IntGauge, IntGaugeVec, TextEncoder,
+};
+use std::{collections::HashMap, time::Duration};
+
+lazy_static! {
+    static ref RECONCILER_POLLS: IntCounterVec = register_int_counter_vec!(
+        "core_reconciler_polls_total",
+        "Number of reconciliation loop iterations, per poller and result",
+        &["poller", "result"]
+    )
+    .expect("reconciler polls metric must be valid");
+    static ref RECONCILER_POLL_DURATION: HistogramVec = register_histogram_vec!(
+        "core_reconciler_poll_duration_seconds",
+        "Duration of the reconciliation loop iterations, per poller",
+        &["poller"]
+    )
+    .expect("reconciler poll duration metric must be valid");
+    static ref NODE_REQUEST_DURATION: HistogramVec = register_histogram_vec!(
+        "core_node_grpc_request_duration_seconds",
+        "Latency of the gRPC requests sent to the io-engine nodes",
+        &["node", "request"]
+    )
+    .expect("node request duration metric must be valid");
+    static ref NODE_REQUEST_ERRORS: IntCounterVec = register_int_counter_vec!(
+        "core_node_grpc_request_errors_total",
+        "Number of failed gRPC requests sent to the io-engine nodes",
+        &["node", "request"]
+    )
+    .expect("node request errors metric must be valid");
+    static ref VOLUMES: IntGaugeVec = register_int_gauge_vec!(
+        "core_volumes",
+        "Number of volumes, per volume status",
+        &["status"]
+    )
+    .expect("volumes metric must be valid");
+    static ref POOLS: IntGaugeVec = register_int_gauge_vec!(
+        "core_pools",
+        "Number of pools, per pool status",
+        &["status"]
+    )
+    .expect("pools metric must be valid");
+    static ref REBUILDS: IntGauge =
+        register_int_gauge!("core_rebuilds", "Number of rebuilds currently in progress")
+            .expect("rebuilds metric must be valid");
+    static ref MAX_REBUILDS: IntGauge = register_int_gauge!(
+        "core_max_rebuilds",
+        "Maximum number of system-wide rebuilds allowed at any given time"
+    )
+    .expect("max rebuilds metric must be valid");
+}
+
+/// Record an iteration of the given reconciliation `poller` which took `elapsed` to complete.
+pub(crate) fn observe_reconciler_poll(poller: &str, result: &PollResult, elapsed: Duration) {
+    let result = match result {
+        Ok(PollerState::Idle) => "idle",
+        Ok(_) => "busy",
+        Err(_) => "error",
+    };
+    RECONCILER_POLLS.with_label_values(&[poller, result]).inc();
+    RECONCILER_POLL_DURATION
+        .with_label_values(&[poller])
+        .observe(elapsed.as_secs_f64());
+}
+
+/// Record a gRPC `request` sent to the given io-engine `node` which took `elapsed` to complete.
+pub(crate) fn observe_node_request(
+    node: &NodeId,
+    request: &MessageId,
+    failed: bool,
+    elapsed: Duration,
+) {
+    let request = request.to_string();
+    let labels = [node.as_str(), request.as_str()];
+    NODE_REQUEST_DURATION
+        .with_label_values(&labels)
+        .observe(elapsed.as_secs_f64());
+    if failed {
+        NODE_REQUEST_ERRORS.with_label_values(&labels).inc();
+    }
+}
+
+/// Render all of the registered metrics in the prometheus text format.
+fn render() -> Result<Vec<u8>, prometheus::Error> {
+    let mut buffer = vec![];
+    TextEncoder::new().encode(&prometheus::gather(), &mut buffer)?;
+    Ok(buffer)
+}
+
+/// Refresh the resource gauges from the current contents of the registry.
+/// This is done when the metrics are scraped rather than on every resource update.
+async fn update_resource_gauges(registry: &Registry) {
+    let mut volumes = [
+        VolumeStatus::Unknown,
+        VolumeStatus::Online,
+        VolumeStatus::Degraded,
+        VolumeStatus::Faulted,
+        VolumeStatus::Shutdown,
+    ]
+    .into_iter()
+    .map(|status| (status.to_string(), 0))
+    .collect::<HashMap<_, i64>>();
+    for volume in registry.volumes().await {
+        *volumes
+            .entry(volume.state().status.to_string())
+            .or_default() += 1;
+    }
+    for (status, count) in volumes {
+        VOLUMES.with_label_values(&[&status]).set(count);
+    }
+
+    let mut pools = [
+        PoolStatus::Unknown,
+        PoolStatus::Online,
+        PoolStatus::Degraded,
+        PoolStatus::Faulted,
+    ]
+    .into_iter()
+    .map(|status| (status.to_string(), 0))
+    .collect::<HashMap<_, i64>>();
+    for pool in registry.pool_states_inner().await {
+        *pools.entry(pool.status.to_string()).or_default() += 1;
+    }
+    for (status, count) in pools {
+        POOLS.with_label_values(&[&status]).set(count);
+    }
+
+    REBUILDS.set(registry.num_rebuilds().await as i64);
+    if let Some(max_rebuilds) = registry.max_rebuilds() {
+        MAX_REBUILDS.set(max_rebuilds as i64);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common_lib::types::v0::transport::MessageIdVs;
+
+    fn rendered() -> String {
+        String::from_utf8(render().expect("metrics must render")).expect("metrics must be utf8")
+    }
+
+    #[test]
+    fn reconciler_polls() {
+        let busy = Ok(PollerState::Busy);
+        observe_reconciler_poll("test_poller", &busy, Duration::from_millis(250));
+        observe_reconciler_poll("test_poller", &busy, Duration::from_millis(750));
+        observe_reconciler_poll("test_poller", &Ok(PollerState::Idle), Duration::ZERO);
+
+        let metrics = rendered();
+        assert!(metrics
+            .contains(r#"core_reconciler_polls_total{poller="test_poller",result="busy"} 2"#));
+        assert!(metrics
+            .contains(r#"core_reconciler_polls_total{poller="test_poller",result="idle"} 1"#));
+        assert!(metrics
+            .contains(r#"core_reconciler_poll_duration_seconds_count{poller="test_poller"} 3"#));
+        assert!(metrics
+            .contains(r#"core_reconciler_poll_duration_seconds_sum{poller="test_poller"} 1"#));
+    }
+
+    #[test]
+    fn node_requests() {
+        let node = NodeId::from("test-node");
+        let request = MessageId::from(MessageIdVs::CreatePool);
+        observe_node_request(&node, &request, false, Duration::from_millis(10));
+        observe_node_request(&node, &request, true, Duration::from_millis(10));
+
+        let metrics = rendered();
+        let labels = r#"{node="test-node",request="v0/createPool"}"#;
+        assert!(metrics.contains(&format!(
+            "core_node_grpc_request_duration_seconds_count{} 2",
+            labels
+        )));
+        assert!(metrics.contains(&format!("core_node_grpc_request_errors_total{} 1", labels)));
+    }
+}
@@ -0,0 +1,52 @@
+use super::{render, update_resource_gauges};
+use crate::controller::registry::Registry;
+use hyper::{
+    header::CONTENT_TYPE,
+    service::{make_service_fn, service_fn},
+    Body, Method, Request, Response, Server, StatusCode,
+};
+use prometheus::{Encoder, TextEncoder};
+use std::{convert::Infallible, net::SocketAddr};
+
+/// Serve the prometheus metrics on the given address, at the `/metrics` path.
+/// The resource gauges are refreshed from the `registry` on every scrape.
+pub(crate) async fn serve(addr: SocketAddr, registry: Registry) {
+    let make_service = make_service_fn(move |_| {
+        let registry = registry.clone();
+        async move {
+            Ok::<_, Infallible>(service_fn(move |request| {
+                let registry = registry.clone();
+                async move { Ok::<_, Infallible>(handle(request, &registry).await) }
+            }))
+        }
+    });
+
+    tracing::info!(%addr, "Serving prometheus metrics");
+    if let Err(error) = Server::bind(&addr).serve(make_service).await {
+        tracing::error!(%error, %addr, "Failed to serve prometheus metrics");
+    }
+}
+
+async fn handle(request: Request<Body>, registry: &Registry) -> Response<Body> {
+    let response = match (request.method(), request.uri().path()) {
+        (&Method::GET, "/metrics") => {
+            update_resource_gauges(registry).await;
+
+            match render() {
+                Ok(buffer) => Response::builder()
+                    .header(CONTENT_TYPE, TextEncoder::new().format_type())
+                    .body(Body::from(buffer)),
+                Err(error) => {
+                    tracing::error!(%error, "Failed to encode the prometheus metrics");
+                    Response::builder()
+                        .status(StatusCode::INTERNAL_SERVER_ERROR)
+                        .body(Body::empty())
+                }
+            }
+        }
+        _ => Response::builder()
+            .status(StatusCode::NOT_FOUND)
+            .body(Body::empty()),
+    };
+    response.unwrap_or_default()
+}
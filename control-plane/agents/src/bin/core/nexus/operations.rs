@@ -3,7 +3,8 @@ use crate::{
         registry::Registry,
         resources::{
             operations::{
-                ResourceLifecycle, ResourceOffspring, ResourceSharing, ResourceShutdownOperations,
+                ResourceLifecycle, ResourceOffspring, ResourceResize, ResourceSharing,
+                ResourceShutdownOperations,
             },
             operations_helper::{GuardedOperationsHelper, OperationSequenceGuard},
             OperationGuardArc, TraceSpan,
@@ -21,7 +22,7 @@ use common_lib::types::v0::{
     },
     transport::{
         child::Child,
-        nexus::{CreateNexus, DestroyNexus, Nexus, ShareNexus, UnshareNexus},
+        nexus::{CreateNexus, DestroyNexus, Nexus, ResizeNexus, ShareNexus, UnshareNexus},
         AddNexusChild, NodeStatus, RemoveNexusChild, ShutdownNexus,
     },
 };
@@ -185,6 +186,32 @@ impl ResourceSharing for Option<&mut OperationGuardArc<NexusSpec>> {
     }
 }
 
+#[async_trait::async_trait]
+impl ResourceResize for OperationGuardArc<NexusSpec> {
+    type Resize = ResizeNexus;
+    type ResizeOutput = Nexus;
+
+    async fn resize(
+        &mut self,
+        registry: &Registry,
+        request: &Self::Resize,
+    ) -> Result<Self::ResizeOutput, SvcError> {
+        let node = registry.node_wrapper(&request.node).await?;
+
+        let status = registry.nexus(&request.uuid).await?;
+        let spec_clone = self
+            .start_update(
+                registry,
+                &status,
+                NexusOperation::Resize(request.requested_size),
+            )
+            .await?;
+
+        let result = node.resize_nexus(request).await;
+        self.complete_update(registry, result, spec_clone).await
+    }
+}
+
 #[async_trait::async_trait]
 impl ResourceOffspring for OperationGuardArc<NexusSpec> {
     type Add = AddNexusChild;
@@ -22,7 +22,7 @@ use common_lib::{
         },
         transport::{
             AddNexusReplica, Child, ChildUri, CreateNexus, Nexus, NexusId, NexusOwners,
-            NexusStatus, RemoveNexusChild, RemoveNexusReplica, ReplicaOwners,
+            NexusStatus, Protocol, RemoveNexusChild, RemoveNexusReplica, ReplicaOwners,
         },
     },
 };
@@ -57,11 +57,18 @@ impl SpecOperationsHelper for NexusSpec {
         op: Self::UpdateOp,
     ) -> Result<(), SvcError> {
         match &op {
-            NexusOperation::Share(_, _) if state.share.shared() => Err(SvcError::AlreadyShared {
-                kind: ResourceKind::Nexus,
-                id: self.uuid_str(),
-                share: state.share.to_string(),
-            }),
+            // A shared nexus may only be shared again to update its allowed hosts.
+            NexusOperation::Share(protocol, allowed_hosts)
+                if state.share.shared()
+                    && (state.share != Protocol::from(*protocol)
+                        || &self.allowed_hosts == allowed_hosts) =>
+            {
+                Err(SvcError::AlreadyShared {
+                    kind: ResourceKind::Nexus,
+                    id: self.uuid_str(),
+                    share: state.share.to_string(),
+                })
+            }
             NexusOperation::Share(_, _) => Ok(()),
             NexusOperation::Unshare if !state.share.shared() => Err(SvcError::NotShared {
                 kind: ResourceKind::Nexus,
@@ -85,6 +92,13 @@ impl SpecOperationsHelper for NexusSpec {
             }
             NexusOperation::RemoveChild(_) => Ok(()),
             NexusOperation::Shutdown => Ok(()),
+            NexusOperation::Resize(size) if *size < state.size => Err(SvcError::ResizeShrink {
+                kind: ResourceKind::Nexus,
+                id: self.uuid_str(),
+                current_size: state.size,
+                requested_size: *size,
+            }),
+            NexusOperation::Resize(_) => Ok(()),
             _ => unreachable!(),
         }?;
         self.start_op(op);
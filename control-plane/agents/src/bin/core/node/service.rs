@@ -5,7 +5,7 @@ use crate::controller::{
 };
 use agents::errors::SvcError;
 use common_lib::types::v0::transport::{
-    Deregister, Filter, Node, NodeId, NodeState, NodeStatus, Register,
+    Deregister, Filter, Node, NodeId, NodeState, NodeStatus, Register, ResourceEventAction,
 };
 
 use crate::controller::wrapper::InternalOps;
@@ -99,8 +99,24 @@ impl NodeOperations for Service {
         Ok(node)
     }
 
-    async fn drain(&self, id: NodeId, label: String) -> Result<Node, ReplyError> {
-        let node = self.drain(id, label).await?;
+    async fn drain(&self, id: NodeId, label: String, evacuate: bool) -> Result<Node, ReplyError> {
+        let node = self.drain(id, label, evacuate).await?;
+        Ok(node)
+    }
+
+    async fn label(
+        &self,
+        id: NodeId,
+        key: String,
+        value: String,
+        overwrite: bool,
+    ) -> Result<Node, ReplyError> {
+        let node = self.label(id, key, value, overwrite).await?;
+        Ok(node)
+    }
+
+    async fn unlabel(&self, id: NodeId, key: String) -> Result<Node, ReplyError> {
+        let node = self.unlabel(id, key).await?;
         Ok(node)
     }
 }
@@ -170,6 +186,13 @@ impl Service {
             let mut node = node.write().await;
             if node.is_online() {
                 node.update_liveness().await;
+                if !node.is_online() {
+                    registry.notify_event(
+                        registry
+                            .specs()
+                            .node_event(id, ResourceEventAction::Updated),
+                    );
+                }
             }
         }
     }
@@ -188,10 +211,19 @@ impl Service {
         let node_state = NodeState::from(registration);
         let nodes = self.registry.nodes();
         let node = nodes.write().await.get_mut(&node_state.id).cloned();
+        let node_id = node_state.id().clone();
+        let action = match node {
+            None => ResourceEventAction::Created,
+            Some(_) => ResourceEventAction::Updated,
+        };
         let send_event = match node {
             None => {
-                let mut node =
-                    NodeWrapper::new(&node_state, self.deadline, self.comms_timeouts.clone());
+                let mut node = NodeWrapper::new(
+                    &node_state,
+                    self.deadline,
+                    self.comms_timeouts.clone(),
+                    self.registry.events_sender(),
+                );
 
                 // On startup api version is not known, thus probe all apiversions
                 let result = match startup {
@@ -236,6 +268,11 @@ impl Service {
             Some(node) => matches!(node.on_register(node_state).await, Ok(true)),
         };
 
+        if send_event {
+            self.registry
+                .notify_event(self.specs().node_event(&node_id, action));
+        }
+
         // don't send these events on startup as the reconciler will start working afterwards anyway
         if send_event && !startup {
             self.registry
@@ -247,15 +284,22 @@ impl Service {
     /// Deregister a node through the deregister information
     #[tracing::instrument(level = "trace", skip(self), fields(node.id = %node.id))]
     pub(super) async fn deregister(&self, node: &Deregister) {
+        let node_id = &node.id;
         let nodes = self.registry.nodes().read().await;
-        match nodes.get(&node.id) {
+        match nodes.get(node_id) {
             None => {}
             // ideally we want this node to disappear completely when it's not
             // part of the daemonset, but we just don't have that kind of
             // information at this level :(
             // maybe nodes should also be registered/deregistered via REST?
             Some(node) => {
-                node.write().await.set_status(NodeStatus::Unknown);
+                let previous = node.write().await.set_status(NodeStatus::Unknown);
+                if previous != NodeStatus::Unknown {
+                    self.registry.notify_event(
+                        self.specs()
+                            .node_event(node_id, ResourceEventAction::Updated),
+                    );
+                }
             }
         }
     }
@@ -339,11 +383,37 @@ impl Service {
         Ok(Node::new(id, Some(spec), state))
     }
 
-    async fn drain(&self, id: NodeId, label: String) -> Result<Node, SvcError> {
+    async fn drain(&self, id: NodeId, label: String, evacuate: bool) -> Result<Node, SvcError> {
+        let spec = self
+            .registry
+            .specs()
+            .drain_node(&self.registry, &id, label, evacuate)
+            .await?;
+        let state = self.registry.node_state(&id).await.ok();
+        Ok(Node::new(id, Some(spec), state))
+    }
+
+    async fn label(
+        &self,
+        id: NodeId,
+        key: String,
+        value: String,
+        overwrite: bool,
+    ) -> Result<Node, SvcError> {
+        let spec = self
+            .registry
+            .specs()
+            .label_node(&self.registry, &id, key, value, overwrite)
+            .await?;
+        let state = self.registry.node_state(&id).await.ok();
+        Ok(Node::new(id, Some(spec), state))
+    }
+
+    async fn unlabel(&self, id: NodeId, key: String) -> Result<Node, SvcError> {
         let spec = self
             .registry
             .specs()
-            .drain_node(&self.registry, &id, label)
+            .unlabel_node(&self.registry, &id, key)
             .await?;
         let state = self.registry.node_state(&id).await.ok();
         Ok(Node::new(id, Some(spec), state))
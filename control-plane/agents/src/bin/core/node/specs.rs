@@ -1,7 +1,10 @@
 use agents::errors::{NodeNotFound, SvcError};
-use common_lib::types::v0::{
-    store::node::{NodeLabels, NodeSpec},
-    transport::{NodeId, Register},
+use common_lib::{
+    transport_api::ResourceKind,
+    types::v0::{
+        store::node::{NodeLabels, NodeSpec, VolumeEvacuation},
+        transport::{NodeId, Register, ResourceEvent, ResourceEventAction},
+    },
 };
 use snafu::OptionExt;
 
@@ -127,6 +130,76 @@ impl ResourceSpecsLocked {
         Ok(uncordoned_node_spec.clone())
     }
 
+    /// Label the node with the given ID.
+    /// The value of an existing label is only changed if `overwrite` is set.
+    /// Return the NodeSpec after labelling.
+    pub(crate) async fn label_node(
+        &self,
+        registry: &Registry,
+        node_id: &NodeId,
+        key: String,
+        value: String,
+        overwrite: bool,
+    ) -> Result<NodeSpec, SvcError> {
+        let node = self.node_rsc(node_id)?;
+        let (changed, labelled_node_spec) = {
+            let mut locked_node = node.lock();
+            match locked_node.labels().get(&key) {
+                Some(existing) if existing == &value => (false, locked_node.clone()),
+                Some(existing) if !overwrite => {
+                    return Err(SvcError::NodeLabelExists {
+                        node_id: node_id.to_string(),
+                        key,
+                        value: existing.clone(),
+                    });
+                }
+                _ => {
+                    locked_node.set_label(key, value);
+                    (true, locked_node.clone())
+                }
+            }
+        };
+        if changed {
+            registry.store_obj(&labelled_node_spec).await?;
+            registry.notify_event(self.node_event(node_id, ResourceEventAction::Updated));
+        }
+        Ok(labelled_node_spec)
+    }
+
+    /// Remove the label with the given key from the node with the given ID.
+    /// Return the NodeSpec after unlabelling.
+    pub(crate) async fn unlabel_node(
+        &self,
+        registry: &Registry,
+        node_id: &NodeId,
+        key: String,
+    ) -> Result<NodeSpec, SvcError> {
+        let node = self.node_rsc(node_id)?;
+        let unlabelled_node_spec = {
+            let mut locked_node = node.lock();
+            // Return an error if the label doesn't exist.
+            if locked_node.remove_label(&key).is_none() {
+                return Err(SvcError::NodeLabelMissing {
+                    node_id: node_id.to_string(),
+                    key,
+                });
+            }
+            locked_node.clone()
+        };
+        registry.store_obj(&unlabelled_node_spec).await?;
+        registry.notify_event(self.node_event(node_id, ResourceEventAction::Updated));
+        Ok(unlabelled_node_spec)
+    }
+
+    /// Get the resource change event for the given node, labelled with its current spec labels.
+    pub(crate) fn node_event(&self, id: &NodeId, action: ResourceEventAction) -> ResourceEvent {
+        let event = ResourceEvent::new(ResourceKind::Node, id.to_string(), action);
+        match self.node_rsc(id) {
+            Ok(node) => event.with_labels(node.lock().labels().clone()),
+            Err(_) => event,
+        }
+    }
+
     /// Get all cordoned nodes.
     pub(crate) fn cordoned_nodes(&self) -> Vec<NodeSpec> {
         self.read()
@@ -150,6 +223,7 @@ impl ResourceSpecsLocked {
         registry: &Registry,
         node_id: &NodeId,
         label: String,
+        evacuate: bool,
     ) -> Result<NodeSpec, SvcError> {
         let node = self.node_rsc(node_id)?;
         let drained_node_spec = {
@@ -161,13 +235,30 @@ impl ResourceSpecsLocked {
                     label,
                 });
             }
-            locked_node.set_drain(label);
+            locked_node.set_drain(label, evacuate);
             locked_node.clone()
         };
         registry.store_obj(&drained_node_spec).await?;
         Ok(drained_node_spec)
     }
 
+    /// Update the evacuation progress of a draining NodeSpec.
+    pub(crate) async fn set_node_evacuations(
+        &self,
+        registry: &Registry,
+        node_id: &NodeId,
+        evacuations: Vec<VolumeEvacuation>,
+    ) -> Result<NodeSpec, SvcError> {
+        let node = self.node_rsc(node_id)?;
+        let node_spec = {
+            let mut locked_node = node.lock();
+            locked_node.set_evacuations(evacuations);
+            locked_node.clone()
+        };
+        registry.store_obj(&node_spec).await?;
+        Ok(node_spec)
+    }
+
     /// Set the NodeSpec to the drained state.
     pub(crate) async fn set_node_drained(
         &self,
@@ -1,16 +1,20 @@
 use crate::controller::{
     registry::Registry,
     resources::{
-        operations::ResourceLifecycle,
-        operations_helper::{GuardedOperationsHelper, OperationSequenceGuard},
+        operations::{ResourceLabel, ResourceLifecycle, ResourceResize},
+        operations_helper::{
+            GuardedOperationsHelper, OperationSequenceGuard, SpecOperationsHelper,
+        },
         OperationGuardArc,
     },
     wrapper::ClientOps,
 };
 use agents::errors::{SvcError, SvcError::CordonedNode};
 use common_lib::types::v0::{
-    store::pool::PoolSpec,
-    transport::{CreatePool, DestroyPool, Pool},
+    store::pool::{PoolOperation, PoolSpec},
+    transport::{
+        CreatePool, DestroyPool, ExpandPool, LabelPool, Pool, ResourceEventAction, UnlabelPool,
+    },
 };
 
 #[async_trait::async_trait]
@@ -30,7 +34,7 @@ impl ResourceLifecycle for OperationGuardArc<PoolSpec> {
                 node_id: request.node.to_string(),
             });
         }
-        if request.disks.len() != 1 {
+        if request.disks.is_empty() {
             return Err(SvcError::InvalidPoolDeviceNum {
                 disks: request.disks.clone(),
             });
@@ -66,6 +70,93 @@ impl ResourceLifecycle for OperationGuardArc<PoolSpec> {
     }
 }
 
+#[async_trait::async_trait]
+impl ResourceResize for OperationGuardArc<PoolSpec> {
+    type Resize = ExpandPool;
+    type ResizeOutput = Pool;
+
+    async fn resize(
+        &mut self,
+        registry: &Registry,
+        request: &Self::Resize,
+    ) -> Result<Self::ResizeOutput, SvcError> {
+        let node = registry.node_wrapper(&request.node).await?;
+
+        let state = registry.get_pool_state(&request.id).await?;
+        let spec_clone = self
+            .start_update(
+                registry,
+                &state,
+                PoolOperation::Expand(request.disks.clone()),
+            )
+            .await?;
+
+        let result = node.expand_pool(request).await;
+        let pool_state = self.complete_update(registry, result, spec_clone).await?;
+        let spec = self.lock().clone();
+        Ok(Pool::new(spec, pool_state))
+    }
+}
+
+#[async_trait::async_trait]
+impl ResourceLabel for OperationGuardArc<PoolSpec> {
+    type Label = LabelPool;
+    type LabelOutput = Pool;
+    type Unlabel = UnlabelPool;
+    type UnlabelOutput = Pool;
+
+    async fn label(
+        &mut self,
+        registry: &Registry,
+        request: &Self::Label,
+    ) -> Result<Self::LabelOutput, SvcError> {
+        let mut spec_clone = self.lock().clone();
+        let labels = spec_clone.labels.get_or_insert_with(Default::default);
+        match labels.insert(request.key.clone(), request.value.clone()) {
+            Some(value) if value == request.value => {
+                return registry.get_pool(&request.id).await;
+            }
+            Some(value) if !request.overwrite => {
+                return Err(SvcError::PoolLabelExists {
+                    pool_id: request.id.to_string(),
+                    key: request.key.clone(),
+                    value,
+                });
+            }
+            _ => {}
+        }
+
+        // labels are not applied to the io-engine, so only the spec is updated
+        registry.store_obj(&spec_clone).await?;
+        registry.notify_event(spec_clone.event(ResourceEventAction::Updated));
+        self.lock().labels = spec_clone.labels;
+        registry.get_pool(&request.id).await
+    }
+
+    async fn unlabel(
+        &mut self,
+        registry: &Registry,
+        request: &Self::Unlabel,
+    ) -> Result<Self::UnlabelOutput, SvcError> {
+        let mut spec_clone = self.lock().clone();
+        let removed = spec_clone
+            .labels
+            .as_mut()
+            .and_then(|labels| labels.remove(&request.key));
+        if removed.is_none() {
+            return Err(SvcError::PoolLabelMissing {
+                pool_id: request.id.to_string(),
+                key: request.key.clone(),
+            });
+        }
+
+        registry.store_obj(&spec_clone).await?;
+        registry.notify_event(spec_clone.event(ResourceEventAction::Updated));
+        self.lock().labels = spec_clone.labels;
+        registry.get_pool(&request.id).await
+    }
+}
+
 #[async_trait::async_trait]
 impl ResourceLifecycle for Option<OperationGuardArc<PoolSpec>> {
     type Create = CreatePool;
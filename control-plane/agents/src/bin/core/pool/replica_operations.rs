@@ -1,7 +1,10 @@
 use crate::controller::{
     registry::Registry,
     resources::{
-        operations::{ResourceLifecycle, ResourceOwnerUpdate, ResourceSharing},
+        operations::{
+            ResourceCloning, ResourceLifecycle, ResourceOwnerUpdate, ResourceResize,
+            ResourceSharing,
+        },
         operations_helper::{GuardedOperationsHelper, OperationSequenceGuard},
         OperationGuardArc, UpdateInnerValue,
     },
@@ -11,7 +14,8 @@ use agents::errors::{SvcError, SvcError::CordonedNode};
 use common_lib::types::v0::{
     store::replica::{ReplicaOperation, ReplicaSpec},
     transport::{
-        CreateReplica, DestroyReplica, Replica, ReplicaOwners, ShareReplica, UnshareReplica,
+        CreateReplica, CreateSnapshotClone, DestroyReplica, Replica, ReplicaOwners, ResizeReplica,
+        ShareReplica, UnshareReplica,
     },
 };
 
@@ -54,6 +58,37 @@ impl ResourceLifecycle for OperationGuardArc<ReplicaSpec> {
     }
 }
 
+#[async_trait::async_trait]
+impl ResourceCloning for OperationGuardArc<ReplicaSpec> {
+    type CreateClone = CreateSnapshotClone;
+    type CreateCloneOutput = Replica;
+
+    async fn create_clone(
+        registry: &Registry,
+        request: &Self::CreateClone,
+    ) -> Result<Self::CreateCloneOutput, SvcError> {
+        let specs = registry.specs();
+        let replica_request = &request.replica;
+
+        if registry.node_cordoned(&replica_request.node)? {
+            return Err(CordonedNode {
+                node_id: replica_request.node.to_string(),
+            });
+        }
+
+        let node = registry.node_wrapper(&replica_request.node).await?;
+
+        let replica = specs
+            .get_or_create_replica(replica_request)
+            .operation_guard_wait()
+            .await?;
+        let _ = replica.start_create(registry, replica_request).await?;
+
+        let result = node.create_snapshot_clone(request).await;
+        replica.complete_create(result, registry).await
+    }
+}
+
 #[async_trait::async_trait]
 impl ResourceLifecycle for Option<&mut OperationGuardArc<ReplicaSpec>> {
     type Create = CreateReplica;
@@ -158,6 +193,32 @@ impl ResourceSharing for Option<&mut OperationGuardArc<ReplicaSpec>> {
     }
 }
 
+#[async_trait::async_trait]
+impl ResourceResize for OperationGuardArc<ReplicaSpec> {
+    type Resize = ResizeReplica;
+    type ResizeOutput = Replica;
+
+    async fn resize(
+        &mut self,
+        registry: &Registry,
+        request: &Self::Resize,
+    ) -> Result<Self::ResizeOutput, SvcError> {
+        let node = registry.node_wrapper(&request.node).await?;
+
+        let status = registry.get_replica(&request.uuid).await?;
+        let spec_clone = self
+            .start_update(
+                registry,
+                &status,
+                ReplicaOperation::Resize(request.requested_size),
+            )
+            .await?;
+
+        let result = node.resize_replica(request).await;
+        self.complete_update(registry, result, spec_clone).await
+    }
+}
+
 #[async_trait::async_trait]
 impl ResourceOwnerUpdate for OperationGuardArc<ReplicaSpec> {
     type Update = ReplicaOwners;
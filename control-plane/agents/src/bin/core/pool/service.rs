@@ -1,7 +1,7 @@
 use crate::controller::{
     registry::Registry,
     resources::{
-        operations::{ResourceLifecycle, ResourceSharing},
+        operations::{ResourceLabel, ResourceLifecycle, ResourceResize, ResourceSharing},
         operations_helper::{OperationSequenceGuard, ResourceSpecsLocked},
         OperationGuardArc, ResourceMutex,
     },
@@ -11,20 +11,24 @@ use agents::errors::{PoolNotFound, ReplicaNotFound, SvcError};
 use common_lib::{
     transport_api::{
         v0::{Pools, Replicas},
-        ReplyError,
+        ReplyError, ResourceKind,
     },
     types::v0::{
         store::{pool::PoolSpec, replica::ReplicaSpec},
         transport::{
-            CreatePool, CreateReplica, DestroyPool, DestroyReplica, Filter, GetPools, GetReplicas,
-            NodeId, Pool, PoolId, Replica, ShareReplica, UnshareReplica,
+            CreatePool, CreateReplica, DestroyPool, DestroyReplica, ExpandPool, Filter, GetPools,
+            GetReplicas, LabelPool, NodeId, Pool, PoolId, Replica, ShareReplica, UnlabelPool,
+            UnshareReplica,
         },
     },
 };
 use grpc::{
     context::Context,
     operations::{
-        pool::traits::{CreatePoolInfo, DestroyPoolInfo, PoolOperations},
+        pool::traits::{
+            CreatePoolInfo, DestroyPoolInfo, ExpandPoolInfo, LabelPoolInfo, PoolOperations,
+            UnlabelPoolInfo,
+        },
         replica::traits::{
             CreateReplicaInfo, DestroyReplicaInfo, ReplicaOperations, ShareReplicaInfo,
             UnshareReplicaInfo,
@@ -68,6 +72,39 @@ impl PoolOperations for Service {
         let pools = self.get_pools(&req).await?;
         Ok(pools)
     }
+
+    async fn expand(
+        &self,
+        pool: &dyn ExpandPoolInfo,
+        _ctx: Option<Context>,
+    ) -> Result<Pool, ReplyError> {
+        let req = pool.into();
+        let service = self.clone();
+        let pool = Context::spawn(async move { service.expand_pool(&req).await }).await??;
+        Ok(pool)
+    }
+
+    async fn label(
+        &self,
+        pool: &dyn LabelPoolInfo,
+        _ctx: Option<Context>,
+    ) -> Result<Pool, ReplyError> {
+        let req = pool.into();
+        let service = self.clone();
+        let pool = Context::spawn(async move { service.label_pool(&req).await }).await??;
+        Ok(pool)
+    }
+
+    async fn unlabel(
+        &self,
+        pool: &dyn UnlabelPoolInfo,
+        _ctx: Option<Context>,
+    ) -> Result<Pool, ReplyError> {
+        let req = pool.into();
+        let service = self.clone();
+        let pool = Context::spawn(async move { service.unlabel_pool(&req).await }).await??;
+        Ok(pool)
+    }
 }
 
 #[tonic::async_trait]
@@ -272,6 +309,40 @@ impl Service {
         pool.destroy(&self.registry, request).await
     }
 
+    /// Expand a pool using the given parameters.
+    #[tracing::instrument(level = "info", skip(self), err, fields(pool.id = %request.id))]
+    pub(super) async fn expand_pool(&self, request: &ExpandPool) -> Result<Pool, SvcError> {
+        // fail early rather than starting a pool update which the io-engine can't carry out
+        if cfg!(not(feature = "pool-grow")) {
+            return Err(SvcError::Unsupported {
+                kind: ResourceKind::Pool,
+                operation: "Growing pools".to_string(),
+            });
+        }
+        let mut pool = self.pool_opt(&request.id).await?.context(PoolNotFound {
+            pool_id: request.id.clone(),
+        })?;
+        pool.resize(&self.registry, request).await
+    }
+
+    /// Label a pool using the given parameters.
+    #[tracing::instrument(level = "info", skip(self), err, fields(pool.id = %request.id))]
+    pub(super) async fn label_pool(&self, request: &LabelPool) -> Result<Pool, SvcError> {
+        let mut pool = self.pool_opt(&request.id).await?.context(PoolNotFound {
+            pool_id: request.id.clone(),
+        })?;
+        pool.label(&self.registry, request).await
+    }
+
+    /// Remove a label from a pool using the given parameters.
+    #[tracing::instrument(level = "info", skip(self), err, fields(pool.id = %request.id))]
+    pub(super) async fn unlabel_pool(&self, request: &UnlabelPool) -> Result<Pool, SvcError> {
+        let mut pool = self.pool_opt(&request.id).await?.context(PoolNotFound {
+            pool_id: request.id.clone(),
+        })?;
+        pool.unlabel(&self.registry, request).await
+    }
+
     /// Create a replica using the given parameters.
     #[tracing::instrument(level = "info", skip(self), err, fields(replica.uuid = %request.uuid))]
     pub(super) async fn create_replica(
@@ -24,6 +24,7 @@ use common_lib::{
         },
     },
 };
+use std::collections::HashMap;
 
 #[async_trait::async_trait]
 impl GuardedOperationsHelper for OperationGuardArc<PoolSpec> {
@@ -31,7 +32,7 @@ impl GuardedOperationsHelper for OperationGuardArc<PoolSpec> {
     type Owners = ();
     type Status = PoolStatus;
     type State = PoolState;
-    type UpdateOp = ();
+    type UpdateOp = PoolOperation;
     type Inner = PoolSpec;
 
     fn validate_destroy(&self, registry: &Registry) -> Result<(), SvcError> {
@@ -59,7 +60,34 @@ impl SpecOperationsHelper for PoolSpec {
     type Owners = ();
     type Status = PoolStatus;
     type State = PoolState;
-    type UpdateOp = ();
+    type UpdateOp = PoolOperation;
+
+    async fn start_update_op(
+        &mut self,
+        _: &Registry,
+        _state: &Self::State,
+        op: Self::UpdateOp,
+    ) -> Result<(), SvcError> {
+        match &op {
+            PoolOperation::Expand(disks) => {
+                let removed = self
+                    .disks
+                    .iter()
+                    .filter(|disk| !disks.contains(disk))
+                    .cloned()
+                    .collect::<Vec<_>>();
+                if !removed.is_empty() {
+                    return Err(SvcError::PoolDiskRemoval {
+                        pool_id: self.id.clone(),
+                        disks: removed,
+                    });
+                }
+            }
+            _ => unreachable!(),
+        }
+        self.start_op(op);
+        Ok(())
+    }
 
     fn start_create_op(&mut self) {
         self.start_op(PoolOperation::Create);
@@ -79,6 +107,9 @@ impl SpecOperationsHelper for PoolSpec {
     fn uuid_str(&self) -> String {
         self.id.to_string()
     }
+    fn event_labels(&self) -> HashMap<String, String> {
+        self.labels.clone().unwrap_or_default()
+    }
     fn status(&self) -> SpecStatus<Self::Status> {
         self.status.clone()
     }
@@ -148,6 +179,13 @@ impl SpecOperationsHelper for ReplicaSpec {
             }
             ReplicaOperation::Unshare => Ok(()),
             ReplicaOperation::OwnerUpdate(_) => Ok(()),
+            ReplicaOperation::Resize(size) if size < state.size => Err(SvcError::ResizeShrink {
+                kind: self.kind(),
+                id: self.uuid_str(),
+                current_size: state.size,
+                requested_size: size,
+            }),
+            ReplicaOperation::Resize(_) => Ok(()),
             _ => unreachable!(),
         }?;
         self.start_op(op);
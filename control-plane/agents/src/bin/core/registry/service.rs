@@ -2,12 +2,27 @@ use crate::{controller, controller::resources::operations_helper::ResourceSpecsL
 use agents::errors::SvcError;
 use common_lib::{
     transport_api::ReplyError,
-    types::v0::transport::{GetSpecs, GetStates, Specs, States},
+    types::v0::{
+        store::{
+            definitions::{StorableObject, StorableObjectType},
+            switchover::{
+                PathFailureHistory, PathFailureHistoryKey, SwitchOverHistory, SwitchOverHistoryKey,
+            },
+        },
+        transport::{
+            GetHaHistory, GetRebuildHistory, GetSpecs, GetStates, HaHistory, RebuildQueue, Specs,
+            States, VolumeRebuilds,
+        },
+    },
 };
 use grpc::{
     context::Context,
-    operations::registry::traits::{GetSpecsInfo, GetStatesInfo, RegistryOperations},
+    operations::registry::traits::{
+        GetHaHistoryInfo, GetRebuildHistoryInfo, GetRebuildQueueInfo, GetSpecsInfo, GetStatesInfo,
+        RegistryOperations,
+    },
 };
+use utils::NVME_TARGET_NQN_PREFIX;
 
 /// Registry Service
 #[derive(Debug, Clone)]
@@ -36,6 +51,35 @@ impl RegistryOperations for Service {
         let states = self.get_states(&req).await?;
         Ok(states)
     }
+
+    async fn get_ha_history(
+        &self,
+        get_history: &dyn GetHaHistoryInfo,
+        _ctx: Option<Context>,
+    ) -> Result<HaHistory, ReplyError> {
+        let req = GetHaHistory::new(get_history.volume_id(), get_history.node_id());
+        let history = self.get_ha_history(&req).await?;
+        Ok(history)
+    }
+
+    async fn get_rebuild_history(
+        &self,
+        get_history: &dyn GetRebuildHistoryInfo,
+        _ctx: Option<Context>,
+    ) -> Result<VolumeRebuilds, ReplyError> {
+        let req = GetRebuildHistory::new(get_history.volume_id());
+        let rebuilds = self.registry.volume_rebuilds(&req).await?;
+        Ok(rebuilds)
+    }
+
+    async fn get_rebuild_queue(
+        &self,
+        _get_queue: &dyn GetRebuildQueueInfo,
+        _ctx: Option<Context>,
+    ) -> Result<RebuildQueue, ReplyError> {
+        let queue = self.registry.rebuild_queue().await?;
+        Ok(queue)
+    }
 }
 
 impl Service {
@@ -59,6 +103,68 @@ impl Service {
         })
     }
 
+    /// Get the persisted HA history, optionally filtered by volume and/or node.
+    pub(crate) async fn get_ha_history(
+        &self,
+        request: &GetHaHistory,
+    ) -> Result<HaHistory, SvcError> {
+        let switchovers = match request.volume_id() {
+            Some(volume_id) => self
+                .load_optional(&SwitchOverHistoryKey::new(volume_id.clone()))
+                .await?
+                .into_iter()
+                .collect::<Vec<SwitchOverHistory>>(),
+            None => {
+                self.registry
+                    .load_obj_prefix(StorableObjectType::FailoverHistory)
+                    .await?
+            }
+        };
+        let path_failures = match request.node_id() {
+            Some(node_id) => self
+                .load_optional(&PathFailureHistoryKey::new(node_id.clone()))
+                .await?
+                .into_iter()
+                .collect::<Vec<PathFailureHistory>>(),
+            None => {
+                self.registry
+                    .load_obj_prefix(StorableObjectType::PathFailureHistory)
+                    .await?
+            }
+        };
+
+        let mut switchovers = switchovers
+            .into_iter()
+            .flat_map(|history| history.records)
+            .filter(|record| request.node_id().map_or(true, |id| id == &record.node_name))
+            .collect::<Vec<_>>();
+        switchovers.sort_by(|a, b| a.started.cmp(&b.started));
+
+        let volume_nqn = request
+            .volume_id()
+            .map(|id| format!("{}{}", NVME_TARGET_NQN_PREFIX, id));
+        let mut path_failures = path_failures
+            .into_iter()
+            .flat_map(|history| history.records)
+            .filter(|record| volume_nqn.as_ref().map_or(true, |nqn| nqn == &record.nqn))
+            .collect::<Vec<_>>();
+        path_failures.sort_by(|a, b| a.timestamp.cmp(&b.timestamp));
+
+        Ok(HaHistory {
+            path_failures,
+            switchovers,
+        })
+    }
+
+    /// Load an object from the persistent store, which may not exist.
+    async fn load_optional<O: StorableObject>(&self, key: &O::Key) -> Result<Option<O>, SvcError> {
+        match self.registry.load_obj(key).await {
+            Ok(object) => Ok(Some(object)),
+            Err(SvcError::StoreMissingEntry { .. }) => Ok(None),
+            Err(error) => Err(error),
+        }
+    }
+
     /// Get state information for all resources.
     pub(crate) async fn get_states(&self, _request: &GetStates) -> Result<States, SvcError> {
         let mut nexuses = vec![];
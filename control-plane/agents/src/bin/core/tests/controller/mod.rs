@@ -3,13 +3,20 @@ use common_lib::{
     types::v0::{
         openapi::models,
         store::{
-            definitions::Store,
+            definitions::{StorableObject, Store},
             registry::{ControlPlaneService, StoreLeaseOwner, StoreLeaseOwnerKey},
+            switchover::{
+                PathFailureHistory, PathFailureHistoryKey, PathFailureRecord, SwitchOverHistory,
+                SwitchOverHistoryKey, SwitchOverRecord, PATH_FAILURE_HISTORY_TTL,
+            },
         },
-        transport,
+        transport::{self, CreateVolume, GetHaHistory},
     },
 };
 use deployer_cluster::{etcd_client::Client, *};
+use grpc::operations::{registry::traits::RegistryOperations, volume::traits::VolumeOperations};
+use std::time::Duration;
+use utils::NVME_TARGET_NQN_PREFIX;
 
 /// Test that the content of the registry is correctly loaded from the persistent store on start up.
 #[tokio::test]
@@ -115,6 +122,50 @@ async fn store_lease_lock() {
             .expect_err("One core-agent is already running!");
 }
 
+/// Test that a standby instance which fails to get the store lease lock doesn't leave its lease
+/// and lock waiter behind, and that it takes over once the leader releases the lock.
+#[tokio::test]
+async fn store_lease_lock_standby() {
+    // deploy etcd only...
+    let _cluster = ClusterBuilder::builder()
+        .with_rest(false)
+        .with_jaeger(false)
+        .with_io_engines(0)
+        .with_agents(vec![])
+        .build()
+        .await
+        .unwrap();
+
+    let lease_ttl = std::time::Duration::from_secs(2);
+    let service = ControlPlaneService::HaClusterAgent;
+    let leader = Etcd::new_leased(["0.0.0.0:2379"], service.clone(), lease_ttl)
+        .await
+        .unwrap();
+
+    let mut store = Client::connect(["0.0.0.0:2379"], None)
+        .await
+        .expect("Failed to connect to etcd.");
+    let leases = store.leases().await.unwrap();
+    assert_eq!(leases.leases().len(), 1);
+    let lease_id = leases.leases().first().unwrap().id();
+
+    for _ in 0 .. 3 {
+        Etcd::new_leased(["0.0.0.0:2379"], service.clone(), lease_ttl)
+            .await
+            .expect_err("The leader is still holding the lock!");
+    }
+    // the leases of the standby attempts have been revoked, only the leader's is left
+    let leases = store.leases().await.unwrap();
+    let lease_ids = leases.leases().iter().map(|l| l.id()).collect::<Vec<_>>();
+    assert_eq!(lease_ids, vec![lease_id]);
+
+    // the standby takes over straight away once the leader hands over the lock
+    leader.revoke().await.unwrap();
+    let _standby = Etcd::new_leased(["0.0.0.0:2379"], service, lease_ttl)
+        .await
+        .expect("The leader has released the lock");
+}
+
 /// Test that store lease lock works as expected
 #[tokio::test]
 async fn core_agent_lease_lock() {
@@ -202,3 +253,122 @@ async fn core_agent_lease_lock() {
     tracing::info!("core: {:?}", core.state);
     assert_eq!(Some(false), core.state.unwrap().running);
 }
+
+/// Test that the HA history is served filtered by volume and node, and that the histories of
+/// deleted volumes and of nodes without recent path failures are garbage collected.
+#[tokio::test]
+async fn ha_history_garbage_collection() {
+    let reconcile_period = Duration::from_millis(500);
+    let cluster = ClusterBuilder::builder()
+        .with_io_engines(1)
+        .with_pools(1)
+        .with_agents(vec!["core"])
+        .with_reconcile_period(reconcile_period, reconcile_period)
+        .build()
+        .await
+        .unwrap();
+
+    let volume = cluster
+        .grpc_client()
+        .volume()
+        .create(
+            &CreateVolume {
+                uuid: transport::VolumeId::new(),
+                size: 5242880,
+                replicas: 1,
+                ..Default::default()
+            },
+            None,
+        )
+        .await
+        .unwrap();
+    let volume_id = volume.uuid().clone();
+    let deleted_volume_id = transport::VolumeId::new();
+    let node = transport::NodeId::from("recent-node");
+    let expired_node = transport::NodeId::from("expired-node");
+
+    let now = chrono::Utc::now();
+    let expired = now
+        - chrono::Duration::from_std(PATH_FAILURE_HISTORY_TTL).unwrap()
+        - chrono::Duration::hours(1);
+    let switchover = |volume: &transport::VolumeId| SwitchOverRecord {
+        volume: volume.clone(),
+        node_name: node.clone(),
+        existing_nqn: format!("{}{}", NVME_TARGET_NQN_PREFIX, volume),
+        new_path: None,
+        started: now,
+        finished: now,
+        outcome: transport::SwitchOverStage::Successful,
+        error: None,
+        stages: vec![],
+    };
+    let path_failure = |node: &transport::NodeId, volume: &transport::VolumeId, timestamp| {
+        let mut history = PathFailureHistory::new(node.clone());
+        history.push(PathFailureRecord {
+            node_name: node.clone(),
+            nqn: format!("{}{}", NVME_TARGET_NQN_PREFIX, volume),
+            timestamp,
+        });
+        history
+    };
+
+    let mut etcd = Etcd::new("0.0.0.0:2379").await.unwrap();
+    for volume in [&volume_id, &deleted_volume_id] {
+        let mut history = SwitchOverHistory::new(volume.clone());
+        history.push(switchover(volume));
+        etcd.put_obj(&history).await.unwrap();
+    }
+    etcd.put_obj(&path_failure(&node, &volume_id, now))
+        .await
+        .unwrap();
+    etcd.put_obj(&path_failure(&expired_node, &deleted_volume_id, expired))
+        .await
+        .unwrap();
+
+    let registry = cluster.grpc_client().registry();
+    let history = registry
+        .get_ha_history(&GetHaHistory::new(Some(volume_id.clone()), None), None)
+        .await
+        .unwrap();
+    assert_eq!(history.switchovers, vec![switchover(&volume_id)]);
+    assert_eq!(history.path_failures.len(), 1);
+    assert_eq!(history.path_failures[0].node_name, node);
+
+    let history = registry
+        .get_ha_history(&GetHaHistory::new(None, Some(expired_node.clone())), None)
+        .await
+        .unwrap();
+    assert!(history.switchovers.is_empty());
+    assert_eq!(history.path_failures.len(), 1);
+    assert_eq!(history.path_failures[0].node_name, expired_node);
+
+    let mut tries = 0;
+    loop {
+        let switchover =
+            etcd_get::<SwitchOverHistory>(SwitchOverHistoryKey::new(deleted_volume_id.clone()))
+                .await;
+        let path_failure =
+            etcd_get::<PathFailureHistory>(PathFailureHistoryKey::new(expired_node.clone())).await;
+        if switchover.is_none() && path_failure.is_none() {
+            break;
+        }
+        tries += 1;
+        assert!(tries < 40, "The HA history was not garbage collected");
+        tokio::time::sleep(reconcile_period).await;
+    }
+
+    // the histories of the existing volume and of the recently failed node are kept
+    let history = registry
+        .get_ha_history(&GetHaHistory::new(None, None), None)
+        .await
+        .unwrap();
+    assert_eq!(history.switchovers, vec![switchover(&volume_id)]);
+    assert_eq!(history.path_failures.len(), 1);
+    assert_eq!(history.path_failures[0].node_name, node);
+}
+
+/// Get the object from the persistent store, if it exists.
+async fn etcd_get<O: StorableObject>(key: O::Key) -> Option<O> {
+    let mut etcd = Etcd::new("0.0.0.0:2379").await.unwrap();
+    etcd.get_obj(&key).await.ok()
+}
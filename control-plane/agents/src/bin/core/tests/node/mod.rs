@@ -1,10 +1,22 @@
-use common_lib::types::v0::{
-    store::node::{NodeLabels, NodeSpec},
-    transport::{ApiVersion, Filter, HostNqn, Node, NodeId, NodeState, NodeStatus},
+use common_lib::{
+    transport_api::ReplyErrorKind,
+    types::v0::{
+        store::{
+            node::{NodeLabels, NodeSpec},
+            volume::VolumeSpec,
+        },
+        transport::{
+            ApiVersion, CreateVolume, Filter, HostNqn, Node, NodeId, NodeState, NodeStatus,
+            PublishVolume, UnpublishVolume, VolumeId,
+        },
+    },
 };
-use deployer_cluster::ClusterBuilder;
-use grpc::operations::node::traits::NodeOperations;
-use std::time::Duration;
+use deployer_cluster::{Cluster, ClusterBuilder};
+use grpc::operations::{
+    node::traits::NodeOperations, replica::traits::ReplicaOperations,
+    volume::traits::VolumeOperations,
+};
+use std::{collections::HashMap, convert::TryInto, time::Duration};
 
 /// Get new `Node` from the given parameters
 fn new_node(
@@ -155,3 +167,295 @@ async fn large_cluster() {
     tracing::info!("Nodes: {:?}", nodes);
     assert_eq!(nodes.0.len(), expected_nodes);
 }
+
+#[tokio::test]
+async fn node_labels() {
+    let cluster = ClusterBuilder::builder()
+        .with_rest(false)
+        .with_agents(vec!["core"])
+        .build()
+        .await
+        .unwrap();
+    let node_client = cluster.grpc_client().node();
+    let node_id = cluster.node(0);
+    let labels = |node: Node| node.spec().map(|spec| spec.labels().clone()).unwrap();
+
+    let node = node_client
+        .label(node_id.clone(), "zone".into(), "a".into(), false)
+        .await
+        .unwrap();
+    assert_eq!(labels(node).get("zone"), Some(&"a".to_string()));
+
+    // labelling again with the same value is a no-op
+    node_client
+        .label(node_id.clone(), "zone".into(), "a".into(), false)
+        .await
+        .unwrap();
+
+    // but changing the value requires the overwrite
+    let error = node_client
+        .label(node_id.clone(), "zone".into(), "b".into(), false)
+        .await
+        .expect_err("The label already exists");
+    assert_eq!(error.kind, ReplyErrorKind::FailedPrecondition);
+    let node = node_client
+        .label(node_id.clone(), "zone".into(), "b".into(), true)
+        .await
+        .unwrap();
+    assert_eq!(labels(node).get("zone"), Some(&"b".to_string()));
+
+    // the labels are persisted
+    cluster.restart_core().await;
+    cluster
+        .node_service_liveness(None)
+        .await
+        .expect("Should have restarted by now");
+    let nodes = node_client
+        .get(Filter::Node(node_id.clone()), None)
+        .await
+        .unwrap();
+    let node = nodes.0.first().cloned().unwrap();
+    assert_eq!(labels(node).get("zone"), Some(&"b".to_string()));
+
+    let node = node_client
+        .unlabel(node_id.clone(), "zone".into())
+        .await
+        .unwrap();
+    assert!(labels(node).is_empty());
+    let error = node_client
+        .unlabel(node_id.clone(), "zone".into())
+        .await
+        .expect_err("The label was already removed");
+    assert_eq!(error.kind, ReplyErrorKind::FailedPrecondition);
+}
+
+/// Get the nodes where the replicas of the given volume live.
+async fn volume_replica_nodes(cluster: &Cluster, volume: &VolumeId) -> Vec<NodeId> {
+    let replicas = cluster
+        .grpc_client()
+        .replica()
+        .get(Filter::Volume(volume.clone()), None)
+        .await
+        .unwrap();
+    replicas.into_inner().into_iter().map(|r| r.node).collect()
+}
+
+#[tokio::test]
+async fn drain_evacuate() {
+    let cluster = ClusterBuilder::builder()
+        .with_rest(false)
+        .with_agents(vec!["core"])
+        .with_io_engines(3)
+        .with_pools(1)
+        .with_cache_period("1s")
+        .with_reconcile_period(Duration::from_secs(1), Duration::from_secs(1))
+        .build()
+        .await
+        .unwrap();
+
+    let node_client = cluster.grpc_client().node();
+    let volume_client = cluster.grpc_client().volume();
+
+    let published: VolumeId = "1e3cf927-80c2-47a8-adf0-95c486bdd7b7".try_into().unwrap();
+    let unpublished: VolumeId = "2e3cf927-80c2-47a8-adf0-95c486bdd7b7".try_into().unwrap();
+    for volume in [&published, &unpublished] {
+        volume_client
+            .create(
+                &CreateVolume {
+                    uuid: volume.clone(),
+                    size: 5242880,
+                    replicas: 2,
+                    ..Default::default()
+                },
+                None,
+            )
+            .await
+            .unwrap();
+    }
+
+    // drain a node which has a replica of both volumes, but not the volume target
+    let published_nodes = volume_replica_nodes(&cluster, &published).await;
+    let unpublished_nodes = volume_replica_nodes(&cluster, &unpublished).await;
+    let drain_node = published_nodes
+        .iter()
+        .find(|node| unpublished_nodes.contains(node))
+        .cloned()
+        .expect("With 3 nodes, 2 volumes of 2 replicas share a node");
+    let target_node = (0 .. 3)
+        .map(|index| cluster.node(index))
+        .find(|node| node != &drain_node)
+        .unwrap();
+    volume_client
+        .publish(
+            &PublishVolume::new(
+                published.clone(),
+                Some(target_node),
+                None,
+                HashMap::new(),
+                vec![],
+                Default::default(),
+            ),
+            None,
+        )
+        .await
+        .unwrap();
+
+    node_client
+        .drain(drain_node.clone(), "evacuate".to_string(), true)
+        .await
+        .unwrap();
+
+    let timeout = Duration::from_secs(60);
+    let start = std::time::Instant::now();
+    loop {
+        let nodes = node_client
+            .get(Filter::Node(drain_node.clone()), None)
+            .await
+            .unwrap();
+        let spec = nodes
+            .0
+            .first()
+            .and_then(|node| node.spec().cloned())
+            .unwrap();
+        if spec.is_drained() {
+            assert!(spec.evacuations().is_empty());
+            break;
+        }
+        if start.elapsed() > timeout {
+            panic!("Timeout waiting for the node to be evacuated: {spec:#?}");
+        }
+        tokio::time::sleep(Duration::from_secs(1)).await;
+    }
+
+    for volume in [&published, &unpublished] {
+        let nodes = volume_replica_nodes(&cluster, volume).await;
+        assert_eq!(nodes.len(), 2, "{volume}: {nodes:?}");
+        assert!(!nodes.contains(&drain_node), "{volume}: {nodes:?}");
+
+        let volumes = volume_client
+            .get(Filter::Volume(volume.clone()), false, None, None)
+            .await
+            .unwrap();
+        let spec = volumes.entries.first().unwrap().spec();
+        assert_eq!(spec.num_replicas, 2);
+        assert_eq!(spec.rebalance, None);
+    }
+}
+
+async fn volume_spec(cluster: &Cluster, volume: &VolumeId) -> VolumeSpec {
+    let volumes = cluster
+        .grpc_client()
+        .volume()
+        .get(Filter::Volume(volume.clone()), false, None, None)
+        .await
+        .unwrap();
+    volumes.entries.first().unwrap().spec()
+}
+
+/// A volume which is unpublished while a replica is being moved off the draining node has no
+/// target left to rebuild the replacement, so the move is either completed or rolled back, and
+/// the replica is then moved as for any unpublished volume.
+#[tokio::test]
+async fn drain_evacuate_unpublish() {
+    let cluster = ClusterBuilder::builder()
+        .with_rest(false)
+        .with_agents(vec!["core"])
+        .with_io_engines(3)
+        .with_pools(1)
+        .with_cache_period("1s")
+        .with_reconcile_period(Duration::from_secs(1), Duration::from_secs(1))
+        .build()
+        .await
+        .unwrap();
+
+    let node_client = cluster.grpc_client().node();
+    let volume_client = cluster.grpc_client().volume();
+
+    let volume: VolumeId = "3e3cf927-80c2-47a8-adf0-95c486bdd7b7".try_into().unwrap();
+    volume_client
+        .create(
+            &CreateVolume {
+                uuid: volume.clone(),
+                size: 60 * 1024 * 1024,
+                replicas: 2,
+                ..Default::default()
+            },
+            None,
+        )
+        .await
+        .unwrap();
+
+    let replica_nodes = volume_replica_nodes(&cluster, &volume).await;
+    let drain_node = replica_nodes.first().cloned().unwrap();
+    let target_node = (0 .. 3)
+        .map(|index| cluster.node(index))
+        .find(|node| node != &drain_node)
+        .unwrap();
+    volume_client
+        .publish(
+            &PublishVolume::new(
+                volume.clone(),
+                Some(target_node),
+                None,
+                HashMap::new(),
+                vec![],
+                Default::default(),
+            ),
+            None,
+        )
+        .await
+        .unwrap();
+
+    node_client
+        .drain(drain_node.clone(), "evacuate".to_string(), true)
+        .await
+        .unwrap();
+
+    // unpublish the volume as soon as the replacement replica is added
+    let timeout = Duration::from_secs(30);
+    let start = std::time::Instant::now();
+    loop {
+        let spec = volume_spec(&cluster, &volume).await;
+        if spec.rebalance.is_some() {
+            assert_eq!(spec.num_replicas, 2);
+            break;
+        }
+        if start.elapsed() > timeout {
+            panic!("Timeout waiting for the replica move to start: {spec:#?}");
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    volume_client
+        .unpublish(&UnpublishVolume::new(&volume, false), None)
+        .await
+        .unwrap();
+
+    let timeout = Duration::from_secs(60);
+    let start = std::time::Instant::now();
+    loop {
+        let nodes = node_client
+            .get(Filter::Node(drain_node.clone()), None)
+            .await
+            .unwrap();
+        let spec = nodes
+            .0
+            .first()
+            .and_then(|node| node.spec().cloned())
+            .unwrap();
+        if spec.is_drained() {
+            break;
+        }
+        if start.elapsed() > timeout {
+            panic!("Timeout waiting for the node to be evacuated: {spec:#?}");
+        }
+        tokio::time::sleep(Duration::from_secs(1)).await;
+    }
+
+    let nodes = volume_replica_nodes(&cluster, &volume).await;
+    assert_eq!(nodes.len(), 2, "{nodes:?}");
+    assert!(!nodes.contains(&drain_node), "{nodes:?}");
+    let spec = volume_spec(&cluster, &volume).await;
+    assert_eq!(spec.num_replicas, 2);
+    assert_eq!(spec.rebalance, None);
+    assert_eq!(spec.operation, None);
+}
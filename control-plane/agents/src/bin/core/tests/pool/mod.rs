@@ -16,9 +16,10 @@ use common_lib::{
             replica::{ReplicaSpec, ReplicaSpecKey},
         },
         transport::{
-            CreatePool, CreateReplica, DestroyPool, DestroyReplica, Filter, GetSpecs, NexusId,
-            NodeId, Protocol, Replica, ReplicaId, ReplicaName, ReplicaOwners, ReplicaShareProtocol,
-            ReplicaStatus, ShareReplica, UnshareReplica, VolumeId,
+            CreatePool, CreateReplica, DestroyPool, DestroyReplica, Filter, GetSpecs, LabelPool,
+            NexusId, NodeId, PoolId, Protocol, Replica, ReplicaId, ReplicaName, ReplicaOwners,
+            ReplicaShareProtocol, ReplicaStatus, ShareReplica, UnlabelPool, UnshareReplica,
+            VolumeId,
         },
     },
 };
@@ -215,6 +216,97 @@ async fn replica_spec(replica: &Replica, client: &dyn RegistryOperations) -> Opt
 }
 
 /// Tests replica share and unshare operations as a transaction
+#[tokio::test]
+async fn pool_labels() {
+    let cluster = ClusterBuilder::builder()
+        .with_rest(false)
+        .with_agents(vec!["core"])
+        .build()
+        .await
+        .unwrap();
+    let pool_client = cluster.grpc_client().pool();
+    let pool_id: PoolId = "labelled".into();
+    let labels = |pool: &common_lib::types::v0::transport::Pool| {
+        pool.spec().and_then(|spec| spec.labels).unwrap_or_default()
+    };
+    let label = |key: &str, value: &str, overwrite: bool| LabelPool {
+        id: pool_id.clone(),
+        key: key.to_string(),
+        value: value.to_string(),
+        overwrite,
+    };
+    let unlabel = |key: &str| UnlabelPool {
+        id: pool_id.clone(),
+        key: key.to_string(),
+    };
+
+    let pool = pool_client
+        .create(
+            &CreatePool {
+                node: cluster.node(0),
+                id: pool_id.clone(),
+                disks: vec!["malloc:///disk0?size_mb=100".into()],
+                labels: Some(HashMap::from([("zone".to_string(), "a".to_string())])),
+            },
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(labels(&pool).get("zone"), Some(&"a".to_string()));
+
+    let pool = pool_client
+        .label(&label("rack", "r1", false), None)
+        .await
+        .unwrap();
+    assert_eq!(labels(&pool).len(), 2);
+    assert_eq!(labels(&pool).get("rack"), Some(&"r1".to_string()));
+
+    // changing the value of an existing label requires the overwrite
+    let error = pool_client
+        .label(&label("zone", "b", false), None)
+        .await
+        .expect_err("The label already exists");
+    assert_eq!(error.kind, ReplyErrorKind::FailedPrecondition);
+    let pool = pool_client
+        .label(&label("zone", "b", true), None)
+        .await
+        .unwrap();
+    assert_eq!(labels(&pool).get("zone"), Some(&"b".to_string()));
+
+    let pool = pool_client.unlabel(&unlabel("rack"), None).await.unwrap();
+    assert_eq!(labels(&pool).get("rack"), None);
+    let error = pool_client
+        .unlabel(&unlabel("rack"), None)
+        .await
+        .expect_err("The label was already removed");
+    assert_eq!(error.kind, ReplyErrorKind::FailedPrecondition);
+
+    // the labels are persisted in the pool spec
+    let specs = cluster
+        .grpc_client()
+        .registry()
+        .get_specs(&GetSpecs {}, None)
+        .await
+        .unwrap();
+    let spec = specs.pools.into_iter().find(|p| p.id == pool_id).unwrap();
+    assert_eq!(
+        spec.labels,
+        Some(HashMap::from([("zone".to_string(), "b".to_string())]))
+    );
+
+    let error = pool_client
+        .label(
+            &LabelPool {
+                id: "missing".into(),
+                ..label("zone", "a", false)
+            },
+            None,
+        )
+        .await
+        .expect_err("The pool does not exist");
+    assert_eq!(error.kind, ReplyErrorKind::NotFound);
+}
+
 #[tokio::test]
 async fn replica_transaction() {
     let cluster = ClusterBuilder::builder()
@@ -790,6 +882,7 @@ async fn disown_unused_replicas() {
                 models::VolumeShareProtocol::Nvmf,
                 None,
                 cluster.csi_node(0),
+                None,
             ),
         )
         .await
@@ -797,7 +890,7 @@ async fn disown_unused_replicas() {
 
     cluster.composer().pause(&node).await.unwrap();
     volumes_api
-        .del_volume_target(&volume.spec.uuid, Some(false))
+        .del_volume_target(&volume.spec.uuid, Some(false), None)
         .await
         .expect_err("io-engine is down");
     cluster.composer().kill(&node).await.unwrap();
@@ -45,6 +45,7 @@ async fn fault_enospc_child() {
                 models::VolumeShareProtocol::Nvmf,
                 None,
                 cluster.csi_node(0),
+                None,
             ),
         )
         .await
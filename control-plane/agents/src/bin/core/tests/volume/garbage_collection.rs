@@ -66,6 +66,7 @@ async fn deleting_volume_reconcile(cluster: &Cluster) {
                 target_node: None,
                 publish_context: HashMap::new(),
                 frontend_nodes: vec![],
+                ..Default::default()
             },
             None,
         )
@@ -163,6 +164,7 @@ async fn offline_replicas_reconcile(cluster: &Cluster, reconcile_period: Duratio
                 models::VolumeShareProtocol::Nvmf,
                 None,
                 cluster.csi_node(0),
+                None,
             ),
         )
         .await
@@ -231,6 +233,7 @@ async fn unused_nexus_reconcile(cluster: &Cluster) {
                 models::VolumeShareProtocol::Nvmf,
                 None,
                 cluster.csi_node(0),
+                None,
             ),
         )
         .await
@@ -253,6 +256,7 @@ async fn unused_nexus_reconcile(cluster: &Cluster) {
         managed: true,
         owner: None,
         config: None,
+        qos: None,
     };
     let nexus = nexus_client.create(&create_nexus, None).await.unwrap();
     let nexus = wait_till_nexus_state(cluster, &nexus.uuid, None).await;
@@ -318,6 +322,7 @@ async fn unused_reconcile(cluster: &Cluster) {
                 models::VolumeShareProtocol::Nvmf,
                 None,
                 cluster.csi_node(0),
+                None,
             ),
         )
         .await
@@ -328,7 +333,7 @@ async fn unused_reconcile(cluster: &Cluster) {
     cluster.composer().kill(&nexus_node.id).await.unwrap();
     // 2. now we force unpublish the volume
     volumes_api
-        .del_volume_target(&volume.spec.uuid, Some(true))
+        .del_volume_target(&volume.spec.uuid, Some(true), None)
         .await
         .unwrap();
     // 3. publish on the previously unused node
@@ -342,6 +347,7 @@ async fn unused_reconcile(cluster: &Cluster) {
                 models::VolumeShareProtocol::Nvmf,
                 None,
                 cluster.csi_node(0),
+                None,
             ),
         )
         .await
@@ -433,6 +439,7 @@ async fn missing_nexus_reconcile(cluster: &Cluster) {
                 models::VolumeShareProtocol::Nvmf,
                 None,
                 cluster.csi_node(0),
+                None,
             ),
         )
         .await
@@ -538,3 +545,104 @@ async fn volume_nexus_reconcile() {
 
     missing_nexus_reconcile(&cluster).await;
 }
+
+/// The replica of a faulted child whose node is offline is kept for a partial rebuild, until the
+/// faulted child wait period passes and it's disowned.
+#[tokio::test]
+async fn faulted_child_wait_period() {
+    let wait_period = Duration::from_secs(20);
+    let cluster = ClusterBuilder::builder()
+        .with_rest(true)
+        .with_agents(vec!["core"])
+        .with_io_engines(2)
+        .with_tmpfs_pool(POOL_SIZE_BYTES)
+        .with_cache_period("1s")
+        .with_node_deadline("2s")
+        .with_reconcile_period(Duration::from_millis(500), Duration::from_millis(500))
+        .with_options(|o| o.with_faulted_child_wait_period(wait_period))
+        .build()
+        .await
+        .unwrap();
+
+    let rest_api = cluster.rest_v00();
+    let volumes_api = rest_api.volumes_api();
+    let volume = volumes_api
+        .put_volume(
+            &"2e3cf927-80c2-47a8-adf0-95c481bdd7b7".parse().unwrap(),
+            models::CreateVolumeBody::new(models::VolumePolicy::default(), 2, 5242880u64, false),
+        )
+        .await
+        .unwrap();
+    let volume_id = volume.spec.uuid;
+
+    // publish on the node which is kept online, the other one having the second replica
+    volumes_api
+        .put_volume_target(
+            &volume_id,
+            PublishVolumeBody::new_all(
+                HashMap::new(),
+                None,
+                cluster.node(0).to_string(),
+                models::VolumeShareProtocol::Nvmf,
+                None,
+                cluster.csi_node(0),
+                None,
+            ),
+        )
+        .await
+        .unwrap();
+    let offline_replica = rest_api
+        .replicas_api()
+        .get_replicas()
+        .await
+        .unwrap()
+        .into_iter()
+        .find(|replica| replica.node == cluster.node(1).to_string())
+        .expect("A replica on each node")
+        .uuid;
+
+    cluster
+        .composer()
+        .stop(cluster.node(1).as_str())
+        .await
+        .unwrap();
+
+    // wait for the faulted child to be removed from the volume target
+    let timeout = Duration::from_secs(60);
+    let start = std::time::Instant::now();
+    loop {
+        let replicas = rest_api.specs_api().get_specs().await.unwrap().replicas;
+        let replica = replicas.iter().find(|r| r.uuid == offline_replica).unwrap();
+        if replica.owners.nexuses.is_empty() {
+            break;
+        }
+        if start.elapsed() > timeout {
+            panic!("Timeout waiting for the faulted child to be removed: {replica:?}");
+        }
+        tokio::time::sleep(Duration::from_millis(500)).await;
+    }
+    let removed = std::time::Instant::now();
+
+    // the replica is kept while its node is briefly offline
+    tokio::time::sleep(wait_period / 2).await;
+    let replicas = rest_api.specs_api().get_specs().await.unwrap().replicas;
+    let replica = replicas.iter().find(|r| r.uuid == offline_replica).unwrap();
+    assert_eq!(replica.owners.volume, Some(volume_id), "{replica:?}");
+
+    // and disowned once the wait period has passed
+    loop {
+        let replicas = rest_api.specs_api().get_specs().await.unwrap().replicas;
+        let owned = replicas
+            .iter()
+            .find(|r| r.uuid == offline_replica)
+            .map_or(false, |r| r.owners.volume == Some(volume_id));
+        if !owned {
+            assert!(start.elapsed() > wait_period);
+            break;
+        }
+        if removed.elapsed() > wait_period + timeout {
+            panic!("Timeout waiting for the replica to be disowned");
+        }
+        tokio::time::sleep(Duration::from_millis(500)).await;
+    }
+}
@@ -72,6 +72,7 @@ async fn hotspare_faulty_children(cluster: &Cluster) {
                 None,
                 HashMap::new(),
                 vec![],
+                Default::default(),
             ),
             None,
         )
@@ -141,6 +142,7 @@ async fn hotspare_unknown_children(cluster: &Cluster) {
                 None,
                 HashMap::new(),
                 vec![],
+                Default::default(),
             ),
             None,
         )
@@ -215,6 +217,7 @@ async fn hotspare_missing_children(cluster: &Cluster) {
                 None,
                 HashMap::new(),
                 vec![],
+                Default::default(),
             ),
             None,
         )
@@ -404,6 +407,7 @@ async fn hotspare_replica_count(cluster: &Cluster) {
                 managed: true,
                 owners: ReplicaOwners::from_volume(volume.uuid()),
                 allowed_hosts: vec![],
+                encryption: None,
             },
             None,
         )
@@ -443,6 +447,7 @@ async fn hotspare_nexus_replica_count(cluster: &Cluster) {
                 None,
                 HashMap::new(),
                 vec![],
+                Default::default(),
             ),
             None,
         )
@@ -4,6 +4,11 @@ mod capacity;
 mod garbage_collection;
 mod helpers;
 mod hotspare;
+mod publish;
+mod rebalance;
+mod rebuild_history;
+mod resize;
+mod snapshot;
 mod switchover;
 
 use common_lib::{
@@ -128,6 +133,7 @@ async fn nexus_persistence_test_iteration(
                 share: None,
                 publish_context: HashMap::new(),
                 frontend_nodes: vec![],
+                ..Default::default()
             },
             None,
         )
@@ -206,6 +212,7 @@ async fn nexus_persistence_test_iteration(
                 share: None,
                 publish_context: HashMap::new(),
                 frontend_nodes: vec![],
+                ..Default::default()
             },
             None,
         )
@@ -303,6 +310,7 @@ async fn publishing_test(cluster: &Cluster) {
                 share: None,
                 publish_context: HashMap::new(),
                 frontend_nodes: vec![],
+                ..Default::default()
             },
             None,
         )
@@ -370,6 +378,7 @@ async fn publishing_test(cluster: &Cluster) {
                 share: None,
                 publish_context: HashMap::new(),
                 frontend_nodes: vec![],
+                ..Default::default()
             },
             None,
         )
@@ -389,6 +398,7 @@ async fn publishing_test(cluster: &Cluster) {
                 share: Some(VolumeShareProtocol::Nvmf),
                 publish_context: HashMap::new(),
                 frontend_nodes: vec![],
+                ..Default::default()
             },
             None,
         )
@@ -424,6 +434,7 @@ async fn publishing_test(cluster: &Cluster) {
                 share: Some(VolumeShareProtocol::Iscsi),
                 publish_context: HashMap::new(),
                 frontend_nodes: vec![],
+                ..Default::default()
             },
             None,
         )
@@ -438,6 +449,7 @@ async fn publishing_test(cluster: &Cluster) {
                 share: Some(VolumeShareProtocol::Nvmf),
                 publish_context: HashMap::new(),
                 frontend_nodes: vec![],
+                ..Default::default()
             },
             None,
         )
@@ -457,6 +469,7 @@ async fn publishing_test(cluster: &Cluster) {
                 share: None,
                 publish_context: HashMap::new(),
                 frontend_nodes: vec![],
+                ..Default::default()
             },
             None,
         )
@@ -0,0 +1,160 @@
+#![cfg(test)]
+
+use common_lib::{
+    transport_api::ReplyErrorKind,
+    types::v0::transport::{
+        CreateVolume, DestroyVolume, PublishVolume, UnpublishVolume, Volume, VolumeAccessMode,
+        VolumeShareProtocol,
+    },
+};
+use deployer_cluster::{Cluster, ClusterBuilder};
+use grpc::operations::volume::traits::VolumeOperations;
+use std::{collections::HashMap, convert::TryInto};
+
+fn publish_request(
+    cluster: &Cluster,
+    volume: &Volume,
+    frontend_node: &str,
+    access_mode: VolumeAccessMode,
+) -> PublishVolume {
+    PublishVolume::new(
+        volume.uuid().clone(),
+        Some(cluster.node(0)),
+        Some(VolumeShareProtocol::Nvmf),
+        HashMap::new(),
+        vec![frontend_node.to_string()],
+        access_mode,
+    )
+}
+
+fn frontend_nodes(volume: &Volume) -> Vec<String> {
+    volume
+        .spec()
+        .active_config()
+        .map(|config| config.frontend().nodes())
+        .unwrap_or_default()
+}
+
+#[tokio::test]
+async fn multi_node_publish() {
+    let cluster = ClusterBuilder::builder()
+        .with_rest(false)
+        .with_agents(vec!["core"])
+        .with_io_engines(1)
+        .with_pools(1)
+        .with_cache_period("1s")
+        .build()
+        .await
+        .unwrap();
+
+    let volume_client = cluster.grpc_client().volume();
+    let volume = volume_client
+        .create(
+            &CreateVolume {
+                uuid: "6e3cf927-80c2-47a8-adf0-95c486bdd7b7".try_into().unwrap(),
+                size: 5242880,
+                replicas: 1,
+                ..Default::default()
+            },
+            None,
+        )
+        .await
+        .unwrap();
+
+    // a single-node target may not be published to other frontend nodes
+    volume_client
+        .publish(
+            &publish_request(
+                &cluster,
+                &volume,
+                "frontend-0",
+                VolumeAccessMode::SingleNodeWriter,
+            ),
+            None,
+        )
+        .await
+        .unwrap();
+    for access_mode in [
+        VolumeAccessMode::SingleNodeWriter,
+        VolumeAccessMode::SingleNodeMultiWriter,
+        VolumeAccessMode::MultiNodeReaderOnly,
+    ] {
+        let error = volume_client
+            .publish(
+                &publish_request(&cluster, &volume, "frontend-1", access_mode),
+                None,
+            )
+            .await
+            .expect_err("The target only allows a single frontend node");
+        assert_eq!(error.kind, ReplyErrorKind::AlreadyPublished);
+    }
+    volume_client
+        .unpublish(&UnpublishVolume::new(volume.uuid(), false), None)
+        .await
+        .unwrap();
+
+    // a multi-node reader target may be published to other frontend nodes
+    volume_client
+        .publish(
+            &publish_request(
+                &cluster,
+                &volume,
+                "frontend-0",
+                VolumeAccessMode::MultiNodeReaderOnly,
+            ),
+            None,
+        )
+        .await
+        .unwrap();
+    let error = volume_client
+        .publish(
+            &publish_request(
+                &cluster,
+                &volume,
+                "frontend-1",
+                VolumeAccessMode::SingleNodeWriter,
+            ),
+            None,
+        )
+        .await
+        .expect_err("The request must also be for a multi-node access mode");
+    assert_eq!(error.kind, ReplyErrorKind::AlreadyPublished);
+    let published = volume_client
+        .publish(
+            &publish_request(
+                &cluster,
+                &volume,
+                "frontend-1",
+                VolumeAccessMode::MultiNodeReaderOnly,
+            ),
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(frontend_nodes(&published), vec!["frontend-0", "frontend-1"]);
+
+    // the target is kept until it's unpublished from the last frontend node
+    let unpublished = volume_client
+        .unpublish(
+            &UnpublishVolume::new(volume.uuid(), false)
+                .with_frontend_node(Some("frontend-0".into())),
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(frontend_nodes(&unpublished), vec!["frontend-1"]);
+    let unpublished = volume_client
+        .unpublish(
+            &UnpublishVolume::new(volume.uuid(), false)
+                .with_frontend_node(Some("frontend-1".into())),
+            None,
+        )
+        .await
+        .unwrap();
+    assert!(unpublished.spec().target().is_none());
+
+    volume_client
+        .destroy(&DestroyVolume::new(volume.uuid()), None)
+        .await
+        .unwrap();
+}
@@ -0,0 +1,102 @@
+#![cfg(test)]
+
+use common_lib::types::v0::transport::{CreateVolume, Filter, NodeId, PublishVolume, VolumeId};
+use deployer_cluster::{Cluster, ClusterBuilder};
+use grpc::operations::{replica::traits::ReplicaOperations, volume::traits::VolumeOperations};
+use std::{collections::HashMap, convert::TryInto, time::Duration};
+
+async fn volume_replica_nodes(cluster: &Cluster, volume: &VolumeId) -> Vec<NodeId> {
+    let replicas = cluster
+        .grpc_client()
+        .replica()
+        .get(Filter::Volume(volume.clone()), None)
+        .await
+        .unwrap();
+    let mut nodes = replicas
+        .into_inner()
+        .into_iter()
+        .map(|r| r.node)
+        .collect::<Vec<_>>();
+    nodes.sort_by(|a, b| a.as_str().cmp(b.as_str()));
+    nodes
+}
+
+#[tokio::test]
+async fn replica_rebalance() {
+    let cluster = ClusterBuilder::builder()
+        .with_rest(false)
+        .with_agents(vec!["core"])
+        .with_io_engines(3)
+        .with_pools(1)
+        .with_cache_period("1s")
+        .with_reconcile_period(Duration::from_secs(1), Duration::from_secs(1))
+        .with_options(|o| o.with_pool_imbalance(Some(10)))
+        .build()
+        .await
+        .unwrap();
+
+    let volume_client = cluster.grpc_client().volume();
+    let volume: VolumeId = "4e3cf927-80c2-47a8-adf0-95c486bdd7b7".try_into().unwrap();
+    volume_client
+        .create(
+            &CreateVolume {
+                uuid: volume.clone(),
+                size: 40 * 1024 * 1024,
+                replicas: 2,
+                ..Default::default()
+            },
+            None,
+        )
+        .await
+        .unwrap();
+    let initial_nodes = volume_replica_nodes(&cluster, &volume).await;
+    let empty_node = (0 .. 3)
+        .map(|index| cluster.node(index))
+        .find(|node| !initial_nodes.contains(node))
+        .unwrap();
+    volume_client
+        .publish(
+            &PublishVolume::new(
+                volume.clone(),
+                Some(cluster.node(0)),
+                None,
+                HashMap::new(),
+                vec![],
+                Default::default(),
+            ),
+            None,
+        )
+        .await
+        .unwrap();
+
+    // a replica is moved onto the empty pool, without changing the replica count
+    let timeout = Duration::from_secs(60);
+    let start = std::time::Instant::now();
+    let nodes = loop {
+        let nodes = volume_replica_nodes(&cluster, &volume).await;
+        let volumes = volume_client
+            .get(Filter::Volume(volume.clone()), false, None, None)
+            .await
+            .unwrap();
+        let spec = volumes.entries.first().unwrap().spec();
+        if let Some(replica) = &spec.rebalance {
+            assert_eq!(spec.num_replicas, 2, "moving {replica}");
+        } else if nodes.len() == 2 && nodes.contains(&empty_node) {
+            assert_eq!(spec.num_replicas, 2);
+            break nodes;
+        }
+        if start.elapsed() > timeout {
+            panic!("Timeout waiting for the replica to be moved: {spec:#?} {nodes:?}");
+        }
+        tokio::time::sleep(Duration::from_millis(500)).await;
+    };
+
+    // the pools are now as balanced as they can be, so the replicas are not moved back
+    tokio::time::sleep(Duration::from_secs(10)).await;
+    let volumes = volume_client
+        .get(Filter::Volume(volume.clone()), false, None, None)
+        .await
+        .unwrap();
+    assert_eq!(volumes.entries.first().unwrap().spec().rebalance, None);
+    assert_eq!(volume_replica_nodes(&cluster, &volume).await, nodes);
+}
@@ -0,0 +1,139 @@
+#![cfg(test)]
+
+use common_lib::{
+    store::etcd::Etcd,
+    types::v0::{
+        store::{
+            definitions::Store,
+            rebuild::{RebuildHistory, RebuildHistoryKey, RebuildOutcome, RebuildReason},
+        },
+        transport::{
+            CreateVolume, DestroyVolume, GetRebuildHistory, PublishVolume, SetVolumeReplica,
+            VolumeId, VolumeShareProtocol,
+        },
+    },
+};
+use deployer_cluster::ClusterBuilder;
+use grpc::operations::{registry::traits::RegistryOperations, volume::traits::VolumeOperations};
+use std::{collections::HashMap, time::Duration};
+
+async fn persisted_history(volume: &VolumeId) -> Option<RebuildHistory> {
+    let mut etcd = Etcd::new("0.0.0.0:2379").await.unwrap();
+    etcd.get_obj(&RebuildHistoryKey::new(volume.clone()))
+        .await
+        .ok()
+}
+
+#[tokio::test]
+async fn rebuild_history() {
+    let reconcile_period = Duration::from_millis(500);
+    let cluster = ClusterBuilder::builder()
+        .with_rest(false)
+        .with_agents(vec!["core"])
+        .with_io_engines(2)
+        .with_pools(1)
+        .with_cache_period("1s")
+        .with_reconcile_period(reconcile_period, reconcile_period)
+        .build()
+        .await
+        .unwrap();
+
+    let volume_client = cluster.grpc_client().volume();
+    let registry_client = cluster.grpc_client().registry();
+    let volume = VolumeId::new();
+    volume_client
+        .create(
+            &CreateVolume {
+                uuid: volume.clone(),
+                size: 5242880,
+                replicas: 1,
+                ..Default::default()
+            },
+            None,
+        )
+        .await
+        .unwrap();
+    volume_client
+        .publish(
+            &PublishVolume {
+                uuid: volume.clone(),
+                target_node: Some(cluster.node(0)),
+                share: Some(VolumeShareProtocol::Nvmf),
+                publish_context: HashMap::new(),
+                frontend_nodes: vec![],
+                ..Default::default()
+            },
+            None,
+        )
+        .await
+        .unwrap();
+    assert!(persisted_history(&volume).await.is_none());
+
+    // the new replica is rebuilt by the volume target
+    volume_client
+        .set_replica(
+            &SetVolumeReplica {
+                uuid: volume.clone(),
+                replicas: 2,
+            },
+            None,
+        )
+        .await
+        .unwrap();
+    let history = persisted_history(&volume)
+        .await
+        .expect("The start of the rebuild is persisted");
+    assert_eq!(history.records.len(), 1);
+    assert_eq!(history.records[0].reason, RebuildReason::ReplicaIncrease);
+
+    let timeout = Duration::from_secs(30);
+    let start = std::time::Instant::now();
+    loop {
+        let rebuilds = registry_client
+            .get_rebuild_history(&GetRebuildHistory::new(Some(volume.clone())), None)
+            .await
+            .unwrap()
+            .rebuilds;
+        assert_eq!(rebuilds.len(), 1, "{rebuilds:?}");
+        if rebuilds[0].outcome == RebuildOutcome::Successful {
+            break;
+        }
+        if start.elapsed() > timeout {
+            panic!("Timeout waiting for the rebuild to finish: {rebuilds:?}");
+        }
+        tokio::time::sleep(reconcile_period).await;
+    }
+    // the end of the rebuild is persisted as well
+    let history = persisted_history(&volume).await.unwrap();
+    assert_eq!(history.records.len(), 1);
+    assert_eq!(history.records[0].outcome, RebuildOutcome::Successful);
+    assert!(history.records[0].finished.is_some());
+
+    // the history of a deleted volume is garbage collected
+    volume_client
+        .destroy(
+            &DestroyVolume {
+                uuid: volume.clone(),
+            },
+            None,
+        )
+        .await
+        .unwrap();
+    let start = std::time::Instant::now();
+    loop {
+        let rebuilds = registry_client
+            .get_rebuild_history(&GetRebuildHistory::new(None), None)
+            .await
+            .unwrap()
+            .rebuilds;
+        let collected = rebuilds.iter().all(|rebuild| rebuild.volume != volume)
+            && persisted_history(&volume).await.is_none();
+        if collected {
+            break;
+        }
+        if start.elapsed() > timeout {
+            panic!("Timeout waiting for the rebuild history to be deleted: {rebuilds:?}");
+        }
+        tokio::time::sleep(reconcile_period).await;
+    }
+}
@@ -0,0 +1,107 @@
+#![cfg(test)]
+
+use common_lib::{
+    transport_api::{ReplyErrorKind, ResourceKind},
+    types::v0::transport::{
+        CreateVolume, DestroyVolume, Filter, PublishVolume, ResizeVolume, UnpublishVolume,
+        VolumeShareProtocol,
+    },
+};
+use deployer_cluster::ClusterBuilder;
+use grpc::operations::{replica::traits::ReplicaOperations, volume::traits::VolumeOperations};
+use std::{collections::HashMap, convert::TryInto, time::Duration};
+
+#[tokio::test]
+async fn resize() {
+    let cluster = ClusterBuilder::builder()
+        .with_rest(false)
+        .with_agents(vec!["core"])
+        .with_io_engines(2)
+        .with_pools(1)
+        .with_cache_period("1s")
+        .with_reconcile_period(Duration::from_secs(1000), Duration::from_secs(1000))
+        .build()
+        .await
+        .unwrap();
+
+    let volume_client = cluster.grpc_client().volume();
+    let replica_client = cluster.grpc_client().replica();
+    let size = 5242880;
+    let volume = volume_client
+        .create(
+            &CreateVolume {
+                uuid: "2e3cf927-80c2-47a8-adf0-95c486bdd7b7".try_into().unwrap(),
+                size,
+                replicas: 2,
+                ..Default::default()
+            },
+            None,
+        )
+        .await
+        .unwrap();
+    let volume_id = volume.uuid().clone();
+    volume_client
+        .publish(
+            &PublishVolume {
+                uuid: volume_id.clone(),
+                target_node: Some(cluster.node(0)),
+                share: Some(VolumeShareProtocol::Nvmf),
+                publish_context: HashMap::new(),
+                frontend_nodes: vec![],
+                ..Default::default()
+            },
+            None,
+        )
+        .await
+        .unwrap();
+
+    let error = volume_client
+        .resize(&ResizeVolume::new(volume_id.clone(), size / 2), None)
+        .await
+        .expect_err("Volumes cannot be shrunk");
+    assert_eq!(error.kind, ReplyErrorKind::InvalidArgument);
+
+    // none of the pools can fit this, so no replica must be resized
+    let error = volume_client
+        .resize(
+            &ResizeVolume::new(volume_id.clone(), 1024 * 1024 * 1024 * 1024),
+            None,
+        )
+        .await
+        .expect_err("The pools don't have enough space");
+    assert_eq!(error.kind, ReplyErrorKind::ResourceExhausted);
+    assert_eq!(error.resource, ResourceKind::Pool);
+    let replicas = replica_client
+        .get(Filter::Volume(volume_id.clone()), None)
+        .await
+        .unwrap()
+        .into_inner();
+    assert!(replicas.iter().all(|r| r.size < size * 2), "{:?}", replicas);
+
+    let volume = volume_client
+        .resize(&ResizeVolume::new(volume_id.clone(), size * 2), None)
+        .await
+        .unwrap();
+    assert_eq!(volume.spec().size, size * 2);
+    assert!(volume.state().target.unwrap().size >= size * 2);
+    let replicas = replica_client
+        .get(Filter::Volume(volume_id.clone()), None)
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(replicas.len(), 2);
+    assert!(
+        replicas.iter().all(|r| r.size >= size * 2),
+        "{:?}",
+        replicas
+    );
+
+    volume_client
+        .unpublish(&UnpublishVolume::new(&volume_id, false), None)
+        .await
+        .unwrap();
+    volume_client
+        .destroy(&DestroyVolume::new(&volume_id), None)
+        .await
+        .unwrap();
+}
@@ -0,0 +1,230 @@
+#![cfg(test)]
+
+use common_lib::types::v0::transport::{
+    CreateVolume, CreateVolumeSnapshot, DestroyVolume, DestroyVolumeSnapshot, Filter,
+    GetVolumeSnapshots, PublishVolume, SnapshotId, UnpublishVolume, VolumeContentSource,
+    VolumeShareProtocol,
+};
+use deployer_cluster::ClusterBuilder;
+use grpc::operations::{replica::traits::ReplicaOperations, volume::traits::VolumeOperations};
+use std::{collections::HashMap, convert::TryInto, time::Duration};
+
+#[tokio::test]
+async fn snapshot() {
+    let cluster = ClusterBuilder::builder()
+        .with_rest(false)
+        .with_agents(vec!["core"])
+        .with_io_engines(2)
+        .with_pools(1)
+        .with_cache_period("1s")
+        .with_reconcile_period(Duration::from_secs(1000), Duration::from_secs(1000))
+        .build()
+        .await
+        .unwrap();
+
+    let volume_client = cluster.grpc_client().volume();
+    let volume = volume_client
+        .create(
+            &CreateVolume {
+                uuid: "1e3cf927-80c2-47a8-adf0-95c486bdd7b7".try_into().unwrap(),
+                size: 5242880,
+                replicas: 2,
+                ..Default::default()
+            },
+            None,
+        )
+        .await
+        .unwrap();
+    let volume_id = volume.uuid().clone();
+
+    // unpublished, every replica is snapshotted directly
+    let snapshot = volume_client
+        .create_snapshot(
+            &CreateVolumeSnapshot::new(&volume_id, SnapshotId::new()),
+            None,
+        )
+        .await
+        .unwrap();
+    let replica_snapshots = &snapshot.spec().replica_snapshots;
+    assert_eq!(replica_snapshots.len(), 2, "{:?}", snapshot);
+    assert_ne!(replica_snapshots[0].uuid, replica_snapshots[1].uuid);
+    assert_ne!(
+        replica_snapshots[0].source_id,
+        replica_snapshots[1].source_id
+    );
+    assert!(snapshot.state().ready_as_source);
+
+    // published, every healthy replica is snapshotted through the nexus
+    volume_client
+        .publish(
+            &PublishVolume {
+                uuid: volume_id.clone(),
+                target_node: Some(cluster.node(0)),
+                share: Some(VolumeShareProtocol::Nvmf),
+                publish_context: HashMap::new(),
+                frontend_nodes: vec![],
+                ..Default::default()
+            },
+            None,
+        )
+        .await
+        .unwrap();
+    let published_snapshot = volume_client
+        .create_snapshot(
+            &CreateVolumeSnapshot::new(&volume_id, SnapshotId::new()),
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        published_snapshot.spec().replica_snapshots.len(),
+        2,
+        "{:?}",
+        published_snapshot
+    );
+
+    for snapshot in [snapshot, published_snapshot] {
+        volume_client
+            .destroy_snapshot(
+                &DestroyVolumeSnapshot::new(Some(volume_id.clone()), snapshot.spec().uuid.clone()),
+                None,
+            )
+            .await
+            .unwrap();
+    }
+
+    volume_client
+        .unpublish(&UnpublishVolume::new(&volume_id, false), None)
+        .await
+        .unwrap();
+    volume_client
+        .destroy(&DestroyVolume::new(&volume_id), None)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn clone() {
+    let cluster = ClusterBuilder::builder()
+        .with_rest(false)
+        .with_agents(vec!["core"])
+        .with_io_engines(2)
+        .with_pools(1)
+        .with_cache_period("1s")
+        // don't let the hot spare reconciler attach the rebuild replica
+        .with_reconcile_period(Duration::from_secs(1000), Duration::from_secs(1000))
+        .build()
+        .await
+        .unwrap();
+
+    let volume_client = cluster.grpc_client().volume();
+    let replica_client = cluster.grpc_client().replica();
+    let source = volume_client
+        .create(
+            &CreateVolume {
+                uuid: "3e3cf927-80c2-47a8-adf0-95c486bdd7b7".try_into().unwrap(),
+                size: 5242880,
+                replicas: 1,
+                ..Default::default()
+            },
+            None,
+        )
+        .await
+        .unwrap();
+    let source_id = source.uuid().clone();
+    let snapshot = volume_client
+        .create_snapshot(
+            &CreateVolumeSnapshot::new(&source_id, SnapshotId::new()),
+            None,
+        )
+        .await
+        .unwrap();
+
+    // there's a single replica snapshot so the second replica is rebuilt from the clone
+    let restored = volume_client
+        .create(
+            &CreateVolume {
+                uuid: "4e3cf927-80c2-47a8-adf0-95c486bdd7b7".try_into().unwrap(),
+                size: 5242880,
+                replicas: 2,
+                content_source: Some(VolumeContentSource::Snapshot(snapshot.spec().uuid.clone())),
+                ..Default::default()
+            },
+            None,
+        )
+        .await
+        .unwrap();
+    let restored_id = restored.uuid().clone();
+    let replicas = replica_client
+        .get(Filter::Volume(restored_id.clone()), None)
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(replicas.len(), 2, "{:?}", replicas);
+
+    // only the clone may be used for the first target, the other replica is empty
+    let restored = volume_client
+        .publish(
+            &PublishVolume {
+                uuid: restored_id.clone(),
+                target_node: Some(cluster.node(0)),
+                share: Some(VolumeShareProtocol::Nvmf),
+                publish_context: HashMap::new(),
+                frontend_nodes: vec![],
+                ..Default::default()
+            },
+            None,
+        )
+        .await
+        .unwrap();
+    let children = restored.state().target.unwrap().children;
+    assert_eq!(children.len(), 1, "{:?}", children);
+
+    // cloning a volume must not leave its intermediate snapshot behind
+    let cloned = volume_client
+        .create(
+            &CreateVolume {
+                uuid: "5e3cf927-80c2-47a8-adf0-95c486bdd7b7".try_into().unwrap(),
+                size: 5242880,
+                replicas: 1,
+                content_source: Some(VolumeContentSource::Volume(source_id.clone())),
+                ..Default::default()
+            },
+            None,
+        )
+        .await
+        .unwrap();
+    let snapshots = volume_client
+        .get_snapshots(
+            &GetVolumeSnapshots::new(Some(source_id.clone()), None),
+            false,
+            None,
+        )
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(snapshots.len(), 1, "{:?}", snapshots);
+    assert_eq!(snapshots[0].spec().uuid, snapshot.spec().uuid);
+
+    volume_client
+        .unpublish(&UnpublishVolume::new(&restored_id, false), None)
+        .await
+        .unwrap();
+    for volume in [cloned.uuid(), &restored_id] {
+        volume_client
+            .destroy(&DestroyVolume::new(volume), None)
+            .await
+            .unwrap();
+    }
+    volume_client
+        .destroy_snapshot(
+            &DestroyVolumeSnapshot::new(Some(source_id.clone()), snapshot.spec().uuid.clone()),
+            None,
+        )
+        .await
+        .unwrap();
+    volume_client
+        .destroy(&DestroyVolume::new(&source_id), None)
+        .await
+        .unwrap();
+}
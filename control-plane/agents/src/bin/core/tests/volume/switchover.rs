@@ -54,6 +54,7 @@ async fn lazy_delete_shutdown_targets() {
                 target_node: Some(cluster.node(0)),
                 publish_context: HashMap::new(),
                 frontend_nodes: vec![cluster.node(1).to_string()],
+                ..Default::default()
             },
             None,
         )
@@ -75,6 +76,7 @@ async fn lazy_delete_shutdown_targets() {
                 share: VolumeShareProtocol::Nvmf,
                 reuse_existing: true,
                 frontend_node: cluster.node(0),
+                defer_shutdown: false,
             },
             None,
         )
@@ -89,6 +91,7 @@ async fn lazy_delete_shutdown_targets() {
                 share: VolumeShareProtocol::Nvmf,
                 reuse_existing: true,
                 frontend_node: cluster.node(1),
+                defer_shutdown: false,
             },
             None,
         )
@@ -194,6 +197,7 @@ async fn volume_republish_nexus_recreation() {
                 target_node: Some(replica_node.into()),
                 publish_context: HashMap::new(),
                 frontend_nodes: vec![cluster.node(1).to_string()],
+                ..Default::default()
             },
             None,
         )
@@ -236,6 +240,7 @@ async fn volume_republish_nexus_recreation() {
                 target_node: None,
                 reuse_existing: true,
                 frontend_node: cluster.node(1),
+                defer_shutdown: false,
             },
             None,
         )
@@ -301,6 +306,7 @@ async fn node_exhaustion() {
                 target_node: None,
                 publish_context: HashMap::new(),
                 frontend_nodes: vec![cluster.node(1).to_string()],
+                ..Default::default()
             },
             None,
         )
@@ -316,6 +322,7 @@ async fn node_exhaustion() {
                 target_node: None,
                 reuse_existing: false,
                 frontend_node: cluster.node(1),
+                defer_shutdown: false,
             },
             None,
         )
@@ -331,6 +338,7 @@ async fn node_exhaustion() {
                 target_node: None,
                 reuse_existing: false,
                 frontend_node: cluster.node(1),
+                defer_shutdown: false,
             },
             None,
         )
@@ -346,6 +354,7 @@ async fn node_exhaustion() {
                 target_node: None,
                 reuse_existing: false,
                 frontend_node: cluster.node(1),
+                defer_shutdown: false,
             },
             None,
         )
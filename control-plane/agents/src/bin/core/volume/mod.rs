@@ -3,11 +3,16 @@ use grpc::operations::volume::server::VolumeServer;
 use std::sync::Arc;
 
 mod operations;
+mod rebuild_history;
+mod rebuild_queue;
 mod registry;
 mod scheduling;
 mod service;
+mod snapshot_operations;
 mod specs;
 
+pub(crate) use rebuild_history::RebuildHistories;
+
 /// Configure the Service and return the builder.
 pub(crate) fn configure(builder: agents::Service) -> agents::Service {
     let registry = builder.shared_state::<Registry>().clone();
@@ -4,8 +4,9 @@ use crate::{
         registry::Registry,
         resources::{
             operations::{
-                ResourceLifecycle, ResourceOwnerUpdate, ResourcePublishing, ResourceReplicas,
-                ResourceSharing, ResourceShutdownOperations,
+                ResourceCloning, ResourceLifecycle, ResourceOwnerUpdate, ResourcePublishing,
+                ResourceQos, ResourceReplicas, ResourceResize, ResourceSharing,
+                ResourceShutdownOperations,
             },
             operations_helper::{
                 GuardedOperationsHelper, OperationSequenceGuard, ResourceSpecsLocked,
@@ -14,32 +15,45 @@ use crate::{
             OperationGuardArc, TraceSpan, TraceStrLog,
         },
     },
-    volume::specs::{create_volume_replicas, get_volume_target_node, healthy_volume_replicas},
+    volume::{
+        scheduling,
+        specs::{
+            create_volume_clone_replicas, create_volume_replicas, get_volume_target_node,
+            healthy_volume_replicas,
+        },
+    },
 };
 use agents::errors::SvcError;
 use common_lib::{
     transport_api::ErrorChain,
     types::v0::{
         store::{
-            nexus_persistence::NexusInfoKey,
+            definitions::ObjectKey,
+            nexus_persistence::{ChildInfo, NexusInfo, NexusInfoKey},
             replica::ReplicaSpec,
+            snapshot::VolumeSnapshotSpec,
+            switchover::SwitchOverHistoryKey,
             volume::{
-                FrontendConfig, PublishOperation, RepublishOperation, TargetConfig,
+                FrontendConfig, InitiatorAC, PublishOperation, RepublishOperation, TargetConfig,
                 VolumeOperation, VolumeSpec, VolumeTarget,
             },
+            SpecTransaction,
         },
         transport::{
-            CreateVolume, DestroyNexus, DestroyReplica, DestroyShutdownTargets, DestroyVolume,
-            Nexus, NexusId, NexusNvmePreemption, NexusNvmfConfig, NodeId, NvmeReservation,
-            NvmfControllerIdRange, Protocol, PublishVolume, Replica, ReplicaOwners,
-            RepublishVolume, SetVolumeReplica, ShareNexus, ShareVolume, ShutdownNexus,
-            UnpublishVolume, UnshareNexus, UnshareVolume, Volume, VolumeShareProtocol,
+            CreateReplica, CreateSnapshotClone, CreateVolume, CreateVolumeSnapshot, DestroyNexus,
+            DestroyReplica, DestroyShutdownTargets, DestroyVolume, DestroyVolumeSnapshot, Nexus,
+            NexusId, NexusNvmePreemption, NexusNvmfConfig, NodeId, NvmeReservation,
+            NvmfControllerIdRange, PoolId, Protocol, PublishVolume, Replica, ReplicaOwners,
+            RepublishVolume, ResizeNexus, ResizeReplica, ResizeVolume, SetVolumeQos,
+            SetVolumeReplica, ShareNexus, ShareVolume, ShutdownNexus, SnapshotId, UnpublishVolume,
+            UnshareNexus, UnshareVolume, Volume, VolumeAccessMode, VolumeContentSource, VolumeId,
+            VolumeShareProtocol, VolumeState,
         },
     },
     HostAccessControl,
 };
 use http::Uri;
-use std::ops::Deref;
+use std::{collections::HashMap, ops::Deref};
 use tracing::info;
 
 #[async_trait::async_trait]
@@ -59,41 +73,23 @@ impl ResourceLifecycle for OperationGuardArc<VolumeSpec> {
             .await?;
         let volume_clone = volume.start_create(registry, request).await?;
 
-        // todo: pick nodes and pools using the Node&Pool Topology
-        // todo: virtually increase the pool usage to avoid a race for space with concurrent calls
-        let result = create_volume_replicas(registry, request).await;
-        let create_replicas = volume.validate_create_step(registry, result).await?;
-
-        let mut replicas = Vec::<Replica>::new();
-        for replica in &create_replicas {
-            if replicas.len() >= request.replicas as usize {
-                break;
-            } else if replicas.iter().any(|r| r.node == replica.node) {
-                // don't reuse the same node
-                continue;
+        let replicas = match &request.content_source {
+            None => {
+                // todo: pick nodes and pools using the Node&Pool Topology
+                // todo: virtually increase the pool usage to avoid a race for space with
+                // concurrent calls
+                let result = create_volume_replicas(registry, request).await;
+                let create_replicas = volume.validate_create_step(registry, result).await?;
+                create_volume_replica_set(registry, &volume_clone, request, &create_replicas).await
             }
-            let replica = if replicas.is_empty() {
-                let mut replica = replica.clone();
-                // the local replica needs to be connected via "bdev:///"
-                replica.share = Protocol::None;
-                replica
-            } else {
-                replica.clone()
-            };
-            match OperationGuardArc::<ReplicaSpec>::create(registry, &replica).await {
-                Ok(replica) => {
-                    replicas.push(replica);
-                }
-                Err(error) => {
-                    volume_clone.error(&format!(
-                        "Failed to create replica {:?} for volume, error: {}",
-                        replica,
-                        error.full_string()
-                    ));
-                    // continue trying...
-                }
-            };
-        }
+            Some(source) => {
+                let result = volume_clone_source(registry, request, source).await;
+                let snapshot = volume.validate_create_step(registry, result).await?;
+                let result = create_volume_clones(registry, &volume, request, &snapshot).await;
+                destroy_volume_clone_source(registry, &request.uuid, source).await;
+                volume.validate_create_step(registry, result).await?
+            }
+        };
 
         // we can't fulfil the required replication factor, so let the caller
         // decide what to do next
@@ -114,6 +110,14 @@ impl ResourceLifecycle for OperationGuardArc<VolumeSpec> {
                     ));
                 }
             }
+            let health_info_id = volume.as_ref().health_info_id().cloned();
+            if let Some(nexus_id) = health_info_id {
+                ResourceSpecsLocked::delete_nexus_info(
+                    &NexusInfoKey::new(&Some(request.uuid.clone()), &nexus_id),
+                    registry,
+                )
+                .await;
+            }
             Err(SvcError::ReplicaCreateNumber {
                 id: request.uuid.to_string(),
             })
@@ -169,6 +173,29 @@ impl ResourceLifecycle for OperationGuardArc<VolumeSpec> {
             }
         }
 
+        // The volume may have been cloned and never published, in which case the seeded
+        // NexusInfo isn't owned by any nexus.
+        if let Some(nexus_id) = self.as_ref().last_nexus_id.clone() {
+            ResourceSpecsLocked::delete_nexus_info(
+                &NexusInfoKey::new(&Some(request.uuid.clone()), &nexus_id),
+                registry,
+            )
+            .await;
+        }
+        if let Some(source) = self.as_ref().content_source.clone() {
+            destroy_volume_clone_source(registry, &request.uuid, &source).await;
+        }
+
+        // The HA switchover history is of no use once the volume is gone.
+        let history = SwitchOverHistoryKey::new(request.uuid.clone());
+        if let Err(error) = registry.delete_kv(&history.key()).await {
+            tracing::warn!(
+                %error,
+                volume.uuid = %request.uuid,
+                "Failed to delete the switchover history. It will be garbage collected later."
+            );
+        }
+
         let replicas = specs.volume_replicas(&request.uuid);
         for replica in replicas {
             let mut replica = match replica.operation_guard_wait().await {
@@ -289,12 +316,34 @@ impl ResourcePublishing for OperationGuardArc<VolumeSpec> {
     ) -> Result<Self::PublishOutput, SvcError> {
         let specs = registry.specs();
         let state = registry.volume_state(&request.uuid).await?;
+
+        // A volume which is already published may also be published to other frontend nodes,
+        // provided the request is compatible with the existing target and both the target and
+        // the request allow the volume to be accessed from multiple nodes.
+        let new_frontend_nodes = self.new_frontend_nodes(request);
+        if !new_frontend_nodes.is_empty() {
+            self.validate_multi_node_access(request)?;
+            for node in new_frontend_nodes {
+                let initiator = self.frontend_node_initiator(registry, &node);
+                let operation = VolumeOperation::AddFrontendNode(node, initiator);
+                self.update_frontend_node(registry, &state, operation)
+                    .await?;
+            }
+            return registry.volume(&request.uuid).await;
+        }
+
         let nexus_node = get_volume_target_node(registry, &state, request, false).await?;
 
         let last_target = self.as_ref().health_info_id().cloned();
         let frontend_nodes = &request.frontend_nodes;
         let target_cfg = self
-            .next_target_config(registry, &nexus_node, &request.share, frontend_nodes)
+            .next_target_config(
+                registry,
+                &nexus_node,
+                &request.share,
+                frontend_nodes,
+                request.access_mode,
+            )
             .await;
 
         let operation = VolumeOperation::Publish(PublishOperation::new(
@@ -363,6 +412,23 @@ impl ResourcePublishing for OperationGuardArc<VolumeSpec> {
 
         let state = registry.volume_state(&request.uuid).await?;
 
+        // If the volume is published to other frontend nodes, then only this frontend node is
+        // removed and the target is kept for the others.
+        if let Some(node) = request.frontend_node() {
+            let nodes = match self.as_ref().active_config() {
+                Some(config) => config.frontend().nodes(),
+                None => vec![],
+            };
+            if !nodes.is_empty() && !nodes.iter().any(|n| n == node.as_str()) {
+                // not published to this frontend node, nothing to do
+                return Ok(());
+            }
+            if nodes.len() > 1 {
+                let operation = VolumeOperation::RemoveFrontendNode(node.to_string());
+                return self.update_frontend_node(registry, &state, operation).await;
+            }
+        }
+
         let spec_clone = self
             .start_update(registry, &state, VolumeOperation::Unpublish)
             .await?;
@@ -455,22 +521,36 @@ impl ResourcePublishing for OperationGuardArc<VolumeSpec> {
 
         // Get the newer target node for the new nexus creation.
         let nexus_node = get_volume_target_node(registry, &state, request, true).await?;
-        let nodes = target_cfg.frontend().node_names();
+        let nodes = target_cfg.frontend().nodes();
+        let access_mode = target_cfg.frontend().access_mode();
         let target_cfg = self
-            .next_target_config(registry, &nexus_node, &Some(request.share), &nodes)
+            .next_target_config(
+                registry,
+                &nexus_node,
+                &Some(request.share),
+                &nodes,
+                access_mode,
+            )
             .await;
-        let operation = VolumeOperation::Republish(RepublishOperation::new(target_cfg.clone()));
+        let older_nexus_id = older_nexus.uuid().clone();
+        // A planned move keeps the older nexus serving until the frontend nodes are connected to
+        // the newer nexus, after which it's shut down along with the other shutdown targets.
+        let defer_shutdown = request.defer_shutdown && !older_nexus.as_ref().is_shutdown();
+        let operation = VolumeOperation::Republish(
+            RepublishOperation::new(target_cfg.clone())
+                .with_moved_target(defer_shutdown.then(|| older_nexus_id.clone())),
+        );
 
         let spec_clone = self.start_update(registry, &state, operation).await?;
 
-        let older_nexus_id = older_nexus.uuid().clone();
-
-        // Shutdown the older nexus before newer nexus creation.
-        let result = older_nexus
-            .shutdown(registry, &ShutdownNexus::new(older_nexus_id, true))
-            .await;
-        self.validate_update_step(registry, result, &spec_clone)
-            .await?;
+        if !defer_shutdown {
+            // Shutdown the older nexus before newer nexus creation.
+            let result = older_nexus
+                .shutdown(registry, &ShutdownNexus::new(older_nexus_id, true))
+                .await;
+            self.validate_update_step(registry, result, &spec_clone)
+                .await?;
+        }
 
         // Create a Nexus on the requested or auto-selected node.
         let result = specs
@@ -540,6 +620,368 @@ impl ResourceReplicas for OperationGuardArc<VolumeSpec> {
     }
 }
 
+#[async_trait::async_trait]
+impl ResourceResize for OperationGuardArc<VolumeSpec> {
+    type Resize = ResizeVolume;
+    type ResizeOutput = Volume;
+
+    async fn resize(
+        &mut self,
+        registry: &Registry,
+        request: &Self::Resize,
+    ) -> Result<Self::ResizeOutput, SvcError> {
+        if self.as_ref().size == request.requested_size {
+            return registry.volume(&request.uuid).await;
+        }
+        let state = registry.volume_state(&request.uuid).await?;
+
+        let operation = VolumeOperation::Resize(request.requested_size);
+        let spec_clone = self.start_update(registry, &state, operation).await?;
+
+        let result = resize_volume_children(registry, &spec_clone, request.requested_size).await;
+        self.complete_update(registry, result, spec_clone).await?;
+
+        registry.volume(&request.uuid).await
+    }
+}
+
+#[async_trait::async_trait]
+impl ResourceQos for OperationGuardArc<VolumeSpec> {
+    type SetQos = SetVolumeQos;
+    type SetQosOutput = Volume;
+
+    async fn set_qos(
+        &mut self,
+        registry: &Registry,
+        request: &Self::SetQos,
+    ) -> Result<Self::SetQosOutput, SvcError> {
+        let state = registry.volume_state(&request.uuid).await?;
+
+        let operation = VolumeOperation::SetQos(request.qos.clone());
+        let spec_clone = self.start_update(registry, &state, operation).await?;
+
+        // If the volume is published, the new limits are applied to its target right away,
+        // otherwise they're applied when the target is created.
+        let result = match spec_clone.target() {
+            Some(target) => {
+                ResourceSpecsLocked::set_target_qos(
+                    registry,
+                    target.node(),
+                    target.nexus(),
+                    &request.qos,
+                )
+                .await
+            }
+            None => Ok(()),
+        };
+        self.complete_update(registry, result, spec_clone).await?;
+
+        registry.volume(&request.uuid).await
+    }
+}
+
+/// Create up to `request.replicas` replicas for a new volume from the given candidates, using
+/// only one replica per node.
+async fn create_volume_replica_set(
+    registry: &Registry,
+    volume: &VolumeSpec,
+    request: &CreateVolume,
+    candidates: &[CreateReplica],
+) -> Vec<Replica> {
+    let mut replicas = Vec::<Replica>::new();
+    for replica in candidates {
+        if replicas.len() >= request.replicas as usize {
+            break;
+        } else if replicas.iter().any(|r| r.node == replica.node) {
+            // don't reuse the same node
+            continue;
+        } else if !replicas.is_empty()
+            && !scheduling::volume_spread_candidate(registry, volume, replica).await
+        {
+            // nor the same topology spread label values
+            continue;
+        }
+        let replica = if replicas.is_empty() {
+            let mut replica = replica.clone();
+            // the local replica needs to be connected via "bdev:///"
+            replica.share = Protocol::None;
+            replica
+        } else {
+            replica.clone()
+        };
+        match OperationGuardArc::<ReplicaSpec>::create(registry, &replica).await {
+            Ok(replica) => {
+                replicas.push(replica);
+            }
+            Err(error) => {
+                volume.error(&format!(
+                    "Failed to create replica {:?} for volume, error: {}",
+                    replica,
+                    error.full_string()
+                ));
+                // continue trying...
+            }
+        };
+    }
+    replicas
+}
+
+/// Get the snapshot from which the replicas of a new volume should be cloned.
+/// When the source is another volume, a snapshot of it is taken first. The snapshot uses the uuid
+/// of the new volume so that a retried create request reuses it.
+async fn volume_clone_source(
+    registry: &Registry,
+    request: &CreateVolume,
+    source: &VolumeContentSource,
+) -> Result<VolumeSnapshotSpec, SvcError> {
+    match source {
+        VolumeContentSource::Snapshot(snap_id) => registry.specs().snapshot_clone(snap_id),
+        VolumeContentSource::Volume(source_id) => {
+            let snap_id = SnapshotId::from(request.uuid.uuid());
+            match registry.specs().snapshot_clone(&snap_id) {
+                Ok(snapshot) if snapshot.status.created() && &snapshot.source_id == source_id => {
+                    Ok(snapshot)
+                }
+                _ => {
+                    let request = CreateVolumeSnapshot::new(source_id, snap_id);
+                    let snapshot =
+                        OperationGuardArc::<VolumeSnapshotSpec>::create(registry, &request).await?;
+                    Ok(snapshot.as_ref().clone())
+                }
+            }
+        }
+    }
+}
+
+/// Destroy the intermediate snapshot which `volume_clone_source` takes when cloning a volume.
+/// The io-engine only discards the snapshot data once the replica clones no longer need it.
+async fn destroy_volume_clone_source(
+    registry: &Registry,
+    volume: &VolumeId,
+    source: &VolumeContentSource,
+) {
+    let source_id = match source {
+        VolumeContentSource::Volume(source_id) => source_id,
+        VolumeContentSource::Snapshot(_) => return,
+    };
+    let snap_id = SnapshotId::from(volume.uuid());
+    let mut snapshot = match registry.specs().snapshot(&snap_id).await {
+        Ok(snapshot) => snapshot,
+        Err(_) => return,
+    };
+    let request = DestroyVolumeSnapshot::new(Some(source_id.clone()), snap_id);
+    if let Err(error) = snapshot.destroy(registry, &request).await {
+        snapshot.warn(&format!(
+            "Failed to destroy the clone source snapshot, it will be retried when the volume is \
+             destroyed, error: {}",
+            error.full_string()
+        ));
+    }
+}
+
+/// Create the replicas of a new volume from the given snapshot: a replica clone on each pool
+/// with a suitable replica snapshot and, if those are not enough, empty replicas which are
+/// rebuilt from the replica clones.
+async fn create_volume_clones(
+    registry: &Registry,
+    volume: &OperationGuardArc<VolumeSpec>,
+    request: &CreateVolume,
+    snapshot: &VolumeSnapshotSpec,
+) -> Result<Vec<Replica>, SvcError> {
+    let create_clones = create_volume_clone_replicas(registry, request, snapshot).await?;
+    let volume_spec = volume.as_ref().clone();
+    let clones = create_volume_clone_set(registry, &volume_spec, request, &create_clones).await;
+    Ok(create_volume_clone_rebuild_set(registry, volume, request, clones).await)
+}
+
+/// Create the replicas which a cloned volume requires on top of its replica clones.
+/// These start off empty and are rebuilt from the replica clones once the volume is published,
+/// so the volume health information is seeded with only the replica clones marked as healthy,
+/// which prevents the empty replicas from being used when the volume target is first created.
+async fn create_volume_clone_rebuild_set(
+    registry: &Registry,
+    volume: &OperationGuardArc<VolumeSpec>,
+    request: &CreateVolume,
+    clones: Vec<Replica>,
+) -> Vec<Replica> {
+    let missing = (request.replicas as usize).saturating_sub(clones.len());
+    if clones.is_empty() || missing == 0 {
+        return clones;
+    }
+
+    let nexus_id = NexusId::new();
+    let info = NexusInfo {
+        uuid: nexus_id.clone(),
+        volume_uuid: Some(request.uuid.clone()),
+        clean_shutdown: true,
+        children: clones
+            .iter()
+            .map(|clone| ChildInfo {
+                uuid: clone.uuid.to_string(),
+                healthy: true,
+            })
+            .collect(),
+    };
+    if let Err(error) = registry.store_obj(&info).await {
+        volume.error(&format!(
+            "Failed to store the health information of the replica clones, error: {}",
+            error.full_string()
+        ));
+        return clones;
+    }
+    volume.lock().last_nexus_id = Some(nexus_id);
+
+    let volume_spec = volume.as_ref().clone();
+    let mut replicas = clones;
+    match registry
+        .specs()
+        .create_volume_replicas(registry, &volume_spec, missing)
+        .await
+    {
+        Ok(created) => {
+            for replica in created {
+                match registry.get_replica(&replica).await {
+                    Ok(replica) => replicas.push(replica),
+                    Err(error) => volume.error(&format!(
+                        "Failed to get the rebuild replica {}, error: {}",
+                        replica,
+                        error.full_string()
+                    )),
+                }
+            }
+        }
+        Err(error) => {
+            volume.error(&format!(
+                "Failed to create the rebuild replicas for the volume clone, error: {}",
+                error.full_string()
+            ));
+        }
+    }
+    replicas
+}
+
+/// Create up to `request.replicas` replica clones for a new volume from the given candidates,
+/// using only one replica per node.
+/// Clones are as large as their snapshot, so they're grown to the volume size once created.
+async fn create_volume_clone_set(
+    registry: &Registry,
+    volume: &VolumeSpec,
+    request: &CreateVolume,
+    candidates: &[CreateSnapshotClone],
+) -> Vec<Replica> {
+    let mut replicas = Vec::<Replica>::new();
+    for clone in candidates {
+        if replicas.len() >= request.replicas as usize {
+            break;
+        } else if replicas.iter().any(|r| r.node == clone.replica.node) {
+            // don't reuse the same node
+            continue;
+        }
+        match create_volume_clone(registry, clone, request.size).await {
+            Ok(replica) => {
+                replicas.push(replica);
+            }
+            Err(error) => {
+                volume.error(&format!(
+                    "Failed to create replica clone {:?} for volume, error: {}",
+                    clone,
+                    error.full_string()
+                ));
+                // continue trying...
+            }
+        };
+    }
+    replicas
+}
+
+/// Create a replica clone and grow it to the `size` of the volume.
+/// If the clone cannot be grown, it's destroyed.
+async fn create_volume_clone(
+    registry: &Registry,
+    request: &CreateSnapshotClone,
+    size: u64,
+) -> Result<Replica, SvcError> {
+    let replica = OperationGuardArc::<ReplicaSpec>::create_clone(registry, request).await?;
+    if replica.size >= size {
+        return Ok(replica);
+    }
+    let mut guard = registry.specs().replica(&replica.uuid).await?;
+    let result = guard
+        .resize(registry, &ResizeReplica::new(&replica, size))
+        .await;
+    if result.is_err() {
+        // an undersized clone is of no use to the volume
+        let request = DestroyReplica::from(replica).with_disown_all();
+        guard.destroy(registry, &request).await.ok();
+    }
+    result
+}
+
+/// Grow all replicas of the volume and then its target nexus, if any, to the `requested_size`.
+/// Children which have already been resized are skipped so that a failed resize may be retried.
+async fn resize_volume_children(
+    registry: &Registry,
+    volume: &VolumeSpec,
+    requested_size: u64,
+) -> Result<(), SvcError> {
+    let specs = registry.specs();
+
+    validate_resize_pools(registry, volume, requested_size).await?;
+
+    for replica in specs.volume_replicas(&volume.uuid) {
+        let mut replica = replica.operation_guard_wait().await?;
+        let replica_uuid = replica.lock().uuid.clone();
+        let state = registry.get_replica(&replica_uuid).await?;
+        if state.size >= requested_size {
+            continue;
+        }
+        let request = ResizeReplica::new(&state, requested_size);
+        replica.resize(registry, &request).await?;
+    }
+
+    if let Some(mut nexus) = specs.volume_target_nexus(volume).await? {
+        let nexus_spec = nexus.as_ref().clone();
+        let state = registry.nexus(&nexus_spec.uuid).await?;
+        if state.size < requested_size {
+            let request = ResizeNexus::new(&nexus_spec.node, &nexus_spec.uuid, requested_size);
+            nexus.resize(registry, &request).await?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Check that the pools of the volume replicas have enough free space to grow every replica to
+/// the requested size, so that we don't end up with only some of the replicas resized.
+async fn validate_resize_pools(
+    registry: &Registry,
+    volume: &VolumeSpec,
+    requested_size: u64,
+) -> Result<(), SvcError> {
+    let mut required = HashMap::<PoolId, u64>::new();
+    for replica in registry.specs().volume_replicas_cln(&volume.uuid) {
+        let state = registry.get_replica(&replica.uuid).await?;
+        if state.thin || state.size >= requested_size {
+            continue;
+        }
+        *required.entry(state.pool_id).or_default() += requested_size - state.size;
+    }
+
+    for (pool_id, required) in required {
+        let pool = registry.get_pool_state(&pool_id).await?;
+        let free = pool.capacity.saturating_sub(pool.used);
+        if free < required {
+            return Err(SvcError::ResizePoolSpace {
+                volume_id: volume.uuid.to_string(),
+                pool_id: pool_id.to_string(),
+                free,
+                required,
+            });
+        }
+    }
+    Ok(())
+}
+
 #[async_trait::async_trait]
 impl ResourceShutdownOperations for OperationGuardArc<VolumeSpec> {
     type RemoveShutdownTargets = DestroyShutdownTargets;
@@ -559,6 +1001,8 @@ impl ResourceShutdownOperations for OperationGuardArc<VolumeSpec> {
         registry: &Registry,
         request: &Self::RemoveShutdownTargets,
     ) -> Result<(), SvcError> {
+        self.shutdown_moved_target(registry, request).await?;
+
         let shutdown_nexuses = registry
             .specs()
             .volume_shutdown_nexuses(request.uuid())
@@ -636,9 +1080,129 @@ fn target_registered(
 }
 
 impl OperationGuardArc<VolumeSpec> {
+    /// Shutdown the previous target of a planned move, now that the frontend nodes have been
+    /// connected to the new target, unless it's still registered on the frontend nodes.
+    async fn shutdown_moved_target(
+        &self,
+        registry: &Registry,
+        request: &DestroyShutdownTargets,
+    ) -> Result<(), SvcError> {
+        let moved_target = match &self.as_ref().moved_target {
+            Some(moved_target) => moved_target.clone(),
+            None => return Ok(()),
+        };
+        let mut nexus = match registry.specs().nexus(&moved_target).await {
+            Ok(nexus) if !nexus.as_ref().is_shutdown() => nexus,
+            _ => return Ok(()),
+        };
+        if let Ok(state) = registry.nexus(&moved_target).await {
+            if target_registered(request.registered_targets(), state)? {
+                return Ok(());
+            }
+        }
+        nexus
+            .shutdown(registry, &ShutdownNexus::new(moved_target, true))
+            .await
+    }
+
     fn published(&self) -> bool {
         self.as_ref().target().is_some()
     }
+    /// Get the frontend nodes of the publish request which the volume is not yet published to.
+    /// This is only the case when the volume is already published via the same protocol and
+    /// on the same target node, if any was requested.
+    fn new_frontend_nodes(&self, request: &PublishVolume) -> Vec<String> {
+        match self.as_ref().active_config() {
+            Some(config)
+                if config.target().protocol() == request.share.as_ref()
+                    && request
+                        .target_node
+                        .as_ref()
+                        .map(|node| node == config.target().node())
+                        .unwrap_or(true)
+                    && !config.frontend().nodes().is_empty() =>
+            {
+                request
+                    .frontend_nodes
+                    .iter()
+                    .filter(|node| !config.frontend().node_published(node))
+                    .cloned()
+                    .collect()
+            }
+            _ => vec![],
+        }
+    }
+    /// Check that the volume may be published to the frontend nodes of the request in addition to
+    /// the frontend nodes which it's already published to.
+    /// This is only allowed when both the target and the request use a multi-node access mode.
+    fn validate_multi_node_access(&self, request: &PublishVolume) -> Result<(), SvcError> {
+        match self.as_ref().active_config() {
+            Some(config)
+                if config.frontend().access_mode().multi_node()
+                    && config.frontend().access_mode() == request.access_mode =>
+            {
+                Ok(())
+            }
+            Some(config) => Err(SvcError::VolumeAlreadyPublished {
+                vol_id: request.uuid.to_string(),
+                node: config.target().node().to_string(),
+                protocol: format!("{:?}", config.target().protocol()),
+            }),
+            None => Err(SvcError::VolumeNotPublished {
+                vol_id: request.uuid.to_string(),
+            }),
+        }
+    }
+    /// Get the initiator which the given frontend node connects to the target with.
+    /// This is `None` if access to the target is not restricted by the host access list.
+    fn frontend_node_initiator(&self, registry: &Registry, node: &str) -> Option<InitiatorAC> {
+        match self.as_ref().active_config() {
+            Some(config) if config.frontend().host_acl_enabled() => registry
+                .host_acl_nodename(HostAccessControl::Nexuses, &[node.to_string()])
+                .pop(),
+            _ => None,
+        }
+    }
+    /// Add or remove a frontend node of the published volume, updating the hosts which are
+    /// allowed to connect to the target accordingly.
+    async fn update_frontend_node(
+        &mut self,
+        registry: &Registry,
+        state: &VolumeState,
+        operation: VolumeOperation,
+    ) -> Result<(), SvcError> {
+        let spec_clone = self.start_update(registry, state, operation).await?;
+
+        let mut updated_spec = spec_clone.clone();
+        updated_spec.commit_op();
+        let result = match (spec_clone.active_config(), updated_spec.active_config()) {
+            (Some(current), Some(updated))
+                if current.frontend().node_nqns() != updated.frontend().node_nqns() =>
+            {
+                Self::share_target(registry, updated).await
+            }
+            _ => Ok(()),
+        };
+
+        self.complete_update(registry, result, spec_clone).await
+    }
+    /// Share the target nexus with the hosts allowed by the given target configuration.
+    async fn share_target(registry: &Registry, config: &TargetConfig) -> Result<(), SvcError> {
+        let protocol = match config.target().protocol() {
+            Some(protocol) => *protocol,
+            None => return Ok(()),
+        };
+        let mut nexus = registry.specs().nexus(config.target().nexus()).await?;
+        let nexus_state = registry.nexus(config.target().nexus()).await?;
+        let allowed_hosts = config.frontend().node_nqns();
+        nexus
+            .share(
+                registry,
+                &ShareNexus::new(&nexus_state, protocol, allowed_hosts),
+            )
+            .await?;
+        Ok(())
+    }
     /// Make the next target config.
     /// This essentially bumps up the controller id by 1 as otherwise the initiator cannot tell
     /// this target apart from others.
@@ -649,6 +1213,7 @@ impl OperationGuardArc<VolumeSpec> {
         node: &NodeId,
         share: &Option<VolumeShareProtocol>,
         frontend_nodes: &[String],
+        access_mode: VolumeAccessMode,
     ) -> TargetConfig {
         let nexus = NexusId::new();
         let resv_key = nexus.as_u128() as u64;
@@ -668,7 +1233,7 @@ impl OperationGuardArc<VolumeSpec> {
             }
         };
         let host_acl = registry.host_acl_nodename(HostAccessControl::Nexuses, frontend_nodes);
-        let frontend = FrontendConfig::from_acls(host_acl);
+        let frontend = FrontendConfig::new(frontend_nodes.to_vec(), host_acl, access_mode);
         TargetConfig::new(
             VolumeTarget::new(node.clone(), nexus, *share),
             NexusNvmfConfig::new(
@@ -0,0 +1,224 @@
+use crate::controller::registry::Registry;
+use agents::errors::SvcError;
+use common_lib::types::v0::{
+    store::{
+        definitions::{ObjectKey, StorableObjectType},
+        rebuild::{RebuildHistory, RebuildHistoryKey, RebuildReason, RebuildRecord},
+    },
+    transport::{GetRebuildHistory, NexusId, ReplicaId, VolumeId, VolumeRebuilds},
+};
+use std::collections::HashMap;
+
+/// In-memory copy of the persisted rebuild histories of the volumes.
+/// The histories are loaded from the store once and then kept up to date in memory, so they're
+/// only written back when a rebuild starts or finishes rather than as its progress changes.
+#[derive(Debug, Default)]
+pub(crate) struct RebuildHistories {
+    /// The histories of the volumes, including the running rebuilds progress.
+    histories: HashMap<VolumeId, RebuildHistory>,
+    /// All of the persisted histories have been loaded, and not only the ones of the volumes
+    /// whose history was requested.
+    loaded: bool,
+}
+
+/// Check if the rebuilds of the history changed from the `original` ones, ie: if a rebuild was
+/// started or finished, as opposed to having only made progress.
+pub(crate) fn rebuilds_changed(original: &RebuildHistory, history: &RebuildHistory) -> bool {
+    let rebuilds = |history: &RebuildHistory| {
+        history
+            .records
+            .iter()
+            .map(|record| (record.replica.clone(), record.started, record.outcome))
+            .collect::<Vec<_>>()
+    };
+    rebuilds(original) != rebuilds(history)
+}
+
+impl Registry {
+    /// Get the rebuild history of the given volume.
+    /// It's loaded from the store the first time, and an empty history is returned if the volume
+    /// has none yet.
+    pub(crate) async fn rebuild_history(
+        &self,
+        volume: &VolumeId,
+    ) -> Result<RebuildHistory, SvcError> {
+        if let Some(history) = self.rebuild_history_cache().lock().histories.get(volume) {
+            return Ok(history.clone());
+        }
+        let history = match self
+            .load_obj::<RebuildHistory>(&RebuildHistoryKey::new(volume.clone()))
+            .await
+        {
+            Ok(history) => history,
+            Err(SvcError::StoreMissingEntry { .. }) => RebuildHistory::new(volume.clone()),
+            Err(error) => return Err(error),
+        };
+        let mut histories = self.rebuild_history_cache().lock();
+        Ok(histories
+            .histories
+            .entry(volume.clone())
+            .or_insert(history)
+            .clone())
+    }
+
+    /// Get the rebuild histories of all volumes, loading them from the store the first time.
+    pub(crate) async fn rebuild_histories(&self) -> Result<Vec<RebuildHistory>, SvcError> {
+        if !self.rebuild_history_cache().lock().loaded {
+            let loaded = self
+                .load_obj_prefix::<RebuildHistory>(StorableObjectType::RebuildHistory)
+                .await?;
+            let mut histories = self.rebuild_history_cache().lock();
+            if !histories.loaded {
+                for history in loaded {
+                    histories
+                        .histories
+                        .entry(history.volume.clone())
+                        .or_insert(history);
+                }
+                histories.loaded = true;
+            }
+        }
+        Ok(self
+            .rebuild_history_cache()
+            .lock()
+            .histories
+            .values()
+            .cloned()
+            .collect())
+    }
+
+    /// Update the in-memory rebuild history of the volume, persisting it only if a rebuild was
+    /// started or finished.
+    pub(crate) async fn update_rebuild_history(
+        &self,
+        original: &RebuildHistory,
+        history: RebuildHistory,
+    ) -> Result<(), SvcError> {
+        if rebuilds_changed(original, &history) {
+            self.store_obj(&history).await?;
+        }
+        self.rebuild_history_cache()
+            .lock()
+            .histories
+            .insert(history.volume.clone(), history);
+        Ok(())
+    }
+
+    /// Delete the rebuild history of a volume which no longer exists.
+    /// An empty history was never persisted, and so it's only removed from memory.
+    pub(crate) async fn delete_rebuild_history(&self, volume: &VolumeId) -> Result<(), SvcError> {
+        let persisted = self
+            .rebuild_history_cache()
+            .lock()
+            .histories
+            .get(volume)
+            .map_or(true, |history| !history.records.is_empty());
+        if persisted {
+            self.delete_kv(&RebuildHistoryKey::new(volume.clone()).key())
+                .await?;
+        }
+        self.rebuild_history_cache().lock().histories.remove(volume);
+        Ok(())
+    }
+
+    /// Record the start of a rebuild of the given replica by the volume nexus.
+    /// The history is best-effort only, and so failing to persist it is not an error: the
+    /// in-memory history still records the rebuild, which is persisted once it finishes.
+    pub(crate) async fn record_rebuild_start(
+        &self,
+        volume: &VolumeId,
+        nexus: &NexusId,
+        replica: &ReplicaId,
+        reason: RebuildReason,
+    ) {
+        let result = async {
+            let mut history = self.rebuild_history(volume).await?;
+            history.push(RebuildRecord::new(
+                volume.clone(),
+                nexus.clone(),
+                replica.clone(),
+                reason,
+            ));
+            self.rebuild_history_cache()
+                .lock()
+                .histories
+                .insert(volume.clone(), history.clone());
+            self.store_obj(&history).await
+        }
+        .await;
+        if let Err(error) = result {
+            tracing::warn!(
+                volume.uuid = %volume,
+                replica.uuid = %replica,
+                %error,
+                "Failed to record the start of the replica rebuild"
+            );
+        }
+    }
+
+    /// Get the volume replica rebuilds, optionally filtered by volume.
+    /// The progress of the running rebuilds is retrieved from the volume target state.
+    pub(crate) async fn volume_rebuilds(
+        &self,
+        request: &GetRebuildHistory,
+    ) -> Result<VolumeRebuilds, SvcError> {
+        let histories = match request.volume_id() {
+            Some(volume_id) => vec![self.rebuild_history(volume_id).await?],
+            None => self.rebuild_histories().await?,
+        };
+
+        let mut rebuilds = vec![];
+        for history in histories {
+            let state = match history.records.iter().any(|record| record.running()) {
+                true => self.volume_state(&history.volume).await.ok(),
+                false => None,
+            };
+            for mut record in history.records {
+                if !record.running() {
+                    rebuilds.push(record);
+                    continue;
+                }
+                let progress = state
+                    .as_ref()
+                    .and_then(|state| state.replica_topology.get(&record.replica))
+                    .and_then(|topology| topology.rebuild_progress());
+                if let (Some(progress), Some(state)) = (progress, &state) {
+                    record.set_progress(progress, state.size);
+                }
+                rebuilds.push(record);
+            }
+        }
+        rebuilds.sort_by(|a, b| a.started.cmp(&b.started));
+
+        Ok(VolumeRebuilds { rebuilds })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common_lib::types::v0::store::rebuild::RebuildOutcome;
+
+    #[test]
+    fn rebuilds_changes() {
+        let volume = VolumeId::new();
+        let mut history = RebuildHistory::new(volume.clone());
+        let original = history.clone();
+
+        history.push(RebuildRecord::new(
+            volume.clone(),
+            NexusId::new(),
+            ReplicaId::new(),
+            RebuildReason::Replacement,
+        ));
+        assert!(rebuilds_changed(&original, &history));
+
+        // the progress of a running rebuild is not persisted on its own
+        let original = history.clone();
+        history.records[0].set_progress(50, 100 * 1024 * 1024);
+        assert!(!rebuilds_changed(&original, &history));
+
+        history.records[0].finish(RebuildOutcome::Successful);
+        assert!(rebuilds_changed(&original, &history));
+    }
+}
@@ -0,0 +1,63 @@
+use crate::controller::registry::Registry;
+use agents::errors::SvcError;
+use common_lib::types::v0::{
+    store::volume::VolumeSpec,
+    transport::{ChildState, QueuedRebuild, RebuildQueue},
+};
+
+impl Registry {
+    /// Get the rebuild queue entry of the given volume, from the state of its target.
+    /// Only the volume target rebuilds replicas, and so an unpublished volume is never missing
+    /// any replicas.
+    pub(crate) async fn volume_rebuild_order(
+        &self,
+        volume: &VolumeSpec,
+    ) -> Result<QueuedRebuild, SvcError> {
+        let state = self.volume_state(&volume.uuid).await?;
+        let (healthy, rebuilding) = match &state.target {
+            Some(nexus) => (
+                nexus
+                    .children
+                    .iter()
+                    .filter(|c| c.state == ChildState::Online && c.rebuild_progress.is_none())
+                    .count(),
+                nexus
+                    .children
+                    .iter()
+                    .filter(|c| c.rebuild_progress.is_some())
+                    .count(),
+            ),
+            None => (volume.effective_num_replicas() as usize, 0),
+        };
+        let healthy = healthy.min(u8::MAX as usize) as u8;
+        let rebuilding = rebuilding.min(u8::MAX as usize) as u8;
+
+        Ok(QueuedRebuild {
+            volume: volume.uuid.clone(),
+            priority: volume.policy.rebuild_priority,
+            healthy_replicas: healthy,
+            missing_replicas: volume
+                .effective_num_replicas()
+                .saturating_sub(healthy)
+                .saturating_sub(rebuilding),
+        })
+    }
+
+    /// Get the self-healing volumes whose target is missing replicas, in the order in which the
+    /// rebuilds of their replicas are started.
+    pub(crate) async fn rebuild_queue(&self) -> Result<RebuildQueue, SvcError> {
+        let mut rebuilds = vec![];
+        for volume in self.specs().volumes() {
+            if !volume.policy.self_heal || !volume.status.created() {
+                continue;
+            }
+            match self.volume_rebuild_order(&volume).await {
+                Ok(queued) if queued.missing_replicas > 0 => rebuilds.push(queued),
+                _ => {}
+            }
+        }
+        rebuilds.sort_by(|a, b| a.rebuild_order(b));
+
+        Ok(RebuildQueue { rebuilds })
+    }
+}
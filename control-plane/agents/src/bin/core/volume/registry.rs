@@ -1,7 +1,8 @@
 use crate::controller::registry::Registry;
 use agents::errors::SvcError;
 use common_lib::types::v0::transport::{
-    uri_with_hostnqn, NexusStatus, ReplicaTopology, Volume, VolumeId, VolumeState, VolumeStatus,
+    uri_with_hostnqn, Nexus, NexusStatus, ReplicaTopology, Volume, VolumeId, VolumeState,
+    VolumeStatus,
 };
 
 use crate::controller::reconciler::PollTriggerEvent;
@@ -48,7 +49,8 @@ impl Registry {
         for replica_spec in &replica_specs {
             replica_topology.insert(
                 replica_spec.uuid.clone(),
-                self.replica_topology(replica_spec).await,
+                self.replica_topology(replica_spec, nexus.as_ref().map(|(_, state)| state))
+                    .await,
             );
         }
 
@@ -60,7 +62,8 @@ impl Registry {
                 size: nexus_state.size,
                 status: match nexus_state.status {
                     NexusStatus::Online
-                        if nexus_state.children.len() != volume_spec.num_replicas as usize =>
+                        if nexus_state.children.len()
+                            != volume_spec.effective_num_replicas() as usize =>
                     {
                         VolumeStatus::Degraded
                     }
@@ -90,11 +93,18 @@ impl Registry {
         })
     }
 
-    /// Construct a replica topology from a replica spec.
+    /// Construct a replica topology from a replica spec, including its rebuild progress if it's
+    /// being rebuilt by the given volume nexus.
     /// If the replica cannot be found, return the default replica topology.
-    async fn replica_topology(&self, spec: &ReplicaSpec) -> ReplicaTopology {
+    async fn replica_topology(&self, spec: &ReplicaSpec, nexus: Option<&Nexus>) -> ReplicaTopology {
         match self.get_replica(&spec.uuid).await {
-            Ok(state) => ReplicaTopology::new(Some(state.node), Some(state.pool_id), state.status),
+            Ok(state) => {
+                let rebuild_progress = nexus
+                    .and_then(|nexus| nexus.children.iter().find(|c| c.uri == state.uri))
+                    .and_then(|child| child.rebuild_progress);
+                ReplicaTopology::new(Some(state.node), Some(state.pool_id), state.status)
+                    .with_rebuild_progress(rebuild_progress)
+            }
             Err(_) => {
                 tracing::trace!(replica.uuid = %spec.uuid, "Replica not found. Constructing default replica topology");
                 ReplicaTopology::default()
@@ -11,7 +11,10 @@ use crate::controller::{
     wrapper::PoolWrapper,
 };
 use agents::errors::SvcError;
-use common_lib::types::v0::store::{nexus::NexusSpec, volume::VolumeSpec};
+use common_lib::types::v0::{
+    store::{nexus::NexusSpec, volume::VolumeSpec},
+    transport::{CreateReplica, PoolId},
+};
 
 /// Return a list of pre sorted pools to be used by a volume
 pub(crate) async fn volume_pool_candidates(
@@ -26,6 +29,39 @@ pub(crate) async fn volume_pool_candidates(
         .collect()
 }
 
+/// Check if the given replica candidate is still suitable for the volume, now that the replicas
+/// created before it are taken into account.
+/// Only the topology spread depends on the other volume replicas, and so the candidate is only
+/// checked again if the volume topology spreads the replicas.
+pub(crate) async fn volume_spread_candidate(
+    registry: &Registry,
+    volume: &VolumeSpec,
+    candidate: &CreateReplica,
+) -> bool {
+    match &volume.topology {
+        Some(topology) if topology.spread() => volume_pool_candidates(volume, registry)
+            .await
+            .iter()
+            .any(|pool| pool.id == candidate.pool_id),
+        _ => true,
+    }
+}
+
+/// Return a list of pre sorted pools to be used by a volume which is cloned from a snapshot
+/// whose replica snapshots live on the given `source_pools`
+pub(crate) async fn volume_clone_pool_candidates(
+    request: impl Into<GetSuitablePools>,
+    registry: &Registry,
+    source_pools: &[PoolId],
+) -> Vec<PoolWrapper> {
+    volume::AddVolumeReplica::builder_for_clone(request, registry, source_pools)
+        .await
+        .collect()
+        .into_iter()
+        .map(|e| e.collect())
+        .collect()
+}
+
 /// Return a volume child candidate to be removed from a volume
 /// This list includes healthy and non_healthy candidates, so care must be taken to
 /// make sure we don't remove "too many healthy" candidates and make the volume degraded
@@ -2,8 +2,8 @@ use crate::controller::{
     registry::Registry,
     resources::{
         operations::{
-            ResourceLifecycle, ResourcePublishing, ResourceReplicas, ResourceSharing,
-            ResourceShutdownOperations,
+            ResourceLifecycle, ResourcePublishing, ResourceQos, ResourceReplicas, ResourceResize,
+            ResourceSharing, ResourceShutdownOperations,
         },
         operations_helper::ResourceSpecsLocked,
         OperationGuardArc,
@@ -11,12 +11,17 @@ use crate::controller::{
 };
 use agents::errors::SvcError;
 use common_lib::{
-    transport_api::{v0::Volumes, ReplyError},
+    transport_api::{
+        v0::{VolumeSnapshots, Volumes},
+        ReplyError, ResourceKind,
+    },
     types::v0::{
-        store::volume::VolumeSpec,
+        store::{snapshot::VolumeSnapshotSpec, volume::VolumeSpec},
         transport::{
-            CreateVolume, DestroyShutdownTargets, DestroyVolume, Filter, PublishVolume,
-            RepublishVolume, SetVolumeReplica, ShareVolume, UnpublishVolume, UnshareVolume, Volume,
+            CreateVolume, CreateVolumeSnapshot, DestroyShutdownTargets, DestroyVolume,
+            DestroyVolumeSnapshot, Filter, GetVolumeSnapshots, PublishVolume, RepublishVolume,
+            ResizeVolume, SetVolumeQos, SetVolumeReplica, ShareVolume, UnpublishVolume,
+            UnshareVolume, Volume, VolumeSnapshot, VolumeSnapshotState,
         },
     },
 };
@@ -24,9 +29,11 @@ use grpc::{
     context::Context,
     operations::{
         volume::traits::{
-            CreateVolumeInfo, DestroyShutdownTargetsInfo, DestroyVolumeInfo, PublishVolumeInfo,
-            RepublishVolumeInfo, SetVolumeReplicaInfo, ShareVolumeInfo, UnpublishVolumeInfo,
-            UnshareVolumeInfo, VolumeOperations,
+            CreateVolumeInfo, CreateVolumeSnapshotInfo, DestroyShutdownTargetsInfo,
+            DestroyVolumeInfo, DestroyVolumeSnapshotInfo, GetVolumeSnapshotsInfo,
+            PublishVolumeInfo, RepublishVolumeInfo, ResizeVolumeInfo, SetVolumeQosInfo,
+            SetVolumeReplicaInfo, ShareVolumeInfo, UnpublishVolumeInfo, UnshareVolumeInfo,
+            VolumeOperations,
         },
         Pagination,
     },
@@ -149,6 +156,30 @@ impl VolumeOperations for Service {
         Ok(volume)
     }
 
+    async fn resize(
+        &self,
+        req: &dyn ResizeVolumeInfo,
+        _ctx: Option<Context>,
+    ) -> Result<Volume, ReplyError> {
+        let resize_volume = req.into();
+        let service = self.clone();
+        let volume =
+            Context::spawn(async move { service.resize_volume(&resize_volume).await }).await??;
+        Ok(volume)
+    }
+
+    async fn set_qos(
+        &self,
+        req: &dyn SetVolumeQosInfo,
+        _ctx: Option<Context>,
+    ) -> Result<Volume, ReplyError> {
+        let set_qos = req.into();
+        let service = self.clone();
+        let volume =
+            Context::spawn(async move { service.set_volume_qos(&set_qos).await }).await??;
+        Ok(volume)
+    }
+
     async fn probe(&self, _ctx: Option<Context>) -> Result<bool, ReplyError> {
         return Ok(true);
     }
@@ -164,6 +195,40 @@ impl VolumeOperations for Service {
             .await??;
         Ok(())
     }
+
+    async fn create_snapshot(
+        &self,
+        req: &dyn CreateVolumeSnapshotInfo,
+        _ctx: Option<Context>,
+    ) -> Result<VolumeSnapshot, ReplyError> {
+        let create_snapshot = req.into();
+        let service = self.clone();
+        let snapshot =
+            Context::spawn(async move { service.create_snapshot(&create_snapshot).await })
+                .await??;
+        Ok(snapshot)
+    }
+
+    async fn destroy_snapshot(
+        &self,
+        req: &dyn DestroyVolumeSnapshotInfo,
+        _ctx: Option<Context>,
+    ) -> Result<(), ReplyError> {
+        let destroy_snapshot = req.into();
+        let service = self.clone();
+        Context::spawn(async move { service.destroy_snapshot(&destroy_snapshot).await }).await??;
+        Ok(())
+    }
+
+    async fn get_snapshots(
+        &self,
+        req: &dyn GetVolumeSnapshotsInfo,
+        ignore_notfound: bool,
+        _ctx: Option<Context>,
+    ) -> Result<VolumeSnapshots, ReplyError> {
+        let snapshots = self.get_snapshots(&req.into(), ignore_notfound).await?;
+        Ok(snapshots)
+    }
 }
 
 impl Service {
@@ -218,6 +283,12 @@ impl Service {
     /// Create a volume using the given parameters.
     #[tracing::instrument(level = "info", skip(self), err, fields(volume.uuid = %request.uuid))]
     pub(super) async fn create_volume(&self, request: &CreateVolume) -> Result<Volume, SvcError> {
+        if request.policy.qos.is_some() {
+            check_qos_supported()?;
+        }
+        if request.encryption.is_some() {
+            check_encryption_supported()?;
+        }
         OperationGuardArc::<VolumeSpec>::create(&self.registry, request).await?;
         self.registry.volume(&request.uuid).await
     }
@@ -294,4 +365,112 @@ impl Service {
         volume.set_replica(&self.registry, request).await?;
         self.registry.volume(&request.uuid).await
     }
+
+    /// Resize volume
+    #[tracing::instrument(level = "info", skip(self), err, fields(volume.uuid = %request.uuid))]
+    pub(super) async fn resize_volume(&self, request: &ResizeVolume) -> Result<Volume, SvcError> {
+        let mut volume = self.specs().volume(&request.uuid).await?;
+        volume.resize(&self.registry, request).await
+    }
+
+    /// Set the volume QoS limits.
+    #[tracing::instrument(level = "info", skip(self), err, fields(volume.uuid = %request.uuid))]
+    pub(super) async fn set_volume_qos(&self, request: &SetVolumeQos) -> Result<Volume, SvcError> {
+        check_qos_supported()?;
+        let mut volume = self.specs().volume(&request.uuid).await?;
+        volume.set_qos(&self.registry, request).await
+    }
+
+    /// Create a volume snapshot using the given parameters.
+    #[tracing::instrument(level = "info", skip(self), err, fields(volume.uuid = %request.source_id, snapshot.uuid = %request.snap_id))]
+    pub(super) async fn create_snapshot(
+        &self,
+        request: &CreateVolumeSnapshot,
+    ) -> Result<VolumeSnapshot, SvcError> {
+        OperationGuardArc::<VolumeSnapshotSpec>::create(&self.registry, request).await?;
+        let spec = self.specs().snapshot_clone(&request.snap_id)?;
+        let state = VolumeSnapshotState::from(&spec);
+        Ok(VolumeSnapshot::new(spec, state))
+    }
+
+    /// Destroy a volume snapshot using the given parameters.
+    #[tracing::instrument(level = "info", skip(self), err, fields(snapshot.uuid = %request.snap_id))]
+    pub(super) async fn destroy_snapshot(
+        &self,
+        request: &DestroyVolumeSnapshot,
+    ) -> Result<(), SvcError> {
+        let mut snapshot = self.specs().snapshot(&request.snap_id).await?;
+        snapshot.destroy(&self.registry, request).await
+    }
+
+    /// Get volume snapshots, optionally filtered by the source volume and the snapshot uuid.
+    #[tracing::instrument(level = "info", skip(self), err)]
+    pub(super) async fn get_snapshots(
+        &self,
+        request: &GetVolumeSnapshots,
+        ignore_notfound: bool,
+    ) -> Result<VolumeSnapshots, SvcError> {
+        let specs = match &request.snap_id {
+            Some(snap_id) => match self.specs().snapshot_clone(snap_id) {
+                Ok(spec) if request.source_id.is_none() => vec![spec],
+                Ok(spec) if Some(spec.source_id()) == request.source_id.as_ref() => vec![spec],
+                Ok(_) | Err(SvcError::SnapshotNotFound { .. }) if ignore_notfound => vec![],
+                Ok(_) => {
+                    return Err(SvcError::SnapshotNotFound {
+                        snap_id: snap_id.to_string(),
+                    })
+                }
+                Err(error) => return Err(error),
+            },
+            None => match &request.source_id {
+                Some(volume_id) => {
+                    // make sure the volume exists, unless we don't care about it
+                    match self.specs().volume_clone(volume_id) {
+                        Ok(_) => {}
+                        Err(SvcError::VolumeNotFound { .. }) if ignore_notfound => {}
+                        Err(error) => return Err(error),
+                    }
+                    self.specs().volume_snapshots(volume_id)
+                }
+                None => self.specs().snapshots(),
+            },
+        };
+
+        Ok(VolumeSnapshots(
+            specs
+                .into_iter()
+                .map(|spec| {
+                    let state = VolumeSnapshotState::from(&spec);
+                    VolumeSnapshot::new(spec, state)
+                })
+                .collect(),
+        ))
+    }
+}
+
+/// Nexus QoS limits are not supported by the io-engine api which we're built against, unless the
+/// `nexus-qos` feature is enabled.
+/// Volumes with QoS limits are rejected up front rather than failing to create or update their
+/// targets later on.
+fn check_qos_supported() -> Result<(), SvcError> {
+    match cfg!(feature = "nexus-qos") {
+        true => Ok(()),
+        false => Err(SvcError::Unsupported {
+            kind: ResourceKind::Volume,
+            operation: "Setting volume QoS limits".to_string(),
+        }),
+    }
+}
+
+/// Encryption at rest is not supported by the io-engine api which we're built against, unless the
+/// `replica-encryption` feature is enabled.
+/// Encrypted volumes are rejected up front rather than failing to create their replicas.
+fn check_encryption_supported() -> Result<(), SvcError> {
+    match cfg!(feature = "replica-encryption") {
+        true => Ok(()),
+        false => Err(SvcError::Unsupported {
+            kind: ResourceKind::Volume,
+            operation: "Encryption at rest".to_string(),
+        }),
+    }
 }
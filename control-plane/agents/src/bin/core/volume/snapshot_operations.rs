@@ -0,0 +1,248 @@
+use crate::controller::{
+    registry::Registry,
+    resources::{
+        operations::ResourceLifecycle,
+        operations_helper::{GuardedOperationsHelper, OperationSequenceGuard, ResourceSpecsLocked},
+        OperationGuardArc, TraceStrLog,
+    },
+    wrapper::ClientOps,
+};
+use agents::errors::SvcError;
+use common_lib::{
+    transport_api::{ErrorChain, ResourceKind},
+    types::v0::{
+        store::{
+            replica::ReplicaSpec,
+            snapshot::{ReplicaSnapshot, VolumeSnapshotSpec},
+            volume::{VolumeSpec, VolumeTarget},
+        },
+        transport::{
+            CreateNexusSnapshot, CreateReplicaSnapshot, CreateVolumeSnapshot,
+            DestroyReplicaSnapshot, DestroyVolumeSnapshot, NexusSnapshotReplica, SnapshotId,
+        },
+    },
+};
+
+#[async_trait::async_trait]
+impl ResourceLifecycle for OperationGuardArc<VolumeSnapshotSpec> {
+    type Create = CreateVolumeSnapshot;
+    type CreateOutput = Self;
+    type Destroy = DestroyVolumeSnapshot;
+
+    /// Create a volume snapshot based on the given `CreateVolumeSnapshot` request.
+    /// The volume is guarded for the duration of the snapshot so that its replica set cannot
+    /// change underneath us.
+    async fn create(
+        registry: &Registry,
+        request: &Self::Create,
+    ) -> Result<Self::CreateOutput, SvcError> {
+        let specs = registry.specs();
+        let volume = specs.volume(&request.source_id).await?;
+        let volume_spec = volume.as_ref().clone();
+        if !volume_spec.status.created() {
+            return Err(SvcError::NotReady {
+                kind: ResourceKind::Volume,
+                id: volume_spec.uuid.to_string(),
+            });
+        }
+
+        let snapshot = specs
+            .get_or_create_snapshot(request)
+            .operation_guard_wait()
+            .await?;
+        snapshot.start_create(registry, request).await?;
+
+        let result = snapshot_volume_replicas(registry, &volume_spec, request).await;
+        let replica_snapshots = snapshot.validate_create_step(registry, result).await?;
+
+        {
+            let mut spec = snapshot.lock();
+            spec.timestamp = Some(chrono::Utc::now());
+            spec.size = volume_spec.size;
+            spec.replica_snapshots = replica_snapshots;
+            spec.encryption = volume_spec.encryption.clone();
+        }
+
+        snapshot.complete_create(Ok(()), registry).await?;
+        Ok(snapshot)
+    }
+
+    /// Destroy a volume snapshot based on the given `DestroyVolumeSnapshot` request.
+    async fn destroy(
+        &mut self,
+        registry: &Registry,
+        request: &Self::Destroy,
+    ) -> Result<(), SvcError> {
+        if let Some(source_id) = &request.source_id {
+            if source_id != self.as_ref().source_id() {
+                return Err(SvcError::SnapshotNotFound {
+                    snap_id: request.snap_id.to_string(),
+                });
+            }
+        }
+        self.start_destroy(registry).await?;
+
+        let mut result = Ok(());
+        for replica_snapshot in self.as_ref().replica_snapshots.clone() {
+            if let Err(error) = destroy_replica_snapshot(registry, &replica_snapshot).await {
+                self.error(&format!(
+                    "Failed to destroy replica snapshot {:?}, error: {}",
+                    replica_snapshot,
+                    error.full_string()
+                ));
+                result = Err(error);
+            }
+        }
+
+        self.complete_destroy(result, registry).await
+    }
+}
+
+/// Take a snapshot of every healthy replica of the given volume.
+/// When the volume is published the snapshots are taken through its nexus, which pauses the I/O
+/// for the duration, making the replica snapshots crash-consistent with each other.
+/// Otherwise there is no I/O to the replicas and each one is snapshotted directly.
+async fn snapshot_volume_replicas(
+    registry: &Registry,
+    volume: &VolumeSpec,
+    request: &CreateVolumeSnapshot,
+) -> Result<Vec<ReplicaSnapshot>, SvcError> {
+    let replicas = registry.specs().volume_replicas_cln(&volume.uuid);
+    let candidates = match registry
+        .nexus_info(Some(&volume.uuid), volume.health_info_id(), false)
+        .await?
+    {
+        Some(info) => replicas
+            .into_iter()
+            .filter(|r| info.is_replica_healthy(&r.uuid))
+            .collect::<Vec<_>>(),
+        // the volume has never been published, so all replicas are equally up to date
+        None => replicas,
+    };
+    if candidates.is_empty() {
+        return Err(SvcError::NoHealthyReplicas {
+            id: volume.uuid.to_string(),
+        });
+    }
+    let candidates = candidates
+        .into_iter()
+        .map(|replica| (replica, SnapshotId::new()))
+        .collect::<Vec<_>>();
+
+    let snapshots = match volume.target() {
+        Some(target) => snapshot_nexus(registry, volume, target, &candidates, request).await?,
+        None => snapshot_replicas(registry, volume, &candidates, request).await,
+    };
+    if snapshots.is_empty() {
+        return Err(SvcError::NoHealthyReplicas {
+            id: volume.uuid.to_string(),
+        });
+    }
+    Ok(snapshots)
+}
+
+/// Snapshot the given replicas through the volume nexus.
+async fn snapshot_nexus(
+    registry: &Registry,
+    volume: &VolumeSpec,
+    target: &VolumeTarget,
+    candidates: &[(ReplicaSpec, SnapshotId)],
+    request: &CreateVolumeSnapshot,
+) -> Result<Vec<ReplicaSnapshot>, SvcError> {
+    let node_wrapper = registry.node_wrapper(target.node()).await?;
+    let status = node_wrapper
+        .create_nexus_snapshot(&CreateNexusSnapshot {
+            node: target.node().clone(),
+            nexus: target.nexus().clone(),
+            replicas: candidates
+                .iter()
+                .map(|(replica, snap_id)| NexusSnapshotReplica {
+                    replica_id: replica.uuid.clone(),
+                    snap_id: snap_id.clone(),
+                })
+                .collect(),
+            snap_name: request.snap_id.to_string(),
+            entity_id: volume.uuid.to_string(),
+            txn_id: request.snap_id.to_string(),
+        })
+        .await?;
+
+    for (replica, code) in &status.replicas_failed {
+        tracing::warn!(replica.uuid=%replica, status.code=%code,
+            "Failed to snapshot volume replica through the nexus"
+        );
+    }
+    Ok(candidates
+        .iter()
+        .filter(|(replica, _)| status.replicas_done.contains(&replica.uuid))
+        .map(|(replica, snap_id)| {
+            ReplicaSnapshot::new(
+                snap_id.clone(),
+                replica.uuid.clone(),
+                replica.pool.pool_name().clone(),
+            )
+        })
+        .collect())
+}
+
+/// Snapshot each of the given replicas directly.
+/// Only safe when the volume is not published as otherwise the snapshots are not consistent.
+async fn snapshot_replicas(
+    registry: &Registry,
+    volume: &VolumeSpec,
+    candidates: &[(ReplicaSpec, SnapshotId)],
+    request: &CreateVolumeSnapshot,
+) -> Vec<ReplicaSnapshot> {
+    let mut snapshots = Vec::with_capacity(candidates.len());
+    for (replica, snap_id) in candidates {
+        let node = match ResourceSpecsLocked::replica_node(registry, replica).await {
+            Some(node) => node,
+            None => continue,
+        };
+        let create = CreateReplicaSnapshot {
+            node,
+            replica_id: replica.uuid.clone(),
+            snap_id: snap_id.clone(),
+            snap_name: request.snap_id.to_string(),
+            entity_id: volume.uuid.to_string(),
+            txn_id: request.snap_id.to_string(),
+        };
+        let result = match registry.node_wrapper(&create.node).await {
+            Ok(node_wrapper) => node_wrapper.create_repl_snapshot(&create).await,
+            Err(error) => Err(error),
+        };
+        match result {
+            Ok(_) => snapshots.push(ReplicaSnapshot::new(
+                snap_id.clone(),
+                replica.uuid.clone(),
+                replica.pool.pool_name().clone(),
+            )),
+            Err(error) => {
+                tracing::warn!(replica.uuid=%replica.uuid, error=%error.full_string(),
+                    "Failed to snapshot volume replica"
+                );
+            }
+        }
+    }
+    snapshots
+}
+
+/// Destroy the given replica snapshot from the node where its pool currently lives.
+async fn destroy_replica_snapshot(
+    registry: &Registry,
+    replica_snapshot: &ReplicaSnapshot,
+) -> Result<(), SvcError> {
+    let node = ResourceSpecsLocked::pool_node(registry, &replica_snapshot.pool_id)
+        .await
+        .ok_or(SvcError::PoolNotFound {
+            pool_id: replica_snapshot.pool_id.clone(),
+        })?;
+    let node_wrapper = registry.node_wrapper(&node).await?;
+    node_wrapper
+        .destroy_repl_snapshot(&DestroyReplicaSnapshot {
+            node,
+            pool_id: replica_snapshot.pool_id.clone(),
+            snap_id: replica_snapshot.uuid.clone(),
+        })
+        .await
+}
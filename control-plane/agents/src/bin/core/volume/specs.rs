@@ -18,6 +18,7 @@ use crate::{
             },
             ResourceFilter,
         },
+        wrapper::ClientOps,
     },
     nexus::scheduling::target_node_candidate,
     volume::scheduling,
@@ -37,14 +38,18 @@ use common_lib::{
             nexus::{NexusSpec, ReplicaUri},
             nexus_child::NexusChild,
             nexus_persistence::NexusInfoKey,
+            rebuild::RebuildReason,
             replica::ReplicaSpec,
+            snapshot::{VolumeSnapshotOperation, VolumeSnapshotSpec},
             volume::{VolumeOperation, VolumeSpec},
             SpecStatus, SpecTransaction,
         },
         transport::{
-            AddNexusReplica, ChildUri, CreateNexus, CreateReplica, CreateVolume, DestroyReplica,
-            Nexus, NodeId, PoolId, Protocol, RemoveNexusReplica, Replica, ReplicaId, ReplicaName,
-            ReplicaOwners, Volume, VolumeId, VolumeShareProtocol, VolumeState, VolumeStatus,
+            AddNexusReplica, ChildUri, CreateNexus, CreateReplica, CreateSnapshotClone,
+            CreateVolume, CreateVolumeSnapshot, DestroyReplica, Nexus, NexusId, NodeId, PoolId,
+            Protocol, RemoveNexusReplica, Replica, ReplicaId, ReplicaName, ReplicaOwners,
+            SetNexusQos, SnapshotId, Volume, VolumeId, VolumeQos, VolumeShareProtocol,
+            VolumeSnapshotState, VolumeState, VolumeStatus,
         },
     },
 };
@@ -57,7 +62,7 @@ use common_lib::types::v0::{
 };
 use grpc::operations::volume::traits::PublishVolumeInfo;
 use snafu::OptionExt;
-use std::convert::From;
+use std::{collections::HashMap, convert::From};
 
 /// Select a replica to be removed from the volume
 pub(crate) async fn volume_replica_remove_candidate(
@@ -81,6 +86,30 @@ pub(crate) async fn volume_replica_remove_candidate(
         })
 }
 
+/// Select the given replica to be removed from the volume, once it's been moved off its pool
+/// It is not selected if the volume's redundancy would be compromised by its removal
+pub(crate) async fn volume_rebalance_remove_candidate(
+    spec: &VolumeSpec,
+    state: &VolumeState,
+    replica: &ReplicaId,
+    registry: &Registry,
+) -> Result<ReplicaItem, SvcError> {
+    let mut candidates = scheduling::volume_replica_remove_candidates(
+        &GetChildForRemoval::new(spec, state, false),
+        registry,
+    )
+    .await?
+    .candidates();
+
+    spec.trace_span(|| tracing::trace!("Volume Rebalance removal candidates: {:?}", candidates));
+
+    candidates
+        .next_of(replica)
+        .context(errors::ReplicaRemovalNoCandidates {
+            id: spec.uuid_str(),
+        })
+}
+
 /// Get replica candidates to be removed from the volume
 /// This list includes healthy and non_healthy candidates, so care must be taken to
 /// make sure we don't remove "too many healthy" candidates
@@ -176,6 +205,7 @@ pub(crate) async fn volume_replica_candidates(
                 managed: true,
                 owners: ReplicaOwners::from_volume(&request.uuid),
                 allowed_hosts: vec![],
+                encryption: request.encryption.clone(),
             }
         })
         .collect::<Vec<_>>())
@@ -206,6 +236,90 @@ pub(crate) async fn create_volume_replicas(
     }
 }
 
+/// Return a list of appropriate requests which can be used to create replica clones of the given
+/// snapshot when creating a volume from it.
+/// A replica clone lives on the same pool as its replica snapshot and it's as large as the
+/// snapshot, so it might have to be resized up to the requested volume size later.
+pub(crate) async fn create_volume_clone_replicas(
+    registry: &Registry,
+    request: &CreateVolume,
+    snapshot: &VolumeSnapshotSpec,
+) -> Result<Vec<CreateSnapshotClone>, SvcError> {
+    if !snapshot.status.created() {
+        return Err(SvcError::NotReady {
+            kind: ResourceKind::VolumeSnapshot,
+            id: snapshot.uuid.to_string(),
+        });
+    }
+    if request.size < snapshot.size {
+        return Err(SvcError::CloneSourceSize {
+            volume_id: request.uuid.to_string(),
+            snap_id: snapshot.uuid.to_string(),
+            snapshot_size: snapshot.size,
+            requested_size: request.size,
+        });
+    }
+    // the clones share the snapshot data, which is encrypted like its source volume was
+    if snapshot.encryption != request.encryption {
+        return Err(SvcError::CloneSourceEncryption {
+            volume_id: request.uuid.to_string(),
+            snap_id: snapshot.uuid.to_string(),
+            source_id: snapshot.source_id.to_string(),
+        });
+    }
+    if !request.allowed_nodes().is_empty()
+        && request.replicas > request.allowed_nodes().len() as u64
+    {
+        return Err(SvcError::InvalidArguments {});
+    }
+
+    let source_pools = snapshot
+        .replica_snapshots
+        .iter()
+        .map(|r| r.pool_id.clone())
+        .collect::<Vec<_>>();
+    let pools = scheduling::volume_clone_pool_candidates(request, registry, &source_pools).await;
+
+    snapshot.trace(&format!(
+        "Clone pool candidates for volume '{}': {:?}",
+        request.uuid,
+        pools.iter().map(|p| p.state()).collect::<Vec<_>>()
+    ));
+
+    let clones = pools
+        .iter()
+        .filter_map(|p| {
+            let replica_snapshot = snapshot
+                .replica_snapshots
+                .iter()
+                .find(|r| r.pool_id == p.id)?;
+            let replica_uuid = ReplicaId::new();
+            let replica = CreateReplica {
+                node: p.node.clone(),
+                name: Some(ReplicaName::new(&replica_uuid, Some(&request.uuid))),
+                uuid: replica_uuid,
+                pool_id: p.id.clone(),
+                pool_uuid: None,
+                size: snapshot.size,
+                thin: true,
+                share: Protocol::None,
+                managed: true,
+                owners: ReplicaOwners::from_volume(&request.uuid),
+                allowed_hosts: vec![],
+                encryption: request.encryption.clone(),
+            };
+            Some(CreateSnapshotClone::new(&replica_snapshot.uuid, replica))
+        })
+        .collect::<Vec<_>>();
+
+    // any replicas which cannot be cloned are rebuilt from the clones
+    if clones.is_empty() {
+        Err(SvcError::from(NotEnough::OfPools { have: 0, need: 1 }))
+    } else {
+        Ok(clones)
+    }
+}
+
 /// Get all usable healthy replicas for volume nexus creation
 /// If no usable replica is available, return an error
 pub(crate) async fn healthy_volume_replicas(
@@ -257,6 +371,11 @@ impl ResourceSpecs {
 
         PaginatedResult::new(self.volumes.paginate(offset, length), last_result)
     }
+
+    /// Gets all VolumeSnapshotSpec's
+    pub(crate) fn snapshots(&self) -> Vec<VolumeSnapshotSpec> {
+        self.snapshots.values().map(|v| v.lock().clone()).collect()
+    }
 }
 impl ResourceSpecsLocked {
     /// Get the resourced VolumeSpec for the given volume `id`, if any exists
@@ -579,11 +698,19 @@ impl ResourceSpecsLocked {
         registry: &Registry,
         status: &VolumeState,
         replica: Replica,
+        reason: RebuildReason,
     ) -> Result<(), SvcError> {
         if let Some(nexus) = &status.target {
             let mut nexus_guard = self.nexus(&nexus.uuid).await?;
-            self.attach_replica_to_nexus(registry, &mut nexus_guard, &status.uuid, nexus, &replica)
-                .await
+            self.attach_replica_to_nexus(
+                registry,
+                &mut nexus_guard,
+                &status.uuid,
+                nexus,
+                &replica,
+                reason,
+            )
+            .await
         } else {
             Ok(())
         }
@@ -614,7 +741,13 @@ impl ResourceSpecsLocked {
             .await?;
 
         // Add the newly created replica to the nexus, if it's up
-        let result = self.add_replica_to_volume(registry, &state, replica).await;
+        let reason = match spec_clone.operation.as_ref().map(|op| &op.operation) {
+            Some(VolumeOperation::StartRebalance(_)) => RebuildReason::Rebalance,
+            _ => RebuildReason::ReplicaIncrease,
+        };
+        let result = self
+            .add_replica_to_volume(registry, &state, replica, reason)
+            .await;
         volume.complete_update(registry, result, spec_clone).await?;
 
         registry.volume(&state.uuid).await
@@ -703,6 +836,128 @@ impl ResourceSpecsLocked {
         registry.volume(&state.uuid).await
     }
 
+    /// Start moving the given replica off its pool, to rebalance the pool usage
+    /// Creates an extra data replica, which is rebuilt by the volume nexus, without changing the
+    /// replica count of the volume.
+    /// The given replica is removed once the volume is healthy again, see
+    /// `complete_volume_rebalance`.
+    pub(crate) async fn start_volume_rebalance(
+        &self,
+        volume: &mut OperationGuardArc<VolumeSpec>,
+        registry: &Registry,
+        replica: &ReplicaId,
+    ) -> Result<Volume, SvcError> {
+        let state = registry.volume_state(volume.uuid()).await?;
+        let operation = VolumeOperation::StartRebalance(replica.clone());
+        let spec_clone = volume.start_update(registry, &state, operation).await?;
+
+        self.increase_volume_replica(volume, registry, state, spec_clone)
+            .await
+    }
+
+    /// Complete moving the given replica off its pool
+    /// Removes the given replica from the volume nexus and destroys it.
+    /// If the volume no longer has a target, the new replica can no longer be rebuilt, so unless
+    /// it had been rebuilt already, the rebalance is rolled back by removing the extra replica.
+    pub(crate) async fn complete_volume_rebalance(
+        &self,
+        volume: &mut OperationGuardArc<VolumeSpec>,
+        registry: &Registry,
+        replica: &ReplicaId,
+    ) -> Result<Volume, SvcError> {
+        let state = registry.volume_state(volume.uuid()).await?;
+        let operation = VolumeOperation::CompleteRebalance(replica.clone());
+        let spec_clone = volume.start_update(registry, &state, operation).await?;
+
+        if self.replica_rsc(replica).is_none() {
+            // The replica has already been removed from the volume, eg: it had faulted.
+            volume.complete_update(registry, Ok(()), spec_clone).await?;
+            return registry.volume(&state.uuid).await;
+        }
+
+        // Can fail if the new replica is not yet healthy, so fail gracefully
+        let result =
+            match volume_rebalance_remove_candidate(&spec_clone, &state, replica, registry).await {
+                Err(ReplicaRemovalNoCandidates { .. }) if state.target.is_none() => {
+                    volume_replica_remove_candidate(&spec_clone, &state, registry).await
+                }
+                result => result,
+            };
+        let remove = volume
+            .validate_update_step(registry, result, &spec_clone)
+            .await?;
+
+        // Remove the replica from its nexus (where it exists as a child)
+        let result = self
+            .remove_volume_child_candidate(&spec_clone, registry, &remove)
+            .await;
+        volume
+            .validate_update_step(registry, result, &spec_clone)
+            .await?;
+
+        // now remove the replica from the pool
+        let result = self
+            .destroy_replica_spec(
+                registry,
+                remove.spec(),
+                ReplicaOwners::from_volume(&state.uuid),
+            )
+            .await;
+
+        volume.complete_update(registry, result, spec_clone).await?;
+        registry.volume(&state.uuid).await
+    }
+
+    /// Move the given replica of an unpublished volume off its pool
+    /// There's no volume target to rebuild a replacement replica, so the replacement is created
+    /// and the given replica is removed straight away, provided another healthy replica is left
+    /// for the replacement to be rebuilt from once the volume is published.
+    pub(crate) async fn move_unpublished_volume_replica(
+        &self,
+        volume: &mut OperationGuardArc<VolumeSpec>,
+        registry: &Registry,
+        replica: &ReplicaId,
+    ) -> Result<Volume, SvcError> {
+        let nexus_info = registry
+            .nexus_info(Some(volume.uuid()), volume.as_ref().health_info_id(), true)
+            .await?;
+        let healthy_left = self
+            .volume_replicas(volume.uuid())
+            .iter()
+            .map(|spec| spec.lock().uuid.clone())
+            .filter(|uuid| uuid != replica)
+            .any(|uuid| {
+                nexus_info
+                    .as_ref()
+                    .map_or(true, |info| info.is_replica_healthy(&uuid))
+            });
+        if !healthy_left {
+            return Err(SvcError::ReplicaRemovalNoCandidates {
+                id: volume.as_ref().uuid_str(),
+            });
+        }
+
+        self.start_volume_rebalance(volume, registry, replica)
+            .await?;
+
+        let state = registry.volume_state(volume.uuid()).await?;
+        let operation = VolumeOperation::CompleteRebalance(replica.clone());
+        let spec_clone = volume.start_update(registry, &state, operation).await?;
+
+        let result = match self.replica_rsc(replica) {
+            Some(spec) => {
+                let spec = spec.lock().clone();
+                self.destroy_replica_spec(registry, &spec, ReplicaOwners::from_volume(&state.uuid))
+                    .await
+            }
+            // The replica has already been removed from the volume
+            None => Ok(()),
+        };
+
+        volume.complete_update(registry, result, spec_clone).await?;
+        registry.volume(&state.uuid).await
+    }
+
     /// Make the replica accessible on the specified `NodeId`
     /// This means the replica might have to be shared/unshared so it can be open through
     /// the correct protocol (loopback locally, and nvmf remotely)
@@ -770,7 +1025,7 @@ impl ResourceSpecsLocked {
                 nodes.push(item.state().node.clone());
             }
         }
-        nexus_replicas.truncate(vol_spec.num_replicas as usize);
+        nexus_replicas.truncate(vol_spec.effective_num_replicas() as usize);
 
         // Create the nexus on the requested node
         let (guard, nexus) = OperationGuardArc::<NexusSpec>::create(
@@ -783,7 +1038,8 @@ impl ResourceSpecsLocked {
                 true,
                 Some(&vol_spec.uuid),
                 Some(target_config.config().clone()),
-            ),
+            )
+            .with_qos(vol_spec.policy.qos.as_ref()),
         )
         .await?;
 
@@ -800,8 +1056,22 @@ impl ResourceSpecsLocked {
         Ok((guard, nexus))
     }
 
+    /// Set the volume `qos` limits on the volume target nexus `nexus_id` on the given `node`.
+    pub(crate) async fn set_target_qos(
+        registry: &Registry,
+        node: &NodeId,
+        nexus_id: &NexusId,
+        qos: &VolumeQos,
+    ) -> Result<(), SvcError> {
+        let node_wrapper = registry.node_wrapper(node).await?;
+        node_wrapper
+            .set_nexus_qos(&SetNexusQos::new(node, nexus_id, qos))
+            .await
+    }
+
     /// Attach the specified replica to the volume nexus
     /// The replica might need to be shared/unshared so it can be opened by the nexus
+    /// The start of its rebuild is recorded in the volume's rebuild history with the given reason.
     pub(crate) async fn attach_replica_to_nexus(
         &self,
         registry: &Registry,
@@ -809,6 +1079,7 @@ impl ResourceSpecsLocked {
         volume_uuid: &VolumeId,
         nexus: &Nexus,
         replica: &Replica,
+        reason: RebuildReason,
     ) -> Result<(), SvcError> {
         // Adding a replica to a nexus will initiate a rebuild.
         // First check that we are able to start a rebuild.
@@ -830,7 +1101,14 @@ impl ResourceSpecsLocked {
             )
             .await
         {
-            Ok(_) => Ok(()),
+            Ok(_) => {
+                // a replica kept for a partial rebuild is no longer faulted once it's re-added
+                registry.forget_faulted_replica(&replica.uuid);
+                registry
+                    .record_rebuild_start(volume_uuid, &nexus.uuid, &replica.uuid, reason)
+                    .await;
+                Ok(())
+            }
             Err(error) => {
                 if let Some(replica) = self.replica_rsc(&replica.uuid) {
                     let mut replica = replica.lock();
@@ -951,7 +1229,7 @@ impl ResourceSpecsLocked {
         let volume_children = self
             .volume_replicas(&vol_spec_clone.uuid)
             .len()
-            .min(vol_spec_clone.num_replicas as usize);
+            .min(vol_spec_clone.effective_num_replicas() as usize);
         let mut nexus_children = nexus_spec_clone.children.len();
 
         let replicas =
@@ -963,7 +1241,14 @@ impl ResourceSpecsLocked {
                 break;
             }
             match self
-                .attach_replica_to_nexus(registry, nexus, &vol_uuid, nexus_state, replica.state())
+                .attach_replica_to_nexus(
+                    registry,
+                    nexus,
+                    &vol_uuid,
+                    nexus_state,
+                    replica.state(),
+                    RebuildReason::Replacement,
+                )
                 .await
             {
                 Ok(_) => {
@@ -998,7 +1283,7 @@ impl ResourceSpecsLocked {
     ) -> Result<(), SvcError> {
         let vol_spec_clone = volume.lock().clone();
         let nexus_spec_clone = nexus.lock().clone();
-        let volume_children = vol_spec_clone.num_replicas as usize;
+        let volume_children = vol_spec_clone.effective_num_replicas() as usize;
         let mut nexus_replica_children =
             nexus_spec_clone
                 .children
@@ -1127,6 +1412,96 @@ impl ResourceSpecsLocked {
         }
     }
 
+    /// Get the resourced VolumeSnapshotSpec for the given snapshot `id`, if any exists.
+    pub(crate) fn snapshot_rsc(
+        &self,
+        id: &SnapshotId,
+    ) -> Option<ResourceMutex<VolumeSnapshotSpec>> {
+        let specs = self.read();
+        specs.snapshots.get(id).cloned()
+    }
+
+    /// Get a copy of the VolumeSnapshotSpec for the snapshot with the given ID.
+    pub(crate) fn snapshot_clone(&self, id: &SnapshotId) -> Result<VolumeSnapshotSpec, SvcError> {
+        match self.snapshot_rsc(id) {
+            Some(locked_spec) => {
+                let spec = locked_spec.lock();
+                Ok(spec.clone())
+            }
+            None => Err(SvcError::SnapshotNotFound {
+                snap_id: id.to_string(),
+            }),
+        }
+    }
+
+    /// Get a guarded VolumeSnapshotSpec for the snapshot with the given ID.
+    pub(crate) async fn snapshot(
+        &self,
+        id: &SnapshotId,
+    ) -> Result<OperationGuardArc<VolumeSnapshotSpec>, SvcError> {
+        match self.snapshot_rsc(id) {
+            Some(spec) => spec.operation_guard_wait().await,
+            None => Err(SvcError::SnapshotNotFound {
+                snap_id: id.to_string(),
+            }),
+        }
+    }
+
+    /// Gets a copy of all VolumeSnapshotSpec's.
+    pub(crate) fn snapshots(&self) -> Vec<VolumeSnapshotSpec> {
+        let specs = self.read();
+        specs.snapshots()
+    }
+
+    /// Gets a copy of all locked VolumeSnapshotSpec's.
+    pub(crate) fn snapshots_rsc(&self) -> Vec<ResourceMutex<VolumeSnapshotSpec>> {
+        let specs = self.read();
+        specs.snapshots.to_vec()
+    }
+
+    /// Gets a copy of all VolumeSnapshotSpec's taken from the volume with the given `id`.
+    pub(crate) fn volume_snapshots(&self, id: &VolumeId) -> Vec<VolumeSnapshotSpec> {
+        self.snapshots()
+            .into_iter()
+            .filter(|s| &s.source_id == id)
+            .collect()
+    }
+
+    /// Remove snapshot by its `id`.
+    pub(super) fn remove_snapshot(&self, id: &SnapshotId) {
+        let mut specs = self.write();
+        specs.snapshots.remove(id);
+    }
+
+    /// Get or Create the resourced VolumeSnapshotSpec for the given request.
+    pub(crate) fn get_or_create_snapshot(
+        &self,
+        request: &CreateVolumeSnapshot,
+    ) -> ResourceMutex<VolumeSnapshotSpec> {
+        let mut specs = self.write();
+        if let Some(snapshot) = specs.snapshots.get(&request.snap_id) {
+            snapshot.clone()
+        } else {
+            specs.snapshots.insert(VolumeSnapshotSpec::from(request))
+        }
+    }
+
+    /// Worker that reconciles dirty VolumeSnapshotSpec's with the persistent store.
+    pub(crate) async fn reconcile_dirty_snapshots(&self, registry: &Registry) -> bool {
+        let mut pending_ops = false;
+
+        let snapshots = self.snapshots_rsc();
+        for snapshot_spec in snapshots {
+            if let Ok(mut guard) = snapshot_spec.operation_guard() {
+                if !guard.handle_incomplete_ops(registry).await {
+                    // Not all pending operations could be handled.
+                    pending_ops = true;
+                }
+            }
+        }
+        pending_ops
+    }
+
     /// Worker that reconciles dirty VolumeSpecs's with the persistent store.
     /// This is useful when nexus operations are performed but we fail to
     /// update the spec with the persistent store.
@@ -1380,6 +1755,68 @@ impl SpecOperationsHelper for VolumeSpec {
                 }
             }
 
+            VolumeOperation::Resize(size) if *size < self.size => Err(SvcError::ResizeShrink {
+                kind: self.kind(),
+                id: self.uuid_str(),
+                current_size: self.size,
+                requested_size: *size,
+            }),
+            VolumeOperation::Resize(_) if state.status != VolumeStatus::Online => {
+                Err(SvcError::VolumeResizeState {
+                    volume_id: self.uuid_str(),
+                    volume_state: state.status.to_string(),
+                })
+            }
+            VolumeOperation::Resize(_) => Ok(()),
+
+            VolumeOperation::AddFrontendNode(..) | VolumeOperation::RemoveFrontendNode(_)
+                if self.target().is_none() =>
+            {
+                Err(SvcError::VolumeNotPublished {
+                    vol_id: self.uuid_str(),
+                })
+            }
+            // only targets with a multi-node access mode may be accessed from other nodes
+            VolumeOperation::AddFrontendNode(node, _) => match self.active_config() {
+                Some(config)
+                    if config.frontend().node_published(node)
+                        || !config.frontend().access_mode().multi_node() =>
+                {
+                    Err(SvcError::VolumeAlreadyPublished {
+                        vol_id: self.uuid_str(),
+                        node: node.to_string(),
+                        protocol: format!("{:?}", config.target().protocol()),
+                    })
+                }
+                _ => Ok(()),
+            },
+            VolumeOperation::RemoveFrontendNode(node) => match self.active_config() {
+                Some(config) if config.frontend().node_published(node) => Ok(()),
+                _ => Err(SvcError::FrontendNodeNotPublished {
+                    node: node.to_string(),
+                    vol_id: self.uuid_str(),
+                }),
+            },
+
+            VolumeOperation::SetQos(_) => Ok(()),
+
+            VolumeOperation::StartRebalance(_) if self.rebalance.is_some() => {
+                Err(SvcError::Conflict {})
+            }
+            VolumeOperation::StartRebalance(_) if state.status != VolumeStatus::Online => {
+                Err(SvcError::ReplicaIncrease {
+                    volume_id: self.uuid_str(),
+                    volume_state: state.status.to_string(),
+                })
+            }
+            VolumeOperation::StartRebalance(_) => Ok(()),
+            VolumeOperation::CompleteRebalance(replica)
+                if self.rebalance.as_ref() != Some(replica) =>
+            {
+                Err(SvcError::Conflict {})
+            }
+            VolumeOperation::CompleteRebalance(_) => Ok(()),
+
             VolumeOperation::Create => unreachable!(),
             VolumeOperation::Destroy => unreachable!(),
         }?;
@@ -1401,6 +1838,58 @@ impl SpecOperationsHelper for VolumeSpec {
     fn uuid_str(&self) -> String {
         self.uuid.to_string()
     }
+    fn event_labels(&self) -> HashMap<String, String> {
+        self.labels.clone().unwrap_or_default()
+    }
+    fn status(&self) -> SpecStatus<Self::Status> {
+        self.status.clone()
+    }
+    fn set_status(&mut self, status: SpecStatus<Self::Status>) {
+        self.status = status;
+    }
+    fn operation_result(&self) -> Option<Option<bool>> {
+        self.operation.as_ref().map(|r| r.result)
+    }
+}
+
+#[async_trait::async_trait]
+impl GuardedOperationsHelper for OperationGuardArc<VolumeSnapshotSpec> {
+    type Create = CreateVolumeSnapshot;
+    type Owners = ();
+    type Status = ();
+    type State = VolumeSnapshotState;
+    type UpdateOp = ();
+    type Inner = VolumeSnapshotSpec;
+
+    fn remove_spec(&self, registry: &Registry) {
+        let uuid = self.lock().uuid.clone();
+        registry.specs().remove_snapshot(&uuid);
+    }
+}
+
+#[async_trait::async_trait]
+impl SpecOperationsHelper for VolumeSnapshotSpec {
+    type Create = CreateVolumeSnapshot;
+    type Owners = ();
+    type Status = ();
+    type State = VolumeSnapshotState;
+    type UpdateOp = ();
+
+    fn start_create_op(&mut self) {
+        self.start_op(VolumeSnapshotOperation::Create);
+    }
+    fn start_destroy_op(&mut self) {
+        self.start_op(VolumeSnapshotOperation::Destroy);
+    }
+    fn dirty(&self) -> bool {
+        self.pending_op()
+    }
+    fn kind(&self) -> ResourceKind {
+        ResourceKind::VolumeSnapshot
+    }
+    fn uuid_str(&self) -> String {
+        self.uuid.to_string()
+    }
     fn status(&self) -> SpecStatus<Self::Status> {
         self.status.clone()
     }
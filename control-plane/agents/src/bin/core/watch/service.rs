@@ -5,19 +5,22 @@ use crate::{
 pub(crate) use agents::errors::SvcError;
 
 use common_lib::{
-    transport_api::{v0::Watches, ReplyError},
-    types::v0::transport::{CreateWatch, DeleteWatch, GetWatches},
+    transport_api::{v0::Watches, ReplyError, ReplyErrorKind},
+    types::v0::transport::{CreateWatch, DeleteWatch, GetWatches, WatchEvents},
 };
 use grpc::{
     context::Context,
-    operations::watch::traits::{GetWatchInfo, WatchInfo, WatchOperations},
+    operations::watch::traits::{
+        GetWatchInfo, ResourceEventStream, WatchEventsInfo, WatchInfo, WatchOperations,
+    },
 };
 
 use std::sync::Arc;
-use tokio::sync::Mutex;
+use tokio::sync::{broadcast::error::RecvError, Mutex};
 
 #[derive(Clone, Debug)]
 pub(super) struct Service {
+    registry: Registry,
     watch: Arc<Mutex<StoreWatch>>,
 }
 
@@ -46,16 +49,61 @@ impl WatchOperations for Service {
         Context::spawn(async move { service.delete_watch(&destroy_watch).await }).await??;
         Ok(())
     }
+
+    async fn watch_events(
+        &self,
+        req: &dyn WatchEventsInfo,
+        _ctx: Option<Context>,
+    ) -> Result<ResourceEventStream, ReplyError> {
+        let watch_events = req.into();
+        Ok(self.events_stream(watch_events))
+    }
 }
 
 /// Watch Agent's Service
 impl Service {
     pub(super) fn new(registry: Registry) -> Self {
         Self {
+            registry: registry.clone(),
             watch: Arc::new(Mutex::new(StoreWatch::new(registry))),
         }
     }
 
+    /// Stream the resource change events which match the request filters.
+    /// The stream ends when the registry stops publishing events, or with an `Aborted` error
+    /// when the subscriber lags behind and events are dropped, so the client can re-list the
+    /// resources before watching again.
+    pub(super) fn events_stream(&self, request: WatchEvents) -> ResourceEventStream {
+        let receiver = self.registry.subscribe_events();
+        Box::pin(futures::stream::unfold(
+            Some((receiver, request)),
+            |state| async move {
+                let (mut receiver, request) = state?;
+                loop {
+                    match receiver.recv().await {
+                        Ok(event) if request.matches(&event) => {
+                            return Some((Ok(event), Some((receiver, request))))
+                        }
+                        Ok(_) => {}
+                        Err(RecvError::Lagged(missed)) => {
+                            tracing::warn!(
+                                missed,
+                                "Resource events subscriber is lagging behind, ending the stream"
+                            );
+                            let error = ReplyError::tonic_reply_error(
+                                ReplyErrorKind::Aborted,
+                                format!("Missed {} resource events", missed),
+                                "Re-list the resources and watch the events again".to_string(),
+                            );
+                            return Some((Err(error), None));
+                        }
+                        Err(RecvError::Closed) => return None,
+                    }
+                }
+            },
+        ))
+    }
+
     /// Create new resource watch
     #[tracing::instrument(level = "debug", skip(self), err)]
     pub(super) async fn create_watch(&self, request: &CreateWatch) -> Result<(), SvcError> {
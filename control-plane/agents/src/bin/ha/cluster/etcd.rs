@@ -5,7 +5,8 @@ use common_lib::{
         definitions::{
             key_prefix_obj, ObjectKey, StorableObject, StorableObjectType, Store, StoreError,
         },
-        switchover::SwitchOverSpec,
+        registry::ControlPlaneService,
+        switchover::{SwitchOverHistory, SwitchOverSpec, TargetMoveSpec},
     },
 };
 use futures::lock::Mutex;
@@ -13,6 +14,9 @@ use http::Uri;
 use std::{sync::Arc, time::Duration};
 use tracing::{debug, error};
 
+/// The operation of a `StoreError::Timeout` when connecting to the persistent store.
+pub const CONNECT_OPERATION: &str = "connect";
+
 /// Represent object to access Etcd.
 #[derive(Debug, Clone)]
 pub struct EtcdStore {
@@ -21,27 +25,48 @@ pub struct EtcdStore {
 }
 
 impl EtcdStore {
-    /// Create a new Etcd client.
-    pub async fn new(endpoint: Uri, timeout: Duration) -> Result<Self, StoreError> {
-        match tokio::time::timeout(timeout, async { Etcd::new(&endpoint.to_string()).await }).await
-        {
-            Ok(v) => {
-                let store = v?;
-                Ok(Self {
-                    store: Arc::new(Mutex::new(store)),
-                    timeout,
-                })
-            }
+    /// Create a new Etcd client which holds the cluster-agent's lease lock.
+    /// Only one instance of the cluster-agent may hold the lock, and so this waits for up to
+    /// `lease_ttl` for the current holder to go away, returning `StoreError::Timeout` otherwise,
+    /// in which case the lease granted for waiting is revoked.
+    /// Connecting to the store may take up to `timeout` on top of that, otherwise a
+    /// `StoreError::Timeout` for the `CONNECT_OPERATION` is returned.
+    /// If another instance takes over the lock later on, this instance gives up and panics.
+    pub async fn new_leased(
+        endpoint: Uri,
+        timeout: Duration,
+        lease_ttl: Duration,
+    ) -> Result<Self, StoreError> {
+        let store = Etcd::new_leased(
+            [endpoint.to_string()],
+            ControlPlaneService::HaClusterAgent,
+            lease_ttl,
+        );
+        match tokio::time::timeout(timeout + lease_ttl, store).await {
+            Ok(store) => Ok(Self {
+                store: Arc::new(Mutex::new(store?)),
+                timeout,
+            }),
             Err(error) => {
                 error!(%error, "Failed to create persistent store client");
                 Err(StoreError::Timeout {
-                    operation: "connect".to_string(),
+                    operation: CONNECT_OPERATION.to_string(),
                     timeout,
                 })
             }
         }
     }
 
+    /// Revokes the lease and releases the lock, allowing a standby instance to take over.
+    pub async fn revoke(&self) {
+        let store = self.store.lock().await;
+        match tokio::time::timeout(self.timeout, store.revoke()).await {
+            Ok(Ok(())) => {}
+            Ok(Err(error)) => error!(%error, "Failed to revoke the persistent store lease"),
+            Err(error) => error!(%error, "Timed out revoking the persistent store lease"),
+        }
+    }
+
     /// Serialized write to the persistent store.
     pub async fn store_obj<O: StorableObject>(&self, object: &O) -> Result<(), anyhow::Error> {
         let mut store = self.store.lock().await;
@@ -58,6 +83,62 @@ impl EtcdStore {
         }
     }
 
+    /// Serialized read-modify-write of an object in the persistent store.
+    /// If the object does not exist yet, then the `default` one is modified and stored instead.
+    pub async fn update_obj<O: StorableObject>(
+        &self,
+        key: &O::Key,
+        default: impl FnOnce() -> O,
+        update: impl FnOnce(&mut O),
+    ) -> Result<(), anyhow::Error> {
+        let mut store = self.store.lock().await;
+        match tokio::time::timeout(self.timeout, async move {
+            let mut object = match store.get_obj::<O>(key).await {
+                Ok(object) => object,
+                Err(StoreError::MissingEntry { .. }) => default(),
+                Err(error) => return Err(error),
+            };
+            update(&mut object);
+            store.put_obj(&object).await
+        })
+        .await
+        {
+            Ok(result) => result.map_err(Into::into),
+            Err(error) => {
+                error!(%error, "Failed to update the persistent store");
+                Err(StoreError::Timeout {
+                    operation: "Update".to_string(),
+                    timeout: self.timeout,
+                }
+                .into())
+            }
+        }
+    }
+
+    /// Get the object from the persistent store, if it exists.
+    pub async fn get_obj<O: StorableObject>(
+        &self,
+        key: &O::Key,
+    ) -> Result<Option<O>, anyhow::Error> {
+        let mut store = self.store.lock().await;
+        match tokio::time::timeout(self.timeout, async move { store.get_obj::<O>(key).await }).await
+        {
+            Ok(result) => match result {
+                Ok(object) => Ok(Some(object)),
+                Err(StoreError::MissingEntry { .. }) => Ok(None),
+                Err(error) => Err(error.into()),
+            },
+            Err(error) => {
+                error!(%error, "Failed to read from persistent store");
+                Err(StoreError::Timeout {
+                    operation: "Get".to_string(),
+                    timeout: self.timeout,
+                }
+                .into())
+            }
+        }
+    }
+
     /// Delete the object from the persistent store.
     pub async fn delete_obj<O: StorableObject>(&self, object: &O) -> Result<(), anyhow::Error> {
         let mut store = self.store.lock().await;
@@ -99,4 +180,40 @@ impl EtcdStore {
 
         Ok(entries.iter().map(Into::into).collect())
     }
+
+    /// Get the switchover histories of all volumes stored in Etcd.
+    pub async fn fetch_switchover_histories(
+        &self,
+    ) -> Result<Vec<SwitchOverHistory>, anyhow::Error> {
+        let mut store = self.store.lock().await;
+        let key = key_prefix_obj(StorableObjectType::FailoverHistory);
+        let store_entries = store.get_values_prefix(&key).await?;
+
+        debug!(
+            count = store_entries.len(),
+            "fetched switchover histories from etcd"
+        );
+
+        store_entries
+            .into_iter()
+            .map(|(_, v)| serde_json::from_value(v).map_err(Into::into))
+            .collect()
+    }
+
+    /// Get the planned target moves stored in Etcd, which are still in progress.
+    pub async fn fetch_target_moves(&self) -> Result<Vec<TargetMoveSpec>, anyhow::Error> {
+        let mut store = self.store.lock().await;
+        let key = key_prefix_obj(StorableObjectType::TargetMove);
+        let store_entries = store.get_values_prefix(&key).await?;
+
+        debug!(
+            count = store_entries.len(),
+            "fetched target moves from etcd"
+        );
+
+        store_entries
+            .into_iter()
+            .map(|(_, v)| serde_json::from_value(v).map_err(Into::into))
+            .collect()
+    }
 }
@@ -1,8 +1,15 @@
+use common_lib::types::v0::store::definitions::StoreError;
 use grpc::client::CoreClient;
 use http::Uri;
 use once_cell::sync::OnceCell;
 use opentelemetry::{global, KeyValue};
-use std::net::SocketAddr;
+use std::{
+    net::SocketAddr,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
 use structopt::StructOpt;
 use tracing::info;
 use utils::{
@@ -11,6 +18,7 @@ use utils::{
 };
 mod etcd;
 mod nodes;
+mod readiness;
 mod server;
 mod switchover;
 mod volume;
@@ -22,6 +30,12 @@ struct Cli {
     #[structopt(long, short, default_value = DEFAULT_CLUSTER_AGENT_SERVER_ADDR)]
     grpc_endpoint: SocketAddr,
 
+    /// The address on which to serve the liveness and readiness probes (at the `/live` and
+    /// `/ready` paths). Only the leader instance is ready.
+    /// If `None` the probes are not served.
+    #[structopt(long)]
+    probes_addr: Option<SocketAddr>,
+
     /// The Persistent Store URL to connect to.
     #[structopt(long, short, default_value = "http://localhost:2379")]
     store: Uri,
@@ -30,6 +44,11 @@ struct Cli {
     #[structopt(long, default_value = utils::STORE_OP_TIMEOUT)]
     store_timeout: humantime::Duration,
 
+    /// The lease lock ttl for the persistent store after which we'll lose the leadership, allowing
+    /// a standby instance to take over.
+    #[structopt(long, default_value = utils::STORE_LEASE_LOCK_TTL)]
+    store_lease_ttl: humantime::Duration,
+
     /// Core gRPC server URL or address.
     #[structopt(long, short, default_value = DEFAULT_GRPC_CLIENT_ADDR)]
     core_grpc: Uri,
@@ -67,6 +86,33 @@ fn initialize_tracing(args: &Cli) {
     )
 }
 
+/// Connect to the persistent store and wait until this instance becomes the leader, ie: until it
+/// holds the cluster-agent's lease lock. Standby instances keep waiting here until the leader goes
+/// away and its lease expires. Each attempt waits for the lock for up to the lease ttl with a new
+/// lease, which is revoked if the lock is not acquired.
+async fn wait_for_leadership(cli: &Cli) -> anyhow::Result<etcd::EtcdStore> {
+    loop {
+        match etcd::EtcdStore::new_leased(
+            cli.store.clone(),
+            cli.store_timeout.into(),
+            cli.store_lease_ttl.into(),
+        )
+        .await
+        {
+            Ok(store) => {
+                info!("Acquired the cluster-agent leadership");
+                return Ok(store);
+            }
+            // timing out while connecting means the store is not reachable, rather than another
+            // instance being the leader
+            Err(StoreError::Timeout { operation, .. }) if operation != etcd::CONNECT_OPERATION => {
+                info!("Another cluster-agent instance is the leader, standing by");
+            }
+            Err(error) => return Err(error.into()),
+        }
+    }
+}
+
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
     utils::print_package_info!();
@@ -81,20 +127,38 @@ async fn main() -> anyhow::Result<()> {
         .ok()
         .expect("Expect to be initialised only once");
 
-    let store = etcd::EtcdStore::new(cli.store, cli.store_timeout.into()).await?;
+    // Standby instances don't serve the gRPC, so the probes are served straight away.
+    let leader = Arc::new(AtomicBool::new(false));
+    if let Some(probes_addr) = cli.probes_addr {
+        tokio::spawn(readiness::serve(probes_addr, leader.clone()));
+    }
+
+    let store = wait_for_leadership(&cli).await?;
     let node_list = nodes::NodeList::new();
 
+    // Resume the switchovers which the previous leader did not complete.
     let entries = store.fetch_incomplete_requests().await?;
 
     // Node list has ref counted list internally.
-    let mover = volume::VolumeMover::new(store, node_list.clone());
+    let mover = volume::VolumeMover::new(store.clone(), node_list.clone());
     mover.send_switchover_req(entries).await?;
 
+    // Resume the planned target moves which the previous leader did not complete.
+    let target_moves = store.fetch_target_moves().await?;
+    mover.resume_target_moves(target_moves).await;
+
+    // The gRPC server is only started by the leader, and so the node-agents register with it
+    // when they next refresh their registration.
     info!("starting cluster-agent server");
-    server::ClusterAgent::new(cli.grpc_endpoint, node_list, mover)
+    leader.store(true, Ordering::Relaxed);
+    let result = server::ClusterAgent::new(cli.grpc_endpoint, node_list, mover)
         .run()
         .await
-        .map_err(|e| anyhow::anyhow!("Error running server: {e}"))?;
+        .map_err(|e| anyhow::anyhow!("Error running server: {e}"));
+
+    // Hand over the leadership to a standby instance straight away.
+    store.revoke().await;
+    result?;
 
     global::shutdown_tracer_provider();
     Ok(())
@@ -24,6 +24,22 @@ impl NodeList {
         list.insert(name, endpoint);
     }
 
+    /// Get the endpoint of the node agent running on the given node, if registered.
+    pub async fn endpoint(&self, node: &NodeId) -> Option<SocketAddr> {
+        self.list.lock().await.get(node).cloned()
+    }
+
+    /// Reserve the path so that reports of its failure are rejected until it's removed.
+    /// Returns false if the path is already reserved or reported for switchover.
+    pub async fn reserve_path(&self, path: String, endpoint: SocketAddr) -> bool {
+        let mut failed_path = self.failed_path.lock().await;
+        if failed_path.contains_key(&path) {
+            return false;
+        }
+        failed_path.insert(path, endpoint);
+        true
+    }
+
     /// Remove path from failed_path list.
     pub async fn remove_failed_path(&self, path: String) {
         let mut failed_path = self.failed_path.lock().await;
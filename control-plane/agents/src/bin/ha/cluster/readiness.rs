@@ -0,0 +1,75 @@
+use hyper::{
+    service::{make_service_fn, service_fn},
+    Body, Method, Request, Response, Server, StatusCode,
+};
+use std::{
+    convert::Infallible,
+    net::SocketAddr,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
+
+/// Serve the liveness and readiness probes on the given address, at the `/live` and `/ready`
+/// paths.
+/// Only the leader is ready, as standby instances don't serve the cluster-agent gRPC until they
+/// take over the leadership, which is flagged through `leader`.
+pub(crate) async fn serve(addr: SocketAddr, leader: Arc<AtomicBool>) {
+    let make_service = make_service_fn(move |_| {
+        let leader = leader.clone();
+        async move {
+            Ok::<_, Infallible>(service_fn(move |request| {
+                let leader = leader.load(Ordering::Relaxed);
+                async move { Ok::<_, Infallible>(handle(&request, leader)) }
+            }))
+        }
+    });
+
+    tracing::info!(%addr, "Serving the liveness and readiness probes");
+    if let Err(error) = Server::bind(&addr).serve(make_service).await {
+        tracing::error!(%error, %addr, "Failed to serve the liveness and readiness probes");
+    }
+}
+
+fn handle(request: &Request<Body>, leader: bool) -> Response<Body> {
+    let status = match (request.method(), request.uri().path()) {
+        (&Method::GET, "/live") => StatusCode::OK,
+        (&Method::GET, "/ready") if leader => StatusCode::OK,
+        (&Method::GET, "/ready") => StatusCode::SERVICE_UNAVAILABLE,
+        _ => StatusCode::NOT_FOUND,
+    };
+    Response::builder()
+        .status(status)
+        .body(Body::empty())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn status(method: Method, path: &str, leader: bool) -> StatusCode {
+        let request = Request::builder()
+            .method(method)
+            .uri(path)
+            .body(Body::empty())
+            .unwrap();
+        handle(&request, leader).status()
+    }
+
+    #[test]
+    fn probes() {
+        // standby instances are alive but not ready
+        assert_eq!(status(Method::GET, "/live", false), StatusCode::OK);
+        assert_eq!(
+            status(Method::GET, "/ready", false),
+            StatusCode::SERVICE_UNAVAILABLE
+        );
+        assert_eq!(status(Method::GET, "/live", true), StatusCode::OK);
+        assert_eq!(status(Method::GET, "/ready", true), StatusCode::OK);
+
+        assert_eq!(status(Method::GET, "/metrics", true), StatusCode::NOT_FOUND);
+        assert_eq!(status(Method::POST, "/ready", true), StatusCode::NOT_FOUND);
+    }
+}
@@ -1,10 +1,16 @@
 use crate::{nodes::NodeList, volume::VolumeMover};
-use common_lib::transport_api::{ReplyError, ReplyErrorKind, ResourceKind};
+use common_lib::{
+    transport_api::{ReplyError, ReplyErrorKind, ResourceKind},
+    types::v0::transport::{NodeId, SwitchOvers, Volume},
+};
 use grpc::{
     context::Context,
     operations::ha_node::{
         server::ClusterAgentServer,
-        traits::{ClusterAgentOperations, NodeInfo, ReportFailedPathsInfo},
+        traits::{
+            ClusterAgentOperations, GetSwitchOversInfo, MoveVolumeTargetInfo, NodeInfo,
+            ReportFailedPathsInfo, SwitchOverVolumeInfo,
+        },
     },
 };
 use std::{net::SocketAddr, sync::Arc};
@@ -72,6 +78,16 @@ impl ClusterAgentOperations for ClusterAgentSvc {
     ) -> Result<(), ReplyError> {
         let mut v: Vec<(String, String)> = Vec::new();
 
+        let node: NodeId = request.node().into();
+        let nqns = request
+            .failed_paths()
+            .iter()
+            .map(|path| path.target_nqn().to_string())
+            .collect();
+        if let Err(error) = self.mover.record_path_failures(&node, nqns).await {
+            tracing::error!(%error, "Failed to record the reported path failures");
+        }
+
         for x in request.failed_paths().into_iter() {
             let nodes = self.nodes.clone();
             match nodes
@@ -102,4 +118,45 @@ impl ClusterAgentOperations for ClusterAgentSvc {
         }
         Ok(())
     }
+
+    #[tracing::instrument(level = "debug", skip(self), err)]
+    async fn get_switchovers(
+        &self,
+        request: &dyn GetSwitchOversInfo,
+        _context: Option<Context>,
+    ) -> Result<SwitchOvers, ReplyError> {
+        let volume_id = request.volume_id();
+        Ok(SwitchOvers(
+            self.mover.switchovers(volume_id.as_ref()).await?,
+        ))
+    }
+
+    #[tracing::instrument(level = "info", skip(self), err, fields(volume.uuid = %request.volume_id()))]
+    async fn cancel_switchover(
+        &self,
+        request: &dyn SwitchOverVolumeInfo,
+        _context: Option<Context>,
+    ) -> Result<(), ReplyError> {
+        self.mover.cancel_switchover(&request.volume_id()).await
+    }
+
+    #[tracing::instrument(level = "info", skip(self), err, fields(volume.uuid = %request.volume_id()))]
+    async fn retry_switchover(
+        &self,
+        request: &dyn SwitchOverVolumeInfo,
+        _context: Option<Context>,
+    ) -> Result<(), ReplyError> {
+        self.mover.retry_switchover(&request.volume_id()).await
+    }
+
+    #[tracing::instrument(level = "info", skip(self), err, fields(volume.uuid = %request.volume_id()))]
+    async fn move_volume_target(
+        &self,
+        request: &dyn MoveVolumeTargetInfo,
+        _context: Option<Context>,
+    ) -> Result<Volume, ReplyError> {
+        self.mover
+            .move_target(&request.volume_id(), request.target_node())
+            .await
+    }
 }
@@ -2,15 +2,18 @@ use crate::{core_grpc, etcd::EtcdStore, nodes::NodeList};
 use anyhow::anyhow;
 use chrono::Utc;
 use common_lib::{
-    transport_api::ReplyErrorKind,
+    transport_api::{ReplyError, ReplyErrorKind, ResourceKind},
     types::v0::{
         store::{
-            switchover::{Operation, OperationState, SwitchOverSpec, SwitchOverTime},
+            switchover::{
+                Operation, OperationState, StageTiming, SwitchOverHistory, SwitchOverHistoryKey,
+                SwitchOverRecord, SwitchOverSpec, SwitchOverTime,
+            },
             SpecTransaction,
         },
         transport::{
-            DestroyShutdownTargets, GetController, NodeId, ReplacePath, RepublishVolume, VolumeId,
-            VolumeShareProtocol,
+            DestroyShutdownTargets, GetController, NodeId, ReplacePath, RepublishVolume,
+            SwitchOver, SwitchOverStage, VolumeId, VolumeShareProtocol,
         },
     },
 };
@@ -69,6 +72,12 @@ pub struct SwitchOverRequest {
     reuse_existing: bool,
     /// Publish context.
     publish_context: Option<HashMap<String, String>>,
+    /// Error message of the last failed attempt.
+    #[serde(default)]
+    error: Option<String>,
+    /// Timings of the stages which have been started so far.
+    #[serde(default)]
+    stage_timings: Vec<StageTiming>,
 }
 
 impl Ord for SwitchOverRequest {
@@ -105,9 +114,68 @@ impl SwitchOverRequest {
             retry_count: 0,
             reuse_existing: true,
             publish_context: None,
+            error: None,
+            stage_timings: vec![],
         }
     }
 
+    /// Create a new request which retries the failed switchover of the record from the
+    /// beginning, as if the path had just been reported again.
+    fn retry_of(callback_uri: SocketAddr, record: &SwitchOverRecord) -> SwitchOverRequest {
+        Self::new(
+            callback_uri,
+            record.volume.clone(),
+            record.node_name.clone(),
+            record.existing_nqn.clone(),
+        )
+    }
+
+    /// Account for a new attempt of the current stage.
+    fn begin_stage_timing(&mut self) {
+        let stage = SwitchOverStage::from(self.stage);
+        match self.stage_timings.last_mut() {
+            Some(timing) if timing.stage == stage && timing.finished.is_none() => {
+                timing.attempts += 1;
+            }
+            _ => {
+                let mut timing = StageTiming::new(stage);
+                timing.attempts = 1;
+                self.stage_timings.push(timing);
+            }
+        }
+    }
+
+    /// Mark the given stage as finished, if it's the one being timed.
+    fn finish_stage_timing(&mut self, stage: Stage) {
+        let stage = SwitchOverStage::from(stage);
+        if let Some(timing) = self.stage_timings.last_mut() {
+            if timing.stage == stage && timing.finished.is_none() {
+                timing.finished = Some(Utc::now());
+            }
+        }
+    }
+
+    /// Add the outcome of the finished request to the switchover history of its volume.
+    async fn record_outcome(&self, etcd: &EtcdStore) -> Result<(), anyhow::Error> {
+        let record = SwitchOverRecord {
+            volume: self.volume_id.clone(),
+            node_name: self.node_name.clone(),
+            existing_nqn: self.existing_nqn.clone(),
+            new_path: self.new_path.clone(),
+            started: self.timestamp,
+            finished: Utc::now(),
+            outcome: self.stage.into(),
+            error: self.error.clone(),
+            stages: self.stage_timings.clone(),
+        };
+        etcd.update_obj(
+            &SwitchOverHistoryKey::new(self.volume_id.clone()),
+            || SwitchOverHistory::new(self.volume_id.clone()),
+            |history| history.push(record),
+        )
+        .await
+    }
+
     /// Set the reuse_existing flag.
     pub fn set_reuse_existing(&mut self, reuse_existing: bool) {
         self.reuse_existing = reuse_existing;
@@ -182,6 +250,7 @@ impl SwitchOverRequest {
             share: VolumeShareProtocol::Nvmf,
             reuse_existing: self.reuse_existing,
             frontend_node: self.node_name.clone(),
+            defer_shutdown: false,
         };
         let vol = match client().republish(&republish_req, None).await {
             Ok(vol) => Ok(vol),
@@ -370,17 +439,63 @@ impl SwitchOverRequest {
     }
 }
 
+/// Switchover requests which are currently being worked on or waiting in the work queue.
+/// Finished requests are only kept in the persisted switchover history of their volume.
+#[derive(Debug, Default)]
+struct SwitchOverTracker {
+    in_flight: HashMap<VolumeId, SwitchOverRequest>,
+}
+
+impl SwitchOverTracker {
+    /// Check if the request is still the one being tracked for its volume.
+    /// A request which is no longer tracked has been cancelled.
+    fn is_current(&self, request: &SwitchOverRequest) -> bool {
+        matches!(
+            self.in_flight.get(&request.volume_id),
+            Some(r) if r.timestamp == request.timestamp
+        )
+    }
+}
+
+/// Get the most recently failed or cancelled switchovers from the histories of the volumes,
+/// most recent first.
+fn failed_switchovers(histories: Vec<SwitchOverHistory>) -> Vec<SwitchOver> {
+    let mut failed = histories
+        .into_iter()
+        .flat_map(|history| history.records)
+        .filter(|record| record.outcome == SwitchOverStage::Errored)
+        .collect::<Vec<_>>();
+    failed.sort_by(|a, b| b.finished.cmp(&a.finished));
+    failed
+        .iter()
+        .take(FAILED_HISTORY)
+        .map(SwitchOver::from)
+        .collect()
+}
+
+/// Get the record of the last switchover of the volume, if it has failed or was cancelled.
+fn last_failed(history: &SwitchOverHistory) -> Option<&SwitchOverRecord> {
+    history
+        .records
+        .back()
+        .filter(|record| record.outcome == SwitchOverStage::Errored)
+}
+
 /// SwitchOverEngine defines spec for switchover engine.
 #[derive(Debug, Clone)]
 pub struct SwitchOverEngine {
     etcd: EtcdStore,
     nodes: NodeList,
     channel: UnboundedSender<SwitchOverRequest>,
+    tracker: Arc<Mutex<SwitchOverTracker>>,
 }
 
 /// Number of Switchover worker tasks to be spawned.
 const WORKER_NUM: u8 = 4;
 
+/// Maximum number of failed Switchover requests which are listed for inspection.
+const FAILED_HISTORY: usize = 32;
+
 enum ReQueue {
     /// Sleep duration during Fast requeue phase.
     Fast = 10,
@@ -399,6 +514,7 @@ impl SwitchOverEngine {
             channel: rq_tx,
             etcd,
             nodes,
+            tracker: Default::default(),
         };
 
         sw.init_worker(Arc::new(Mutex::new(rq_rx)));
@@ -437,17 +553,49 @@ impl SwitchOverEngine {
     /// Failed requests are sent back to the work queue to be picked up later.
     async fn work_request(&self, mut request: SwitchOverRequest) {
         loop {
+            let (current, retried) = {
+                let tracker = self.tracker.lock().await;
+                (
+                    tracker.is_current(&request),
+                    tracker.in_flight.contains_key(&request.volume_id),
+                )
+            };
+            if !current {
+                info!(volume.uuid = %request.volume_id, "Switchover request was cancelled");
+                // a stage which was running while cancelling may have stored the request again,
+                // unless it has been retried since, in which case the entry belongs to the retry
+                if !retried {
+                    if let Err(error) = request.delete_request(&self.etcd).await {
+                        error!(
+                            volume.uuid = %request.volume_id,
+                            %error,
+                            "Failed to delete cancelled Switchover request"
+                        );
+                    }
+                }
+                break;
+            }
+            let stage = request.stage;
+            if !matches!(stage, Stage::Successful | Stage::Errored) {
+                request.begin_stage_timing();
+            }
             let result = match request.stage {
                 Stage::Init => request.initialize(&self.etcd).await,
                 Stage::RepublishVolume => request.republish_volume(&self.etcd).await,
                 Stage::ReplacePath => request.replace_path(&self.etcd, &self.nodes).await,
                 Stage::DeleteTarget => request.delete_target(&self.etcd).await,
                 Stage::Errored => match request.errored_switchover(&self.etcd).await {
-                    Ok(_) => break,
+                    Ok(_) => {
+                        self.complete(request).await;
+                        break;
+                    }
                     Err(e) => Err(e),
                 },
                 Stage::Successful => match request.delete_switchover(&self.etcd).await {
-                    Ok(_) => break,
+                    Ok(_) => {
+                        self.complete(request).await;
+                        break;
+                    }
                     Err(e) => Err(e),
                 },
             };
@@ -455,6 +603,10 @@ impl SwitchOverEngine {
                 Ok(_) => {
                     // reset retry count back to the start after successfully completing a stage.
                     request.retry_count = 0;
+                    if request.stage != stage {
+                        request.finish_stage_timing(stage);
+                    }
+                    self.update(&request).await;
                 }
                 Err(error) => {
                     info!(
@@ -463,6 +615,8 @@ impl SwitchOverEngine {
                         "Sending failed Switchover request back to the work queue"
                     );
                     request.retry_count += 1;
+                    request.error = Some(error.to_string());
+                    self.update(&request).await;
                     self.enqueue(request);
                     break;
                 }
@@ -470,6 +624,205 @@ impl SwitchOverEngine {
         }
     }
 
+    /// Update the tracked copy of the request, unless it has been cancelled meanwhile.
+    async fn update(&self, request: &SwitchOverRequest) {
+        let mut tracker = self.tracker.lock().await;
+        if tracker.is_current(request) {
+            tracker
+                .in_flight
+                .insert(request.volume_id.clone(), request.clone());
+        }
+    }
+
+    /// Stop tracking a finished request and add its outcome to the persisted switchover
+    /// history of the volume.
+    /// The tracker is not locked while writing to the persistent store.
+    async fn complete(&self, request: SwitchOverRequest) {
+        {
+            let mut tracker = self.tracker.lock().await;
+            if !tracker.is_current(&request) {
+                return;
+            }
+            tracker.in_flight.remove(&request.volume_id);
+        }
+        if let Err(error) = request.record_outcome(&self.etcd).await {
+            error!(volume.uuid = %request.volume_id, %error, "Failed to record Switchover outcome");
+        }
+    }
+
+    /// Start tracking the Switchover request and send it to the work queue.
+    pub async fn submit(&self, req: SwitchOverRequest) {
+        self.tracker
+            .lock()
+            .await
+            .in_flight
+            .insert(req.volume_id.clone(), req.clone());
+        self.enqueue(req);
+    }
+
+    /// Check if a Switchover request of the given volume is in-flight.
+    pub async fn in_flight(&self, volume_id: &VolumeId) -> bool {
+        self.tracker.lock().await.in_flight.contains_key(volume_id)
+    }
+
+    /// Get the persisted switchover histories, optionally only the one of the given volume.
+    async fn histories(
+        &self,
+        volume_id: Option<&VolumeId>,
+    ) -> Result<Vec<SwitchOverHistory>, ReplyError> {
+        let result = match volume_id {
+            Some(id) => self
+                .etcd
+                .get_obj::<SwitchOverHistory>(&SwitchOverHistoryKey::new(id.clone()))
+                .await
+                .map(|history| history.into_iter().collect()),
+            None => self.etcd.fetch_switchover_histories().await,
+        };
+        result.map_err(|error| {
+            ReplyError::internal_error(
+                ResourceKind::SwitchOver,
+                volume_id.map(ToString::to_string).unwrap_or_default(),
+                error.to_string(),
+            )
+        })
+    }
+
+    /// Get the in-flight and the recently failed Switchover requests, optionally only those
+    /// of the given volume. In-flight requests are listed first.
+    /// The failed requests are taken from the persisted switchover histories.
+    pub async fn switchovers(
+        &self,
+        volume_id: Option<&VolumeId>,
+    ) -> Result<Vec<SwitchOver>, ReplyError> {
+        let mut switchovers = {
+            let tracker = self.tracker.lock().await;
+            let mut in_flight = tracker
+                .in_flight
+                .values()
+                .filter(|request| volume_id.map_or(true, |id| id == &request.volume_id))
+                .collect::<Vec<_>>();
+            in_flight.sort();
+            in_flight
+                .into_iter()
+                .map(SwitchOver::from)
+                .collect::<Vec<_>>()
+        };
+        let histories = self.histories(volume_id).await?;
+        switchovers.extend(failed_switchovers(histories));
+        Ok(switchovers)
+    }
+
+    /// Cancel the in-flight Switchover request of the given volume.
+    /// The request is removed from the persistent store and recorded as failed in the
+    /// switchover history of the volume.
+    /// The tracker is not locked while writing to the persistent store.
+    pub async fn cancel(&self, volume_id: &VolumeId) -> Result<(), ReplyError> {
+        let request = match self.tracker.lock().await.in_flight.get(volume_id) {
+            Some(request) => request.clone(),
+            None => {
+                return Err(ReplyError::not_found(
+                    ResourceKind::SwitchOver,
+                    volume_id.to_string(),
+                    "No in-flight switchover for the volume".to_string(),
+                ))
+            }
+        };
+        request.delete_request(&self.etcd).await.map_err(|error| {
+            ReplyError::internal_error(
+                ResourceKind::SwitchOver,
+                volume_id.to_string(),
+                error.to_string(),
+            )
+        })?;
+        let mut request = {
+            let mut tracker = self.tracker.lock().await;
+            if !tracker.is_current(&request) {
+                return Err(ReplyError::failed_precondition(
+                    ResourceKind::SwitchOver,
+                    volume_id.to_string(),
+                    "Switchover for the volume finished before it could be cancelled".to_string(),
+                ));
+            }
+            // a worker which notices the cancellation deletes anything a running stage stores
+            tracker.in_flight.remove(volume_id).unwrap_or(request)
+        };
+        // allow the node to report the path again
+        self.nodes
+            .remove_failed_path(request.existing_nqn.clone())
+            .await;
+        info!(volume.uuid = %volume_id, "Cancelled Switchover request");
+        request.stage = Stage::Errored;
+        request.error = Some("Cancelled".to_string());
+        if let Err(error) = request.record_outcome(&self.etcd).await {
+            error!(volume.uuid = %volume_id, %error, "Failed to record Switchover outcome");
+        }
+        Ok(())
+    }
+
+    /// Retry the last Switchover request of the given volume from the beginning, if it has
+    /// failed or was cancelled.
+    /// The tracker is not locked while writing to the persistent store.
+    pub async fn retry(&self, volume_id: &VolumeId) -> Result<(), ReplyError> {
+        if self.in_flight(volume_id).await {
+            return Err(Self::in_progress(volume_id));
+        }
+        let histories = self.histories(Some(volume_id)).await?;
+        let record = match histories.iter().find_map(last_failed) {
+            Some(record) => record,
+            None => {
+                return Err(ReplyError::not_found(
+                    ResourceKind::SwitchOver,
+                    volume_id.to_string(),
+                    "No failed switchover for the volume".to_string(),
+                ))
+            }
+        };
+        let callback_uri = match self.nodes.endpoint(&record.node_name).await {
+            Some(endpoint) => endpoint,
+            None => {
+                return Err(ReplyError::failed_precondition(
+                    ResourceKind::Node,
+                    record.node_name.to_string(),
+                    "HA node agent is not registered on the node".to_string(),
+                ))
+            }
+        };
+        let request = SwitchOverRequest::retry_of(callback_uri, record);
+        {
+            let mut tracker = self.tracker.lock().await;
+            if tracker.in_flight.contains_key(volume_id) {
+                return Err(Self::in_progress(volume_id));
+            }
+            tracker
+                .in_flight
+                .insert(request.volume_id.clone(), request.clone());
+        }
+        if let Err(error) = request.start_op(&self.etcd).await {
+            let mut tracker = self.tracker.lock().await;
+            if tracker.is_current(&request) {
+                tracker.in_flight.remove(volume_id);
+            }
+            return Err(ReplyError::internal_error(
+                ResourceKind::SwitchOver,
+                volume_id.to_string(),
+                error.to_string(),
+            ));
+        }
+        info!(volume.uuid = %volume_id, "Retrying Switchover request");
+        // if it was cancelled meanwhile the worker deletes the request from the store
+        self.enqueue(request);
+        Ok(())
+    }
+
+    /// Error for a Switchover request of the given volume which is already in-flight.
+    fn in_progress(volume_id: &VolumeId) -> ReplyError {
+        ReplyError::failed_precondition(
+            ResourceKind::SwitchOver,
+            volume_id.to_string(),
+            "Switchover for the volume is already in progress".to_string(),
+        )
+    }
+
     /// Sends Switchover request to the channel after sleeping for sometime (if necessary).
     pub fn enqueue(&self, req: SwitchOverRequest) {
         let tx_clone = self.channel.clone();
@@ -502,6 +855,7 @@ impl From<&SwitchOverRequest> for SwitchOverSpec {
             retry_count: req.retry_count,
             reuse_existing: req.reuse_existing,
             publish_context: req.publish_context.clone(),
+            stage_timings: req.stage_timings.clone(),
         }
     }
 }
@@ -524,6 +878,51 @@ impl From<&SwitchOverSpec> for SwitchOverRequest {
             retry_count: req.retry_count,
             reuse_existing: req.reuse_existing,
             publish_context: req.publish_context.clone(),
+            error: None,
+            stage_timings: req.stage_timings.clone(),
+        }
+    }
+}
+
+impl From<Stage> for SwitchOverStage {
+    fn from(stage: Stage) -> Self {
+        match stage {
+            Stage::Init => SwitchOverStage::Init,
+            Stage::RepublishVolume => SwitchOverStage::RepublishVolume,
+            Stage::ReplacePath => SwitchOverStage::ReplacePath,
+            Stage::DeleteTarget => SwitchOverStage::DeleteTarget,
+            Stage::Successful => SwitchOverStage::Successful,
+            Stage::Errored => SwitchOverStage::Errored,
+        }
+    }
+}
+
+impl From<&SwitchOverRequest> for SwitchOver {
+    fn from(req: &SwitchOverRequest) -> Self {
+        Self {
+            volume_id: req.volume_id.clone(),
+            node_name: req.node_name.clone(),
+            stage: req.stage.into(),
+            timestamp: req.timestamp,
+            retry_count: req.retry_count,
+            existing_nqn: req.existing_nqn.clone(),
+            new_path: req.new_path.clone(),
+            error: req.error.clone(),
+        }
+    }
+}
+
+impl From<&SwitchOverRecord> for SwitchOver {
+    fn from(record: &SwitchOverRecord) -> Self {
+        Self {
+            volume_id: record.volume.clone(),
+            node_name: record.node_name.clone(),
+            stage: record.outcome,
+            timestamp: record.started,
+            retry_count: record.stages.last().map_or(0, |timing| timing.attempts),
+            existing_nqn: record.existing_nqn.clone(),
+            new_path: record.new_path.clone(),
+            error: record.error.clone(),
         }
     }
 }
@@ -588,3 +987,128 @@ fn switchover_ord() {
     test_vec.sort();
     assert_eq!(test_vec, vec![sw1, sw4, sw5, sw3, sw2]);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::{IpAddr, Ipv4Addr};
+
+    fn endpoint() -> SocketAddr {
+        SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 8080)
+    }
+
+    fn record(volume: &VolumeId, outcome: SwitchOverStage, finished: i64) -> SwitchOverRecord {
+        let started = Utc::now();
+        let mut timing = StageTiming::new(SwitchOverStage::RepublishVolume);
+        timing.attempts = 3;
+        SwitchOverRecord {
+            volume: volume.clone(),
+            node_name: "nn".into(),
+            existing_nqn: "nw".to_string(),
+            new_path: None,
+            started,
+            finished: started + chrono::Duration::seconds(finished),
+            outcome,
+            error: match outcome {
+                SwitchOverStage::Errored => Some("failed".to_string()),
+                _ => None,
+            },
+            stages: vec![timing],
+        }
+    }
+
+    fn history(volume: &VolumeId, records: Vec<SwitchOverRecord>) -> SwitchOverHistory {
+        let mut history = SwitchOverHistory::new(volume.clone());
+        records.into_iter().for_each(|record| history.push(record));
+        history
+    }
+
+    #[test]
+    fn failed_switchovers_listing() {
+        let vol1 = VolumeId::new();
+        let vol2 = VolumeId::new();
+        let histories = vec![
+            history(
+                &vol1,
+                vec![
+                    record(&vol1, SwitchOverStage::Errored, 1),
+                    record(&vol1, SwitchOverStage::Successful, 2),
+                    record(&vol1, SwitchOverStage::Errored, 4),
+                ],
+            ),
+            history(&vol2, vec![record(&vol2, SwitchOverStage::Errored, 3)]),
+        ];
+        let failed = failed_switchovers(histories);
+        // successful switchovers are not listed, and the most recent failures come first
+        let volumes = failed.iter().map(|s| &s.volume_id).collect::<Vec<_>>();
+        assert_eq!(volumes, vec![&vol1, &vol2, &vol1]);
+        assert!(failed.iter().all(|s| s.stage == SwitchOverStage::Errored));
+        assert_eq!(failed[0].retry_count, 3);
+        assert_eq!(failed[0].error, Some("failed".to_string()));
+
+        let histories = (0 .. 2)
+            .map(|_| {
+                let volume = VolumeId::new();
+                let records = (0 .. FAILED_HISTORY as i64)
+                    .map(|i| record(&volume, SwitchOverStage::Errored, i))
+                    .collect();
+                history(&volume, records)
+            })
+            .collect();
+        assert_eq!(failed_switchovers(histories).len(), FAILED_HISTORY);
+    }
+
+    #[test]
+    fn retry_last_failed() {
+        let volume = VolumeId::new();
+        assert!(last_failed(&history(&volume, vec![])).is_none());
+        let succeeded = history(
+            &volume,
+            vec![
+                record(&volume, SwitchOverStage::Errored, 1),
+                record(&volume, SwitchOverStage::Successful, 2),
+            ],
+        );
+        assert!(last_failed(&succeeded).is_none());
+
+        let failed = history(
+            &volume,
+            vec![
+                record(&volume, SwitchOverStage::Successful, 1),
+                record(&volume, SwitchOverStage::Errored, 2),
+            ],
+        );
+        let record = last_failed(&failed).expect("the last switchover has failed");
+        let request = SwitchOverRequest::retry_of(endpoint(), record);
+        // the retry starts from the beginning
+        assert_eq!(request.stage, Stage::Init);
+        assert_eq!(request.retry_count, 0);
+        assert_eq!(request.error, None);
+        assert!(request.stage_timings.is_empty());
+        assert_eq!(request.callback_uri, endpoint());
+        assert_eq!(request.volume_id, volume);
+        assert_eq!(request.node_name, record.node_name);
+        assert_eq!(request.existing_nqn, record.existing_nqn);
+    }
+
+    #[test]
+    fn tracker_cancel_and_retry() {
+        let volume = VolumeId::new();
+        let mut tracker = SwitchOverTracker::default();
+        let request = SwitchOverRequest::new(endpoint(), volume.clone(), "nn".into(), "nw".into());
+        assert!(!tracker.is_current(&request));
+        tracker.in_flight.insert(volume.clone(), request.clone());
+        assert!(tracker.is_current(&request));
+
+        // cancelling stops tracking the request, which its worker notices
+        tracker.in_flight.remove(&volume);
+        assert!(!tracker.is_current(&request));
+
+        // a retry replaces the cancelled request, which must not be mistaken for it
+        let mut retry = request.clone();
+        retry.timestamp = request.timestamp + chrono::Duration::seconds(1);
+        tracker.in_flight.insert(volume.clone(), retry.clone());
+        assert!(tracker.is_current(&retry));
+        assert!(!tracker.is_current(&request));
+    }
+}
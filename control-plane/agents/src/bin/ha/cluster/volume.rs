@@ -1,10 +1,29 @@
 use crate::{
+    core_grpc,
     etcd::EtcdStore,
     nodes::NodeList,
     switchover::{SwitchOverEngine, SwitchOverRequest},
 };
-use common_lib::types::v0::transport::{NodeId, VolumeId};
+use chrono::Utc;
+use common_lib::{
+    transport_api::{ReplyError, ResourceKind},
+    types::v0::{
+        store::switchover::{
+            PathFailureHistory, PathFailureHistoryKey, PathFailureRecord, TargetMoveSpec,
+        },
+        transport::{
+            DestroyShutdownTargets, Filter, NodeId, ReplacePath, RepublishVolume, SwitchOver,
+            Volume, VolumeId, VolumeShareProtocol,
+        },
+    },
+};
+use grpc::operations::{
+    ha_node::{client::NodeAgentClient, traits::NodeAgentOperations},
+    volume::traits::VolumeOperations,
+};
 use std::{convert::TryFrom, net::SocketAddr};
+use tonic::transport::Uri;
+use tracing::{info, warn};
 use utils::NVME_TARGET_NQN_PREFIX;
 
 /// Defines spec for VolumeMover.
@@ -12,13 +31,18 @@ use utils::NVME_TARGET_NQN_PREFIX;
 pub struct VolumeMover {
     etcd: EtcdStore,
     engine: SwitchOverEngine,
+    nodes: NodeList,
 }
 
 impl VolumeMover {
     pub fn new(etcd: EtcdStore, nodes: NodeList) -> Self {
-        let sw = SwitchOverEngine::new(etcd.clone(), nodes);
+        let sw = SwitchOverEngine::new(etcd.clone(), nodes.clone());
 
-        Self { engine: sw, etcd }
+        Self {
+            engine: sw,
+            etcd,
+            nodes,
+        }
     }
 
     /// Switchover build the switchover request for the given nqn and send it to SwitchOverEngine.
@@ -43,10 +67,34 @@ impl VolumeMover {
 
         // calling start_op here to store the request in etcd
         req.start_op(&self.etcd).await?;
-        self.engine.enqueue(req);
+        self.engine.submit(req).await;
         Ok(())
     }
 
+    /// Add the failed paths reported by the node to its persisted path failure history.
+    pub async fn record_path_failures(
+        &self,
+        node: &NodeId,
+        nqns: Vec<String>,
+    ) -> Result<(), anyhow::Error> {
+        let timestamp = Utc::now();
+        self.etcd
+            .update_obj(
+                &PathFailureHistoryKey::new(node.clone()),
+                || PathFailureHistory::new(node.clone()),
+                |history| {
+                    for nqn in nqns {
+                        history.push(PathFailureRecord {
+                            node_name: node.clone(),
+                            nqn,
+                            timestamp,
+                        });
+                    }
+                },
+            )
+            .await
+    }
+
     /// Send batch of switchover request to SwitchOverEngine.
     #[tracing::instrument(level = "info", skip(self), err)]
     pub async fn send_switchover_req(
@@ -56,8 +104,232 @@ impl VolumeMover {
         req.sort();
         for entry in req {
             entry.start_op(&self.etcd).await?;
-            self.engine.enqueue(entry);
+            self.engine.submit(entry).await;
         }
         Ok(())
     }
+
+    /// Get the in-flight and recently failed switchovers, optionally only those of the volume.
+    pub async fn switchovers(
+        &self,
+        volume_id: Option<&VolumeId>,
+    ) -> Result<Vec<SwitchOver>, ReplyError> {
+        self.engine.switchovers(volume_id).await
+    }
+
+    /// Cancel the in-flight switchover of the volume.
+    pub async fn cancel_switchover(&self, volume_id: &VolumeId) -> Result<(), ReplyError> {
+        self.engine.cancel(volume_id).await
+    }
+
+    /// Retry the failed switchover of the volume.
+    pub async fn retry_switchover(&self, volume_id: &VolumeId) -> Result<(), ReplyError> {
+        self.engine.retry(volume_id).await
+    }
+
+    /// Move the target of a healthy published volume, optionally onto the given node.
+    /// The new target is created while the existing one keeps serving, every frontend node is
+    /// then connected to the new target and only then is the existing target shut down and
+    /// destroyed.
+    /// The move is persisted along with the reservation of the volume path, which rejects
+    /// switchovers of the volume until the move completes.
+    #[tracing::instrument(level = "info", skip(self), err)]
+    pub async fn move_target(
+        &self,
+        volume_id: &VolumeId,
+        target_node: Option<NodeId>,
+    ) -> Result<Volume, ReplyError> {
+        let volume = get_volume(volume_id).await?;
+        let frontend_nodes = match volume.spec().active_config() {
+            Some(config) => config.frontend().nodes(),
+            None => vec![],
+        };
+        if frontend_nodes.is_empty() {
+            return Err(ReplyError::failed_precondition(
+                ResourceKind::Volume,
+                volume_id.to_string(),
+                "Volume is not published to any frontend node".to_string(),
+            ));
+        }
+
+        let mut frontends = Vec::with_capacity(frontend_nodes.len());
+        for node in frontend_nodes {
+            let node = NodeId::from(node);
+            match self.nodes.endpoint(&node).await {
+                Some(endpoint) => frontends.push((node, endpoint)),
+                None => {
+                    return Err(ReplyError::failed_precondition(
+                        ResourceKind::Node,
+                        node.to_string(),
+                        "HA node agent is not registered on the frontend node".to_string(),
+                    ))
+                }
+            }
+        }
+
+        if self.engine.in_flight(volume_id).await {
+            return Err(ReplyError::failed_precondition(
+                ResourceKind::SwitchOver,
+                volume_id.to_string(),
+                "Switchover for the volume is already in progress".to_string(),
+            ));
+        }
+        // Hold off switchovers of the volume while its target is being moved, as the
+        // existing path is expected to go away.
+        let nqn = format!("{NVME_TARGET_NQN_PREFIX}{volume_id}");
+        if !self.nodes.reserve_path(nqn.clone(), frontends[0].1).await {
+            return Err(ReplyError::failed_precondition(
+                ResourceKind::SwitchOver,
+                volume_id.to_string(),
+                "Switchover for the volume is already in progress".to_string(),
+            ));
+        }
+
+        let mut target_move = TargetMoveSpec::new(volume_id.clone(), nqn, target_node, frontends);
+        let result = match self.etcd.store_obj(&target_move).await {
+            Ok(()) => self.move_reserved_target(&mut target_move).await,
+            Err(error) => Err(store_error(volume_id, error)),
+        };
+        self.complete_target_move(&target_move).await;
+        result?;
+        get_volume(volume_id).await
+    }
+
+    /// Resume the planned target moves which the previous leader did not complete, keeping
+    /// the volume paths reserved until each move completes.
+    pub async fn resume_target_moves(&self, target_moves: Vec<TargetMoveSpec>) {
+        for mut target_move in target_moves {
+            let endpoint = match target_move.frontends.first() {
+                Some((_, endpoint)) => *endpoint,
+                None => {
+                    self.complete_target_move(&target_move).await;
+                    continue;
+                }
+            };
+            self.nodes
+                .reserve_path(target_move.nqn.clone(), endpoint)
+                .await;
+            let mover = self.clone();
+            tokio::spawn(async move {
+                info!(volume.uuid=%target_move.volume, "Resuming volume target move");
+                if let Err(error) = mover.move_reserved_target(&mut target_move).await {
+                    warn!(volume.uuid=%target_move.volume, %error, "Failed to move volume target");
+                }
+                mover.complete_target_move(&target_move).await;
+            });
+        }
+    }
+
+    /// Create the new target, connect every frontend node to it and then shut down and destroy
+    /// the previous target, persisting the progress of the move along the way.
+    async fn move_reserved_target(
+        &self,
+        target_move: &mut TargetMoveSpec,
+    ) -> Result<(), ReplyError> {
+        let volume_id = target_move.volume.clone();
+        let new_path = match target_move.new_path.clone() {
+            Some(new_path) => new_path,
+            None => {
+                info!(volume.uuid=%volume_id, target_node=?target_move.target_node, "Moving volume target");
+                let republish = RepublishVolume::new(
+                    volume_id.clone(),
+                    target_move.target_node.clone(),
+                    target_move.frontends[0].0.clone(),
+                    VolumeShareProtocol::Nvmf,
+                    false,
+                )
+                .with_deferred_shutdown(true);
+                let volume = core_grpc().volume().republish(&republish, None).await?;
+                let new_path = match volume.state().target {
+                    Some(target) => target.device_uri,
+                    None => {
+                        return Err(ReplyError::failed_precondition(
+                            ResourceKind::Volume,
+                            volume_id.to_string(),
+                            "Couldn't find device uri for the volume".to_string(),
+                        ))
+                    }
+                };
+                target_move.new_path = Some(new_path.clone());
+                target_move.publish_context = volume.spec().publish_context;
+                self.etcd
+                    .store_obj(target_move)
+                    .await
+                    .map_err(|error| store_error(&volume_id, error))?;
+                new_path
+            }
+        };
+
+        let replace = ReplacePath::new(
+            target_move.nqn.clone(),
+            new_path,
+            target_move.publish_context.clone(),
+        )
+        .with_planned(true);
+        for (node, endpoint) in target_move.frontends.clone() {
+            if target_move.connected.contains(&node) {
+                continue;
+            }
+            let uri = Uri::builder()
+                .scheme("http")
+                .authority(endpoint.to_string())
+                .path_and_query("")
+                .build()
+                .map_err(|error| {
+                    ReplyError::invalid_argument(ResourceKind::Node, "endpoint", error.to_string())
+                })?;
+            info!(volume.uuid=%volume_id, node.id=%node, "Sending new volume target to node agent");
+            NodeAgentClient::new(uri, None)
+                .await
+                .replace_path(&replace, None)
+                .await?;
+            target_move.connected.push(node);
+            self.etcd
+                .store_obj(target_move)
+                .await
+                .map_err(|error| store_error(&volume_id, error))?;
+        }
+
+        // Every frontend node is connected to the new target, so the previous target may now be
+        // shut down and destroyed.
+        let destroy = DestroyShutdownTargets::new(volume_id, None);
+        core_grpc()
+            .volume()
+            .destroy_shutdown_target(&destroy, None)
+            .await
+    }
+
+    /// Forget the target move, releasing the reservation of the volume path.
+    async fn complete_target_move(&self, target_move: &TargetMoveSpec) {
+        if let Err(error) = self.etcd.delete_obj(target_move).await {
+            warn!(volume.uuid=%target_move.volume, %error, "Failed to delete the volume target move");
+        }
+        self.nodes.remove_failed_path(target_move.nqn.clone()).await;
+    }
+}
+
+/// Get the volume from the core agent.
+async fn get_volume(volume_id: &VolumeId) -> Result<Volume, ReplyError> {
+    core_grpc()
+        .volume()
+        .get(Filter::Volume(volume_id.clone()), false, None, None)
+        .await?
+        .entries
+        .pop()
+        .ok_or_else(|| {
+            ReplyError::not_found(
+                ResourceKind::Volume,
+                volume_id.to_string(),
+                "Volume not found".to_string(),
+            )
+        })
+}
+
+/// Map a persistent store error of a target move into a `ReplyError`.
+fn store_error(volume_id: &VolumeId, error: anyhow::Error) -> ReplyError {
+    ReplyError::internal_error(
+        ResourceKind::Volume,
+        volume_id.to_string(),
+        format!("Failed to persist the volume target move: {error}"),
+    )
 }
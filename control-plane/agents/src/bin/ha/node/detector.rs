@@ -1,10 +1,11 @@
 use crate::{
-    path_provider::{CachedNvmePathProvider, NvmePath, NvmePathNameCollection},
+    path_provider::{AnaState, CachedNvmePathProvider, NvmePath, NvmePathNameCollection},
+    policy::{DetectionPolicy, PolicyProvider},
     reporter::PathReporter,
     Cli,
 };
 use nvmeadm::nvmf_subsystem::Subsystem;
-use std::{collections::HashMap, convert::From, rc::Rc, sync::Arc};
+use std::{collections::HashMap, convert::From, rc::Rc, sync::Arc, time::Instant};
 use tokio::{
     sync::mpsc::{channel, Receiver, Sender},
     time::{sleep, Duration},
@@ -41,12 +42,18 @@ impl PathRecords {
             paths: HashMap::from([(path, record)]),
         }
     }
-    fn report_connecting(&mut self, ctrlr: &NvmePath, epoch: u64, reporter: &Rc<PathReporter>) {
+    fn report_connecting(
+        &mut self,
+        ctrlr: &NvmePath,
+        epoch: u64,
+        reporter: &Rc<PathReporter>,
+        policy: &DetectionPolicy,
+    ) {
         let path = ctrlr.path().to_string_lossy().to_string();
         let record = self.paths.entry(path.clone()).or_insert_with(|| {
             PathRecord::new(ctrlr.nqn().to_owned(), epoch, path, reporter.clone())
         });
-        record.report_connecting();
+        record.report_connecting(policy);
         record.set_epoch(epoch);
     }
     fn report_live(&mut self, ctrlr: &NvmePath, epoch: u64, reporter: &Rc<PathReporter>) {
@@ -75,6 +82,10 @@ struct PathRecord {
     epoch: u64,
     nqn: String,
     state: PathState,
+    /// When the path entered its current state.
+    since: Instant,
+    /// Whether the failed path has been reported.
+    reported: bool,
     path: String,
     reporter: Rc<PathReporter>,
 }
@@ -87,6 +98,8 @@ impl PathRecord {
             epoch,
             path,
             state: PathState::Good,
+            since: Instant::now(),
+            reported: false,
             reporter,
         }
     }
@@ -106,10 +119,12 @@ impl PathRecord {
     }
 
     /// Trigger state transition based on 'connecting' state of the underlying NVMe controller.
-    fn report_connecting(&mut self) {
+    /// A suspected path is only considered failed once the policy's grace period has elapsed.
+    fn report_connecting(&mut self, policy: &DetectionPolicy) {
         match self.state {
             PathState::Good => {
                 self.state = PathState::Suspected;
+                self.since = Instant::now();
                 tracing::info!(
                     state=%PathState::Suspected,
                     target=self.nqn,
@@ -117,22 +132,39 @@ impl PathRecord {
                     "Target state transition"
                 );
             }
+            PathState::Suspected if self.since.elapsed() < policy.grace_period => {
+                tracing::debug!(
+                    target = self.nqn,
+                    path = self.path,
+                    "Suspected path within its grace period"
+                );
+            }
             PathState::Suspected => {
                 self.state = PathState::Failed;
+                self.since = Instant::now();
                 tracing::error!(
                     state=%PathState::Failed,
                     target=self.nqn,
                     path=self.path,
                     "Target state transition",
                 );
-                self.reporter.report_failed_path(self.nqn.clone());
+                self.reported = self.reporter.report_failed_path(self.nqn.clone(), policy);
             }
-            PathState::Failed => {} // Multiple failures don't cause any state transitions.
+            // Multiple failures don't cause any state transitions, but a report which was held
+            // back by the policy is attempted again.
+            PathState::Failed if !self.reported => {
+                self.reported = self.reporter.report_failed_path(self.nqn.clone(), policy);
+            }
+            PathState::Failed => {}
         }
     }
 
     fn report_live(&mut self) {
-        self.state = PathState::Good;
+        if self.state != PathState::Good {
+            self.state = PathState::Good;
+            self.since = Instant::now();
+            self.reported = false;
+        }
     }
 }
 
@@ -158,7 +190,7 @@ pub(crate) struct NvmeController {
 
 impl NvmeController {
     /// Instantiate Nvme controller for given NQN and system path.
-    fn new(nqn: String, path: String) -> Self {
+    pub(crate) fn new(nqn: String, path: String) -> Self {
         Self { _nqn: nqn, path }
     }
 }
@@ -197,7 +229,8 @@ type NvmeCacheMessage = (NvmePathCacheCommand, Sender<NvmePathCacheCommandRespon
 /// as:
 ///  Good - path is fully functional.
 ///  Suspected - path experiences connectivity problems for the first time.
-///  Failed - path has experienced connectivity problems two times in a row.
+///  Failed - path has experienced connectivity problems two times in a row, and for longer
+///           than the grace period of the detection policy.
 /// Once a path is classified as Failed, it's reported to PathReporter and gets sent to
 /// HA Cluster agent.
 #[derive(Debug)]
@@ -206,6 +239,7 @@ pub struct PathFailureDetector {
     detection_period: Duration,
     suspected_paths: HashMap<String, PathRecords>,
     reporter: Rc<PathReporter>,
+    policies: PolicyProvider,
     cache_channel_rx: Receiver<NvmeCacheMessage>,
     cache_channel_tx: Arc<Sender<NvmeCacheMessage>>,
 }
@@ -213,6 +247,7 @@ pub struct PathFailureDetector {
 impl PathFailureDetector {
     /// Return a new `Self`.
     pub(crate) fn new(args: &Cli) -> Self {
+        let policies = PolicyProvider::new(args);
         let reporter = PathReporter::new(
             args.node_name.clone(),
             *args.retransmission_period,
@@ -226,48 +261,65 @@ impl PathFailureDetector {
             detection_period: *args.detection_period,
             suspected_paths: HashMap::new(),
             reporter: Rc::new(reporter),
+            policies,
             cache_channel_tx: Arc::new(tx),
             cache_channel_rx: rx,
         }
     }
 
-    fn rescan_paths(&mut self, path_collection: &mut NvmePathNameCollection) {
+    async fn rescan_paths(&mut self, path_collection: &mut NvmePathNameCollection) {
         // Update epoch before scanning controllers.
         self.epoch += 1;
 
         tracing::trace!(epoch=self.epoch, suspected=?self.suspected_paths, "Rescanning");
 
+        // Silently ignore errors when we can't get a subsystem for target path,
+        // as we might see lots of false-positive errors when removing a failed path
+        // from a multi-pathed NVMe subsystem.
+        let controllers = path_collection
+            .get_entries()
+            .filter_map(|ctrlr| {
+                Subsystem::new(ctrlr.path())
+                    .ok()
+                    .map(|subsystem| (ctrlr.clone(), subsystem))
+            })
+            .collect::<Vec<_>>();
+
         // Scan all reported NVMe paths on system and check for connectivity.
-        for ctrlr in path_collection.get_entries() {
-            // Silently ignore errors when we can't get a subsystem for target path,
-            // as we might see lots of false-positive errors when removing a failed path
-            // from a multi-pathed NVMe subsystem.
-            if let Ok(subsystem) = Subsystem::new(ctrlr.path()) {
-                match subsystem.state.as_str() {
-                    "connecting" => {
-                        // Add a new record in case no record exists for target NQN.
-                        let rec = self
-                            .suspected_paths
-                            .entry(subsystem.nqn)
-                            .or_insert_with(|| {
-                                PathRecords::new(PathRecord::new(
-                                    ctrlr.nqn().to_owned(),
-                                    self.epoch,
-                                    ctrlr.path().to_string_lossy().to_string(),
-                                    self.reporter.clone(),
-                                ))
-                            });
-                        rec.report_connecting(ctrlr, self.epoch, &self.reporter);
+        for (ctrlr, subsystem) in &controllers {
+            match subsystem.state.as_str() {
+                "connecting" => {
+                    let policy = self.policies.policy(&subsystem.nqn);
+                    if policy.ana_aware && Self::accessible_elsewhere(&controllers, ctrlr) {
+                        tracing::debug!(
+                            target = subsystem.nqn,
+                            "Target is still accessible through another path"
+                        );
+                        continue;
                     }
-                    "live" => {
-                        if let Some(rec) = self.suspected_paths.get_mut(&subsystem.nqn) {
-                            rec.report_live(ctrlr, self.epoch, &self.reporter);
-                        }
+                    self.report_connecting(ctrlr, &subsystem.nqn, &policy);
+                }
+                "live" => {
+                    let policy = self.policies.policy(&subsystem.nqn);
+                    let inaccessible = policy.ana_aware
+                        && matches!(
+                            ctrlr.ana_state(),
+                            Some(state) if !state.is_accessible() && state != AnaState::Change
+                        );
+                    if inaccessible {
+                        self.report_connecting(ctrlr, &subsystem.nqn, &policy);
+                    } else if let Some(rec) = self.suspected_paths.get_mut(&subsystem.nqn) {
+                        rec.report_live(ctrlr, self.epoch, &self.reporter);
                     }
-                    _ => {}
                 }
+                _ => {}
             }
         }
+        self.policies.retain(
+            controllers
+                .iter()
+                .map(|(_, subsystem)| subsystem.nqn.as_str()),
+        );
 
         // Remove all existing records that don't have underlying NVMe controllers:
         // can happen in case controller was removed after it had been identified as
@@ -286,6 +338,35 @@ impl PathFailureDetector {
             .retain(|_nqn, recs| recs.has_broken_paths());
     }
 
+    /// Trigger state transition for a path experiencing connectivity problems.
+    fn report_connecting(&mut self, ctrlr: &NvmePath, nqn: &str, policy: &DetectionPolicy) {
+        // Add a new record in case no record exists for target NQN.
+        let rec = self
+            .suspected_paths
+            .entry(nqn.to_string())
+            .or_insert_with(|| {
+                PathRecords::new(PathRecord::new(
+                    ctrlr.nqn().to_owned(),
+                    self.epoch,
+                    ctrlr.path().to_string_lossy().to_string(),
+                    self.reporter.clone(),
+                ))
+            });
+        rec.report_connecting(ctrlr, self.epoch, &self.reporter, policy);
+    }
+
+    /// Check if the target of the controller is accessible through another live path.
+    fn accessible_elsewhere(controllers: &[(NvmePath, Subsystem)], ctrlr: &NvmePath) -> bool {
+        controllers.iter().any(|(other, subsystem)| {
+            other.path() != ctrlr.path()
+                && other.nqn() == ctrlr.nqn()
+                && subsystem.state == "live"
+                && other
+                    .ana_state()
+                    .map_or(false, |state| state.is_accessible())
+        })
+    }
+
     /// Handle command for NVMe cache.
     fn handle_cache_command(&self, cmd: NvmePathCacheCommand) -> NvmePathCacheCommandResponse {
         match cmd {
@@ -326,7 +407,7 @@ impl PathFailureDetector {
                 },
 
                 // Path rescan loop.
-                _ = sleep(self.detection_period) => self.rescan_paths(&mut path_collection),
+                _ = sleep(self.detection_period) => self.rescan_paths(&mut path_collection).await,
 
                 // Cache command loop.
                 msg = self.cache_channel_rx.recv() => {
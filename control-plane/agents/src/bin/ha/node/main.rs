@@ -2,6 +2,7 @@ use common_lib::{
     transport_api::TimeoutOptions, types::v0::transport::cluster_agent::NodeAgentInfo,
 };
 use grpc::{
+    client::CoreClient,
     csi_node_nvme::nvme_operations_client::NvmeOperationsClient,
     operations::ha_node::{client::ClusterAgentClient, traits::ClusterAgentOperations},
 };
@@ -14,12 +15,14 @@ use tokio::net::UnixStream;
 use tonic::transport::{Channel, Endpoint};
 use tower::service_fn;
 use utils::{
-    package_description, version_info_str, DEFAULT_CLUSTER_AGENT_CLIENT_ADDR,
-    DEFAULT_NODE_AGENT_SERVER_ADDR, NVME_PATH_AGGREGATION_PERIOD, NVME_PATH_CHECK_PERIOD,
-    NVME_PATH_RETRANSMISSION_PERIOD,
+    package_description, version_info_str, CACHE_POLL_PERIOD, DEFAULT_CLUSTER_AGENT_CLIENT_ADDR,
+    DEFAULT_NODE_AGENT_SERVER_ADDR, NODE_AGENT_REGISTRATION_PERIOD, NVME_PATH_AGGREGATION_PERIOD,
+    NVME_PATH_CHECK_PERIOD, NVME_PATH_MIN_REPORT_INTERVAL, NVME_PATH_RETRANSMISSION_PERIOD,
+    NVME_PATH_SUSPECT_GRACE_PERIOD,
 };
 mod detector;
 mod path_provider;
+mod policy;
 mod reporter;
 mod server;
 
@@ -58,6 +61,32 @@ struct Cli {
     #[structopt(short, long, env = "AGGREGATION_PERIOD", default_value = NVME_PATH_AGGREGATION_PERIOD)]
     aggregation_period: humantime::Duration,
 
+    /// Period for refreshing the registration with the cluster-agent, so that a newly elected
+    /// cluster-agent leader learns about this node.
+    #[structopt(long, env = "REGISTRATION_PERIOD", default_value = NODE_AGENT_REGISTRATION_PERIOD)]
+    registration_period: humantime::Duration,
+
+    /// Time for which a path must remain suspected before it's considered failed and reported.
+    #[structopt(long, env = "SUSPECT_GRACE_PERIOD", default_value = NVME_PATH_SUSPECT_GRACE_PERIOD)]
+    suspect_grace_period: humantime::Duration,
+
+    /// Minimum time between two consecutive reports of the same failed path.
+    #[structopt(long, env = "MIN_REPORT_INTERVAL", default_value = NVME_PATH_MIN_REPORT_INTERVAL)]
+    min_report_interval: humantime::Duration,
+
+    /// Take the ANA state of the NVMe paths into account when detecting failed paths.
+    #[structopt(long, env = "ANA_AWARE")]
+    ana_aware: bool,
+
+    /// Core gRPC server URL or address, used to look up the labels of the volumes which may
+    /// override the detection policy. Without it the policy is the same for all volumes.
+    #[structopt(long)]
+    core_grpc: Option<Uri>,
+
+    /// Period for refreshing the detection policy of the volumes.
+    #[structopt(long, default_value = CACHE_POLL_PERIOD)]
+    policy_refresh_period: humantime::Duration,
+
     /// Sends opentelemetry spans to the Jaeger endpoint agent.
     #[structopt(long, short)]
     jaeger: Option<String>,
@@ -71,6 +100,8 @@ static CLUSTER_AGENT_CLIENT: OnceCell<ClusterAgentClient> = OnceCell::new();
 
 static CSI_NODE_NVME_CLIENT: OnceCell<NvmeOperationsClient<Channel>> = OnceCell::new();
 
+static CORE_CLIENT: OnceCell<CoreClient> = OnceCell::new();
+
 pub fn cluster_agent_client() -> &'static ClusterAgentClient {
     CLUSTER_AGENT_CLIENT
         .get()
@@ -83,6 +114,11 @@ pub fn csi_node_nvme_client() -> &'static NvmeOperationsClient<Channel> {
         .expect("Csi node nvme client should have been initialized")
 }
 
+/// Get the Core gRPC client, if configured.
+pub(crate) fn core_grpc() -> Option<&'static CoreClient> {
+    CORE_CLIENT.get()
+}
+
 impl Cli {
     fn args() -> Self {
         Cli::from_args()
@@ -113,19 +149,15 @@ async fn main() {
         ))
         .expect("Expect to be initialized only once");
 
-    if let Err(error) = cluster_agent_client()
-        .register(
-            &NodeAgentInfo::new(cli_args.node_name.clone(), cli_args.grpc_endpoint),
-            None,
-        )
-        .await
-    {
-        tracing::error!(
-            %error,
-            "Failed to register HA Node agent with Cluster HA agent"
-        );
+    if let Some(core_grpc) = cli_args.core_grpc.clone() {
+        CORE_CLIENT
+            .set(CoreClient::new(core_grpc, None).await)
+            .ok()
+            .expect("Expect to be initialized only once");
     }
 
+    register_periodically(&cli_args);
+
     // Instantiate path failure detector along with Nvme cache object.
     let detector = PathFailureDetector::new(&cli_args);
 
@@ -145,6 +177,24 @@ async fn main() {
     }
 }
 
+/// Register with the cluster-agent and keep refreshing the registration, as only the cluster-agent
+/// leader serves requests and a new leader does not know about the previously registered nodes.
+fn register_periodically(cli_args: &Cli) {
+    let node_info = NodeAgentInfo::new(cli_args.node_name.clone(), cli_args.grpc_endpoint);
+    let period: Duration = cli_args.registration_period.into();
+    tokio::spawn(async move {
+        loop {
+            if let Err(error) = cluster_agent_client().register(&node_info, None).await {
+                tracing::error!(
+                    %error,
+                    "Failed to register HA Node agent with Cluster HA agent"
+                );
+            }
+            tokio::time::sleep(period).await;
+        }
+    });
+}
+
 // helper function to connect to csi-node nvme operations svc over uds.
 fn get_nvme_connection_client(
     socket_path: &std::path::Path,
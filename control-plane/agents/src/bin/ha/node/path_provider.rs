@@ -14,7 +14,7 @@ use std::{
 use tokio_udev::{AsyncMonitorSocket, EventType, MonitorBuilder};
 use utils::NVME_TARGET_NQN_PREFIX;
 
-const SYSFS_PREFIX: &str = "/sys/devices/virtual/nvme-fabrics/ctl/";
+pub(crate) const SYSFS_PREFIX: &str = "/sys/devices/virtual/nvme-fabrics/ctl/";
 /// Maximum number of events to process upon cache update (to prevent event flooding).
 const CACHE_EVENTS_BATCH_SIZE: usize = 128;
 
@@ -41,6 +41,49 @@ impl NvmePath {
     pub fn nqn(&self) -> &String {
         &self.nqn
     }
+
+    /// Get the ANA state of the path, from the first namespace path of the controller.
+    /// Returns None if the controller has no namespace paths or doesn't report an ANA state.
+    pub fn ana_state(&self) -> Option<AnaState> {
+        std::fs::read_dir(self.path())
+            .ok()?
+            .flatten()
+            .filter(|entry| entry.path().join("ana_state").exists())
+            .find_map(|entry| {
+                std::fs::read_to_string(entry.path().join("ana_state"))
+                    .ok()
+                    .and_then(|state| AnaState::parse(state.trim()))
+            })
+    }
+}
+
+/// Asymmetric Namespace Access state of an NVMe path.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum AnaState {
+    Optimized,
+    NonOptimized,
+    Inaccessible,
+    PersistentLoss,
+    Change,
+}
+
+impl AnaState {
+    /// Parse the ANA state as reported by sysfs.
+    fn parse(state: &str) -> Option<Self> {
+        match state {
+            "optimized" => Some(Self::Optimized),
+            "non-optimized" => Some(Self::NonOptimized),
+            "inaccessible" => Some(Self::Inaccessible),
+            "persistent-loss" => Some(Self::PersistentLoss),
+            "change" => Some(Self::Change),
+            _ => None,
+        }
+    }
+
+    /// Check if IO can be issued through a path in this state.
+    pub fn is_accessible(&self) -> bool {
+        matches!(self, Self::Optimized | Self::NonOptimized)
+    }
 }
 
 /// Check that NVMe path represents a target created by our product and
@@ -243,3 +286,28 @@ impl NvmePathNameCollection {
         self.entries.values()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::AnaState;
+
+    #[test]
+    fn ana_state_parse() {
+        let states = [
+            ("optimized", AnaState::Optimized, true),
+            ("non-optimized", AnaState::NonOptimized, true),
+            ("inaccessible", AnaState::Inaccessible, false),
+            ("persistent-loss", AnaState::PersistentLoss, false),
+            ("change", AnaState::Change, false),
+        ];
+        for (value, state, accessible) in states {
+            assert_eq!(AnaState::parse(value), Some(state));
+            assert_eq!(state.is_accessible(), accessible);
+        }
+
+        assert_eq!(AnaState::parse(""), None);
+        assert_eq!(AnaState::parse("Optimized"), None);
+        // the sysfs value is expected to be trimmed already
+        assert_eq!(AnaState::parse("optimized\n"), None);
+    }
+}
@@ -0,0 +1,362 @@
+use crate::{core_grpc, Cli};
+use common_lib::{
+    transport_api::{ReplyError, ReplyErrorKind},
+    types::v0::transport::{Filter, VolumeId, VolumeLabels},
+};
+use grpc::operations::volume::traits::VolumeOperations;
+use std::{
+    collections::{HashMap, HashSet},
+    convert::TryFrom,
+    fmt::Display,
+    str::FromStr,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+use utils::{
+    HA_ANA_AWARE_LABEL, HA_FAILOVER_LABEL, HA_GRACE_PERIOD_LABEL, HA_MIN_REPORT_INTERVAL_LABEL,
+    NVME_TARGET_NQN_PREFIX,
+};
+
+/// Path failure detection policy, either the cluster-wide one or the one of a volume.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub(crate) struct DetectionPolicy {
+    /// Time for which a path must remain suspected before it's considered failed.
+    pub(crate) grace_period: Duration,
+    /// Minimum time between two consecutive reports of the same failed path.
+    pub(crate) min_report_interval: Duration,
+    /// Take the ANA state of the paths into account: a path whose target is still accessible
+    /// through another path is not failed, and a live path which is not accessible is suspected.
+    pub(crate) ana_aware: bool,
+    /// Report failed paths to the HA Cluster agent, triggering a switchover.
+    pub(crate) failover: bool,
+}
+
+impl DetectionPolicy {
+    /// Get the cluster-wide policy from the command line arguments.
+    pub(crate) fn new(args: &Cli) -> Self {
+        Self {
+            grace_period: *args.suspect_grace_period,
+            min_report_interval: *args.min_report_interval,
+            ana_aware: args.ana_aware,
+            failover: true,
+        }
+    }
+
+    /// Get a copy of `self` overridden by the given volume labels.
+    /// Labels with invalid values are ignored.
+    fn with_labels(&self, labels: &VolumeLabels) -> Self {
+        let mut policy = self.clone();
+        if let Some(failover) = parse_label(labels, HA_FAILOVER_LABEL, bool::from_str) {
+            policy.failover = failover;
+        }
+        if let Some(period) = parse_label(labels, HA_GRACE_PERIOD_LABEL, humantime::parse_duration)
+        {
+            policy.grace_period = period;
+        }
+        if let Some(interval) = parse_label(
+            labels,
+            HA_MIN_REPORT_INTERVAL_LABEL,
+            humantime::parse_duration,
+        ) {
+            policy.min_report_interval = interval;
+        }
+        if let Some(ana_aware) = parse_label(labels, HA_ANA_AWARE_LABEL, bool::from_str) {
+            policy.ana_aware = ana_aware;
+        }
+        policy
+    }
+}
+
+/// Parse the value of the given label, if present.
+fn parse_label<T, E: Display>(
+    labels: &VolumeLabels,
+    key: &str,
+    parse: impl Fn(&str) -> Result<T, E>,
+) -> Option<T> {
+    let value = labels.get(key)?;
+    match parse(value) {
+        Ok(value) => Some(value),
+        Err(error) => {
+            tracing::warn!(label = key, value, %error, "Ignoring invalid detection policy label");
+            None
+        }
+    }
+}
+
+/// Detection policy of a volume along with the time it was last refreshed.
+#[derive(Debug)]
+struct CachedPolicy {
+    policy: DetectionPolicy,
+    refreshed: Instant,
+}
+
+/// Provides the detection policy for each NVMe target.
+/// The policy of a volume is the cluster-wide one overridden by the labels of the volume, which
+/// are looked up from the core agent in the background the first time the volume is seen and
+/// cached for the refresh period, after which they're refreshed in the background.
+/// The cluster-wide policy applies until the first lookup returns.
+/// If the labels can't be looked up, or if the core agent is not configured, the cluster-wide
+/// policy applies.
+#[derive(Debug, Clone)]
+pub(crate) struct PolicyProvider {
+    default: DetectionPolicy,
+    refresh_period: Duration,
+    volumes: Arc<Mutex<HashMap<VolumeId, CachedPolicy>>>,
+}
+
+impl PolicyProvider {
+    /// Return a new `Self`.
+    pub(crate) fn new(args: &Cli) -> Self {
+        Self {
+            default: DetectionPolicy::new(args),
+            refresh_period: *args.policy_refresh_period,
+            volumes: Default::default(),
+        }
+    }
+
+    /// Get the detection policy for the given NVMe target NQN.
+    /// This never waits for the core agent: the labels are looked up in the background.
+    pub(crate) fn policy(&self, nqn: &str) -> DetectionPolicy {
+        let volume_id = match volume_id(nqn) {
+            Some(volume_id) if core_grpc().is_some() => volume_id,
+            _ => return self.default.clone(),
+        };
+
+        let (policy, lookup) = self.cached_policy(&volume_id);
+        if lookup {
+            self.refresh(volume_id);
+        }
+        policy
+    }
+
+    /// Get the cached policy of the volume and whether its labels need to be looked up.
+    /// A volume which is seen for the first time gets the cluster-wide policy until the lookup
+    /// of its labels returns.
+    fn cached_policy(&self, volume_id: &VolumeId) -> (DetectionPolicy, bool) {
+        let mut volumes = self.volumes.lock().expect("not poisoned");
+        match volumes.get_mut(volume_id) {
+            Some(cached) => {
+                let stale = cached.refreshed.elapsed() >= self.refresh_period;
+                if stale {
+                    cached.refreshed = Instant::now();
+                }
+                (cached.policy.clone(), stale)
+            }
+            None => {
+                volumes.insert(
+                    volume_id.clone(),
+                    CachedPolicy {
+                        policy: self.default.clone(),
+                        refreshed: Instant::now(),
+                    },
+                );
+                (self.default.clone(), true)
+            }
+        }
+    }
+
+    /// Evict the cached policies of the volumes which no longer have a path on this node,
+    /// ie: which have been unpublished or moved elsewhere.
+    pub(crate) fn retain<'a>(&self, nqns: impl Iterator<Item = &'a str>) {
+        let volumes = nqns.filter_map(volume_id).collect::<HashSet<_>>();
+        self.volumes
+            .lock()
+            .expect("not poisoned")
+            .retain(|volume_id, _| volumes.contains(volume_id));
+    }
+
+    /// Look up the labels of the volume in the background and update its cached policy.
+    /// A volume which is no longer published falls back to the cluster-wide policy until its
+    /// paths go away and it's evicted.
+    fn refresh(&self, volume_id: VolumeId) {
+        let default = self.default.clone();
+        let volumes = self.volumes.clone();
+        tokio::spawn(async move {
+            match lookup(&default, &volume_id).await {
+                Ok(Some(policy)) => {
+                    if let Some(cached) = volumes.lock().expect("not poisoned").get_mut(&volume_id)
+                    {
+                        cached.policy = policy;
+                    }
+                }
+                Ok(None) => {
+                    if let Some(cached) = volumes.lock().expect("not poisoned").get_mut(&volume_id)
+                    {
+                        cached.policy = default;
+                    }
+                }
+                Err(error) => {
+                    tracing::warn!(
+                        volume.uuid = %volume_id,
+                        %error,
+                        "Failed to refresh the volume detection policy"
+                    );
+                }
+            }
+        });
+    }
+}
+
+/// Get the volume of the given NVMe target NQN.
+fn volume_id(nqn: &str) -> Option<VolumeId> {
+    nqn.strip_prefix(NVME_TARGET_NQN_PREFIX)
+        .and_then(|uuid| VolumeId::try_from(uuid).ok())
+}
+
+/// Look up the detection policy of the volume from its labels.
+/// Returns `None` if the volume no longer exists or is not published.
+async fn lookup(
+    default: &DetectionPolicy,
+    volume_id: &VolumeId,
+) -> Result<Option<DetectionPolicy>, ReplyError> {
+    let client = match core_grpc() {
+        Some(client) => client.volume(),
+        None => return Ok(Some(default.clone())),
+    };
+    let found = match client
+        .get(Filter::Volume(volume_id.clone()), false, None, None)
+        .await
+    {
+        Ok(found) => found,
+        Err(error) if error.kind == ReplyErrorKind::NotFound => return Ok(None),
+        Err(error) => return Err(error),
+    };
+    Ok(found
+        .entries
+        .first()
+        .map(|volume| volume.spec())
+        .filter(|spec| spec.target().is_some())
+        .map(|spec| match spec.labels {
+            Some(labels) => default.with_labels(&labels),
+            None => default.clone(),
+        }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn default_policy() -> DetectionPolicy {
+        DetectionPolicy {
+            grace_period: Duration::from_secs(5),
+            min_report_interval: Duration::from_secs(10),
+            ana_aware: false,
+            failover: true,
+        }
+    }
+
+    fn labels(labels: &[(&str, &str)]) -> VolumeLabels {
+        labels
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn parse_labels() {
+        let labels = labels(&[
+            (HA_FAILOVER_LABEL, "false"),
+            (HA_GRACE_PERIOD_LABEL, "soon"),
+        ]);
+
+        assert_eq!(
+            parse_label(&labels, HA_FAILOVER_LABEL, bool::from_str),
+            Some(false)
+        );
+        // invalid values are ignored
+        assert_eq!(
+            parse_label(&labels, HA_GRACE_PERIOD_LABEL, humantime::parse_duration),
+            None
+        );
+        // so are missing labels
+        assert_eq!(
+            parse_label(&labels, HA_ANA_AWARE_LABEL, bool::from_str),
+            None
+        );
+    }
+
+    #[test]
+    fn policy_with_labels() {
+        let default = default_policy();
+        assert_eq!(default.with_labels(&labels(&[])), default);
+
+        let policy = default.with_labels(&labels(&[
+            (HA_FAILOVER_LABEL, "false"),
+            (HA_GRACE_PERIOD_LABEL, "1m"),
+            (HA_MIN_REPORT_INTERVAL_LABEL, "30s"),
+            (HA_ANA_AWARE_LABEL, "true"),
+            ("openebs.io/created-by", "msp-operator"),
+        ]));
+        assert_eq!(
+            policy,
+            DetectionPolicy {
+                grace_period: Duration::from_secs(60),
+                min_report_interval: Duration::from_secs(30),
+                ana_aware: true,
+                failover: false,
+            }
+        );
+
+        // the invalid labels keep the default values, the valid ones still apply
+        let policy = default.with_labels(&labels(&[
+            (HA_FAILOVER_LABEL, "no"),
+            (HA_GRACE_PERIOD_LABEL, "-1s"),
+            (HA_MIN_REPORT_INTERVAL_LABEL, "1s"),
+            (HA_ANA_AWARE_LABEL, ""),
+        ]));
+        assert_eq!(
+            policy,
+            DetectionPolicy {
+                min_report_interval: Duration::from_secs(1),
+                ..default
+            }
+        );
+    }
+
+    fn provider(refresh_period: Duration) -> PolicyProvider {
+        PolicyProvider {
+            default: default_policy(),
+            refresh_period,
+            volumes: Default::default(),
+        }
+    }
+
+    #[test]
+    fn cached_policy_lookups() {
+        let provider = provider(Duration::from_secs(60));
+        let volume_id = VolumeId::new();
+
+        // a new volume gets the default policy right away and its labels are looked up
+        assert_eq!(provider.cached_policy(&volume_id), (default_policy(), true));
+        // without another lookup while the first one is still in flight
+        assert_eq!(
+            provider.cached_policy(&volume_id),
+            (default_policy(), false)
+        );
+
+        // once the lookup returns, the policy of the volume applies
+        let policy = default_policy().with_labels(&labels(&[(HA_FAILOVER_LABEL, "false")]));
+        provider
+            .volumes
+            .lock()
+            .unwrap()
+            .get_mut(&volume_id)
+            .unwrap()
+            .policy = policy.clone();
+        assert_eq!(provider.cached_policy(&volume_id), (policy.clone(), false));
+
+        // the volume is evicted once it has no paths left
+        provider.retain(std::iter::empty::<&str>());
+        assert_eq!(provider.cached_policy(&volume_id), (default_policy(), true));
+    }
+
+    #[test]
+    fn cached_policy_refresh() {
+        let provider = provider(Duration::ZERO);
+        let volume_id = VolumeId::new();
+
+        assert_eq!(provider.cached_policy(&volume_id), (default_policy(), true));
+        // a stale policy is still used while it's refreshed
+        assert_eq!(provider.cached_policy(&volume_id), (default_policy(), true));
+    }
+}
@@ -1,6 +1,7 @@
-use crate::{cluster_agent_client, Cli};
+use crate::{cluster_agent_client, policy::DetectionPolicy, Cli};
 use common_lib::types::v0::transport::{FailedPath, ReportFailedPaths};
 use grpc::operations::ha_node::traits::ClusterAgentOperations;
+use std::{cell::RefCell, collections::HashMap, time::Instant};
 use tokio::{
     sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender},
     time::{sleep, Duration},
@@ -13,17 +14,20 @@ const DEFAULT_BATCH_SIZE: usize = 16;
 /// Failed NVMe paths are always aggregated before sending, which avoids
 /// many gRPC invocations for ebery individual failed path and fully utilizes
 /// the ability to report multiple failed paths in one call.
+/// Paths are only reported as allowed by the detection policy of their volume.
 #[derive(Debug)]
 pub struct PathReporter {
     node_name: String,
     channel: UnboundedSender<String>,
     retransmission_period: Duration,
     aggregation_period: Duration,
+    /// When each path was last reported.
+    last_reported: RefCell<HashMap<String, Instant>>,
 }
 
 impl PathReporter {
     /// Get a new `Self` with the given parameters.
-    pub fn new(
+    pub(crate) fn new(
         node_name: String,
         retransmission_period: Duration,
         aggregation_period: Duration,
@@ -35,6 +39,7 @@ impl PathReporter {
             node_name,
             retransmission_period,
             aggregation_period,
+            last_reported: Default::default(),
         };
 
         reporter.start(rx);
@@ -87,9 +92,29 @@ impl PathReporter {
         });
     }
 
-    /// Reports the given NVMe NQN as a failed path.
-    pub fn report_failed_path(&self, nqn: String) {
+    /// Reports the given NVMe NQN as a failed path, unless the given detection policy of its
+    /// volume disallows failover or the path has been reported too recently.
+    /// Returns whether the path was reported.
+    pub(crate) fn report_failed_path(&self, nqn: String, policy: &DetectionPolicy) -> bool {
+        if !policy.failover {
+            tracing::debug!(
+                target = nqn,
+                "Failover disabled by policy, not reporting path"
+            );
+            return false;
+        }
+
+        let mut last_reported = self.last_reported.borrow_mut();
+        if let Some(last) = last_reported.get(&nqn) {
+            if last.elapsed() < policy.min_report_interval {
+                tracing::debug!(target = nqn, "Path reported too recently, delaying report");
+                return false;
+            }
+        }
+        last_reported.insert(nqn.clone(), Instant::now());
+
         self.channel.send(nqn).ok();
+        true
     }
 }
 
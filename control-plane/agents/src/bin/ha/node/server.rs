@@ -1,7 +1,7 @@
 use crate::{
     csi_node_nvme_client,
     detector::{NvmeController, NvmePathCache},
-    path_provider::get_nvme_path_entry,
+    path_provider::{get_nvme_path_entry, SYSFS_PREFIX},
 };
 use agents::errors::{SvcError, SvcError::SubsystemNotFound};
 use common_lib::transport_api::{ErrorChain, ReplyError, ResourceKind};
@@ -122,6 +122,23 @@ fn disconnect_controller(ctrlr: &NvmeController, new_path: String) -> Result<(),
 }
 
 impl NodeAgentSvc {
+    /// Get all the NVMe controllers currently connected to the given target NQN.
+    fn lookup_live_controllers(&self, nqn: String) -> Result<Vec<NvmeController>, ReplyError> {
+        let subsystems = Subsystem::try_from_nqn(nqn.as_str()).map_err(|_| {
+            ReplyError::failed_precondition(
+                ResourceKind::NvmeSubsystem,
+                HA_AGENT_ERR_SOURCE.to_string(),
+                "Failed to lookup controllers".to_string(),
+            )
+        })?;
+        Ok(subsystems
+            .into_iter()
+            .map(|subsystem| {
+                NvmeController::new(nqn.clone(), format!("{SYSFS_PREFIX}{}", subsystem.name))
+            })
+            .collect())
+    }
+
     /// Connect to the NVMe controller.
     /// Waits until the controller is fully connected.
     async fn connect_controller(
@@ -243,18 +260,23 @@ impl NodeAgentOperations for NodeAgentSvc {
         _context: Option<Context>,
     ) -> Result<(), ReplyError> {
         tracing::info!("Replacing failed NVMe path: {:?}", request);
-        // Lookup NVMe controller whose path has failed.
-        let ctrlr = self
-            .path_cache
-            .lookup_controllers(request.target_nqn())
-            .await
-            .map_err(|_| {
-                ReplyError::failed_precondition(
-                    ResourceKind::Nexus,
-                    HA_AGENT_ERR_SOURCE.to_string(),
-                    "Failed to lookup controller".to_string(),
-                )
-            })?;
+        let ctrlr = if request.planned() {
+            // The existing paths are healthy and so not tracked by the path cache, hence
+            // take them straight from the system.
+            self.lookup_live_controllers(request.target_nqn())?
+        } else {
+            // Lookup NVMe controller whose path has failed.
+            self.path_cache
+                .lookup_controllers(request.target_nqn())
+                .await
+                .map_err(|_| {
+                    ReplyError::failed_precondition(
+                        ResourceKind::Nexus,
+                        HA_AGENT_ERR_SOURCE.to_string(),
+                        "Failed to lookup controller".to_string(),
+                    )
+                })?
+        };
 
         // Step 1: populate an additional healthy path to target NQN in addition to
         // existing failed path. Once this additional path is created, client I/O
@@ -35,6 +35,19 @@ pub enum SvcError {
     CordonLabel { node_id: String, label: String },
     #[snafu(display("Node {} does not have a cordon label '{}'", node_id, label))]
     UncordonLabel { node_id: String, label: String },
+    #[snafu(display(
+        "Node {} already has the label '{}' with value '{}'",
+        node_id,
+        key,
+        value
+    ))]
+    NodeLabelExists {
+        node_id: String,
+        key: String,
+        value: String,
+    },
+    #[snafu(display("Node {} does not have the label '{}'", node_id, key))]
+    NodeLabelMissing { node_id: String, key: String },
     #[snafu(display(
         "Timed out after '{:?}' attempting to connect to node '{}' via gRPC endpoint '{}'",
         timeout,
@@ -82,8 +95,26 @@ pub enum SvcError {
     NodeNotFound { node_id: NodeId },
     #[snafu(display("Pool '{}' not found", pool_id))]
     PoolNotFound { pool_id: PoolId },
-    #[snafu(display("Disk list should have only 1 device. Received :{:?}", disks))]
+    #[snafu(display("Disk list should have at least 1 device. Received :{:?}", disks))]
     InvalidPoolDeviceNum { disks: Vec<PoolDeviceUri> },
+    #[snafu(display("Disks {:?} cannot be removed from Pool '{}'", disks, pool_id))]
+    PoolDiskRemoval {
+        pool_id: PoolId,
+        disks: Vec<PoolDeviceUri>,
+    },
+    #[snafu(display(
+        "Pool {} already has the label '{}' with value '{}'",
+        pool_id,
+        key,
+        value
+    ))]
+    PoolLabelExists {
+        pool_id: String,
+        key: String,
+        value: String,
+    },
+    #[snafu(display("Pool {} does not have the label '{}'", pool_id, key))]
+    PoolLabelMissing { pool_id: String, key: String },
     #[snafu(display("Nexus '{}' not found", nexus_id))]
     NexusNotFound { nexus_id: String },
     #[snafu(display("{} '{}' not found", kind.to_string(), id))]
@@ -100,8 +131,12 @@ pub enum SvcError {
     VolumeNotFound { vol_id: String },
     #[snafu(display("Volume '{}' not published", vol_id))]
     VolumeNotPublished { vol_id: String },
+    #[snafu(display("Snapshot '{}' not found", snap_id))]
+    SnapshotNotFound { snap_id: String },
     #[snafu(display("Node '{}' not allowed to access target for volume '{}'", node, vol_id))]
     FrontendNodeNotAllowed { node: String, vol_id: String },
+    #[snafu(display("Volume '{}' is not published to frontend node '{}'", vol_id, node))]
+    FrontendNodeNotPublished { node: String, vol_id: String },
     #[snafu(display("{} {} cannot be shared over invalid protocol '{}'", kind.to_string(), id, share))]
     InvalidShareProtocol {
         kind: ResourceKind,
@@ -129,6 +164,61 @@ pub enum SvcError {
     },
     #[snafu(display("{} '{}' is not shared", kind.to_string(), id))]
     NotShared { kind: ResourceKind, id: String },
+    #[snafu(display(
+        "{} '{}' cannot be resized from {} to {} bytes",
+        kind.to_string(),
+        id,
+        current_size,
+        requested_size
+    ))]
+    ResizeShrink {
+        kind: ResourceKind,
+        id: String,
+        current_size: u64,
+        requested_size: u64,
+    },
+    #[snafu(display("Unable to resize volume '{}' in state '{}'", volume_id, volume_state))]
+    VolumeResizeState {
+        volume_id: String,
+        volume_state: String,
+    },
+    #[snafu(display(
+        "Pool '{}' has {} free bytes but resizing volume '{}' requires {} bytes",
+        pool_id,
+        free,
+        volume_id,
+        required
+    ))]
+    ResizePoolSpace {
+        volume_id: String,
+        pool_id: String,
+        free: u64,
+        required: u64,
+    },
+    #[snafu(display(
+        "Volume '{}' of size '{}' cannot be created from snapshot '{}' of size '{}'",
+        volume_id,
+        requested_size,
+        snap_id,
+        snapshot_size
+    ))]
+    CloneSourceSize {
+        volume_id: String,
+        snap_id: String,
+        snapshot_size: u64,
+        requested_size: u64,
+    },
+    #[snafu(display(
+        "Volume '{}' must use the same encryption as the source '{}' of snapshot '{}'",
+        volume_id,
+        source_id,
+        snap_id
+    ))]
+    CloneSourceEncryption {
+        volume_id: String,
+        snap_id: String,
+        source_id: String,
+    },
     #[snafu(display("Invalid filter value: {:?}", filter))]
     InvalidFilter { filter: Filter },
     #[snafu(display("Operation failed due to insufficient resources"))]
@@ -242,6 +332,14 @@ pub enum SvcError {
     NvmeParseError {},
     #[snafu(display("Nvme connect failed : {}", details))]
     NvmeConnectError { details: String },
+    #[snafu(display(
+        "{} is not supported by the io-engine api which the control-plane is built against",
+        operation
+    ))]
+    Unsupported {
+        kind: ResourceKind,
+        operation: String,
+    },
 }
 
 impl SvcError {
@@ -304,6 +402,36 @@ impl From<SvcError> for ReplyError {
                 source: desc.to_string(),
                 extra: error_str,
             },
+            SvcError::ResizeShrink { kind, .. } => ReplyError {
+                kind: ReplyErrorKind::InvalidArgument,
+                resource: kind,
+                source: desc.to_string(),
+                extra: error_str,
+            },
+            SvcError::VolumeResizeState { .. } => ReplyError {
+                kind: ReplyErrorKind::FailedPrecondition,
+                resource: ResourceKind::Volume,
+                source: desc.to_string(),
+                extra: error.full_string(),
+            },
+            SvcError::ResizePoolSpace { .. } => ReplyError {
+                kind: ReplyErrorKind::ResourceExhausted,
+                resource: ResourceKind::Pool,
+                source: desc.to_string(),
+                extra: error.full_string(),
+            },
+            SvcError::CloneSourceSize { .. } => ReplyError {
+                kind: ReplyErrorKind::InvalidArgument,
+                resource: ResourceKind::Volume,
+                source: desc.to_string(),
+                extra: error.full_string(),
+            },
+            SvcError::CloneSourceEncryption { .. } => ReplyError {
+                kind: ReplyErrorKind::InvalidArgument,
+                resource: ResourceKind::Volume,
+                source: desc.to_string(),
+                extra: error.full_string(),
+            },
             SvcError::InvalidShareProtocol { kind, .. } => ReplyError {
                 kind: ReplyErrorKind::InvalidArgument,
                 resource: kind,
@@ -420,6 +548,20 @@ impl From<SvcError> for ReplyError {
                 extra: error.full_string(),
             },
 
+            SvcError::NodeLabelExists { .. } => ReplyError {
+                kind: ReplyErrorKind::FailedPrecondition,
+                resource: ResourceKind::Node,
+                source: desc.to_string(),
+                extra: error.full_string(),
+            },
+
+            SvcError::NodeLabelMissing { .. } => ReplyError {
+                kind: ReplyErrorKind::FailedPrecondition,
+                resource: ResourceKind::Node,
+                source: desc.to_string(),
+                extra: error.full_string(),
+            },
+
             SvcError::GrpcConnectTimeout { .. } => ReplyError {
                 kind: ReplyErrorKind::Timeout,
                 resource: ResourceKind::Unknown,
@@ -494,6 +636,24 @@ impl From<SvcError> for ReplyError {
                 source: desc.to_string(),
                 extra: error.full_string(),
             },
+            SvcError::PoolDiskRemoval { .. } => ReplyError {
+                kind: ReplyErrorKind::InvalidArgument,
+                resource: ResourceKind::Pool,
+                source: desc.to_string(),
+                extra: error.full_string(),
+            },
+            SvcError::PoolLabelExists { .. } => ReplyError {
+                kind: ReplyErrorKind::FailedPrecondition,
+                resource: ResourceKind::Pool,
+                source: desc.to_string(),
+                extra: error.full_string(),
+            },
+            SvcError::PoolLabelMissing { .. } => ReplyError {
+                kind: ReplyErrorKind::FailedPrecondition,
+                resource: ResourceKind::Pool,
+                source: desc.to_string(),
+                extra: error.full_string(),
+            },
             SvcError::ReplicaNotFound { .. } => ReplyError {
                 kind: ReplyErrorKind::NotFound,
                 resource: ResourceKind::Replica,
@@ -530,6 +690,12 @@ impl From<SvcError> for ReplyError {
                 source: desc.to_string(),
                 extra: error.full_string(),
             },
+            SvcError::SnapshotNotFound { .. } => ReplyError {
+                kind: ReplyErrorKind::NotFound,
+                resource: ResourceKind::VolumeSnapshot,
+                source: desc.to_string(),
+                extra: error.full_string(),
+            },
             SvcError::VolumeNotPublished { .. } => ReplyError {
                 kind: ReplyErrorKind::NotPublished,
                 resource: ResourceKind::Volume,
@@ -548,6 +714,12 @@ impl From<SvcError> for ReplyError {
                 source: desc.to_string(),
                 extra: error.full_string(),
             },
+            SvcError::FrontendNodeNotPublished { .. } => ReplyError {
+                kind: ReplyErrorKind::NotPublished,
+                resource: ResourceKind::Volume,
+                source: desc.to_string(),
+                extra: error.full_string(),
+            },
             SvcError::WatchResourceNotFound { kind } => ReplyError {
                 kind: ReplyErrorKind::NotFound,
                 resource: kind,
@@ -686,6 +858,12 @@ impl From<SvcError> for ReplyError {
                 source: desc.to_string(),
                 extra: error.full_string(),
             },
+            SvcError::Unsupported { ref kind, .. } => ReplyError {
+                kind: ReplyErrorKind::Unimplemented,
+                resource: kind.clone(),
+                source: desc.to_string(),
+                extra: error.full_string(),
+            },
         }
     }
 }
@@ -131,6 +131,11 @@ impl AgentToIoEngine for transport::CreateReplica {
             size: self.size,
             share: self.share as i32,
             allowed_hosts: self.allowed_hosts.clone().into_vec(),
+            #[cfg(feature = "replica-encryption")]
+            encryption_key_ref: self
+                .encryption
+                .as_ref()
+                .map(|encryption| encryption.key_ref.clone()),
         }
     }
 }
@@ -155,6 +160,16 @@ impl AgentToIoEngine for transport::UnshareReplica {
     }
 }
 
+impl AgentToIoEngine for transport::ResizeReplica {
+    type IoEngineMessage = v1_rpc::replica::ResizeReplicaRequest;
+    fn to_rpc(&self) -> Self::IoEngineMessage {
+        Self::IoEngineMessage {
+            uuid: ReplicaName::from_opt_uuid(self.name.as_ref(), &self.uuid).into(),
+            requested_size: self.requested_size,
+        }
+    }
+}
+
 impl AgentToIoEngine for transport::DestroyReplica {
     type IoEngineMessage = v1_rpc::replica::DestroyReplicaRequest;
     fn to_rpc(&self) -> Self::IoEngineMessage {
@@ -164,6 +179,112 @@ impl AgentToIoEngine for transport::DestroyReplica {
     }
 }
 
+impl AgentToIoEngine for transport::CreateReplicaSnapshot {
+    type IoEngineMessage = v1_rpc::snapshot::CreateReplicaSnapshotRequest;
+    fn to_rpc(&self) -> Self::IoEngineMessage {
+        Self::IoEngineMessage {
+            replica_uuid: self.replica_id.to_string(),
+            snapshot_uuid: self.snap_id.to_string(),
+            snapshot_name: self.snap_name.clone(),
+            entity_id: self.entity_id.clone(),
+            txn_id: self.txn_id.clone(),
+            ..Default::default()
+        }
+    }
+}
+
+impl AgentToIoEngine for transport::DestroyReplicaSnapshot {
+    type IoEngineMessage = v1_rpc::snapshot::DestroySnapshotRequest;
+    fn to_rpc(&self) -> Self::IoEngineMessage {
+        Self::IoEngineMessage {
+            snapshot_uuid: self.snap_id.to_string(),
+            ..Default::default()
+        }
+    }
+}
+
+impl AgentToIoEngine for transport::CreateSnapshotClone {
+    type IoEngineMessage = v1_rpc::snapshot::CreateSnapshotCloneRequest;
+    fn to_rpc(&self) -> Self::IoEngineMessage {
+        let replica = &self.replica;
+        Self::IoEngineMessage {
+            snapshot_uuid: self.snap_id.to_string(),
+            clone_name: ReplicaName::from_opt_uuid(replica.name.as_ref(), &replica.uuid).into(),
+            clone_uuid: replica.uuid.to_string(),
+        }
+    }
+}
+
+impl AgentToIoEngine for transport::CreateNexusSnapshot {
+    type IoEngineMessage = v1_rpc::snapshot::NexusCreateSnapshotRequest;
+    fn to_rpc(&self) -> Self::IoEngineMessage {
+        Self::IoEngineMessage {
+            nexus_uuid: self.nexus.to_string(),
+            entity_id: self.entity_id.clone(),
+            txn_id: self.txn_id.clone(),
+            snapshot_name: self.snap_name.clone(),
+            replicas: self
+                .replicas
+                .iter()
+                .map(
+                    |replica| v1_rpc::snapshot::NexusCreateSnapshotReplicaDescriptor {
+                        replica_uuid: replica.replica_id.to_string(),
+                        snapshot_uuid: Some(replica.snap_id.to_string()),
+                        skip: false,
+                    },
+                )
+                .collect(),
+        }
+    }
+}
+
+/// convert rpc nexus snapshot response to an agent nexus snapshot status
+pub fn rpc_nexus_snapshot_to_agent(
+    response: &v1_rpc::snapshot::NexusCreateSnapshotResponse,
+) -> Result<transport::NexusSnapshotStatus, SvcError> {
+    let replica_id = |uuid: &str| {
+        ReplicaId::try_from(uuid).map_err(|_| SvcError::InvalidUuid {
+            uuid: uuid.to_string(),
+            kind: ResourceKind::Replica,
+        })
+    };
+    let mut status = transport::NexusSnapshotStatus::default();
+    for replica in &response.replicas_done {
+        let uuid = replica_id(&replica.replica_uuid)?;
+        match replica.status_code {
+            0 => status.replicas_done.push(uuid),
+            code => status.replicas_failed.push((uuid, code)),
+        }
+    }
+    for replica in &response.replicas_skipped {
+        status.replicas_skipped.push(replica_id(replica)?);
+    }
+    Ok(status)
+}
+
+/// convert rpc snapshot info to an agent replica snapshot
+pub fn rpc_replica_snapshot_to_agent(
+    rpc_snapshot: &v1_rpc::snapshot::SnapshotInfo,
+    id: &NodeId,
+) -> Result<transport::ReplicaSnapshotInfo, SvcError> {
+    Ok(transport::ReplicaSnapshotInfo {
+        node: id.clone(),
+        snap_id: transport::SnapshotId::try_from(rpc_snapshot.snapshot_uuid.as_str()).map_err(
+            |_| SvcError::InvalidUuid {
+                uuid: rpc_snapshot.snapshot_uuid.clone(),
+                kind: ResourceKind::VolumeSnapshot,
+            },
+        )?,
+        replica_id: ReplicaId::try_from(rpc_snapshot.source_uuid.as_str()).map_err(|_| {
+            SvcError::InvalidUuid {
+                uuid: rpc_snapshot.source_uuid.clone(),
+                kind: ResourceKind::Replica,
+            }
+        })?,
+        size: rpc_snapshot.snapshot_size,
+    })
+}
+
 /// convert rpc replica to a agent replica
 pub fn rpc_replica_to_agent(
     rpc_replica: &v1_rpc::replica::Replica,
@@ -266,6 +387,8 @@ impl AgentToIoEngine for transport::CreateNexus {
             .config
             .clone()
             .unwrap_or_else(|| NexusNvmfConfig::default().with_no_resv());
+        #[cfg(feature = "nexus-qos")]
+        let qos = self.qos.clone().unwrap_or_default();
         Self::IoEngineMessage {
             name: self.name(),
             uuid: self.uuid.clone().into(),
@@ -282,6 +405,15 @@ impl AgentToIoEngine for transport::CreateNexus {
             preempt_policy: v1_rpc::nexus::NexusNvmePreemption::from(ExternalType(
                 nexus_config.preempt_policy(),
             )) as i32,
+            // A limit of 0 means the nexus is not limited in that regard.
+            #[cfg(feature = "nexus-qos")]
+            read_iops: qos.read_iops.unwrap_or_default(),
+            #[cfg(feature = "nexus-qos")]
+            write_iops: qos.write_iops.unwrap_or_default(),
+            #[cfg(feature = "nexus-qos")]
+            read_mbps: qos.read_mbps.unwrap_or_default(),
+            #[cfg(feature = "nexus-qos")]
+            write_mbps: qos.write_mbps.unwrap_or_default(),
         }
     }
 }
@@ -316,6 +448,40 @@ impl AgentToIoEngine for transport::UnshareNexus {
     }
 }
 
+impl AgentToIoEngine for transport::ResizeNexus {
+    type IoEngineMessage = v1_rpc::nexus::ResizeNexusRequest;
+    fn to_rpc(&self) -> Self::IoEngineMessage {
+        Self::IoEngineMessage {
+            uuid: self.uuid.clone().into(),
+            requested_size: self.requested_size,
+        }
+    }
+}
+
+#[cfg(feature = "nexus-qos")]
+impl AgentToIoEngine for transport::SetNexusQos {
+    type IoEngineMessage = v1_rpc::nexus::SetNexusQosRequest;
+    /// A limit of 0 means the nexus is not limited in that regard.
+    fn to_rpc(&self) -> Self::IoEngineMessage {
+        Self::IoEngineMessage {
+            uuid: self.uuid.clone().into(),
+            read_iops: self.qos.read_iops.unwrap_or_default(),
+            write_iops: self.qos.write_iops.unwrap_or_default(),
+            read_mbps: self.qos.read_mbps.unwrap_or_default(),
+            write_mbps: self.qos.write_mbps.unwrap_or_default(),
+        }
+    }
+}
+
+#[cfg(feature = "rebuild-bandwidth")]
+impl AgentToIoEngine for transport::SetRebuildBandwidth {
+    type IoEngineMessage = v1_rpc::nexus::SetRebuildBandwidthRequest;
+    /// A limit of 0 means the rebuilds are not limited.
+    fn to_rpc(&self) -> Self::IoEngineMessage {
+        Self::IoEngineMessage { mbps: self.mbps }
+    }
+}
+
 impl AgentToIoEngine for transport::AddNexusChild {
     type IoEngineMessage = v1_rpc::nexus::AddChildNexusRequest;
     fn to_rpc(&self) -> Self::IoEngineMessage {
@@ -411,6 +577,19 @@ impl AgentToIoEngine for transport::CreatePool {
     }
 }
 
+#[cfg(feature = "pool-grow")]
+impl AgentToIoEngine for transport::ExpandPool {
+    type IoEngineMessage = v1_rpc::pool::GrowPoolRequest;
+    /// This converts Control plane ExpandPool struct to IO Engine gRPC message.
+    fn to_rpc(&self) -> Self::IoEngineMessage {
+        Self::IoEngineMessage {
+            name: self.id.clone().into(),
+            uuid: None,
+            disks: self.disks.iter().map(|d| d.to_string()).collect(),
+        }
+    }
+}
+
 impl AgentToIoEngine for transport::DestroyPool {
     type IoEngineMessage = v1_rpc::pool::DestroyPoolRequest;
     /// This converts Control plane DeletePool struct to IO Engine gRPC message.
@@ -4,7 +4,8 @@ use common_lib::types::v0::openapi::{
     clients::tower::StatusCode,
     models::{
         CreateVolumeBody, Node, NodeTopology, Pool, PoolTopology, PublishVolumeBody, RestJsonError,
-        Topology, Volume, VolumePolicy, VolumeShareProtocol, Volumes,
+        Topology, Volume, VolumeAccessMode, VolumeContentSource, VolumeEncryption, VolumePolicy,
+        VolumeShareProtocol, VolumeSnapshot, Volumes,
     },
 };
 use std::collections::HashMap;
@@ -30,6 +31,8 @@ pub enum ApiClientError {
     MalformedUrl(String),
     /// Invalid argument.
     InvalidArgument(String),
+    /// The operation is not supported by the control plane.
+    Unimplemented(String),
 }
 
 /// Placeholder for volume topology for volume creation operation.
@@ -62,6 +65,8 @@ impl From<clients::tower::Error<RestJsonError>> for ApiClientError {
                         Self::ResourceNotExists(response.to_string())
                     } else if response.status() == StatusCode::UNPROCESSABLE_ENTITY {
                         Self::ResourceAlreadyExists(response.to_string())
+                    } else if response.status() == StatusCode::NOT_IMPLEMENTED {
+                        Self::Unimplemented(response.to_string())
                     } else {
                         Self::GenericOperation(response.to_string())
                     }
@@ -188,6 +193,7 @@ impl IoEngineApiClient {
     /// Create a volume of target size and provision storage resources for it.
     /// This operation is not idempotent, so the caller is responsible for taking
     /// all actions with regards to idempotency.
+    #[allow(clippy::too_many_arguments)]
     #[instrument(fields(volume.uuid = %volume_id), skip(volume_id))]
     pub(crate) async fn create_volume(
         &self,
@@ -196,6 +202,9 @@ impl IoEngineApiClient {
         size: u64,
         volume_topology: CreateVolumeTopology,
         thin: bool,
+        content_source: Option<VolumeContentSource>,
+        policy: VolumePolicy,
+        encryption: Option<VolumeEncryption>,
     ) -> Result<Volume, ApiClientError> {
         let topology =
             Topology::new_all(volume_topology.node_topology, volume_topology.pool_topology);
@@ -205,8 +214,10 @@ impl IoEngineApiClient {
             size,
             thin,
             topology: Some(topology),
-            policy: VolumePolicy::new_all(true),
+            policy,
             labels: None,
+            content_source,
+            encryption,
         };
 
         let result = self
@@ -230,6 +241,21 @@ impl IoEngineApiClient {
         Ok(())
     }
 
+    /// Grow the volume to the requested size, resizing all of its storage resources.
+    #[instrument(fields(volume.uuid = %volume_id), skip(volume_id))]
+    pub(crate) async fn resize_volume(
+        &self,
+        volume_id: &uuid::Uuid,
+        size: u64,
+    ) -> Result<Volume, ApiClientError> {
+        let result = self
+            .rest_client
+            .volumes_api()
+            .put_volume_size(volume_id, size)
+            .await?;
+        Ok(result.into_body())
+    }
+
     /// Check HTTP status code, handle DELETE idempotency transparently.
     pub(crate) fn delete_idempotent<T>(
         result: Result<clients::tower::ResponseContent<T>, clients::tower::Error<RestJsonError>>,
@@ -285,16 +311,19 @@ impl IoEngineApiClient {
     }
 
     /// Unpublish volume (i.e. destroy a target which exposes the volume).
+    /// If a frontend node is specified, the volume is only unpublished from that node and the
+    /// target is kept for any other frontend nodes.
     #[instrument(fields(volume.uuid = %volume_id), skip(volume_id))]
     pub(crate) async fn unpublish_volume(
         &self,
         volume_id: &uuid::Uuid,
         force: bool,
+        frontend_node: Option<&str>,
     ) -> Result<(), ApiClientError> {
         Self::delete_idempotent(
             self.rest_client
                 .volumes_api()
-                .del_volume_target(volume_id, Some(force))
+                .del_volume_target(volume_id, Some(force), frontend_node)
                 .await,
             true,
         )?;
@@ -310,6 +339,7 @@ impl IoEngineApiClient {
         node: Option<&str>,
         protocol: VolumeShareProtocol,
         frontend_node: String,
+        access_mode: VolumeAccessMode,
         publish_context: &HashMap<String, String>,
     ) -> Result<Volume, ApiClientError> {
         let publish_volume_body = PublishVolumeBody::new_all(
@@ -319,6 +349,7 @@ impl IoEngineApiClient {
             protocol,
             None,
             frontend_node,
+            access_mode,
         );
         let volume = self
             .rest_client
@@ -327,4 +358,53 @@ impl IoEngineApiClient {
             .await?;
         Ok(volume.into_body())
     }
+
+    /// Create a snapshot of the given volume.
+    #[instrument(fields(volume.uuid = %volume_id, snapshot.uuid = %snapshot_id), skip(volume_id, snapshot_id))]
+    pub(crate) async fn create_volume_snapshot(
+        &self,
+        volume_id: &uuid::Uuid,
+        snapshot_id: &uuid::Uuid,
+    ) -> Result<VolumeSnapshot, ApiClientError> {
+        let snapshot = self
+            .rest_client
+            .snapshots_api()
+            .put_volume_snapshot(volume_id, snapshot_id)
+            .await?;
+        Ok(snapshot.into_body())
+    }
+
+    /// Delete a volume snapshot.
+    /// This operation is idempotent, so the caller does not see errors indicating
+    /// absence of the resource.
+    #[instrument(fields(snapshot.uuid = %snapshot_id), skip(snapshot_id))]
+    pub(crate) async fn delete_volume_snapshot(
+        &self,
+        snapshot_id: &uuid::Uuid,
+    ) -> Result<(), ApiClientError> {
+        Self::delete_idempotent(
+            self.rest_client
+                .snapshots_api()
+                .del_snapshot(snapshot_id)
+                .await,
+            true,
+        )?;
+        debug!(snapshot.uuid=%snapshot_id, "Volume Snapshot successfully deleted");
+        Ok(())
+    }
+
+    /// List volume snapshots, optionally filtered by the source volume and the snapshot uuid.
+    #[instrument(skip(self))]
+    pub(crate) async fn list_volume_snapshots(
+        &self,
+        volume_id: Option<&uuid::Uuid>,
+        snapshot_id: Option<&uuid::Uuid>,
+    ) -> Result<Vec<VolumeSnapshot>, ApiClientError> {
+        let response = self
+            .rest_client
+            .snapshots_api()
+            .get_volumes_snapshots(snapshot_id, volume_id)
+            .await?;
+        Ok(response.into_body().entries)
+    }
 }
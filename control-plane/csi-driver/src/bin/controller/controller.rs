@@ -1,13 +1,13 @@
 use crate::{ApiClientError, CreateVolumeTopology, CsiControllerConfig, IoEngineApiClient};
 
 use common_lib::types::v0::openapi::models::{
-    LabelledTopology, NodeSpec, NodeStatus, Pool, PoolStatus, PoolTopology, SpecStatus, Volume,
-    VolumeShareProtocol,
+    self, LabelledTopology, NodeSpec, NodeStatus, NodeTopology, Pool, PoolStatus, PoolTopology,
+    SpecStatus, Volume, VolumeShareProtocol, VolumeSnapshot,
 };
 use rpc::csi::{Topology as CsiTopology, *};
 use utils::{CREATED_BY_KEY, DSP_OPERATOR};
 
-use csi_driver::context::{CreateParams, PublishParams};
+use csi_driver::context::{CreateParams, Parameters, PublishParams};
 use regex::Regex;
 use std::collections::HashMap;
 use tonic::{Response, Status};
@@ -17,16 +17,32 @@ use uuid::Uuid;
 const OPENEBS_TOPOLOGY_KEY: &str = "openebs.io/nodename";
 const VOLUME_NAME_PATTERN: &str =
     r"pvc-([0-9a-f]{8}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{12})";
+const SNAPSHOT_NAME_PATTERN: &str =
+    r"snapshot-([0-9a-f]{8}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{12})";
 
 #[derive(Debug, Default)]
 pub(crate) struct CsiControllerSvc {}
 
-/// Check whether target volume capabilities are valid. As of now, only
-/// SingleNodeWriter capability is supported.
+/// The volume access modes which are supported.
+const SUPPORTED_ACCESS_MODES: [volume_capability::access_mode::Mode; 3] = [
+    volume_capability::access_mode::Mode::SingleNodeWriter,
+    volume_capability::access_mode::Mode::SingleNodeMultiWriter,
+    volume_capability::access_mode::Mode::MultiNodeReaderOnly,
+];
+
+/// Check whether the given access mode is supported.
+fn supported_access_mode(mode: i32) -> bool {
+    SUPPORTED_ACCESS_MODES
+        .iter()
+        .any(|supported| *supported as i32 == mode)
+}
+
+/// Check whether target volume capabilities are valid. As of now, only the
+/// SingleNodeWriter, SingleNodeMultiWriter and MultiNodeReaderOnly capabilities are supported.
 fn check_volume_capabilities(capabilities: &[VolumeCapability]) -> Result<(), tonic::Status> {
     for c in capabilities {
         if let Some(access_mode) = c.access_mode.as_ref() {
-            if access_mode.mode != volume_capability::access_mode::Mode::SingleNodeWriter as i32 {
+            if !supported_access_mode(access_mode.mode) {
                 return Err(Status::invalid_argument(format!(
                     "Invalid volume access mode: {:?}",
                     access_mode.mode
@@ -37,6 +53,23 @@ fn check_volume_capabilities(capabilities: &[VolumeCapability]) -> Result<(), to
     Ok(())
 }
 
+/// Get the volume access mode requested by the volume capability.
+fn access_mode(capability: &VolumeCapability) -> models::VolumeAccessMode {
+    match capability
+        .access_mode
+        .as_ref()
+        .and_then(|access_mode| volume_capability::access_mode::Mode::from_i32(access_mode.mode))
+    {
+        Some(volume_capability::access_mode::Mode::MultiNodeReaderOnly) => {
+            models::VolumeAccessMode::MultiNodeReaderOnly
+        }
+        Some(volume_capability::access_mode::Mode::SingleNodeMultiWriter) => {
+            models::VolumeAccessMode::SingleNodeMultiWriter
+        }
+        _ => models::VolumeAccessMode::SingleNodeWriter,
+    }
+}
+
 /// Parse string protocol into REST API protocol enum.
 fn parse_protocol(proto: Option<&String>) -> Result<VolumeShareProtocol, Status> {
     match proto.map(|s| s.as_str()) {
@@ -57,10 +90,85 @@ fn get_volume_share_location(volume: &Volume) -> Option<(String, String)> {
         .map(|nexus| (nexus.node.to_string(), nexus.device_uri.to_string()))
 }
 
+/// Convert a volume snapshot into its CSI representation.
+fn csi_snapshot(snapshot: &VolumeSnapshot) -> Snapshot {
+    let creation_time = snapshot
+        .state
+        .timestamp
+        .as_ref()
+        .and_then(|t| chrono::DateTime::parse_from_rfc3339(t).ok())
+        .map(|t| prost_types::Timestamp {
+            seconds: t.timestamp(),
+            nanos: t.timestamp_subsec_nanos() as i32,
+        });
+    Snapshot {
+        size_bytes: snapshot.state.size as i64,
+        snapshot_id: snapshot.spec.uuid.to_string(),
+        source_volume_id: snapshot.spec.source_volume.to_string(),
+        creation_time,
+        ready_to_use: snapshot.state.ready_as_source,
+    }
+}
+
+/// Convert the CSI content source of a new volume into the source of its replicas.
+fn volume_content_source(
+    source: &VolumeContentSource,
+) -> Result<models::VolumeContentSource, Status> {
+    match &source.r#type {
+        Some(volume_content_source::Type::Volume(volume)) => {
+            let uuid = Uuid::parse_str(&volume.volume_id).map_err(|_e| {
+                Status::invalid_argument(format!(
+                    "Malformed source volume UUID: {}",
+                    volume.volume_id
+                ))
+            })?;
+            Ok(models::VolumeContentSource::new_all(
+                models::VolumeContentSourceType::Volume,
+                uuid,
+            ))
+        }
+        Some(volume_content_source::Type::Snapshot(snapshot)) => {
+            let uuid = Uuid::parse_str(&snapshot.snapshot_id).map_err(|_e| {
+                Status::invalid_argument(format!(
+                    "Malformed source snapshot UUID: {}",
+                    snapshot.snapshot_id
+                ))
+            })?;
+            Ok(models::VolumeContentSource::new_all(
+                models::VolumeContentSourceType::Snapshot,
+                uuid,
+            ))
+        }
+        None => Err(Status::invalid_argument(
+            "Volume content source type is not provided",
+        )),
+    }
+}
+
+/// Convert the source of a volume into a CSI volume content source.
+fn csi_content_source(source: &models::VolumeContentSource) -> VolumeContentSource {
+    let r#type = match source.source_type {
+        models::VolumeContentSourceType::Volume => {
+            volume_content_source::Type::Volume(volume_content_source::VolumeSource {
+                volume_id: source.uuid.to_string(),
+            })
+        }
+        models::VolumeContentSourceType::Snapshot => {
+            volume_content_source::Type::Snapshot(volume_content_source::SnapshotSource {
+                snapshot_id: source.uuid.to_string(),
+            })
+        }
+    };
+    VolumeContentSource {
+        r#type: Some(r#type),
+    }
+}
+
 impl From<ApiClientError> for Status {
     fn from(error: ApiClientError) -> Self {
         match error {
             ApiClientError::ResourceNotExists(reason) => Status::not_found(reason),
+            ApiClientError::Unimplemented(reason) => Status::unimplemented(reason),
             error => Status::internal(format!("Operation failed: {:?}", error)),
         }
     }
@@ -75,11 +183,15 @@ fn check_existing_volume(
     replica_count: u8,
     size: u64,
     thin: bool,
+    content_source: &Option<models::VolumeContentSource>,
+    encryption: &Option<models::VolumeEncryption>,
 ) -> Result<(), Status> {
     // Check if the existing volume is compatible, which means
     //  - number of replicas is equal or greater
     //  - size is equal or greater
     //  - volume is fully created
+    //  - volume was populated from the same source
+    //  - volume is encrypted with the same key
     let spec = &volume.spec;
 
     if spec.status != SpecStatus::Created {
@@ -110,9 +222,44 @@ fn check_existing_volume(
         )));
     }
 
+    if &spec.content_source != content_source {
+        return Err(Status::already_exists(format!(
+            "Existing volume {} has content source {:?} ({:?} requested)",
+            spec.uuid, spec.content_source, content_source
+        )));
+    }
+
+    if &spec.encryption != encryption {
+        return Err(Status::already_exists(format!(
+            "Existing volume {} has encryption {:?} ({:?} requested)",
+            spec.uuid, spec.encryption, encryption
+        )));
+    }
+
     Ok(())
 }
 
+/// Get the encryption of a new volume, if `Parameters::Encrypted` is set.
+/// The key reference is provided through the `Parameters::EncryptionKeyRef` secret so that it's
+/// not exposed in the StorageClass.
+fn volume_encryption(
+    context: &CreateParams,
+    secrets: &HashMap<String, String>,
+) -> Result<Option<models::VolumeEncryption>, Status> {
+    if !context.encrypted() {
+        return Ok(None);
+    }
+    match secrets.get(Parameters::EncryptionKeyRef.as_ref()) {
+        Some(key_ref) if !key_ref.is_empty() => {
+            Ok(Some(models::VolumeEncryption::new(key_ref.clone())))
+        }
+        _ => Err(Status::invalid_argument(format!(
+            "Encrypted volumes require the '{}' secret",
+            Parameters::EncryptionKeyRef.as_ref()
+        ))),
+    }
+}
+
 struct VolumeTopologyMapper {}
 
 impl VolumeTopologyMapper {
@@ -139,12 +286,6 @@ impl rpc::csi::controller_server::Controller for CsiControllerSvc {
         let args = request.into_inner();
         tracing::trace!(request = ?args);
 
-        if args.volume_content_source.is_some() {
-            return Err(Status::invalid_argument(
-                "Source for create volume is not supported",
-            ));
-        }
-
         // k8s uses names pvc-{uuid} and we use uuid further as ID in SPDK so we
         // must require it.
         let re = Regex::new(VOLUME_NAME_PATTERN).unwrap();
@@ -161,6 +302,12 @@ impl rpc::csi::controller_server::Controller for CsiControllerSvc {
 
         check_volume_capabilities(&args.volume_capabilities)?;
 
+        let content_source = args
+            .volume_content_source
+            .as_ref()
+            .map(volume_content_source)
+            .transpose()?;
+
         // Check volume size.
         let size = match args.capacity_range {
             Some(range) => {
@@ -180,9 +327,10 @@ impl rpc::csi::controller_server::Controller for CsiControllerSvc {
 
         let context = CreateParams::try_from(&args.parameters)?;
         let replica_count = context.replica_count();
+        let thin = context.thin();
+        let encryption = volume_encryption(&context, &args.secrets)?;
 
-        let mut inclusive_label_topology: HashMap<String, String> = HashMap::new();
-
+        let mut inclusive_label_topology = context.pool_affinity().clone();
         inclusive_label_topology.insert(String::from(CREATED_BY_KEY), String::from(DSP_OPERATOR));
 
         let u = Uuid::parse_str(&volume_uuid).map_err(|_e| {
@@ -192,18 +340,20 @@ impl rpc::csi::controller_server::Controller for CsiControllerSvc {
 
         let vt_mapper = VolumeTopologyMapper::init().await?;
 
-        let thin = match args.parameters.get("thin") {
-            Some(value) => value == "true",
-            None => false,
-        };
-
         // First check if the volume already exists.
         match IoEngineApiClient::get_client()
             .get_volume_for_create(&u)
             .await
         {
             Ok(volume) => {
-                check_existing_volume(&volume, replica_count, size, thin)?;
+                check_existing_volume(
+                    &volume,
+                    replica_count,
+                    size,
+                    thin,
+                    &content_source,
+                    &encryption,
+                )?;
                 debug!(
                     "Volume {} already exists and is compatible with requested config",
                     volume_uuid
@@ -211,16 +361,42 @@ impl rpc::csi::controller_server::Controller for CsiControllerSvc {
             }
             // If the volume doesn't exist, create it.
             Err(ApiClientError::ResourceNotExists(_)) => {
+                let node_topology = match context.node_affinity().is_empty()
+                    && context.node_anti_affinity().is_empty()
+                    && context.node_spread().is_empty()
+                {
+                    true => None,
+                    false => Some(NodeTopology::labelled(LabelledTopology {
+                        exclusion: context.node_anti_affinity().clone(),
+                        inclusion: context.node_affinity().clone(),
+                        spread: Some(context.node_spread().clone()),
+                    })),
+                };
                 let volume_topology = CreateVolumeTopology::new(
-                    None,
+                    node_topology,
                     Some(PoolTopology::labelled(LabelledTopology {
-                        exclusion: Default::default(),
+                        exclusion: context.pool_anti_affinity().clone(),
                         inclusion: inclusive_label_topology,
+                        spread: Some(context.pool_spread().clone()),
                     })),
                 );
+                let policy = models::VolumePolicy::new_all(
+                    context.self_heal(),
+                    context.qos(),
+                    context.rebuild_priority(),
+                );
 
                 IoEngineApiClient::get_client()
-                    .create_volume(&u, replica_count, size, volume_topology, thin)
+                    .create_volume(
+                        &u,
+                        replica_count,
+                        size,
+                        volume_topology,
+                        thin,
+                        content_source.clone(),
+                        policy,
+                        encryption.clone(),
+                    )
                     .await?;
 
                 debug!(volume.uuid = volume_uuid, "Volume successfully created");
@@ -232,7 +408,7 @@ impl rpc::csi::controller_server::Controller for CsiControllerSvc {
             capacity_bytes: size as i64,
             volume_id: volume_uuid,
             volume_context: args.parameters.clone(),
-            content_source: None,
+            content_source: args.volume_content_source.clone(),
             accessible_topology: vt_mapper.volume_accessible_topology(),
         };
 
@@ -272,11 +448,6 @@ impl rpc::csi::controller_server::Controller for CsiControllerSvc {
     ) -> Result<tonic::Response<ControllerPublishVolumeResponse>, tonic::Status> {
         let args = request.into_inner();
         tracing::trace!(volume.uuid = %args.volume_id, request = ?args);
-        if args.readonly {
-            return Err(Status::invalid_argument(
-                "Read-only volumes are not supported",
-            ));
-        }
 
         let protocol = parse_protocol(args.volume_context.get("protocol"))?;
 
@@ -293,14 +464,21 @@ impl rpc::csi::controller_server::Controller for CsiControllerSvc {
         })?;
         let _guard = csi_driver::limiter::VolumeOpGuard::new(volume_id)?;
 
-        match args.volume_capability {
+        let access_mode = match args.volume_capability {
             Some(c) => {
-                check_volume_capabilities(&[c])?;
+                check_volume_capabilities(&[c.clone()])?;
+                access_mode(&c)
             }
             None => {
                 return Err(Status::invalid_argument("Missing volume capability"));
             }
         };
+        let multi_node_reader = access_mode == models::VolumeAccessMode::MultiNodeReaderOnly;
+        if args.readonly && !multi_node_reader {
+            return Err(Status::invalid_argument(
+                "Read-only volumes are only supported with the MULTI_NODE_READER_ONLY access mode",
+            ));
+        }
 
         // Check if the volume is already published.
         let volume = IoEngineApiClient::get_client()
@@ -326,8 +504,44 @@ impl rpc::csi::controller_server::Controller for CsiControllerSvc {
                     }
 
                     if let Some((node, uri)) = get_volume_share_location(&volume) {
-                        // Make sure volume is published at the same node.
-                        if node_id != node {
+                        let frontend_nodes = target.frontend_nodes.clone().unwrap_or_default();
+                        let published = match frontend_nodes.is_empty() {
+                            true => node_id == node,
+                            false => frontend_nodes.contains(&node_id),
+                        };
+
+                        if published {
+                            debug!("Volume {} already published at {}", volume_id, uri);
+                            uri
+                        } else if multi_node_reader {
+                            // Volumes which are only read from may be published to multiple nodes.
+                            let v = IoEngineApiClient::get_client()
+                                .publish_volume(
+                                    &volume_id,
+                                    Some(target.node.as_str()),
+                                    protocol,
+                                    node_id.clone(),
+                                    access_mode,
+                                    &publish_context,
+                                )
+                                .await?;
+
+                            if let Some((node, uri)) = get_volume_share_location(&v) {
+                                debug!(
+                                    "Volume {} successfully published to frontend node {} via {}",
+                                    volume_id, node_id, node
+                                );
+                                uri
+                            } else {
+                                let m = format!(
+                                    "Volume {} has been successfully published but URI is not available",
+                                    volume_id
+                                );
+                                error!("{}", m);
+                                return Err(Status::internal(m));
+                            }
+                        } else {
+                            // Make sure volume is published at the same node.
                             let m = format!(
                                 "Volume {} already published on a different node: {}",
                                 volume_id, node,
@@ -335,9 +549,6 @@ impl rpc::csi::controller_server::Controller for CsiControllerSvc {
                             error!("{}", m);
                             return Err(Status::failed_precondition(m));
                         }
-
-                        debug!("Volume {} already published at {}", volume_id, uri);
-                        uri
                     } else {
                         let m = format!(
                             "Volume {} reports no info about its publishing status",
@@ -372,7 +583,7 @@ impl rpc::csi::controller_server::Controller for CsiControllerSvc {
 
                 // Volume is not published.
                 let v = IoEngineApiClient::get_client()
-                    .publish_volume(&volume_id, target_node, protocol, args.node_id.clone(), &publish_context)
+                    .publish_volume(&volume_id, target_node, protocol, args.node_id.clone(), access_mode, &publish_context)
                     .await?;
 
                 if let Some((node, uri)) = get_volume_share_location(&v) {
@@ -438,8 +649,10 @@ impl rpc::csi::controller_server::Controller for CsiControllerSvc {
         }
 
         // Do forced volume upublish as Kubernetes already detached the volume.
+        // Only the requesting node is unpublished as the volume may be published to other nodes.
+        let frontend_node = Some(args.node_id.as_str()).filter(|node| !node.is_empty());
         IoEngineApiClient::get_client()
-            .unpublish_volume(&volume_uuid, true)
+            .unpublish_volume(&volume_uuid, true, frontend_node)
             .await
             .map_err(|e| {
                 Status::not_found(format!(
@@ -474,14 +687,10 @@ impl rpc::csi::controller_server::Controller for CsiControllerSvc {
             .volume_capabilities
             .into_iter()
             .filter(|cap| {
-                if let Some(access_mode) = cap.access_mode.as_ref() {
-                    if access_mode.mode
-                        == volume_capability::access_mode::Mode::SingleNodeWriter as i32
-                    {
-                        return true;
-                    }
-                }
-                false
+                cap.access_mode
+                    .as_ref()
+                    .map(|access_mode| supported_access_mode(access_mode.mode))
+                    .unwrap_or(false)
             })
             .collect();
 
@@ -497,7 +706,7 @@ impl rpc::csi::controller_server::Controller for CsiControllerSvc {
         } else {
             ValidateVolumeCapabilitiesResponse {
                 confirmed: None,
-                message: "The only supported capability is SINGLE_NODE_WRITER".to_string(),
+                message: "The only supported capabilities are SINGLE_NODE_WRITER, SINGLE_NODE_MULTI_WRITER and MULTI_NODE_READER_ONLY".to_string(),
             }
         };
 
@@ -532,7 +741,7 @@ impl rpc::csi::controller_server::Controller for CsiControllerSvc {
                     volume_id: v.spec.uuid.to_string(),
                     capacity_bytes: v.spec.size as i64,
                     volume_context: HashMap::new(),
-                    content_source: None,
+                    content_source: v.spec.content_source.as_ref().map(csi_content_source),
                     accessible_topology: vt_mapper.volume_accessible_topology(),
                 };
 
@@ -621,6 +830,12 @@ impl rpc::csi::controller_server::Controller for CsiControllerSvc {
             controller_service_capability::rpc::Type::PublishUnpublishVolume,
             controller_service_capability::rpc::Type::ListVolumes,
             controller_service_capability::rpc::Type::GetCapacity,
+            controller_service_capability::rpc::Type::CreateDeleteSnapshot,
+            controller_service_capability::rpc::Type::ListSnapshots,
+            controller_service_capability::rpc::Type::ExpandVolume,
+            controller_service_capability::rpc::Type::CloneVolume,
+            controller_service_capability::rpc::Type::PublishReadonly,
+            controller_service_capability::rpc::Type::SingleNodeMultiWriter,
         ];
 
         Ok(Response::new(ControllerGetCapabilitiesResponse {
@@ -635,36 +850,211 @@ impl rpc::csi::controller_server::Controller for CsiControllerSvc {
         }))
     }
 
-    #[instrument(error)]
+    #[instrument(error, fields(volume.uuid = %request.get_ref().source_volume_id, snapshot.uuid))]
     async fn create_snapshot(
         &self,
-        _request: tonic::Request<CreateSnapshotRequest>,
+        request: tonic::Request<CreateSnapshotRequest>,
     ) -> Result<tonic::Response<CreateSnapshotResponse>, tonic::Status> {
-        Err(Status::unimplemented("Not implemented"))
+        let args = request.into_inner();
+        tracing::trace!(request = ?args);
+
+        let volume_uuid = Uuid::parse_str(&args.source_volume_id).map_err(|_e| {
+            Status::invalid_argument(format!("Malformed volume UUID: {}", args.source_volume_id))
+        })?;
+        let re = Regex::new(SNAPSHOT_NAME_PATTERN).unwrap();
+        let snapshot_uuid = match re.captures(&args.name) {
+            Some(captures) => Uuid::parse_str(captures.get(1).unwrap().as_str()).map_err(|_e| {
+                Status::invalid_argument(format!("Malformed snapshot name: {}", args.name))
+            })?,
+            None => {
+                return Err(Status::invalid_argument(format!(
+                    "Expected the snapshot name in snapshot-<UUID> format: {}",
+                    args.name
+                )))
+            }
+        };
+        tracing::Span::current().record("snapshot.uuid", &snapshot_uuid.to_string().as_str());
+        let _guard = csi_driver::limiter::VolumeOpGuard::new(volume_uuid)?;
+
+        // Handle the request idempotently, a snapshot with the same name may already exist.
+        let existing = IoEngineApiClient::get_client()
+            .list_volume_snapshots(None, Some(&snapshot_uuid))
+            .await?;
+        let snapshot = match existing.into_iter().next() {
+            Some(snapshot) if snapshot.spec.source_volume == volume_uuid => {
+                debug!(
+                    "Snapshot {} already exists for volume {}",
+                    snapshot_uuid, volume_uuid
+                );
+                snapshot
+            }
+            Some(snapshot) => {
+                return Err(Status::already_exists(format!(
+                    "Snapshot {} already exists for a different source volume {}",
+                    snapshot_uuid, snapshot.spec.source_volume
+                )))
+            }
+            None => IoEngineApiClient::get_client()
+                .create_volume_snapshot(&volume_uuid, &snapshot_uuid)
+                .await
+                .map_err(|e| {
+                    Status::internal(format!(
+                        "Failed to create snapshot {} for volume {}, error = {:?}",
+                        snapshot_uuid, volume_uuid, e
+                    ))
+                })?,
+        };
+
+        Ok(Response::new(CreateSnapshotResponse {
+            snapshot: Some(csi_snapshot(&snapshot)),
+        }))
     }
 
-    #[instrument(error)]
+    #[instrument(error, fields(snapshot.uuid = %request.get_ref().snapshot_id))]
     async fn delete_snapshot(
         &self,
-        _request: tonic::Request<DeleteSnapshotRequest>,
+        request: tonic::Request<DeleteSnapshotRequest>,
     ) -> Result<tonic::Response<DeleteSnapshotResponse>, tonic::Status> {
-        Err(Status::unimplemented("Not implemented"))
+        let args = request.into_inner();
+        tracing::trace!(request = ?args);
+
+        let snapshot_uuid = Uuid::parse_str(&args.snapshot_id).map_err(|_e| {
+            Status::invalid_argument(format!("Malformed snapshot UUID: {}", args.snapshot_id))
+        })?;
+
+        IoEngineApiClient::get_client()
+            .delete_volume_snapshot(&snapshot_uuid)
+            .await
+            .map_err(|e| {
+                Status::internal(format!(
+                    "Failed to delete snapshot {}, error = {:?}",
+                    args.snapshot_id, e
+                ))
+            })?;
+
+        Ok(Response::new(DeleteSnapshotResponse {}))
     }
 
     #[instrument(error)]
     async fn list_snapshots(
         &self,
-        _request: tonic::Request<ListSnapshotsRequest>,
+        request: tonic::Request<ListSnapshotsRequest>,
     ) -> Result<tonic::Response<ListSnapshotsResponse>, tonic::Status> {
-        Err(Status::unimplemented("Not implemented"))
+        let args = request.into_inner();
+        tracing::trace!(request = ?args);
+
+        if args.max_entries < 0 {
+            return Err(Status::invalid_argument("max_entries can't be negative"));
+        }
+        let starting_token = if args.starting_token.is_empty() {
+            0
+        } else {
+            args.starting_token.parse::<usize>().map_err(|_| {
+                Status::aborted(format!("Invalid starting token: {}", args.starting_token))
+            })?
+        };
+
+        // A malformed id can never match an existing resource, so return an empty list.
+        let parse_filter = |id: &str| match id {
+            "" => Ok(None),
+            id => Uuid::parse_str(id).map(Some),
+        };
+        let (volume_uuid, snapshot_uuid) = match (
+            parse_filter(&args.source_volume_id),
+            parse_filter(&args.snapshot_id),
+        ) {
+            (Ok(volume_uuid), Ok(snapshot_uuid)) => (volume_uuid, snapshot_uuid),
+            _ => return Ok(Response::new(ListSnapshotsResponse::default())),
+        };
+
+        let snapshots = IoEngineApiClient::get_client()
+            .list_volume_snapshots(volume_uuid.as_ref(), snapshot_uuid.as_ref())
+            .await
+            .map_err(|e| Status::internal(format!("Failed to list snapshots, error = {:?}", e)))?;
+
+        let max_entries = match args.max_entries {
+            0 => snapshots.len(),
+            max_entries => max_entries as usize,
+        };
+        let entries = snapshots
+            .iter()
+            .skip(starting_token)
+            .take(max_entries)
+            .map(|s| list_snapshots_response::Entry {
+                snapshot: Some(csi_snapshot(s)),
+            })
+            .collect::<Vec<_>>();
+        let next_token = match starting_token + entries.len() {
+            next if next < snapshots.len() => next.to_string(),
+            _ => "".to_string(),
+        };
+
+        debug!("Available k8s snapshots: {:?}", entries);
+
+        Ok(Response::new(ListSnapshotsResponse {
+            entries,
+            next_token,
+        }))
     }
 
-    #[instrument(error)]
+    #[instrument(error, fields(volume.uuid = %request.get_ref().volume_id))]
     async fn controller_expand_volume(
         &self,
-        _request: tonic::Request<ControllerExpandVolumeRequest>,
+        request: tonic::Request<ControllerExpandVolumeRequest>,
     ) -> Result<tonic::Response<ControllerExpandVolumeResponse>, tonic::Status> {
-        Err(Status::unimplemented("Not implemented"))
+        let args = request.into_inner();
+        tracing::trace!(volume.uuid = %args.volume_id, request = ?args);
+
+        let volume_uuid = Uuid::parse_str(&args.volume_id).map_err(|_e| {
+            Status::invalid_argument(format!("Malformed volume UUID: {}", args.volume_id))
+        })?;
+        let size = match args.capacity_range {
+            Some(range) if range.required_bytes > 0 => range.required_bytes as u64,
+            Some(_) => {
+                return Err(Status::invalid_argument(
+                    "Volume size must be greater than zero",
+                ))
+            }
+            None => {
+                return Err(Status::invalid_argument(
+                    "Volume capacity range is not provided",
+                ))
+            }
+        };
+        let _guard = csi_driver::limiter::VolumeOpGuard::new(volume_uuid)?;
+
+        let volume = IoEngineApiClient::get_client()
+            .get_volume(&volume_uuid)
+            .await?;
+        // The volume may already have been expanded by a previous request.
+        let volume = if volume.spec.size >= size {
+            debug!(
+                "Volume {} already has the requested size: {} bytes ({} requested)",
+                volume_uuid, volume.spec.size, size
+            );
+            volume
+        } else {
+            IoEngineApiClient::get_client()
+                .resize_volume(&volume_uuid, size)
+                .await
+                .map_err(|e| {
+                    Status::internal(format!(
+                        "Failed to expand volume {}, error = {:?}",
+                        volume_uuid, e
+                    ))
+                })?
+        };
+
+        // Raw block volumes don't need any filesystem expansion on the node.
+        let node_expansion_required = !matches!(
+            args.volume_capability.and_then(|c| c.access_type),
+            Some(volume_capability::AccessType::Block(_))
+        );
+
+        Ok(Response::new(ControllerExpandVolumeResponse {
+            capacity_bytes: volume.spec.size as i64,
+            node_expansion_required,
+        }))
     }
 
     #[instrument(error)]
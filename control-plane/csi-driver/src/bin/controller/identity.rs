@@ -49,6 +49,13 @@ impl rpc::csi::identity_server::Identity for CsiIdentitySvc {
                         plugin_capability::Service { r#type: c as i32 },
                     )),
                 })
+                .chain(std::iter::once(PluginCapability {
+                    r#type: Some(plugin_capability::Type::VolumeExpansion(
+                        plugin_capability::VolumeExpansion {
+                            r#type: plugin_capability::volume_expansion::Type::Online as i32,
+                        },
+                    )),
+                }))
                 .collect(),
         }))
     }
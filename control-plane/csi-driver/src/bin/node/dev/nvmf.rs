@@ -288,6 +288,25 @@ pub(crate) fn set_nvmecore_iotimeout(io_timeout_secs: u32) -> Result<(), std::io
     Ok(())
 }
 
+/// Rescan the NVMe controller of the given device, eg: /dev/nvme1n1, so that the kernel picks up
+/// a change in the size of its namespace.
+pub(crate) fn rescan_controller(device: &str) -> Result<(), DeviceError> {
+    let major = DEVICE_REGEX
+        .captures(device)
+        .and_then(|captures| captures.get(1))
+        .ok_or_else(|| {
+            DeviceError::new(&format!(
+                "NVMe device \"{}\" does not match \"{}\"",
+                device, *DEVICE_REGEX,
+            ))
+        })?
+        .as_str();
+    let path = format!("/sys/class/nvme/nvme{}", major);
+    debug!("Rescanning NVMe controller \"{}\"", path);
+    sysfs::write_value(Path::new(&path), "rescan_controller", 1)?;
+    Ok(())
+}
+
 /// Extract uuid from Url string.
 pub(crate) fn volume_uuid_from_url_str(url: &str) -> Result<Uuid, DeviceError> {
     let url = Url::parse(url).map_err(|error| error.to_string())?;
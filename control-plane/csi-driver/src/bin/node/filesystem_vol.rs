@@ -1,6 +1,6 @@
-//! Functions for CSI stage, unstage, publish and unpublish filesystem volumes.
+//! Functions for CSI stage, unstage, publish, unpublish and expand filesystem volumes.
 
-use std::{fs, io::ErrorKind, path::PathBuf};
+use std::{collections::HashMap, fs, io::ErrorKind, path::PathBuf, process::Command};
 
 use tonic::{Code, Status};
 use tracing::{debug, error, info};
@@ -11,14 +11,45 @@ macro_rules! failure {
 }
 
 use crate::{
+    dev::nvmf,
     format::prepare_device,
     mount::{self, subset, ReadOnly},
 };
-use csi_driver::csi::{
-    volume_capability::MountVolume, NodePublishVolumeRequest, NodeStageVolumeRequest,
-    NodeUnpublishVolumeRequest, NodeUnstageVolumeRequest,
+use csi_driver::{
+    csi::{
+        volume_capability::MountVolume, NodePublishVolumeRequest, NodeStageVolumeRequest,
+        NodeUnpublishVolumeRequest, NodeUnstageVolumeRequest,
+    },
+    FilesystemParams,
 };
 
+/// Get the volume filesystem parameters from the volume context.
+fn filesystem_params(
+    volume_id: &str,
+    volume_context: &HashMap<String, String>,
+) -> Result<FilesystemParams, Status> {
+    FilesystemParams::try_from(volume_context).map_err(|error| {
+        failure!(
+            Code::InvalidArgument,
+            "Invalid filesystem parameters for volume {}: {}",
+            volume_id,
+            error.message()
+        )
+    })
+}
+
+/// Get the mount options from the volume capability along with the `Parameters::MountOptions`
+/// of the volume, which are only added if not already present.
+fn mount_options(mnt: &MountVolume, params: &FilesystemParams) -> Vec<String> {
+    let mut options = mnt.mount_flags.clone();
+    for option in params.mount_options() {
+        if !options.contains(option) {
+            options.push(option.clone());
+        }
+    }
+    options
+}
+
 pub(crate) async fn stage_fs_volume(
     msg: &NodeStageVolumeRequest,
     device_path: &str,
@@ -43,6 +74,9 @@ pub(crate) async fn stage_fs_volume(
 
     debug!("Staging volume {} to {}", volume_id, fs_staging_path);
 
+    let params = filesystem_params(volume_id, &msg.volume_context)?;
+    let options = mount_options(mnt, &params);
+
     let fstype = if mnt.fs_type.is_empty() {
         String::from(&filesystems[0])
     } else {
@@ -97,7 +131,7 @@ pub(crate) async fn stage_fs_volume(
         ));
     }
 
-    if let Err(error) = prepare_device(device_path, &fstype).await {
+    if let Err(error) = prepare_device(device_path, &fstype, params.mkfs_options()).await {
         return Err(failure!(
             Code::Internal,
             "Failed to stage volume {}: error preparing device {}: {}",
@@ -109,9 +143,7 @@ pub(crate) async fn stage_fs_volume(
 
     debug!("Mounting device {} onto {}", device_path, fs_staging_path);
 
-    if let Err(error) =
-        mount::filesystem_mount(device_path, fs_staging_path, &fstype, &mnt.mount_flags)
-    {
+    if let Err(error) = mount::filesystem_mount(device_path, fs_staging_path, &fstype, &options) {
         return Err(failure!(
             Code::Internal,
             "Failed to stage volume {}: failed to mount device {} onto {}: {}",
@@ -190,6 +222,8 @@ pub(crate) fn publish_fs_volume(
         volume_id, fs_staging_path, target_path
     );
 
+    let params = filesystem_params(volume_id, &msg.volume_context)?;
+
     let staged = mount::find_mount(None, Some(fs_staging_path)).ok_or_else(|| {
         failure!(
             Code::InvalidArgument,
@@ -286,7 +320,7 @@ pub(crate) fn publish_fs_volume(
     }
 
     if msg.readonly && !readonly {
-        let mut options = mnt.mount_flags.clone();
+        let mut options = mount_options(mnt, &params);
         options.push(String::from("ro"));
 
         debug!("Remounting {} as readonly", target_path);
@@ -371,3 +405,75 @@ pub(crate) fn unpublish_fs_volume(msg: &NodeUnpublishVolumeRequest) -> Result<()
     info!("Volume {} unpublished from {}", volume_id, target_path);
     Ok(())
 }
+
+/// Grow the filesystem mounted on the `volume_path` to fill its underlying device.
+/// Only ext4, xfs and btrfs are supported, all of which may be grown whilst mounted.
+pub(crate) fn expand_fs_volume(volume_id: &str, volume_path: &str) -> Result<(), Status> {
+    let mount = match mount::find_mount(None, Some(volume_path)) {
+        Some(mount) => mount,
+        None => {
+            return Err(failure!(
+                Code::NotFound,
+                "Failed to expand volume {}: no mount found for {}",
+                volume_id,
+                volume_path
+            ));
+        }
+    };
+    let device = mount.source.to_string_lossy().to_string();
+
+    // the controller has already grown the volume, but the kernel may not have noticed yet
+    if device.starts_with("/dev/nvme") {
+        nvmf::rescan_controller(&device).map_err(|error| {
+            failure!(
+                Code::Internal,
+                "Failed to expand volume {}: {}",
+                volume_id,
+                error
+            )
+        })?;
+    }
+
+    // resize2fs operates on the device whereas xfs_growfs and btrfs require the mount point.
+    let (binary, args) = match mount.fstype.as_str() {
+        "ext4" => ("resize2fs", vec![device.as_str()]),
+        "xfs" => ("xfs_growfs", vec![volume_path]),
+        "btrfs" => ("btrfs", vec!["filesystem", "resize", "max", volume_path]),
+        fstype => {
+            return Err(failure!(
+                Code::InvalidArgument,
+                "Failed to expand volume {}: unsupported filesystem type: {}",
+                volume_id,
+                fstype
+            ));
+        }
+    };
+
+    debug!(
+        "Expanding {} filesystem of volume {} on device {}",
+        mount.fstype, volume_id, device
+    );
+
+    let output = Command::new(binary).args(args).output().map_err(|error| {
+        failure!(
+            Code::Internal,
+            "Failed to expand volume {}: failed to execute {}: {}",
+            volume_id,
+            binary,
+            error
+        )
+    })?;
+
+    if !output.status.success() {
+        return Err(failure!(
+            Code::Internal,
+            "Failed to expand volume {}: {} command failed: {}",
+            volume_id,
+            binary,
+            String::from_utf8_lossy(&output.stderr)
+        ));
+    }
+
+    info!("Volume {} expanded on {}", volume_id, volume_path);
+    Ok(())
+}
@@ -4,7 +4,13 @@ use devinfo::blkid::probe::Probe;
 use std::process::Command;
 use tracing::{debug, trace};
 
-pub(crate) async fn prepare_device(device: &str, fstype: &str) -> Result<(), String> {
+/// Create a filesystem of the given type on the device, with the given extra mkfs arguments,
+/// unless the device already has a filesystem.
+pub(crate) async fn prepare_device(
+    device: &str,
+    fstype: &str,
+    mkfs_options: &[String],
+) -> Result<(), String> {
     debug!("Probing device {}", device);
 
     let probe = Probe::new_from_filename(device)
@@ -19,10 +25,14 @@ pub(crate) async fn prepare_device(device: &str, fstype: &str) -> Result<(), Str
         return Ok(());
     }
 
-    debug!("Creating new filesystem ({}) on device {}", fstype, device);
+    debug!(
+        "Creating new filesystem ({}) on device {} (options: {:?})",
+        fstype, device, mkfs_options
+    );
 
     let binary = format!("mkfs.{}", fstype);
     let output = Command::new(&binary)
+        .args(mkfs_options)
         .arg(device)
         .output()
         .map_err(|error| format!("failed to execute {}: {}", binary, error))?;
@@ -87,7 +87,11 @@ pub(super) fn subset(first: &[String], second: &[String]) -> bool {
 
 /// Return supported filesystems.
 pub(crate) fn probe_filesystems() -> Vec<String> {
-    vec![String::from("xfs"), String::from("ext4")]
+    vec![
+        String::from("xfs"),
+        String::from("ext4"),
+        String::from("btrfs"),
+    ]
 }
 
 // Utility function to transform a vector of options
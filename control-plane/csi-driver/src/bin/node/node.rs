@@ -14,7 +14,11 @@ use uuid::Uuid;
 use crate::{
     block_vol::{publish_block_volume, unpublish_block_volume},
     dev::Device,
-    filesystem_vol::{publish_fs_volume, stage_fs_volume, unpublish_fs_volume, unstage_fs_volume},
+    filesystem_vol::{
+        expand_fs_volume, publish_fs_volume, stage_fs_volume, unpublish_fs_volume,
+        unstage_fs_volume,
+    },
+    mount::ReadOnly,
 };
 use csi_driver::csi::volume_capability::{access_mode::Mode, AccessType};
 use rpc::csi::{
@@ -94,7 +98,9 @@ fn check_access_mode(
         Some(capability) => match &capability.access_mode {
             Some(access) => match Mode::from_i32(access.mode) {
                 Some(mode) => match mode {
-                    Mode::SingleNodeWriter | Mode::MultiNodeSingleWriter => Ok(()),
+                    Mode::SingleNodeWriter
+                    | Mode::SingleNodeMultiWriter
+                    | Mode::MultiNodeSingleWriter => Ok(()),
                     Mode::SingleNodeReaderOnly | Mode::MultiNodeReaderOnly => {
                         if readonly {
                             return Ok(());
@@ -118,6 +124,16 @@ fn check_access_mode(
     }
 }
 
+/// Check whether the access_mode from VolumeCapability only allows the volume to be read
+fn readonly_access_mode(volume_capability: &Option<VolumeCapability>) -> bool {
+    volume_capability
+        .as_ref()
+        .and_then(|capability| capability.access_mode.as_ref())
+        .and_then(|access| Mode::from_i32(access.mode))
+        .map(|mode| matches!(mode, Mode::SingleNodeReaderOnly | Mode::MultiNodeReaderOnly))
+        .unwrap_or(false)
+}
+
 /// Retrieve the AccessType from VolumeCapability
 fn get_access_type(volume_capability: &Option<VolumeCapability>) -> Result<&AccessType, String> {
     match volume_capability {
@@ -195,11 +211,12 @@ impl node_server::Node for Node {
         let caps = vec![
             node_service_capability::rpc::Type::StageUnstageVolume,
             node_service_capability::rpc::Type::GetVolumeStats,
+            node_service_capability::rpc::Type::ExpandVolume,
+            node_service_capability::rpc::Type::SingleNodeMultiWriter,
         ];
 
         debug!("NodeGetCapabilities request: {:?}", caps);
 
-        // We don't support stage/unstage and expand volume rpcs
         Ok(Response::new(NodeGetCapabilitiesResponse {
             capabilities: caps
                 .into_iter()
@@ -427,13 +444,61 @@ impl node_server::Node for Node {
         }
     }
 
+    /// This RPC is called by the CO to expand the filesystem of a volume which has
+    /// already been expanded by the controller plugin.
+    /// Raw block volumes require no further action on the node.
+    ///
+    /// This operation MUST be idempotent.
     async fn node_expand_volume(
         &self,
         request: Request<NodeExpandVolumeRequest>,
     ) -> Result<Response<NodeExpandVolumeResponse>, Status> {
         let msg = request.into_inner();
-        error!("Unimplemented {:?}", msg);
-        Err(Status::new(Code::Unimplemented, "Method not implemented"))
+
+        trace!("node_expand_volume {:?}", msg);
+
+        if msg.volume_id.is_empty() {
+            return Err(failure!(
+                Code::InvalidArgument,
+                "Failed to expand volume: missing volume id"
+            ));
+        }
+        if msg.volume_path.is_empty() {
+            return Err(failure!(
+                Code::InvalidArgument,
+                "Failed to expand volume {}: missing volume path",
+                &msg.volume_id
+            ));
+        }
+        let _guard = csi_driver::limiter::VolumeOpGuard::new_str(&msg.volume_id)?;
+
+        let volume_path = Path::new(&msg.volume_path);
+        if !volume_path.exists() {
+            return Err(failure!(
+                Code::NotFound,
+                "Failed to expand volume {}: volume path {} doesn't exist",
+                &msg.volume_id,
+                &msg.volume_path
+            ));
+        }
+
+        let block_volume = match &msg.volume_capability {
+            Some(_) => matches!(
+                get_access_type(&msg.volume_capability),
+                Ok(AccessType::Block(_))
+            ),
+            None => !volume_path.is_dir(),
+        };
+        if !block_volume {
+            expand_fs_volume(&msg.volume_id, &msg.volume_path)?;
+        }
+
+        Ok(Response::new(NodeExpandVolumeResponse {
+            capacity_bytes: msg
+                .capacity_range
+                .map(|range| range.required_bytes)
+                .unwrap_or_default(),
+        }))
     }
 
     async fn node_stage_volume(
@@ -580,8 +645,14 @@ impl node_server::Node for Node {
         // Attach successful, now stage mount if required.
         match access_type {
             AccessType::Mount(mnt) => {
+                // Volumes which may only be read from are staged read-only, as otherwise the
+                // filesystem could still be modified, eg: by replaying its journal.
+                let mut mnt = mnt.clone();
+                if readonly_access_mode(&msg.volume_capability) && !mnt.mount_flags.readonly() {
+                    mnt.mount_flags.push("ro".to_string());
+                }
                 if let Err(fsmount_error) =
-                    stage_fs_volume(&msg, &device_path, mnt, &self.filesystems).await
+                    stage_fs_volume(&msg, &device_path, &mnt, &self.filesystems).await
                 {
                     let mounts = crate::mount::find_src_mounts(&device_path, None);
                     // If the device is mounted elsewhere, don't detach it!
@@ -1,5 +1,9 @@
-use common_lib::types::v0::openapi::models::VolumeShareProtocol;
-use std::{collections::HashMap, num::ParseIntError, str::FromStr};
+use common_lib::types::v0::openapi::models::{VolumeQos, VolumeShareProtocol};
+use std::{
+    collections::HashMap,
+    num::ParseIntError,
+    str::{FromStr, ParseBoolError},
+};
 
 /// The currently supported filesystems.
 #[derive(strum_macros::AsRefStr, strum_macros::EnumString)]
@@ -7,6 +11,7 @@ use std::{collections::HashMap, num::ParseIntError, str::FromStr};
 pub enum FileSystem {
     Ext4,
     Xfs,
+    Btrfs,
 }
 
 /// Parse string protocol into REST API protocol enum.
@@ -34,6 +39,36 @@ pub enum Parameters {
     FileSystem,
     #[strum(serialize = "protocol")]
     ShareProtocol,
+    QosReadIops,
+    QosWriteIops,
+    QosReadMbps,
+    QosWriteMbps,
+    Encrypted,
+    /// The key of the `CreateVolume` secret which holds the encryption key reference.
+    EncryptionKeyRef,
+    Thin,
+    SelfHeal,
+    RebuildPriority,
+    /// Labels which the pools of the volume replicas must have, as comma separated key=value.
+    PoolAffinityTopologyLabel,
+    /// Labels which the pools of the volume replicas must not have, as comma separated key=value.
+    /// A label with an empty value excludes the pools having the key with any value.
+    PoolAntiAffinityTopologyLabel,
+    /// Label keys whose values must differ between the pools of the volume replicas, as comma
+    /// separated keys.
+    PoolSpreadTopologyKey,
+    /// Labels which the nodes of the volume replicas must have, as comma separated key=value.
+    NodeAffinityTopologyLabel,
+    /// Labels which the nodes of the volume replicas must not have, as comma separated key=value.
+    /// A label with an empty value excludes the nodes having the key with any value.
+    NodeAntiAffinityTopologyLabel,
+    /// Label keys whose values must differ between the nodes of the volume replicas, as comma
+    /// separated keys.
+    NodeSpreadTopologyKey,
+    /// Extra arguments for mkfs when the filesystem is created, as whitespace separated arguments.
+    MkfsOptions,
+    /// Extra options for mounting the filesystem, as comma separated options.
+    MountOptions,
 }
 impl Parameters {
     fn parse_u32(value: Option<&String>) -> Result<Option<u32>, ParseIntError> {
@@ -42,6 +77,12 @@ impl Parameters {
             None => None,
         })
     }
+    fn parse_u64(value: Option<&String>) -> Result<Option<u64>, ParseIntError> {
+        Ok(match value {
+            Some(value) => value.parse::<u64>().map(Some)?,
+            None => None,
+        })
+    }
     /// Parse the value for `Self::NvmeCtrlLossTmo`.
     pub fn ctrl_loss_tmo(value: Option<&String>) -> Result<Option<u32>, ParseIntError> {
         Self::parse_u32(value)
@@ -58,6 +99,71 @@ impl Parameters {
     pub fn io_timeout(value: Option<&String>) -> Result<Option<u32>, ParseIntError> {
         Self::parse_u32(value)
     }
+    /// Parse the value for any of the `Self::Qos*` limits.
+    pub fn qos_limit(value: Option<&String>) -> Result<Option<u64>, ParseIntError> {
+        Self::parse_u64(value)
+    }
+    /// Parse the value for `Self::RebuildPriority`.
+    pub fn rebuild_priority(value: Option<&String>) -> Result<Option<u32>, ParseIntError> {
+        Self::parse_u32(value)
+    }
+    /// Parse the value for any of the boolean parameters.
+    pub fn flag(value: Option<&String>) -> Result<Option<bool>, ParseBoolError> {
+        Ok(match value {
+            Some(value) => value.parse::<bool>().map(Some)?,
+            None => None,
+        })
+    }
+    /// Parse the value for any of the `Self::*TopologyLabel` parameters, as comma separated
+    /// key=value labels.
+    pub fn topology_labels(value: Option<&String>) -> Result<HashMap<String, String>, String> {
+        Self::topology_entries(value)
+            .map(|entry| match entry.split_once('=') {
+                Some((key, value)) if !key.trim().is_empty() => {
+                    Ok((key.trim().to_string(), value.trim().to_string()))
+                }
+                _ => Err(entry.to_string()),
+            })
+            .collect()
+    }
+    /// Parse the value for any of the `Self::*TopologyKey` parameters, as comma separated keys.
+    pub fn topology_keys(value: Option<&String>) -> Result<Vec<String>, String> {
+        Self::topology_entries(value)
+            .map(|key| match key.contains('=') {
+                false => Ok(key.to_string()),
+                true => Err(key.to_string()),
+            })
+            .collect()
+    }
+    /// Parse the value for `Self::MkfsOptions`.
+    pub fn mkfs_options(value: Option<&String>) -> Vec<String> {
+        value
+            .map(|value| value.split_whitespace().map(String::from).collect())
+            .unwrap_or_default()
+    }
+    /// Parse the value for `Self::MountOptions`.
+    /// The read-only mode is set through the volume access mode, and so `ro` and `rw` are not
+    /// allowed, nor are empty options or options with whitespace.
+    pub fn mount_options(value: Option<&String>) -> Result<Vec<String>, String> {
+        value
+            .into_iter()
+            .filter(|value| !value.trim().is_empty())
+            .flat_map(|value| value.split(','))
+            .map(str::trim)
+            .map(|option| match option {
+                "" | "ro" | "rw" => Err(option.to_string()),
+                option if option.contains(char::is_whitespace) => Err(option.to_string()),
+                option => Ok(option.to_string()),
+            })
+            .collect()
+    }
+    fn topology_entries(value: Option<&String>) -> impl Iterator<Item = &str> {
+        value
+            .into_iter()
+            .flat_map(|value| value.split(','))
+            .map(str::trim)
+            .filter(|entry| !entry.is_empty())
+    }
 }
 
 /// Volume publish parameters.
@@ -133,12 +239,75 @@ impl TryFrom<&HashMap<String, String>> for PublishParams {
     }
 }
 
+/// Volume filesystem parameters.
+/// These are part of the volume context, and so they're also available when the volume is staged
+/// again, eg: after a node restart.
+pub struct FilesystemParams {
+    fs_type: Option<FileSystem>,
+    mkfs_options: Vec<String>,
+    mount_options: Vec<String>,
+}
+impl FilesystemParams {
+    /// Get the `Parameters::FileSystem` value.
+    pub fn fs_type(&self) -> &Option<FileSystem> {
+        &self.fs_type
+    }
+    /// Get the `Parameters::MkfsOptions` value.
+    pub fn mkfs_options(&self) -> &Vec<String> {
+        &self.mkfs_options
+    }
+    /// Get the `Parameters::MountOptions` value.
+    pub fn mount_options(&self) -> &Vec<String> {
+        &self.mount_options
+    }
+}
+impl TryFrom<&HashMap<String, String>> for FilesystemParams {
+    type Error = tonic::Status;
+
+    fn try_from(args: &HashMap<String, String>) -> Result<Self, Self::Error> {
+        let fs_type = match args.get(Parameters::FileSystem.as_ref()) {
+            Some(fs) => FileSystem::from_str(fs.as_str())
+                .map(Some)
+                .map_err(|_| tonic::Status::invalid_argument("Invalid filesystem type"))?,
+            None => None,
+        };
+
+        let mkfs_options = Parameters::mkfs_options(args.get(Parameters::MkfsOptions.as_ref()));
+        let mount_options = Parameters::mount_options(args.get(Parameters::MountOptions.as_ref()))
+            .map_err(|option| {
+                tonic::Status::invalid_argument(format!(
+                    "Invalid {} option '{}'",
+                    Parameters::MountOptions.as_ref(),
+                    option
+                ))
+            })?;
+
+        Ok(Self {
+            fs_type,
+            mkfs_options,
+            mount_options,
+        })
+    }
+}
+
 /// Volume Creation parameters.
 #[allow(dead_code)]
 pub struct CreateParams {
     publish_params: PublishParams,
+    filesystem_params: FilesystemParams,
     share_protocol: VolumeShareProtocol,
     replica_count: u8,
+    qos: Option<VolumeQos>,
+    encrypted: bool,
+    thin: bool,
+    self_heal: bool,
+    rebuild_priority: Option<u32>,
+    pool_affinity: HashMap<String, String>,
+    pool_anti_affinity: HashMap<String, String>,
+    pool_spread: Vec<String>,
+    node_affinity: HashMap<String, String>,
+    node_anti_affinity: HashMap<String, String>,
+    node_spread: Vec<String>,
 }
 impl CreateParams {
     /// Get the `Parameters::ShareProtocol` value.
@@ -149,12 +318,57 @@ impl CreateParams {
     pub fn replica_count(&self) -> u8 {
         self.replica_count
     }
+    /// Get the volume QoS limits from the `Parameters::Qos*` values, if any were specified.
+    pub fn qos(&self) -> Option<VolumeQos> {
+        self.qos.clone()
+    }
+    /// Get the `Parameters::Encrypted` value.
+    pub fn encrypted(&self) -> bool {
+        self.encrypted
+    }
+    /// Get the `Parameters::Thin` value.
+    pub fn thin(&self) -> bool {
+        self.thin
+    }
+    /// Get the `Parameters::SelfHeal` value.
+    pub fn self_heal(&self) -> bool {
+        self.self_heal
+    }
+    /// Get the `Parameters::RebuildPriority` value.
+    pub fn rebuild_priority(&self) -> Option<u32> {
+        self.rebuild_priority
+    }
+    /// Get the `Parameters::PoolAffinityTopologyLabel` labels.
+    pub fn pool_affinity(&self) -> &HashMap<String, String> {
+        &self.pool_affinity
+    }
+    /// Get the `Parameters::PoolAntiAffinityTopologyLabel` labels.
+    pub fn pool_anti_affinity(&self) -> &HashMap<String, String> {
+        &self.pool_anti_affinity
+    }
+    /// Get the `Parameters::PoolSpreadTopologyKey` keys.
+    pub fn pool_spread(&self) -> &Vec<String> {
+        &self.pool_spread
+    }
+    /// Get the `Parameters::NodeAffinityTopologyLabel` labels.
+    pub fn node_affinity(&self) -> &HashMap<String, String> {
+        &self.node_affinity
+    }
+    /// Get the `Parameters::NodeAntiAffinityTopologyLabel` labels.
+    pub fn node_anti_affinity(&self) -> &HashMap<String, String> {
+        &self.node_anti_affinity
+    }
+    /// Get the `Parameters::NodeSpreadTopologyKey` keys.
+    pub fn node_spread(&self) -> &Vec<String> {
+        &self.node_spread
+    }
 }
 impl TryFrom<&HashMap<String, String>> for CreateParams {
     type Error = tonic::Status;
 
     fn try_from(args: &HashMap<String, String>) -> Result<Self, Self::Error> {
         let publish_params = PublishParams::try_from(args)?;
+        let filesystem_params = FilesystemParams::try_from(args)?;
 
         // Check storage protocol.
         let share_protocol = parse_protocol(args.get(Parameters::ShareProtocol.as_ref()))?;
@@ -174,10 +388,198 @@ impl TryFrom<&HashMap<String, String>> for CreateParams {
             None => 1,
         };
 
+        let qos_limit = |parameter: Parameters| {
+            Parameters::qos_limit(args.get(parameter.as_ref())).map_err(|_| {
+                tonic::Status::invalid_argument(format!("Invalid {}", parameter.as_ref()))
+            })
+        };
+        let read_iops = qos_limit(Parameters::QosReadIops)?;
+        let write_iops = qos_limit(Parameters::QosWriteIops)?;
+        let read_mbps = qos_limit(Parameters::QosReadMbps)?;
+        let write_mbps = qos_limit(Parameters::QosWriteMbps)?;
+        let qos = match (read_iops, write_iops, read_mbps, write_mbps) {
+            (None, None, None, None) => None,
+            _ => Some(VolumeQos::new_all(
+                read_iops, write_iops, read_mbps, write_mbps,
+            )),
+        };
+
+        let flag = |parameter: Parameters| {
+            Parameters::flag(args.get(parameter.as_ref())).map_err(|_| {
+                tonic::Status::invalid_argument(format!(
+                    "Invalid {}, expected true or false",
+                    parameter.as_ref()
+                ))
+            })
+        };
+        let encrypted = flag(Parameters::Encrypted)?.unwrap_or(false);
+        let thin = flag(Parameters::Thin)?.unwrap_or(false);
+        let self_heal = flag(Parameters::SelfHeal)?.unwrap_or(true);
+
+        let rebuild_priority =
+            Parameters::rebuild_priority(args.get(Parameters::RebuildPriority.as_ref()))
+                .map_err(|_| tonic::Status::invalid_argument("Invalid rebuild priority"))?;
+
+        let topology_labels = |parameter: Parameters| {
+            Parameters::topology_labels(args.get(parameter.as_ref())).map_err(|entry| {
+                tonic::Status::invalid_argument(format!(
+                    "Invalid {} label '{}', expected comma separated key=value labels",
+                    parameter.as_ref(),
+                    entry
+                ))
+            })
+        };
+        let topology_keys = |parameter: Parameters| {
+            Parameters::topology_keys(args.get(parameter.as_ref())).map_err(|entry| {
+                tonic::Status::invalid_argument(format!(
+                    "Invalid {} key '{}', expected comma separated label keys",
+                    parameter.as_ref(),
+                    entry
+                ))
+            })
+        };
+        let pool_affinity = topology_labels(Parameters::PoolAffinityTopologyLabel)?;
+        let pool_anti_affinity = topology_labels(Parameters::PoolAntiAffinityTopologyLabel)?;
+        let pool_spread = topology_keys(Parameters::PoolSpreadTopologyKey)?;
+        let node_affinity = topology_labels(Parameters::NodeAffinityTopologyLabel)?;
+        let node_anti_affinity = topology_labels(Parameters::NodeAntiAffinityTopologyLabel)?;
+        let node_spread = topology_keys(Parameters::NodeSpreadTopologyKey)?;
+
         Ok(Self {
             publish_params,
+            filesystem_params,
             share_protocol,
             replica_count,
+            qos,
+            encrypted,
+            thin,
+            self_heal,
+            rebuild_priority,
+            pool_affinity,
+            pool_anti_affinity,
+            pool_spread,
+            node_affinity,
+            node_anti_affinity,
+            node_spread,
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn value(value: &str) -> Option<String> {
+        Some(value.to_string())
+    }
+    fn labels(labels: &[(&str, &str)]) -> HashMap<String, String> {
+        labels
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn topology_labels() {
+        assert_eq!(Parameters::topology_labels(None), Ok(labels(&[])));
+        assert_eq!(
+            Parameters::topology_labels(value("").as_ref()),
+            Ok(labels(&[]))
+        );
+        assert_eq!(
+            Parameters::topology_labels(value(" zone = a ,rack=1,,").as_ref()),
+            Ok(labels(&[("zone", "a"), ("rack", "1")]))
+        );
+        // the value may be empty, but not the key
+        assert_eq!(
+            Parameters::topology_labels(value("zone=").as_ref()),
+            Ok(labels(&[("zone", "")]))
+        );
+        assert_eq!(
+            Parameters::topology_labels(value("zone=a, =b").as_ref()),
+            Err("=b".to_string())
+        );
+        assert_eq!(
+            Parameters::topology_labels(value("zone=a,rack").as_ref()),
+            Err("rack".to_string())
+        );
+    }
+
+    #[test]
+    fn topology_keys() {
+        assert_eq!(Parameters::topology_keys(None), Ok(vec![]));
+        assert_eq!(Parameters::topology_keys(value(" ").as_ref()), Ok(vec![]));
+        assert_eq!(
+            Parameters::topology_keys(value(" zone, rack ,").as_ref()),
+            Ok(vec!["zone".to_string(), "rack".to_string()])
+        );
+        assert_eq!(
+            Parameters::topology_keys(value("zone,rack=1").as_ref()),
+            Err("rack=1".to_string())
+        );
+    }
+
+    #[test]
+    fn mkfs_options() {
+        assert!(Parameters::mkfs_options(None).is_empty());
+        assert!(Parameters::mkfs_options(value(" ").as_ref()).is_empty());
+        assert_eq!(
+            Parameters::mkfs_options(value(" -m 0\t-E  lazy_itable_init=0\n").as_ref()),
+            vec!["-m", "0", "-E", "lazy_itable_init=0"]
+        );
+    }
+
+    #[test]
+    fn mount_options() {
+        assert_eq!(Parameters::mount_options(None), Ok(vec![]));
+        assert_eq!(Parameters::mount_options(value(" ").as_ref()), Ok(vec![]));
+        assert_eq!(
+            Parameters::mount_options(value("noatime, discard").as_ref()),
+            Ok(vec!["noatime".to_string(), "discard".to_string()])
+        );
+        // the read-only mode comes from the volume access mode
+        assert_eq!(
+            Parameters::mount_options(value("noatime,ro").as_ref()),
+            Err("ro".to_string())
+        );
+        assert_eq!(
+            Parameters::mount_options(value("rw").as_ref()),
+            Err("rw".to_string())
+        );
+        assert_eq!(
+            Parameters::mount_options(value("noatime,,discard").as_ref()),
+            Err("".to_string())
+        );
+        assert_eq!(
+            Parameters::mount_options(value("noatime,data= ordered").as_ref()),
+            Err("data= ordered".to_string())
+        );
+    }
+
+    #[test]
+    fn filesystem_params() {
+        let params = FilesystemParams::try_from(&labels(&[])).unwrap();
+        assert!(params.fs_type().is_none());
+        assert!(params.mkfs_options().is_empty());
+        assert!(params.mount_options().is_empty());
+
+        let args = labels(&[
+            ("fsType", "xfs"),
+            ("mkfsOptions", "-m reflink=0"),
+            ("mountOptions", "noatime,nouuid"),
+        ]);
+        let params = FilesystemParams::try_from(&args).unwrap();
+        assert_eq!(params.fs_type().as_ref().map(AsRef::as_ref), Some("xfs"));
+        assert_eq!(params.mkfs_options(), &vec!["-m", "reflink=0"]);
+        assert_eq!(params.mount_options(), &vec!["noatime", "nouuid"]);
+
+        let args = labels(&[("fsType", "vfat")]);
+        let error = FilesystemParams::try_from(&args).err().unwrap();
+        assert_eq!(error.code(), tonic::Code::InvalidArgument);
+
+        let args = labels(&[("fsType", "ext4"), ("mountOptions", "ro")]);
+        let error = FilesystemParams::try_from(&args).err().unwrap();
+        assert_eq!(error.code(), tonic::Code::InvalidArgument);
+        assert_eq!(error.message(), "Invalid mountOptions option 'ro'");
+    }
+}
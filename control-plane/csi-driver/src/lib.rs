@@ -46,7 +46,7 @@ pub const CSI_NODE_TOPOLOGY_VAL: &str = "mayastor";
 pub const NODE_NAME_TOPOLOGY_KEY: &str = "openebs.io/nodename";
 
 /// Volume Parameters parsed from context.
-pub use context::{CreateParams, Parameters, PublishParams};
+pub use context::{CreateParams, FilesystemParams, Parameters, PublishParams};
 
 /// The node plugin exported components.
 pub mod node;
@@ -53,16 +53,29 @@ pub fn timeout_grpc(op_id: MessageId, timeout_opts: TimeoutOptions) -> Duration
                 MessageIdVs::PublishVolume => min_timeouts.nexus(),
                 MessageIdVs::UnpublishVolume => min_timeouts.nexus(),
                 MessageIdVs::RepublishVolume => min_timeouts.nexus() * 2,
+                MessageIdVs::ResizeVolume => min_timeouts.replica() * 3 + min_timeouts.nexus(),
+                MessageIdVs::SetVolumeQos => min_timeouts.nexus(),
 
                 MessageIdVs::CreateNexus => min_timeouts.nexus(),
                 MessageIdVs::DestroyNexus => min_timeouts.nexus(),
                 MessageIdVs::ShutdownNexus => min_timeouts.nexus_shutdown(),
+                MessageIdVs::ResizeNexus => min_timeouts.nexus(),
+                MessageIdVs::SetNexusQos => min_timeouts.nexus(),
 
                 MessageIdVs::CreateReplica => min_timeouts.replica(),
                 MessageIdVs::DestroyReplica => min_timeouts.replica(),
+                MessageIdVs::ResizeReplica => min_timeouts.replica(),
+
+                MessageIdVs::CreateVolumeSnapshot => min_timeouts.replica(),
+                MessageIdVs::DestroyVolumeSnapshot => min_timeouts.replica(),
+                MessageIdVs::CreateReplicaSnapshot => min_timeouts.replica(),
+                MessageIdVs::DestroyReplicaSnapshot => min_timeouts.replica(),
+                MessageIdVs::CreateSnapshotClone => min_timeouts.replica(),
+                MessageIdVs::CreateNexusSnapshot => min_timeouts.nexus() + min_timeouts.replica(),
 
                 MessageIdVs::CreatePool => min_timeouts.pool(),
                 MessageIdVs::DestroyPool => min_timeouts.pool(),
+                MessageIdVs::ExpandPool => min_timeouts.pool(),
                 _ => base,
             },
         };
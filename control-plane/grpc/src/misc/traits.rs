@@ -28,6 +28,8 @@ impl From<ResourceKind> for common::ResourceKind {
             ResourceKind::State => Self::State,
             ResourceKind::NvmeSubsystem => Self::NvmeSubsystem,
             ResourceKind::NvmePath => Self::NvmePath,
+            ResourceKind::VolumeSnapshot => Self::VolumeSnapshot,
+            ResourceKind::SwitchOver => Self::SwitchOver,
         }
     }
 }
@@ -51,6 +53,8 @@ impl From<common::ResourceKind> for ResourceKind {
             common::ResourceKind::State => Self::State,
             common::ResourceKind::NvmeSubsystem => Self::NvmeSubsystem,
             common::ResourceKind::NvmePath => Self::NvmePath,
+            common::ResourceKind::VolumeSnapshot => Self::VolumeSnapshot,
+            common::ResourceKind::SwitchOver => Self::SwitchOver,
         }
     }
 }
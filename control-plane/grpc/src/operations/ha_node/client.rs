@@ -1,17 +1,20 @@
 use crate::{
     context::{Client, Context, TracedChannel},
-    ha_cluster_agent::ha_cluster_rpc_client::HaClusterRpcClient,
+    ha_cluster_agent::{
+        get_switch_overs_reply, ha_cluster_rpc_client::HaClusterRpcClient, move_volume_target_reply,
+    },
     ha_node_agent::{get_nvme_controller_response::Reply, ha_node_rpc_client::HaNodeRpcClient},
     operations::ha_node::traits::{
-        ClusterAgentOperations, GetControllerInfo, NodeAgentOperations, NodeInfo, ReplacePathInfo,
-        ReportFailedPathsInfo,
+        ClusterAgentOperations, GetControllerInfo, GetSwitchOversInfo, MoveVolumeTargetInfo,
+        NodeAgentOperations, NodeInfo, ReplacePathInfo, ReportFailedPathsInfo,
+        SwitchOverVolumeInfo,
     },
 };
 use common_lib::{
     transport_api::{v0::NvmeSubsystems, ReplyError, ResourceKind, TimeoutOptions},
-    types::v0::transport::MessageIdVs,
+    types::v0::transport::{MessageIdVs, SwitchOvers, Volume},
 };
-use std::ops::Deref;
+use std::{convert::TryFrom, ops::Deref};
 use tonic::transport::Uri;
 
 /// Cluster-Agent RPC client
@@ -68,6 +71,80 @@ impl ClusterAgentOperations for ClusterAgentClient {
         self.client().report_failed_nvme_paths(req).await?;
         Ok(())
     }
+
+    #[tracing::instrument(
+        name = "ClusterAgentClient::get_switchovers",
+        level = "debug",
+        skip(self),
+        err
+    )]
+    async fn get_switchovers(
+        &self,
+        request: &dyn GetSwitchOversInfo,
+        context: Option<Context>,
+    ) -> Result<SwitchOvers, ReplyError> {
+        let req = self.request(request, context, MessageIdVs::GetSwitchOvers);
+        let response = self.client().get_switch_overs(req).await?.into_inner();
+        match response.reply {
+            Some(get_switch_overs_reply::Reply::Switchovers(switchovers)) => {
+                SwitchOvers::try_from(switchovers)
+            }
+            Some(get_switch_overs_reply::Reply::Error(err)) => Err(err.into()),
+            None => Err(ReplyError::invalid_response(ResourceKind::SwitchOver)),
+        }
+    }
+
+    #[tracing::instrument(
+        name = "ClusterAgentClient::cancel_switchover",
+        level = "debug",
+        skip(self),
+        err
+    )]
+    async fn cancel_switchover(
+        &self,
+        request: &dyn SwitchOverVolumeInfo,
+        context: Option<Context>,
+    ) -> Result<(), ReplyError> {
+        let req = self.request(request, context, MessageIdVs::CancelSwitchOver);
+        self.client().cancel_switch_over(req).await?;
+        Ok(())
+    }
+
+    #[tracing::instrument(
+        name = "ClusterAgentClient::retry_switchover",
+        level = "debug",
+        skip(self),
+        err
+    )]
+    async fn retry_switchover(
+        &self,
+        request: &dyn SwitchOverVolumeInfo,
+        context: Option<Context>,
+    ) -> Result<(), ReplyError> {
+        let req = self.request(request, context, MessageIdVs::RetrySwitchOver);
+        self.client().retry_switch_over(req).await?;
+        Ok(())
+    }
+
+    #[tracing::instrument(
+        name = "ClusterAgentClient::move_volume_target",
+        level = "debug",
+        skip(self),
+        err
+    )]
+    async fn move_volume_target(
+        &self,
+        request: &dyn MoveVolumeTargetInfo,
+        context: Option<Context>,
+    ) -> Result<Volume, ReplyError> {
+        let req = self.request(request, context, MessageIdVs::MoveVolumeTarget);
+        let response = self.client().move_volume_target(req).await?.into_inner();
+        match response.reply {
+            Some(move_volume_target_reply::Reply::Volume(volume)) => Volume::try_from(volume),
+            Some(move_volume_target_reply::Reply::Error(err)) => Err(err.into()),
+            None => Err(ReplyError::invalid_response(ResourceKind::Volume)),
+        }
+    }
 }
 
 /// Node agent RPC Client.
@@ -2,14 +2,18 @@ use tonic::{Request, Response, Status};
 
 use crate::{
     ha_cluster_agent::{
+        get_switch_overs_reply,
         ha_cluster_rpc_server::{HaClusterRpc, HaClusterRpcServer},
-        HaNodeInfo, ReportFailedNvmePathsRequest,
+        move_volume_target_reply, GetSwitchOversReply, GetSwitchOversRequest, HaNodeInfo,
+        MoveVolumeTargetReply, MoveVolumeTargetRequest, ReportFailedNvmePathsRequest,
+        SwitchOverVolumeRequest,
     },
     ha_node_agent::{
         get_nvme_controller_response,
         ha_node_rpc_server::{HaNodeRpc, HaNodeRpcServer},
         GetNvmeControllerRequest, GetNvmeControllerResponse, ReplacePathRequest,
     },
+    misc::traits::ValidateRequestTypes,
     operations::ha_node::traits::{ClusterAgentOperations, NodeAgentOperations, NodeInfoConv},
 };
 use std::sync::Arc;
@@ -110,4 +114,55 @@ impl HaClusterRpc for ClusterAgentServer {
             ))),
         }
     }
+
+    async fn get_switch_overs(
+        &self,
+        request: tonic::Request<GetSwitchOversRequest>,
+    ) -> Result<tonic::Response<GetSwitchOversReply>, tonic::Status> {
+        let req = request.into_inner().validated()?;
+        match self.service.get_switchovers(&req, None).await {
+            Ok(switchovers) => Ok(Response::new(GetSwitchOversReply {
+                reply: Some(get_switch_overs_reply::Reply::Switchovers(
+                    switchovers.into(),
+                )),
+            })),
+            Err(err) => Ok(Response::new(GetSwitchOversReply {
+                reply: Some(get_switch_overs_reply::Reply::Error(err.into())),
+            })),
+        }
+    }
+    async fn cancel_switch_over(
+        &self,
+        request: tonic::Request<SwitchOverVolumeRequest>,
+    ) -> Result<tonic::Response<()>, tonic::Status> {
+        let req = request.into_inner().validated()?;
+        match self.service.cancel_switchover(&req, None).await {
+            Ok(_) => Ok(Response::new(())),
+            Err(err) => Err(err.into()),
+        }
+    }
+    async fn retry_switch_over(
+        &self,
+        request: tonic::Request<SwitchOverVolumeRequest>,
+    ) -> Result<tonic::Response<()>, tonic::Status> {
+        let req = request.into_inner().validated()?;
+        match self.service.retry_switchover(&req, None).await {
+            Ok(_) => Ok(Response::new(())),
+            Err(err) => Err(err.into()),
+        }
+    }
+    async fn move_volume_target(
+        &self,
+        request: tonic::Request<MoveVolumeTargetRequest>,
+    ) -> Result<tonic::Response<MoveVolumeTargetReply>, tonic::Status> {
+        let req = request.into_inner().validated()?;
+        match self.service.move_volume_target(&req, None).await {
+            Ok(volume) => Ok(Response::new(MoveVolumeTargetReply {
+                reply: Some(move_volume_target_reply::Reply::Volume(volume.into())),
+            })),
+            Err(err) => Ok(Response::new(MoveVolumeTargetReply {
+                reply: Some(move_volume_target_reply::Reply::Error(err.into())),
+            })),
+        }
+    }
 }
@@ -1,18 +1,24 @@
 use crate::{
     common,
     context::Context,
-    ha_cluster_agent::{FailedNvmePath, HaNodeInfo, ReportFailedNvmePathsRequest},
+    ha_cluster_agent::{
+        self, FailedNvmePath, GetSwitchOversRequest, HaNodeInfo, MoveVolumeTargetRequest,
+        ReportFailedNvmePathsRequest, SwitchOverVolumeRequest,
+    },
     ha_node_agent::{GetNvmeControllerRequest, NvmeControllers, ReplacePathRequest},
+    misc::traits::{StringValue, ValidateRequestTypes},
 };
 use common_lib::{
     transport_api::{v0::NvmeSubsystems, ReplyError, ResourceKind},
     types::v0::transport::{
-        cluster_agent::NodeAgentInfo, FailedPath, GetController, NvmeSubsystem, ReplacePath,
-        ReportFailedPaths,
+        cluster_agent::{MoveVolumeTarget, NodeAgentInfo},
+        CancelSwitchOver, FailedPath, GetController, GetSwitchOvers, NodeId, NvmeSubsystem,
+        ReplacePath, ReportFailedPaths, RetrySwitchOver, SwitchOver, SwitchOverStage, SwitchOvers,
+        Volume, VolumeId,
     },
     IntoVec,
 };
-use std::{collections::HashMap, net::SocketAddr};
+use std::{collections::HashMap, convert::TryFrom, net::SocketAddr, time::SystemTime};
 
 /// NodeAgentOperations trait implemented by client which supports cluster-agent operations
 #[tonic::async_trait]
@@ -89,6 +95,8 @@ pub trait ReplacePathInfo: Send + Sync + std::fmt::Debug {
     fn new_path(&self) -> String;
     /// Publish context of the volume
     fn publish_context(&self) -> Option<HashMap<String, String>>;
+    /// Whether the path being replaced is healthy, ie the target is moved on purpose
+    fn planned(&self) -> bool;
 }
 
 impl ReplacePathInfo for ReplacePath {
@@ -103,6 +111,10 @@ impl ReplacePathInfo for ReplacePath {
     fn publish_context(&self) -> Option<HashMap<String, String>> {
         self.publish_context()
     }
+
+    fn planned(&self) -> bool {
+        self.planned()
+    }
 }
 
 impl ReplacePathInfo for ReplacePathRequest {
@@ -118,6 +130,10 @@ impl ReplacePathInfo for ReplacePathRequest {
             .clone()
             .map(|map_wrapper| map_wrapper.map)
     }
+
+    fn planned(&self) -> bool {
+        self.planned
+    }
 }
 
 impl From<&dyn ReplacePathInfo> for ReplacePathRequest {
@@ -126,6 +142,7 @@ impl From<&dyn ReplacePathInfo> for ReplacePathRequest {
             target_nqn: src.target_nqn(),
             new_path: src.new_path(),
             publish_context: src.publish_context().map(|map| common::MapWrapper { map }),
+            planned: src.planned(),
         }
     }
 }
@@ -146,6 +163,34 @@ pub trait ClusterAgentOperations: Send + Sync {
         request: &dyn ReportFailedPathsInfo,
         context: Option<Context>,
     ) -> Result<(), ReplyError>;
+
+    /// Get the in-flight and recently failed volume switchovers.
+    async fn get_switchovers(
+        &self,
+        request: &dyn GetSwitchOversInfo,
+        context: Option<Context>,
+    ) -> Result<SwitchOvers, ReplyError>;
+
+    /// Cancel the in-flight switchover of a volume.
+    async fn cancel_switchover(
+        &self,
+        request: &dyn SwitchOverVolumeInfo,
+        context: Option<Context>,
+    ) -> Result<(), ReplyError>;
+
+    /// Retry the failed switchover of a volume.
+    async fn retry_switchover(
+        &self,
+        request: &dyn SwitchOverVolumeInfo,
+        context: Option<Context>,
+    ) -> Result<(), ReplyError>;
+
+    /// Move the target of a published volume to another node.
+    async fn move_volume_target(
+        &self,
+        request: &dyn MoveVolumeTargetInfo,
+        context: Option<Context>,
+    ) -> Result<Volume, ReplyError>;
 }
 
 /// NodeInfo trait for the node-agent registration to be implemented by entities which want to
@@ -269,3 +314,253 @@ impl From<FailedPath> for FailedNvmePath {
         }
     }
 }
+
+/// Trait to be implemented for GetSwitchOvers operation.
+pub trait GetSwitchOversInfo: Send + Sync + std::fmt::Debug {
+    /// Only return the switchovers of this volume.
+    fn volume_id(&self) -> Option<VolumeId>;
+}
+
+impl GetSwitchOversInfo for GetSwitchOvers {
+    fn volume_id(&self) -> Option<VolumeId> {
+        self.volume_id().cloned()
+    }
+}
+
+/// Intermediate structure that validates the conversion to GetSwitchOversRequest type.
+#[derive(Debug)]
+pub struct ValidatedGetSwitchOversRequest {
+    volume_id: Option<VolumeId>,
+}
+
+impl GetSwitchOversInfo for ValidatedGetSwitchOversRequest {
+    fn volume_id(&self) -> Option<VolumeId> {
+        self.volume_id.clone()
+    }
+}
+
+impl ValidateRequestTypes for GetSwitchOversRequest {
+    type Validated = ValidatedGetSwitchOversRequest;
+    fn validated(self) -> Result<Self::Validated, ReplyError> {
+        Ok(ValidatedGetSwitchOversRequest {
+            volume_id: match self.volume_id {
+                Some(id) => Some(VolumeId::try_from(StringValue(Some(id)))?),
+                None => None,
+            },
+        })
+    }
+}
+
+impl From<&dyn GetSwitchOversInfo> for GetSwitchOversRequest {
+    fn from(info: &dyn GetSwitchOversInfo) -> Self {
+        Self {
+            volume_id: info.volume_id().map(|id| id.to_string()),
+        }
+    }
+}
+
+/// Trait to be implemented for the operations targeting the switchover of a single volume.
+pub trait SwitchOverVolumeInfo: Send + Sync + std::fmt::Debug {
+    /// Volume whose switchover is targeted.
+    fn volume_id(&self) -> VolumeId;
+}
+
+impl SwitchOverVolumeInfo for CancelSwitchOver {
+    fn volume_id(&self) -> VolumeId {
+        self.volume_id().clone()
+    }
+}
+
+impl SwitchOverVolumeInfo for RetrySwitchOver {
+    fn volume_id(&self) -> VolumeId {
+        self.volume_id().clone()
+    }
+}
+
+/// Intermediate structure that validates the conversion to SwitchOverVolumeRequest type.
+#[derive(Debug)]
+pub struct ValidatedSwitchOverVolumeRequest {
+    volume_id: VolumeId,
+}
+
+impl SwitchOverVolumeInfo for ValidatedSwitchOverVolumeRequest {
+    fn volume_id(&self) -> VolumeId {
+        self.volume_id.clone()
+    }
+}
+
+impl ValidateRequestTypes for SwitchOverVolumeRequest {
+    type Validated = ValidatedSwitchOverVolumeRequest;
+    fn validated(self) -> Result<Self::Validated, ReplyError> {
+        Ok(ValidatedSwitchOverVolumeRequest {
+            volume_id: VolumeId::try_from(StringValue(Some(self.volume_id)))?,
+        })
+    }
+}
+
+impl From<&dyn SwitchOverVolumeInfo> for SwitchOverVolumeRequest {
+    fn from(info: &dyn SwitchOverVolumeInfo) -> Self {
+        Self {
+            volume_id: info.volume_id().to_string(),
+        }
+    }
+}
+
+/// Trait to be implemented for MoveVolumeTarget operation.
+pub trait MoveVolumeTargetInfo: Send + Sync + std::fmt::Debug {
+    /// Volume whose target is moved.
+    fn volume_id(&self) -> VolumeId;
+    /// Node where the new target should be created, if any.
+    fn target_node(&self) -> Option<NodeId>;
+}
+
+impl MoveVolumeTargetInfo for MoveVolumeTarget {
+    fn volume_id(&self) -> VolumeId {
+        self.volume_id().clone()
+    }
+
+    fn target_node(&self) -> Option<NodeId> {
+        self.target_node().cloned()
+    }
+}
+
+/// Intermediate structure that validates the conversion to MoveVolumeTargetRequest type.
+#[derive(Debug)]
+pub struct ValidatedMoveVolumeTargetRequest {
+    volume_id: VolumeId,
+    target_node: Option<NodeId>,
+}
+
+impl MoveVolumeTargetInfo for ValidatedMoveVolumeTargetRequest {
+    fn volume_id(&self) -> VolumeId {
+        self.volume_id.clone()
+    }
+
+    fn target_node(&self) -> Option<NodeId> {
+        self.target_node.clone()
+    }
+}
+
+impl ValidateRequestTypes for MoveVolumeTargetRequest {
+    type Validated = ValidatedMoveVolumeTargetRequest;
+    fn validated(self) -> Result<Self::Validated, ReplyError> {
+        Ok(ValidatedMoveVolumeTargetRequest {
+            volume_id: VolumeId::try_from(StringValue(Some(self.volume_id)))?,
+            target_node: self.target_node.map(NodeId::from),
+        })
+    }
+}
+
+impl From<&dyn MoveVolumeTargetInfo> for MoveVolumeTargetRequest {
+    fn from(info: &dyn MoveVolumeTargetInfo) -> Self {
+        Self {
+            volume_id: info.volume_id().to_string(),
+            target_node: info.target_node().map(|node| node.to_string()),
+        }
+    }
+}
+
+impl From<SwitchOverStage> for ha_cluster_agent::SwitchOverStage {
+    fn from(stage: SwitchOverStage) -> Self {
+        match stage {
+            SwitchOverStage::Init => Self::Init,
+            SwitchOverStage::RepublishVolume => Self::RepublishVolume,
+            SwitchOverStage::ReplacePath => Self::ReplacePath,
+            SwitchOverStage::DeleteTarget => Self::DeleteTarget,
+            SwitchOverStage::Successful => Self::Successful,
+            SwitchOverStage::Errored => Self::Errored,
+        }
+    }
+}
+
+impl From<ha_cluster_agent::SwitchOverStage> for SwitchOverStage {
+    fn from(stage: ha_cluster_agent::SwitchOverStage) -> Self {
+        match stage {
+            ha_cluster_agent::SwitchOverStage::Init => Self::Init,
+            ha_cluster_agent::SwitchOverStage::RepublishVolume => Self::RepublishVolume,
+            ha_cluster_agent::SwitchOverStage::ReplacePath => Self::ReplacePath,
+            ha_cluster_agent::SwitchOverStage::DeleteTarget => Self::DeleteTarget,
+            ha_cluster_agent::SwitchOverStage::Successful => Self::Successful,
+            ha_cluster_agent::SwitchOverStage::Errored => Self::Errored,
+        }
+    }
+}
+
+impl From<SwitchOver> for ha_cluster_agent::SwitchOver {
+    fn from(switchover: SwitchOver) -> Self {
+        let stage: ha_cluster_agent::SwitchOverStage = switchover.stage.into();
+        Self {
+            volume_id: switchover.volume_id.to_string(),
+            nodename: switchover.node_name.to_string(),
+            stage: stage as i32,
+            timestamp: Some(SystemTime::from(switchover.timestamp).into()),
+            retry_count: switchover.retry_count,
+            existing_nqn: switchover.existing_nqn,
+            new_path: switchover.new_path,
+            error: switchover.error,
+        }
+    }
+}
+
+impl TryFrom<ha_cluster_agent::SwitchOver> for SwitchOver {
+    type Error = ReplyError;
+    fn try_from(switchover: ha_cluster_agent::SwitchOver) -> Result<Self, Self::Error> {
+        let stage = match ha_cluster_agent::SwitchOverStage::from_i32(switchover.stage) {
+            Some(stage) => stage.into(),
+            None => {
+                return Err(ReplyError::invalid_argument(
+                    ResourceKind::SwitchOver,
+                    "stage",
+                    "".to_string(),
+                ))
+            }
+        };
+        let timestamp = match switchover.timestamp.map(SystemTime::try_from) {
+            Some(Ok(timestamp)) => timestamp.into(),
+            Some(Err(error)) => {
+                return Err(ReplyError::invalid_argument(
+                    ResourceKind::SwitchOver,
+                    "timestamp",
+                    error.to_string(),
+                ))
+            }
+            None => {
+                return Err(ReplyError::missing_argument(
+                    ResourceKind::SwitchOver,
+                    "timestamp",
+                ))
+            }
+        };
+        Ok(Self {
+            volume_id: VolumeId::try_from(StringValue(Some(switchover.volume_id)))?,
+            node_name: switchover.nodename.into(),
+            stage,
+            timestamp,
+            retry_count: switchover.retry_count,
+            existing_nqn: switchover.existing_nqn,
+            new_path: switchover.new_path,
+            error: switchover.error,
+        })
+    }
+}
+
+impl From<SwitchOvers> for ha_cluster_agent::SwitchOvers {
+    fn from(switchovers: SwitchOvers) -> Self {
+        Self {
+            switchovers: switchovers.into_inner().into_vec(),
+        }
+    }
+}
+
+impl TryFrom<ha_cluster_agent::SwitchOvers> for SwitchOvers {
+    type Error = ReplyError;
+    fn try_from(switchovers: ha_cluster_agent::SwitchOvers) -> Result<Self, Self::Error> {
+        Ok(Self(
+            switchovers
+                .switchovers
+                .into_iter()
+                .map(SwitchOver::try_from)
+                .collect::<Result<Vec<_>, _>>()?,
+        ))
+    }
+}
@@ -695,6 +695,7 @@ impl From<&dyn CreateNexusInfo> for CreateNexus {
             managed: data.managed(),
             owner: data.owner(),
             config: data.config(),
+            qos: None,
         }
     }
 }
@@ -3,9 +3,10 @@ use crate::{
     common::NodeFilter,
     context::{Client, Context, TracedChannel},
     node::{
-        cordon_node_reply, drain_node_reply, get_nodes_reply, get_nodes_request,
-        node_grpc_client::NodeGrpcClient, uncordon_node_reply, CordonNodeRequest, DrainNodeRequest,
-        GetNodesRequest, ProbeRequest, UncordonNodeRequest,
+        cordon_node_reply, drain_node_reply, get_nodes_reply, get_nodes_request, label_node_reply,
+        node_grpc_client::NodeGrpcClient, uncordon_node_reply, unlabel_node_reply,
+        CordonNodeRequest, DrainNodeRequest, GetNodesRequest, LabelNodeRequest, ProbeRequest,
+        UncordonNodeRequest, UnlabelNodeRequest,
     },
     operations::node::traits::{GetBlockDeviceInfo, NodeOperations},
 };
@@ -118,10 +119,11 @@ impl NodeOperations for NodeClient {
         }
     }
     #[tracing::instrument(name = "NodeClient::drain", level = "debug", skip(self), err)]
-    async fn drain(&self, id: NodeId, label: String) -> Result<Node, ReplyError> {
+    async fn drain(&self, id: NodeId, label: String, evacuate: bool) -> Result<Node, ReplyError> {
         let req = DrainNodeRequest {
             node_id: id.to_string(),
             label,
+            evacuate,
         };
         let response = self.client().drain_node(req).await?.into_inner();
         match response.reply {
@@ -132,4 +134,44 @@ impl NodeOperations for NodeClient {
             None => Err(ReplyError::invalid_response(ResourceKind::Node)),
         }
     }
+
+    #[tracing::instrument(name = "NodeClient::label", level = "debug", skip(self), err)]
+    async fn label(
+        &self,
+        id: NodeId,
+        key: String,
+        value: String,
+        overwrite: bool,
+    ) -> Result<Node, ReplyError> {
+        let req = LabelNodeRequest {
+            node_id: id.to_string(),
+            key,
+            value,
+            overwrite,
+        };
+        let response = self.client().label_node(req).await?.into_inner();
+        match response.reply {
+            Some(label_node_reply) => match label_node_reply {
+                label_node_reply::Reply::Node(node) => Ok(Node::try_from(node)?),
+                label_node_reply::Reply::Error(err) => Err(err.into()),
+            },
+            None => Err(ReplyError::invalid_response(ResourceKind::Node)),
+        }
+    }
+
+    #[tracing::instrument(name = "NodeClient::unlabel", level = "debug", skip(self), err)]
+    async fn unlabel(&self, id: NodeId, key: String) -> Result<Node, ReplyError> {
+        let req = UnlabelNodeRequest {
+            node_id: id.to_string(),
+            key,
+        };
+        let response = self.client().unlabel_node(req).await?.into_inner();
+        match response.reply {
+            Some(unlabel_node_reply) => match unlabel_node_reply {
+                unlabel_node_reply::Reply::Node(node) => Ok(Node::try_from(node)?),
+                unlabel_node_reply::Reply::Error(err) => Err(err.into()),
+            },
+            None => Err(ReplyError::invalid_response(ResourceKind::Node)),
+        }
+    }
 }
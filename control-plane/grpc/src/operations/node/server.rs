@@ -2,11 +2,12 @@ use crate::{
     blockdevice::{get_block_devices_reply, GetBlockDevicesReply, GetBlockDevicesRequest},
     node,
     node::{
-        cordon_node_reply, drain_node_reply, get_nodes_reply,
+        cordon_node_reply, drain_node_reply, get_nodes_reply, label_node_reply,
         node_grpc_server::{NodeGrpc, NodeGrpcServer},
-        uncordon_node_reply, CordonNodeReply, CordonNodeRequest, DrainNodeReply, DrainNodeRequest,
-        GetNodesReply, GetNodesRequest, ProbeRequest, ProbeResponse, UncordonNodeReply,
-        UncordonNodeRequest,
+        uncordon_node_reply, unlabel_node_reply, CordonNodeReply, CordonNodeRequest,
+        DrainNodeReply, DrainNodeRequest, GetNodesReply, GetNodesRequest, LabelNodeReply,
+        LabelNodeRequest, ProbeRequest, ProbeResponse, UncordonNodeReply, UncordonNodeRequest,
+        UnlabelNodeReply, UnlabelNodeRequest,
     },
     operations::node::traits::NodeOperations,
 };
@@ -108,7 +109,11 @@ impl NodeGrpc for NodeServer {
         request: tonic::Request<DrainNodeRequest>,
     ) -> Result<tonic::Response<DrainNodeReply>, tonic::Status> {
         let req: DrainNodeRequest = request.into_inner();
-        match self.service.drain(req.node_id.into(), req.label).await {
+        match self
+            .service
+            .drain(req.node_id.into(), req.label, req.evacuate)
+            .await
+        {
             Ok(node) => Ok(Response::new(DrainNodeReply {
                 reply: Some(drain_node_reply::Reply::Node(node.into())),
             })),
@@ -117,4 +122,38 @@ impl NodeGrpc for NodeServer {
             })),
         }
     }
+
+    async fn label_node(
+        &self,
+        request: tonic::Request<LabelNodeRequest>,
+    ) -> Result<tonic::Response<LabelNodeReply>, tonic::Status> {
+        let req: LabelNodeRequest = request.into_inner();
+        match self
+            .service
+            .label(req.node_id.into(), req.key, req.value, req.overwrite)
+            .await
+        {
+            Ok(node) => Ok(Response::new(LabelNodeReply {
+                reply: Some(label_node_reply::Reply::Node(node.into())),
+            })),
+            Err(err) => Ok(Response::new(LabelNodeReply {
+                reply: Some(label_node_reply::Reply::Error(err.into())),
+            })),
+        }
+    }
+
+    async fn unlabel_node(
+        &self,
+        request: tonic::Request<UnlabelNodeRequest>,
+    ) -> Result<tonic::Response<UnlabelNodeReply>, tonic::Status> {
+        let req: UnlabelNodeRequest = request.into_inner();
+        match self.service.unlabel(req.node_id.into(), req.key).await {
+            Ok(node) => Ok(Response::new(UnlabelNodeReply {
+                reply: Some(unlabel_node_reply::Reply::Node(node.into())),
+            })),
+            Err(err) => Ok(Response::new(UnlabelNodeReply {
+                reply: Some(unlabel_node_reply::Reply::Error(err.into())),
+            })),
+        }
+    }
 }
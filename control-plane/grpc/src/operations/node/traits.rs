@@ -1,6 +1,6 @@
 use crate::{
-    blockdevice, blockdevice::GetBlockDevicesRequest, context::Context, node,
-    node::get_nodes_request,
+    blockdevice, blockdevice::GetBlockDevicesRequest, context::Context, misc::traits::StringValue,
+    node, node::get_nodes_request,
 };
 use common_lib::{
     transport_api::{
@@ -8,10 +8,10 @@ use common_lib::{
         ReplyError, ResourceKind,
     },
     types::v0::{
-        store::node::{CordonDrainState, CordonedState, DrainState, NodeSpec},
+        store::node::{CordonDrainState, CordonedState, DrainState, NodeSpec, VolumeEvacuation},
         transport::{
             BlockDevice, Filesystem, Filter, GetBlockDevices, Node, NodeId, NodeState, NodeStatus,
-            Partition,
+            Partition, VolumeId,
         },
     },
     TryIntoOption,
@@ -36,7 +36,19 @@ pub trait NodeOperations: Send + Sync {
     /// Uncordon the node with the given ID by removing the associated label.
     async fn uncordon(&self, id: NodeId, label: String) -> Result<Node, ReplyError>;
     /// Drain the node with the given ID and associate the label with the draining node.
-    async fn drain(&self, id: NodeId, label: String) -> Result<Node, ReplyError>;
+    /// If `evacuate` is set the volume replicas are also moved off the node.
+    async fn drain(&self, id: NodeId, label: String, evacuate: bool) -> Result<Node, ReplyError>;
+    /// Label the node with the given ID with the given key and value.
+    /// The value of an existing label is only changed if `overwrite` is set.
+    async fn label(
+        &self,
+        id: NodeId,
+        key: String,
+        value: String,
+        overwrite: bool,
+    ) -> Result<Node, ReplyError>;
+    /// Remove the label with the given key from the node with the given ID.
+    async fn unlabel(&self, id: NodeId, key: String) -> Result<Node, ReplyError>;
 }
 
 impl TryFrom<node::Node> for Node {
@@ -62,18 +74,10 @@ impl TryFrom<node::Node> for Node {
                             Some(CordonDrainState::Cordoned(type_v0_cordoned_state))
                         }
                         Some(node::cordon_drain_state::Cordondrainstate::Draining(state)) => {
-                            let type_v0_draining_state = DrainState {
-                                cordonlabels: state.cordon_labels,
-                                drainlabels: state.drain_labels,
-                            };
-                            Some(CordonDrainState::Draining(type_v0_draining_state))
+                            Some(CordonDrainState::Draining(DrainState::try_from(state)?))
                         }
                         Some(node::cordon_drain_state::Cordondrainstate::Drained(state)) => {
-                            let type_v0_drained_state = DrainState {
-                                cordonlabels: state.cordon_labels,
-                                drainlabels: state.drain_labels,
-                            };
-                            Some(CordonDrainState::Drained(type_v0_drained_state))
+                            Some(CordonDrainState::Drained(DrainState::try_from(state)?))
                         }
                         None => None,
                     },
@@ -139,21 +143,13 @@ impl From<Node> for node::Node {
                                 ))
                             }
                             CordonDrainState::Draining(state) => {
-                                let grpc_draining_state = node::DrainState {
-                                    cordon_labels: state.cordonlabels.clone(),
-                                    drain_labels: state.drainlabels.clone(),
-                                };
                                 Some(node::cordon_drain_state::Cordondrainstate::Draining(
-                                    grpc_draining_state,
+                                    state.clone().into(),
                                 ))
                             }
                             CordonDrainState::Drained(state) => {
-                                let grpc_drained_state = node::DrainState {
-                                    cordon_labels: state.cordonlabels.clone(),
-                                    drain_labels: state.drainlabels.clone(),
-                                };
                                 Some(node::cordon_drain_state::Cordondrainstate::Drained(
-                                    grpc_drained_state,
+                                    state.clone().into(),
                                 ))
                             }
                         },
@@ -245,12 +241,30 @@ impl From<node::CordonedState> for CordonedState {
         }
     }
 }
-impl From<node::DrainState> for DrainState {
-    fn from(src: node::DrainState) -> Self {
-        Self {
+impl TryFrom<node::DrainState> for DrainState {
+    type Error = ReplyError;
+    fn try_from(src: node::DrainState) -> Result<Self, Self::Error> {
+        Ok(Self {
             cordonlabels: src.cordon_labels,
             drainlabels: src.drain_labels,
-        }
+            evacuate: src.evacuate,
+            evacuations: src
+                .evacuations
+                .into_iter()
+                .map(VolumeEvacuation::try_from)
+                .collect::<Result<Vec<_>, _>>()?,
+        })
+    }
+}
+impl TryFrom<node::VolumeEvacuation> for VolumeEvacuation {
+    type Error = ReplyError;
+    fn try_from(src: node::VolumeEvacuation) -> Result<Self, Self::Error> {
+        Ok(Self {
+            volume: VolumeId::try_from(StringValue(Some(src.volume_id)))?,
+            replicas: src.replicas.min(u8::MAX as u32) as u8,
+            rebuild_progress: src.rebuild_progress.map(|progress| progress.min(100) as u8),
+            degraded: src.degraded,
+        })
     }
 }
 
@@ -267,6 +281,18 @@ impl From<DrainState> for node::DrainState {
         Self {
             cordon_labels: src.cordonlabels,
             drain_labels: src.drainlabels,
+            evacuate: src.evacuate,
+            evacuations: src.evacuations.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+impl From<VolumeEvacuation> for node::VolumeEvacuation {
+    fn from(src: VolumeEvacuation) -> Self {
+        Self {
+            volume_id: src.volume.to_string(),
+            replicas: src.replicas as u32,
+            rebuild_progress: src.rebuild_progress.map(|progress| progress as u32),
+            degraded: src.degraded,
         }
     }
 }
@@ -100,7 +100,10 @@ mod test {
             context::Context,
             operations::pool::{
                 test::TimeoutTester,
-                traits::{CreatePoolInfo, DestroyPoolInfo, PoolOperations},
+                traits::{
+                    CreatePoolInfo, DestroyPoolInfo, ExpandPoolInfo, LabelPoolInfo, PoolOperations,
+                    UnlabelPoolInfo,
+                },
             },
         };
         use common_lib::{
@@ -136,6 +139,27 @@ mod test {
                 tester.complete();
                 Ok(Pools(vec![]))
             }
+            async fn expand(
+                &self,
+                _pool: &dyn ExpandPoolInfo,
+                _ctx: Option<Context>,
+            ) -> Result<Pool, ReplyError> {
+                todo!()
+            }
+            async fn label(
+                &self,
+                _pool: &dyn LabelPoolInfo,
+                _ctx: Option<Context>,
+            ) -> Result<Pool, ReplyError> {
+                todo!()
+            }
+            async fn unlabel(
+                &self,
+                _pool: &dyn UnlabelPoolInfo,
+                _ctx: Option<Context>,
+            ) -> Result<Pool, ReplyError> {
+                todo!()
+            }
         }
     }
 }
@@ -2,10 +2,11 @@ use crate::{
     operations::pool::traits::PoolOperations,
     pool,
     pool::{
-        create_pool_reply, get_pools_reply,
+        create_pool_reply, expand_pool_reply, get_pools_reply, label_pool_reply,
         pool_grpc_server::{PoolGrpc, PoolGrpcServer},
-        CreatePoolReply, CreatePoolRequest, DestroyPoolReply, DestroyPoolRequest, GetPoolsReply,
-        GetPoolsRequest,
+        unlabel_pool_reply, CreatePoolReply, CreatePoolRequest, DestroyPoolReply,
+        DestroyPoolRequest, ExpandPoolReply, ExpandPoolRequest, GetPoolsReply, GetPoolsRequest,
+        LabelPoolReply, LabelPoolRequest, UnlabelPoolReply, UnlabelPoolRequest,
     },
 };
 use std::sync::Arc;
@@ -74,4 +75,49 @@ impl PoolGrpc for PoolServer {
             })),
         }
     }
+
+    async fn expand_pool(
+        &self,
+        request: Request<ExpandPoolRequest>,
+    ) -> Result<tonic::Response<ExpandPoolReply>, tonic::Status> {
+        let req: ExpandPoolRequest = request.into_inner();
+        match self.service.expand(&req, None).await {
+            Ok(pool) => Ok(Response::new(ExpandPoolReply {
+                reply: Some(expand_pool_reply::Reply::Pool(pool.into())),
+            })),
+            Err(err) => Ok(Response::new(ExpandPoolReply {
+                reply: Some(expand_pool_reply::Reply::Error(err.into())),
+            })),
+        }
+    }
+
+    async fn label_pool(
+        &self,
+        request: Request<LabelPoolRequest>,
+    ) -> Result<tonic::Response<LabelPoolReply>, tonic::Status> {
+        let req: LabelPoolRequest = request.into_inner();
+        match self.service.label(&req, None).await {
+            Ok(pool) => Ok(Response::new(LabelPoolReply {
+                reply: Some(label_pool_reply::Reply::Pool(pool.into())),
+            })),
+            Err(err) => Ok(Response::new(LabelPoolReply {
+                reply: Some(label_pool_reply::Reply::Error(err.into())),
+            })),
+        }
+    }
+
+    async fn unlabel_pool(
+        &self,
+        request: Request<UnlabelPoolRequest>,
+    ) -> Result<tonic::Response<UnlabelPoolReply>, tonic::Status> {
+        let req: UnlabelPoolRequest = request.into_inner();
+        match self.service.unlabel(&req, None).await {
+            Ok(pool) => Ok(Response::new(UnlabelPoolReply {
+                reply: Some(unlabel_pool_reply::Reply::Pool(pool.into())),
+            })),
+            Err(err) => Ok(Response::new(UnlabelPoolReply {
+                reply: Some(unlabel_pool_reply::Reply::Error(err.into())),
+            })),
+        }
+    }
 }
@@ -2,7 +2,10 @@ use crate::{
     common,
     context::Context,
     pool,
-    pool::{get_pools_request, CreatePoolRequest, DestroyPoolRequest},
+    pool::{
+        get_pools_request, CreatePoolRequest, DestroyPoolRequest, ExpandPoolRequest,
+        LabelPoolRequest, UnlabelPoolRequest,
+    },
 };
 use common_lib::{
     transport_api::{v0::Pools, ReplyError, ResourceKind},
@@ -10,7 +13,8 @@ use common_lib::{
         store::pool::{PoolLabel, PoolSpec, PoolSpecStatus},
         transport,
         transport::{
-            CreatePool, DestroyPool, Filter, NodeId, Pool, PoolDeviceUri, PoolId, PoolState,
+            CreatePool, DestroyPool, ExpandPool, Filter, LabelPool, NodeId, Pool, PoolDeviceUri,
+            PoolId, PoolState, UnlabelPool,
         },
     },
 };
@@ -33,6 +37,24 @@ pub trait PoolOperations: Send + Sync {
     ) -> Result<(), ReplyError>;
     /// Get pools based on the filters
     async fn get(&self, filter: Filter, ctx: Option<Context>) -> Result<Pools, ReplyError>;
+    /// Expand a pool
+    async fn expand(
+        &self,
+        pool: &dyn ExpandPoolInfo,
+        ctx: Option<Context>,
+    ) -> Result<Pool, ReplyError>;
+    /// Label a pool
+    async fn label(
+        &self,
+        pool: &dyn LabelPoolInfo,
+        ctx: Option<Context>,
+    ) -> Result<Pool, ReplyError>;
+    /// Unlabel a pool
+    async fn unlabel(
+        &self,
+        pool: &dyn UnlabelPoolInfo,
+        ctx: Option<Context>,
+    ) -> Result<Pool, ReplyError>;
 }
 
 impl TryFrom<pool::PoolDefinition> for PoolSpec {
@@ -233,6 +255,39 @@ pub trait DestroyPoolInfo: Sync + Send + std::fmt::Debug {
     fn node_id(&self) -> NodeId;
 }
 
+/// ExpandPoolInfo trait for the pool expansion to be implemented by entities which want to avail
+/// this operation
+pub trait ExpandPoolInfo: Send + Sync + std::fmt::Debug {
+    /// Id of the pool
+    fn pool_id(&self) -> PoolId;
+    /// Id of the IoEngine instance
+    fn node_id(&self) -> NodeId;
+    /// Disk device paths or URIs to be claimed by the pool, including the existing ones
+    fn disks(&self) -> Vec<PoolDeviceUri>;
+}
+
+/// LabelPoolInfo trait for the pool labelling to be implemented by entities which want to avail
+/// this operation
+pub trait LabelPoolInfo: Send + Sync + std::fmt::Debug {
+    /// Id of the pool
+    fn pool_id(&self) -> PoolId;
+    /// Key of the label
+    fn key(&self) -> String;
+    /// Value of the label
+    fn value(&self) -> String;
+    /// Overwrite the value of the label if the pool already has it
+    fn overwrite(&self) -> bool;
+}
+
+/// UnlabelPoolInfo trait for the pool unlabelling to be implemented by entities which want to
+/// avail this operation
+pub trait UnlabelPoolInfo: Send + Sync + std::fmt::Debug {
+    /// Id of the pool
+    fn pool_id(&self) -> PoolId;
+    /// Key of the label
+    fn key(&self) -> String;
+}
+
 impl CreatePoolInfo for CreatePool {
     fn pool_id(&self) -> PoolId {
         self.id.clone()
@@ -334,6 +389,150 @@ impl From<&dyn DestroyPoolInfo> for DestroyPool {
     }
 }
 
+impl ExpandPoolInfo for ExpandPool {
+    fn pool_id(&self) -> PoolId {
+        self.id.clone()
+    }
+
+    fn node_id(&self) -> NodeId {
+        self.node.clone()
+    }
+
+    fn disks(&self) -> Vec<PoolDeviceUri> {
+        self.disks.clone()
+    }
+}
+
+impl ExpandPoolInfo for ExpandPoolRequest {
+    fn pool_id(&self) -> PoolId {
+        self.pool_id.clone().into()
+    }
+
+    fn node_id(&self) -> NodeId {
+        self.node_id.clone().into()
+    }
+
+    fn disks(&self) -> Vec<PoolDeviceUri> {
+        self.disks.iter().map(|disk| disk.into()).collect()
+    }
+}
+
+impl From<&dyn ExpandPoolInfo> for ExpandPoolRequest {
+    fn from(data: &dyn ExpandPoolInfo) -> Self {
+        Self {
+            pool_id: data.pool_id().to_string(),
+            node_id: data.node_id().to_string(),
+            disks: data.disks().iter().map(|disk| disk.to_string()).collect(),
+        }
+    }
+}
+
+impl From<&dyn ExpandPoolInfo> for ExpandPool {
+    fn from(data: &dyn ExpandPoolInfo) -> Self {
+        Self {
+            node: data.node_id(),
+            id: data.pool_id(),
+            disks: data.disks(),
+        }
+    }
+}
+
+impl LabelPoolInfo for LabelPool {
+    fn pool_id(&self) -> PoolId {
+        self.id.clone()
+    }
+
+    fn key(&self) -> String {
+        self.key.clone()
+    }
+
+    fn value(&self) -> String {
+        self.value.clone()
+    }
+
+    fn overwrite(&self) -> bool {
+        self.overwrite
+    }
+}
+
+impl LabelPoolInfo for LabelPoolRequest {
+    fn pool_id(&self) -> PoolId {
+        self.pool_id.clone().into()
+    }
+
+    fn key(&self) -> String {
+        self.key.clone()
+    }
+
+    fn value(&self) -> String {
+        self.value.clone()
+    }
+
+    fn overwrite(&self) -> bool {
+        self.overwrite
+    }
+}
+
+impl From<&dyn LabelPoolInfo> for LabelPoolRequest {
+    fn from(data: &dyn LabelPoolInfo) -> Self {
+        Self {
+            pool_id: data.pool_id().to_string(),
+            key: data.key(),
+            value: data.value(),
+            overwrite: data.overwrite(),
+        }
+    }
+}
+
+impl From<&dyn LabelPoolInfo> for LabelPool {
+    fn from(data: &dyn LabelPoolInfo) -> Self {
+        Self {
+            id: data.pool_id(),
+            key: data.key(),
+            value: data.value(),
+            overwrite: data.overwrite(),
+        }
+    }
+}
+
+impl UnlabelPoolInfo for UnlabelPool {
+    fn pool_id(&self) -> PoolId {
+        self.id.clone()
+    }
+
+    fn key(&self) -> String {
+        self.key.clone()
+    }
+}
+
+impl UnlabelPoolInfo for UnlabelPoolRequest {
+    fn pool_id(&self) -> PoolId {
+        self.pool_id.clone().into()
+    }
+
+    fn key(&self) -> String {
+        self.key.clone()
+    }
+}
+
+impl From<&dyn UnlabelPoolInfo> for UnlabelPoolRequest {
+    fn from(data: &dyn UnlabelPoolInfo) -> Self {
+        Self {
+            pool_id: data.pool_id().to_string(),
+            key: data.key(),
+        }
+    }
+}
+
+impl From<&dyn UnlabelPoolInfo> for UnlabelPool {
+    fn from(data: &dyn UnlabelPoolInfo) -> Self {
+        Self {
+            id: data.pool_id(),
+            key: data.key(),
+        }
+    }
+}
+
 impl From<pool::PoolStatus> for transport::PoolStatus {
     fn from(src: pool::PoolStatus) -> Self {
         match src {
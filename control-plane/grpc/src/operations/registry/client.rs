@@ -1,11 +1,17 @@
 use crate::{
     context::{Client, Context, TracedChannel},
-    operations::registry::traits::{GetSpecsInfo, GetStatesInfo, RegistryOperations},
-    registry::{get_specs_reply, get_states_reply, registry_grpc_client::RegistryGrpcClient},
+    operations::registry::traits::{
+        GetHaHistoryInfo, GetRebuildHistoryInfo, GetRebuildQueueInfo, GetSpecsInfo, GetStatesInfo,
+        RegistryOperations,
+    },
+    registry::{
+        get_ha_history_reply, get_rebuild_history_reply, get_rebuild_queue_reply, get_specs_reply,
+        get_states_reply, registry_grpc_client::RegistryGrpcClient,
+    },
 };
 use common_lib::{
     transport_api::{ReplyError, ResourceKind, TimeoutOptions},
-    types::v0::transport::{MessageIdVs, Specs, States},
+    types::v0::transport::{HaHistory, MessageIdVs, RebuildQueue, Specs, States, VolumeRebuilds},
 };
 use std::{convert::TryFrom, ops::Deref};
 use tonic::transport::Uri;
@@ -64,4 +70,54 @@ impl RegistryOperations for RegistryClient {
             None => Err(ReplyError::invalid_response(ResourceKind::State)),
         }
     }
+
+    async fn get_ha_history(
+        &self,
+        request: &dyn GetHaHistoryInfo,
+        ctx: Option<Context>,
+    ) -> Result<HaHistory, ReplyError> {
+        let req = self.request(request, ctx, MessageIdVs::GetHaHistory);
+        let response = self.client().get_ha_history(req).await?.into_inner();
+        match response.reply {
+            Some(get_ha_history_reply) => match get_ha_history_reply {
+                get_ha_history_reply::Reply::History(history) => Ok(HaHistory::try_from(history)?),
+                get_ha_history_reply::Reply::Error(err) => Err(err.into()),
+            },
+            None => Err(ReplyError::invalid_response(ResourceKind::SwitchOver)),
+        }
+    }
+
+    async fn get_rebuild_history(
+        &self,
+        request: &dyn GetRebuildHistoryInfo,
+        ctx: Option<Context>,
+    ) -> Result<VolumeRebuilds, ReplyError> {
+        let req = self.request(request, ctx, MessageIdVs::GetRebuildHistory);
+        let response = self.client().get_rebuild_history(req).await?.into_inner();
+        match response.reply {
+            Some(get_rebuild_history_reply) => match get_rebuild_history_reply {
+                get_rebuild_history_reply::Reply::Rebuilds(rebuilds) => {
+                    Ok(VolumeRebuilds::try_from(rebuilds)?)
+                }
+                get_rebuild_history_reply::Reply::Error(err) => Err(err.into()),
+            },
+            None => Err(ReplyError::invalid_response(ResourceKind::Volume)),
+        }
+    }
+
+    async fn get_rebuild_queue(
+        &self,
+        request: &dyn GetRebuildQueueInfo,
+        ctx: Option<Context>,
+    ) -> Result<RebuildQueue, ReplyError> {
+        let req = self.request(request, ctx, MessageIdVs::GetRebuildQueue);
+        let response = self.client().get_rebuild_queue(req).await?.into_inner();
+        match response.reply {
+            Some(get_rebuild_queue_reply) => match get_rebuild_queue_reply {
+                get_rebuild_queue_reply::Reply::Queue(queue) => Ok(RebuildQueue::try_from(queue)?),
+                get_rebuild_queue_reply::Reply::Error(err) => Err(err.into()),
+            },
+            None => Err(ReplyError::invalid_response(ResourceKind::Volume)),
+        }
+    }
 }
@@ -1,9 +1,13 @@
 use crate::{
+    misc::traits::ValidateRequestTypes,
     operations::registry::traits::RegistryOperations,
     registry::{
-        get_specs_reply, get_states_reply,
+        get_ha_history_reply, get_rebuild_history_reply, get_rebuild_queue_reply, get_specs_reply,
+        get_states_reply,
         registry_grpc_server::{RegistryGrpc, RegistryGrpcServer},
-        GetSpecsReply, GetSpecsRequest, GetStatesReply, GetStatesRequest,
+        GetHaHistoryReply, GetHaHistoryRequest, GetRebuildHistoryReply, GetRebuildHistoryRequest,
+        GetRebuildQueueReply, GetRebuildQueueRequest, GetSpecsReply, GetSpecsRequest,
+        GetStatesReply, GetStatesRequest,
     },
 };
 use std::sync::Arc;
@@ -57,4 +61,46 @@ impl RegistryGrpc for RegistryServer {
             })),
         }
     }
+    async fn get_ha_history(
+        &self,
+        request: tonic::Request<GetHaHistoryRequest>,
+    ) -> Result<tonic::Response<GetHaHistoryReply>, tonic::Status> {
+        let req = request.into_inner().validated()?;
+        match self.service.get_ha_history(&req, None).await {
+            Ok(history) => Ok(Response::new(GetHaHistoryReply {
+                reply: Some(get_ha_history_reply::Reply::History(history.into())),
+            })),
+            Err(err) => Ok(Response::new(GetHaHistoryReply {
+                reply: Some(get_ha_history_reply::Reply::Error(err.into())),
+            })),
+        }
+    }
+    async fn get_rebuild_history(
+        &self,
+        request: tonic::Request<GetRebuildHistoryRequest>,
+    ) -> Result<tonic::Response<GetRebuildHistoryReply>, tonic::Status> {
+        let req = request.into_inner().validated()?;
+        match self.service.get_rebuild_history(&req, None).await {
+            Ok(rebuilds) => Ok(Response::new(GetRebuildHistoryReply {
+                reply: Some(get_rebuild_history_reply::Reply::Rebuilds(rebuilds.into())),
+            })),
+            Err(err) => Ok(Response::new(GetRebuildHistoryReply {
+                reply: Some(get_rebuild_history_reply::Reply::Error(err.into())),
+            })),
+        }
+    }
+    async fn get_rebuild_queue(
+        &self,
+        request: tonic::Request<GetRebuildQueueRequest>,
+    ) -> Result<tonic::Response<GetRebuildQueueReply>, tonic::Status> {
+        let req: GetRebuildQueueRequest = request.into_inner();
+        match self.service.get_rebuild_queue(&req, None).await {
+            Ok(queue) => Ok(Response::new(GetRebuildQueueReply {
+                reply: Some(get_rebuild_queue_reply::Reply::Queue(queue.into())),
+            })),
+            Err(err) => Ok(Response::new(GetRebuildQueueReply {
+                reply: Some(get_rebuild_queue_reply::Reply::Error(err.into())),
+            })),
+        }
+    }
 }
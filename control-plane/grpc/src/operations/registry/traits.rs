@@ -1,18 +1,34 @@
 use crate::{
     context::Context,
+    ha_cluster_agent,
+    misc::traits::{StringValue, ValidateRequestTypes},
     registry,
-    registry::{GetSpecsRequest, GetStatesRequest},
+    registry::{
+        GetHaHistoryRequest, GetRebuildHistoryRequest, GetRebuildQueueRequest, GetSpecsRequest,
+        GetStatesRequest,
+    },
 };
 use common_lib::{
-    transport_api::ReplyError,
+    transport_api::{ReplyError, ResourceKind},
     types::v0::{
         store,
-        store::{nexus::NexusSpec, pool::PoolSpec, replica::ReplicaSpec, volume::VolumeSpec},
+        store::{
+            nexus::NexusSpec,
+            pool::PoolSpec,
+            rebuild::{RebuildOutcome, RebuildReason, RebuildRecord, RebuildTime},
+            replica::ReplicaSpec,
+            switchover::{PathFailureRecord, StageTiming, SwitchOverRecord, SwitchOverTime},
+            volume::VolumeSpec,
+        },
         transport,
-        transport::{GetSpecs, GetStates, Specs},
+        transport::{
+            GetHaHistory, GetRebuildHistory, GetRebuildQueue, GetSpecs, GetStates, HaHistory,
+            NexusId, NodeId, QueuedRebuild, RebuildQueue, ReplicaId, Specs, VolumeId,
+            VolumeRebuilds,
+        },
     },
 };
-use std::convert::TryFrom;
+use std::{convert::TryFrom, time::SystemTime};
 
 /// Trait implemented by services which support registry operations.
 #[tonic::async_trait]
@@ -29,6 +45,24 @@ pub trait RegistryOperations: Send + Sync {
         get_spec: &dyn GetStatesInfo,
         ctx: Option<Context>,
     ) -> Result<transport::States, ReplyError>;
+    /// Get the persisted HA history of path failures and switchover outcomes
+    async fn get_ha_history(
+        &self,
+        get_history: &dyn GetHaHistoryInfo,
+        ctx: Option<Context>,
+    ) -> Result<HaHistory, ReplyError>;
+    /// Get the running and persisted volume replica rebuilds
+    async fn get_rebuild_history(
+        &self,
+        get_history: &dyn GetRebuildHistoryInfo,
+        ctx: Option<Context>,
+    ) -> Result<VolumeRebuilds, ReplyError>;
+    /// Get the volume replica rebuilds which are yet to be started
+    async fn get_rebuild_queue(
+        &self,
+        get_queue: &dyn GetRebuildQueueInfo,
+        ctx: Option<Context>,
+    ) -> Result<RebuildQueue, ReplyError>;
 }
 
 /// GetSpecsInfo trait for the get_specs operation
@@ -45,6 +79,112 @@ impl GetStatesInfo for GetStates {}
 
 impl GetStatesInfo for GetStatesRequest {}
 
+/// GetRebuildQueueInfo trait for the get_rebuild_queue operation
+pub trait GetRebuildQueueInfo: Send + Sync {}
+
+impl GetRebuildQueueInfo for GetRebuildQueue {}
+
+impl GetRebuildQueueInfo for GetRebuildQueueRequest {}
+
+/// GetHaHistoryInfo trait for the get_ha_history operation
+pub trait GetHaHistoryInfo: Send + Sync + std::fmt::Debug {
+    /// Only return the history of this volume
+    fn volume_id(&self) -> Option<VolumeId>;
+    /// Only return the history of this node
+    fn node_id(&self) -> Option<NodeId>;
+}
+
+impl GetHaHistoryInfo for GetHaHistory {
+    fn volume_id(&self) -> Option<VolumeId> {
+        self.volume_id().cloned()
+    }
+    fn node_id(&self) -> Option<NodeId> {
+        self.node_id().cloned()
+    }
+}
+
+/// Intermediate structure that validates the conversion to GetHaHistoryRequest type.
+#[derive(Debug)]
+pub struct ValidatedGetHaHistoryRequest {
+    volume_id: Option<VolumeId>,
+    node_id: Option<NodeId>,
+}
+
+impl GetHaHistoryInfo for ValidatedGetHaHistoryRequest {
+    fn volume_id(&self) -> Option<VolumeId> {
+        self.volume_id.clone()
+    }
+    fn node_id(&self) -> Option<NodeId> {
+        self.node_id.clone()
+    }
+}
+
+impl ValidateRequestTypes for GetHaHistoryRequest {
+    type Validated = ValidatedGetHaHistoryRequest;
+    fn validated(self) -> Result<Self::Validated, ReplyError> {
+        Ok(ValidatedGetHaHistoryRequest {
+            volume_id: match self.volume_id {
+                Some(id) => Some(VolumeId::try_from(StringValue(Some(id)))?),
+                None => None,
+            },
+            node_id: self.node_id.map(Into::into),
+        })
+    }
+}
+
+impl From<&dyn GetHaHistoryInfo> for GetHaHistoryRequest {
+    fn from(info: &dyn GetHaHistoryInfo) -> Self {
+        Self {
+            volume_id: info.volume_id().map(|id| id.to_string()),
+            node_id: info.node_id().map(|id| id.to_string()),
+        }
+    }
+}
+
+/// GetRebuildHistoryInfo trait for the get_rebuild_history operation
+pub trait GetRebuildHistoryInfo: Send + Sync + std::fmt::Debug {
+    /// Only return the rebuilds of this volume
+    fn volume_id(&self) -> Option<VolumeId>;
+}
+
+impl GetRebuildHistoryInfo for GetRebuildHistory {
+    fn volume_id(&self) -> Option<VolumeId> {
+        self.volume_id().cloned()
+    }
+}
+
+/// Intermediate structure that validates the conversion to GetRebuildHistoryRequest type.
+#[derive(Debug)]
+pub struct ValidatedGetRebuildHistoryRequest {
+    volume_id: Option<VolumeId>,
+}
+
+impl GetRebuildHistoryInfo for ValidatedGetRebuildHistoryRequest {
+    fn volume_id(&self) -> Option<VolumeId> {
+        self.volume_id.clone()
+    }
+}
+
+impl ValidateRequestTypes for GetRebuildHistoryRequest {
+    type Validated = ValidatedGetRebuildHistoryRequest;
+    fn validated(self) -> Result<Self::Validated, ReplyError> {
+        Ok(ValidatedGetRebuildHistoryRequest {
+            volume_id: match self.volume_id {
+                Some(id) => Some(VolumeId::try_from(StringValue(Some(id)))?),
+                None => None,
+            },
+        })
+    }
+}
+
+impl From<&dyn GetRebuildHistoryInfo> for GetRebuildHistoryRequest {
+    fn from(info: &dyn GetRebuildHistoryInfo) -> Self {
+        Self {
+            volume_id: info.volume_id().map(|id| id.to_string()),
+        }
+    }
+}
+
 impl From<&dyn GetSpecsInfo> for GetSpecsRequest {
     fn from(_: &dyn GetSpecsInfo) -> Self {
         Self {}
@@ -57,6 +197,18 @@ impl From<&dyn GetSpecsInfo> for GetSpecs {
     }
 }
 
+impl From<&dyn GetRebuildQueueInfo> for GetRebuildQueueRequest {
+    fn from(_: &dyn GetRebuildQueueInfo) -> Self {
+        Self {}
+    }
+}
+
+impl From<&dyn GetRebuildQueueInfo> for GetRebuildQueue {
+    fn from(_: &dyn GetRebuildQueueInfo) -> Self {
+        Self {}
+    }
+}
+
 impl From<&dyn GetStatesInfo> for GetStatesRequest {
     fn from(_: &dyn GetStatesInfo) -> Self {
         Self {}
@@ -191,3 +343,335 @@ impl From<transport::States> for registry::States {
         }
     }
 }
+
+/// A helper to convert a history timestamp into the corresponding grpc type.
+fn to_grpc_timestamp(timestamp: SwitchOverTime) -> Option<prost_types::Timestamp> {
+    Some(SystemTime::from(timestamp).into())
+}
+
+/// A helper to convert a grpc timestamp into a history timestamp of the given resource kind.
+fn from_grpc_timestamp(
+    timestamp: Option<prost_types::Timestamp>,
+    kind: ResourceKind,
+    field: &str,
+) -> Result<SwitchOverTime, ReplyError> {
+    match timestamp.map(SystemTime::try_from) {
+        Some(Ok(time)) => Ok(time.into()),
+        Some(Err(error)) => Err(ReplyError::invalid_argument(kind, field, error.to_string())),
+        None => Err(ReplyError::missing_argument(kind, field)),
+    }
+}
+
+/// A helper to convert an optional grpc timestamp into a rebuild timestamp.
+fn from_grpc_rebuild_time(
+    timestamp: Option<prost_types::Timestamp>,
+    field: &str,
+) -> Result<Option<RebuildTime>, ReplyError> {
+    match timestamp {
+        Some(timestamp) => Ok(Some(from_grpc_timestamp(
+            Some(timestamp),
+            ResourceKind::Volume,
+            field,
+        )?)),
+        None => Ok(None),
+    }
+}
+
+/// A helper to convert a grpc switchover stage into the transport type.
+fn from_grpc_stage(stage: i32, field: &str) -> Result<transport::SwitchOverStage, ReplyError> {
+    match ha_cluster_agent::SwitchOverStage::from_i32(stage) {
+        Some(stage) => Ok(stage.into()),
+        None => Err(ReplyError::invalid_argument(
+            ResourceKind::SwitchOver,
+            field,
+            "".to_string(),
+        )),
+    }
+}
+
+impl From<PathFailureRecord> for registry::PathFailureRecord {
+    fn from(value: PathFailureRecord) -> Self {
+        Self {
+            node_id: value.node_name.to_string(),
+            nqn: value.nqn,
+            timestamp: to_grpc_timestamp(value.timestamp),
+        }
+    }
+}
+
+impl TryFrom<registry::PathFailureRecord> for PathFailureRecord {
+    type Error = ReplyError;
+
+    fn try_from(value: registry::PathFailureRecord) -> Result<Self, Self::Error> {
+        Ok(Self {
+            node_name: value.node_id.into(),
+            nqn: value.nqn,
+            timestamp: from_grpc_timestamp(value.timestamp, ResourceKind::SwitchOver, "timestamp")?,
+        })
+    }
+}
+
+impl From<StageTiming> for registry::StageTiming {
+    fn from(value: StageTiming) -> Self {
+        let stage: ha_cluster_agent::SwitchOverStage = value.stage.into();
+        Self {
+            stage: stage as i32,
+            started: to_grpc_timestamp(value.started),
+            finished: value.finished.and_then(to_grpc_timestamp),
+            attempts: value.attempts,
+        }
+    }
+}
+
+impl TryFrom<registry::StageTiming> for StageTiming {
+    type Error = ReplyError;
+
+    fn try_from(value: registry::StageTiming) -> Result<Self, Self::Error> {
+        Ok(Self {
+            stage: from_grpc_stage(value.stage, "stage")?,
+            started: from_grpc_timestamp(value.started, ResourceKind::SwitchOver, "started")?,
+            finished: match value.finished {
+                Some(finished) => Some(from_grpc_timestamp(
+                    Some(finished),
+                    ResourceKind::SwitchOver,
+                    "finished",
+                )?),
+                None => None,
+            },
+            attempts: value.attempts,
+        })
+    }
+}
+
+impl From<SwitchOverRecord> for registry::SwitchOverRecord {
+    fn from(value: SwitchOverRecord) -> Self {
+        let outcome: ha_cluster_agent::SwitchOverStage = value.outcome.into();
+        Self {
+            volume_id: value.volume.to_string(),
+            node_id: value.node_name.to_string(),
+            existing_nqn: value.existing_nqn,
+            new_path: value.new_path,
+            started: to_grpc_timestamp(value.started),
+            finished: to_grpc_timestamp(value.finished),
+            outcome: outcome as i32,
+            error: value.error,
+            stages: value.stages.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl TryFrom<registry::SwitchOverRecord> for SwitchOverRecord {
+    type Error = ReplyError;
+
+    fn try_from(value: registry::SwitchOverRecord) -> Result<Self, Self::Error> {
+        Ok(Self {
+            volume: VolumeId::try_from(StringValue(Some(value.volume_id)))?,
+            node_name: value.node_id.into(),
+            existing_nqn: value.existing_nqn,
+            new_path: value.new_path,
+            started: from_grpc_timestamp(value.started, ResourceKind::SwitchOver, "started")?,
+            finished: from_grpc_timestamp(value.finished, ResourceKind::SwitchOver, "finished")?,
+            outcome: from_grpc_stage(value.outcome, "outcome")?,
+            error: value.error,
+            stages: value
+                .stages
+                .into_iter()
+                .map(StageTiming::try_from)
+                .collect::<Result<Vec<_>, _>>()?,
+        })
+    }
+}
+
+impl From<HaHistory> for registry::HaHistory {
+    fn from(value: HaHistory) -> Self {
+        Self {
+            path_failures: value.path_failures.into_iter().map(Into::into).collect(),
+            switchovers: value.switchovers.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl TryFrom<registry::HaHistory> for HaHistory {
+    type Error = ReplyError;
+
+    fn try_from(value: registry::HaHistory) -> Result<Self, Self::Error> {
+        Ok(Self {
+            path_failures: value
+                .path_failures
+                .into_iter()
+                .map(PathFailureRecord::try_from)
+                .collect::<Result<Vec<_>, _>>()?,
+            switchovers: value
+                .switchovers
+                .into_iter()
+                .map(SwitchOverRecord::try_from)
+                .collect::<Result<Vec<_>, _>>()?,
+        })
+    }
+}
+
+impl From<RebuildReason> for registry::RebuildReason {
+    fn from(value: RebuildReason) -> Self {
+        match value {
+            RebuildReason::ReplicaIncrease => Self::ReplicaIncrease,
+            RebuildReason::Replacement => Self::Replacement,
+            RebuildReason::Rebalance => Self::Rebalance,
+            RebuildReason::Unknown => Self::Unknown,
+        }
+    }
+}
+
+impl From<registry::RebuildReason> for RebuildReason {
+    fn from(value: registry::RebuildReason) -> Self {
+        match value {
+            registry::RebuildReason::ReplicaIncrease => Self::ReplicaIncrease,
+            registry::RebuildReason::Replacement => Self::Replacement,
+            registry::RebuildReason::Rebalance => Self::Rebalance,
+            registry::RebuildReason::Unknown => Self::Unknown,
+        }
+    }
+}
+
+impl From<RebuildOutcome> for registry::RebuildOutcome {
+    fn from(value: RebuildOutcome) -> Self {
+        match value {
+            RebuildOutcome::Running => Self::Running,
+            RebuildOutcome::Successful => Self::Successful,
+            RebuildOutcome::Failed => Self::Failed,
+        }
+    }
+}
+
+impl From<registry::RebuildOutcome> for RebuildOutcome {
+    fn from(value: registry::RebuildOutcome) -> Self {
+        match value {
+            registry::RebuildOutcome::Running => Self::Running,
+            registry::RebuildOutcome::Successful => Self::Successful,
+            registry::RebuildOutcome::Failed => Self::Failed,
+        }
+    }
+}
+
+impl From<RebuildRecord> for registry::RebuildRecord {
+    fn from(value: RebuildRecord) -> Self {
+        let reason: registry::RebuildReason = value.reason.into();
+        let outcome: registry::RebuildOutcome = value.outcome.into();
+        Self {
+            volume_id: value.volume.to_string(),
+            nexus_id: value.nexus.to_string(),
+            replica_id: value.replica.to_string(),
+            reason: reason as i32,
+            started: to_grpc_timestamp(value.started),
+            finished: value.finished.and_then(to_grpc_timestamp),
+            bytes: value.bytes,
+            outcome: outcome as i32,
+            progress: value.progress.map(Into::into),
+            eta: value.eta.and_then(to_grpc_timestamp),
+        }
+    }
+}
+
+impl TryFrom<registry::RebuildRecord> for RebuildRecord {
+    type Error = ReplyError;
+
+    fn try_from(value: registry::RebuildRecord) -> Result<Self, Self::Error> {
+        Ok(Self {
+            volume: VolumeId::try_from(StringValue(Some(value.volume_id)))?,
+            nexus: NexusId::try_from(StringValue(Some(value.nexus_id)))?,
+            replica: ReplicaId::try_from(StringValue(Some(value.replica_id)))?,
+            reason: match registry::RebuildReason::from_i32(value.reason) {
+                Some(reason) => reason.into(),
+                None => {
+                    return Err(ReplyError::invalid_argument(
+                        ResourceKind::Volume,
+                        "reason",
+                        "".to_string(),
+                    ))
+                }
+            },
+            started: from_grpc_timestamp(value.started, ResourceKind::Volume, "started")?,
+            finished: from_grpc_rebuild_time(value.finished, "finished")?,
+            bytes: value.bytes,
+            outcome: match registry::RebuildOutcome::from_i32(value.outcome) {
+                Some(outcome) => outcome.into(),
+                None => {
+                    return Err(ReplyError::invalid_argument(
+                        ResourceKind::Volume,
+                        "outcome",
+                        "".to_string(),
+                    ))
+                }
+            },
+            progress: value.progress.map(|progress| progress.min(100) as u8),
+            eta: from_grpc_rebuild_time(value.eta, "eta")?,
+        })
+    }
+}
+
+impl From<VolumeRebuilds> for registry::VolumeRebuilds {
+    fn from(value: VolumeRebuilds) -> Self {
+        Self {
+            rebuilds: value.rebuilds.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl TryFrom<registry::VolumeRebuilds> for VolumeRebuilds {
+    type Error = ReplyError;
+
+    fn try_from(value: registry::VolumeRebuilds) -> Result<Self, Self::Error> {
+        Ok(Self {
+            rebuilds: value
+                .rebuilds
+                .into_iter()
+                .map(RebuildRecord::try_from)
+                .collect::<Result<Vec<_>, _>>()?,
+        })
+    }
+}
+
+impl From<QueuedRebuild> for registry::QueuedRebuild {
+    fn from(value: QueuedRebuild) -> Self {
+        Self {
+            volume_id: value.volume.to_string(),
+            priority: value.priority,
+            healthy_replicas: value.healthy_replicas as u32,
+            missing_replicas: value.missing_replicas as u32,
+        }
+    }
+}
+
+impl TryFrom<registry::QueuedRebuild> for QueuedRebuild {
+    type Error = ReplyError;
+
+    fn try_from(value: registry::QueuedRebuild) -> Result<Self, Self::Error> {
+        Ok(Self {
+            volume: VolumeId::try_from(StringValue(Some(value.volume_id)))?,
+            priority: value.priority,
+            healthy_replicas: value.healthy_replicas.min(u8::MAX as u32) as u8,
+            missing_replicas: value.missing_replicas.min(u8::MAX as u32) as u8,
+        })
+    }
+}
+
+impl From<RebuildQueue> for registry::RebuildQueue {
+    fn from(value: RebuildQueue) -> Self {
+        Self {
+            rebuilds: value.rebuilds.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl TryFrom<registry::RebuildQueue> for RebuildQueue {
+    type Error = ReplyError;
+
+    fn try_from(value: registry::RebuildQueue) -> Result<Self, Self::Error> {
+        Ok(Self {
+            rebuilds: value
+                .rebuilds
+                .into_iter()
+                .map(QueuedRebuild::try_from)
+                .collect::<Result<Vec<_>, _>>()?,
+        })
+    }
+}
@@ -17,7 +17,8 @@ use common_lib::{
         transport,
         transport::{
             CreateReplica, DestroyReplica, Filter, HostNqn, NexusId, NodeId, PoolId, PoolUuid,
-            Replica, ReplicaId, ReplicaName, ReplicaOwners, ShareReplica, UnshareReplica, VolumeId,
+            Replica, ReplicaId, ReplicaName, ReplicaOwners, ShareReplica, UnshareReplica,
+            VolumeEncryption, VolumeId,
         },
     },
     IntoVec,
@@ -250,6 +251,8 @@ pub trait CreateReplicaInfo: Send + Sync + std::fmt::Debug {
     fn owners(&self) -> ReplicaOwners;
     /// List of host nqn allowed to connect to the target.
     fn allowed_hosts(&self) -> Vec<HostNqn>;
+    /// Encryption at rest of the replica data
+    fn encryption(&self) -> Option<VolumeEncryption>;
 }
 
 impl CreateReplicaInfo for CreateReplica {
@@ -296,6 +299,10 @@ impl CreateReplicaInfo for CreateReplica {
     fn allowed_hosts(&self) -> Vec<HostNqn> {
         self.allowed_hosts.clone()
     }
+
+    fn encryption(&self) -> Option<VolumeEncryption> {
+        self.encryption.clone()
+    }
 }
 
 /// Intermediate structure that validates the conversion to CreateVolumeRequest type
@@ -353,6 +360,13 @@ impl CreateReplicaInfo for ValidatedCreateReplicaRequest {
     fn allowed_hosts(&self) -> Vec<HostNqn> {
         self.allowed_hosts.clone()
     }
+
+    fn encryption(&self) -> Option<VolumeEncryption> {
+        self.inner
+            .encryption_key_ref
+            .clone()
+            .map(VolumeEncryption::new)
+    }
 }
 
 impl ValidateRequestTypes for CreateReplicaRequest {
@@ -744,6 +758,7 @@ impl From<&dyn CreateReplicaInfo> for CreateReplicaRequest {
             managed: data.managed(),
             owners: Some(data.owners().into()),
             allowed_hosts: data.allowed_hosts().into_vec(),
+            encryption_key_ref: data.encryption().map(|encryption| encryption.key_ref),
         }
     }
 }
@@ -762,6 +777,7 @@ impl From<&dyn CreateReplicaInfo> for CreateReplica {
             managed: data.managed(),
             owners: data.owners(),
             allowed_hosts: data.allowed_hosts(),
+            encryption: data.encryption(),
         }
     }
 }
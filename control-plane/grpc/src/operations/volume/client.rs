@@ -3,22 +3,27 @@ use crate::{
     context::{Client, Context, TracedChannel},
     operations::{
         volume::traits::{
-            CreateVolumeInfo, DestroyShutdownTargetsInfo, DestroyVolumeInfo, PublishVolumeInfo,
-            RepublishVolumeInfo, SetVolumeReplicaInfo, ShareVolumeInfo, UnpublishVolumeInfo,
-            UnshareVolumeInfo, VolumeOperations,
+            CreateVolumeInfo, CreateVolumeSnapshotInfo, DestroyShutdownTargetsInfo,
+            DestroyVolumeInfo, DestroyVolumeSnapshotInfo, GetVolumeSnapshotsInfo,
+            PublishVolumeInfo, RepublishVolumeInfo, ResizeVolumeInfo, SetVolumeQosInfo,
+            SetVolumeReplicaInfo, ShareVolumeInfo, UnpublishVolumeInfo, UnshareVolumeInfo,
+            VolumeOperations,
         },
         Pagination,
     },
     volume::{
-        create_volume_reply, get_volumes_reply, get_volumes_request, publish_volume_reply,
-        republish_volume_reply, set_volume_replica_reply, share_volume_reply,
-        unpublish_volume_reply, volume_grpc_client::VolumeGrpcClient, GetVolumesRequest,
-        ProbeRequest,
+        create_snapshot_reply, create_volume_reply, get_snapshots_reply, get_volumes_reply,
+        get_volumes_request, publish_volume_reply, republish_volume_reply, resize_volume_reply,
+        set_volume_qos_reply, set_volume_replica_reply, share_volume_reply, unpublish_volume_reply,
+        volume_grpc_client::VolumeGrpcClient, GetSnapshotsRequest, GetVolumesRequest, ProbeRequest,
     },
 };
 use common_lib::{
-    transport_api::{v0::Volumes, ReplyError, ResourceKind, TimeoutOptions},
-    types::v0::transport::{Filter, MessageIdVs, Volume},
+    transport_api::{
+        v0::{VolumeSnapshots, Volumes},
+        ReplyError, ResourceKind, TimeoutOptions,
+    },
+    types::v0::transport::{Filter, MessageIdVs, Volume, VolumeSnapshot},
 };
 use std::{convert::TryFrom, ops::Deref};
 use tonic::transport::Uri;
@@ -211,6 +216,40 @@ impl VolumeOperations for VolumeClient {
         }
     }
 
+    #[tracing::instrument(name = "VolumeClient::resize", level = "debug", skip(self), err)]
+    async fn resize(
+        &self,
+        request: &dyn ResizeVolumeInfo,
+        ctx: Option<Context>,
+    ) -> Result<Volume, ReplyError> {
+        let req = self.request(request, ctx, MessageIdVs::ResizeVolume);
+        let response = self.client().resize_volume(req).await?.into_inner();
+        match response.reply {
+            Some(resize_volume_reply) => match resize_volume_reply {
+                resize_volume_reply::Reply::Volume(volume) => Ok(Volume::try_from(volume)?),
+                resize_volume_reply::Reply::Error(err) => Err(err.into()),
+            },
+            None => Err(ReplyError::invalid_response(ResourceKind::Volume)),
+        }
+    }
+
+    #[tracing::instrument(name = "VolumeClient::set_qos", level = "debug", skip(self), err)]
+    async fn set_qos(
+        &self,
+        request: &dyn SetVolumeQosInfo,
+        ctx: Option<Context>,
+    ) -> Result<Volume, ReplyError> {
+        let req = self.request(request, ctx, MessageIdVs::SetVolumeQos);
+        let response = self.client().set_volume_qos(req).await?.into_inner();
+        match response.reply {
+            Some(set_volume_qos_reply) => match set_volume_qos_reply {
+                set_volume_qos_reply::Reply::Volume(volume) => Ok(Volume::try_from(volume)?),
+                set_volume_qos_reply::Reply::Error(err) => Err(err.into()),
+            },
+            None => Err(ReplyError::invalid_response(ResourceKind::Volume)),
+        }
+    }
+
     #[tracing::instrument(name = "VolumeClient::probe", level = "debug", skip(self))]
     async fn probe(&self, _ctx: Option<Context>) -> Result<bool, ReplyError> {
         match self.client().probe(ProbeRequest {}).await {
@@ -235,4 +274,75 @@ impl VolumeOperations for VolumeClient {
             Some(err) => Err(err.into()),
         }
     }
+
+    #[tracing::instrument(
+        name = "VolumeClient::create_snapshot",
+        level = "debug",
+        skip(self),
+        err
+    )]
+    async fn create_snapshot(
+        &self,
+        request: &dyn CreateVolumeSnapshotInfo,
+        ctx: Option<Context>,
+    ) -> Result<VolumeSnapshot, ReplyError> {
+        let req = self.request(request, ctx, MessageIdVs::CreateVolumeSnapshot);
+        let response = self.client().create_snapshot(req).await?.into_inner();
+        match response.reply {
+            Some(create_snapshot_reply) => match create_snapshot_reply {
+                create_snapshot_reply::Reply::Snapshot(snapshot) => {
+                    Ok(VolumeSnapshot::try_from(snapshot)?)
+                }
+                create_snapshot_reply::Reply::Error(err) => Err(err.into()),
+            },
+            None => Err(ReplyError::invalid_response(ResourceKind::VolumeSnapshot)),
+        }
+    }
+
+    #[tracing::instrument(
+        name = "VolumeClient::destroy_snapshot",
+        level = "debug",
+        skip(self),
+        err
+    )]
+    async fn destroy_snapshot(
+        &self,
+        request: &dyn DestroyVolumeSnapshotInfo,
+        ctx: Option<Context>,
+    ) -> Result<(), ReplyError> {
+        let req = self.request(request, ctx, MessageIdVs::DestroyVolumeSnapshot);
+        let response = self.client().destroy_snapshot(req).await?.into_inner();
+        match response.error {
+            None => Ok(()),
+            Some(err) => Err(err.into()),
+        }
+    }
+
+    #[tracing::instrument(name = "VolumeClient::get_snapshots", level = "debug", skip(self), err)]
+    async fn get_snapshots(
+        &self,
+        request: &dyn GetVolumeSnapshotsInfo,
+        ignore_notfound: bool,
+        ctx: Option<Context>,
+    ) -> Result<VolumeSnapshots, ReplyError> {
+        let req = self.request(
+            GetSnapshotsRequest {
+                volume_id: request.source_id().map(|id| id.to_string()),
+                snapshot_id: request.snap_id().map(|id| id.to_string()),
+                ignore_notfound,
+            },
+            ctx,
+            MessageIdVs::GetVolumeSnapshots,
+        );
+        let response = self.client().get_snapshots(req).await?.into_inner();
+        match response.reply {
+            Some(get_snapshots_reply) => match get_snapshots_reply {
+                get_snapshots_reply::Reply::Response(snapshots) => {
+                    Ok(VolumeSnapshots::try_from(snapshots)?)
+                }
+                get_snapshots_reply::Reply::Error(err) => Err(err.into()),
+            },
+            None => Err(ReplyError::invalid_response(ResourceKind::VolumeSnapshot)),
+        }
+    }
 }
@@ -2,15 +2,18 @@ use crate::{
     misc::traits::ValidateRequestTypes,
     operations::{volume::traits::VolumeOperations, Pagination},
     volume::{
-        create_volume_reply, get_volumes_reply, publish_volume_reply, republish_volume_reply,
+        create_snapshot_reply, create_volume_reply, get_snapshots_reply, get_volumes_reply,
+        publish_volume_reply, republish_volume_reply, resize_volume_reply, set_volume_qos_reply,
         set_volume_replica_reply, share_volume_reply, unpublish_volume_reply,
         volume_grpc_server::{VolumeGrpc, VolumeGrpcServer},
-        CreateVolumeReply, CreateVolumeRequest, DestroyShutdownTargetReply,
-        DestroyShutdownTargetRequest, DestroyVolumeReply, DestroyVolumeRequest, GetVolumesReply,
-        GetVolumesRequest, ProbeRequest, ProbeResponse, PublishVolumeReply, PublishVolumeRequest,
-        RepublishVolumeReply, RepublishVolumeRequest, SetVolumeReplicaReply,
-        SetVolumeReplicaRequest, ShareVolumeReply, ShareVolumeRequest, UnpublishVolumeReply,
-        UnpublishVolumeRequest, UnshareVolumeReply, UnshareVolumeRequest,
+        CreateSnapshotReply, CreateSnapshotRequest, CreateVolumeReply, CreateVolumeRequest,
+        DestroyShutdownTargetReply, DestroyShutdownTargetRequest, DestroySnapshotReply,
+        DestroySnapshotRequest, DestroyVolumeReply, DestroyVolumeRequest, GetSnapshotsReply,
+        GetSnapshotsRequest, GetVolumesReply, GetVolumesRequest, ProbeRequest, ProbeResponse,
+        PublishVolumeReply, PublishVolumeRequest, RepublishVolumeReply, RepublishVolumeRequest,
+        ResizeVolumeReply, ResizeVolumeRequest, SetVolumeQosReply, SetVolumeQosRequest,
+        SetVolumeReplicaReply, SetVolumeReplicaRequest, ShareVolumeReply, ShareVolumeRequest,
+        UnpublishVolumeReply, UnpublishVolumeRequest, UnshareVolumeReply, UnshareVolumeRequest,
     },
 };
 use common_lib::types::v0::transport::Filter;
@@ -191,6 +194,34 @@ impl VolumeGrpc for VolumeServer {
             })),
         }
     }
+    async fn resize_volume(
+        &self,
+        request: tonic::Request<ResizeVolumeRequest>,
+    ) -> Result<tonic::Response<ResizeVolumeReply>, tonic::Status> {
+        let req = request.into_inner().validated()?;
+        match self.service.resize(&req, None).await {
+            Ok(volume) => Ok(Response::new(ResizeVolumeReply {
+                reply: Some(resize_volume_reply::Reply::Volume(volume.into())),
+            })),
+            Err(err) => Ok(Response::new(ResizeVolumeReply {
+                reply: Some(resize_volume_reply::Reply::Error(err.into())),
+            })),
+        }
+    }
+    async fn set_volume_qos(
+        &self,
+        request: tonic::Request<SetVolumeQosRequest>,
+    ) -> Result<tonic::Response<SetVolumeQosReply>, tonic::Status> {
+        let req = request.into_inner().validated()?;
+        match self.service.set_qos(&req, None).await {
+            Ok(volume) => Ok(Response::new(SetVolumeQosReply {
+                reply: Some(set_volume_qos_reply::Reply::Volume(volume.into())),
+            })),
+            Err(err) => Ok(Response::new(SetVolumeQosReply {
+                reply: Some(set_volume_qos_reply::Reply::Error(err.into())),
+            })),
+        }
+    }
     async fn probe(
         &self,
         _request: tonic::Request<ProbeRequest>,
@@ -200,4 +231,53 @@ impl VolumeGrpc for VolumeServer {
             Err(_) => Ok(Response::new(ProbeResponse { ready: false })),
         }
     }
+
+    async fn create_snapshot(
+        &self,
+        request: Request<CreateSnapshotRequest>,
+    ) -> Result<Response<CreateSnapshotReply>, Status> {
+        let req = request.into_inner().validated()?;
+        match self.service.create_snapshot(&req, None).await {
+            Ok(snapshot) => Ok(Response::new(CreateSnapshotReply {
+                reply: Some(create_snapshot_reply::Reply::Snapshot(snapshot.into())),
+            })),
+            Err(err) => Ok(Response::new(CreateSnapshotReply {
+                reply: Some(create_snapshot_reply::Reply::Error(err.into())),
+            })),
+        }
+    }
+
+    async fn destroy_snapshot(
+        &self,
+        request: Request<DestroySnapshotRequest>,
+    ) -> Result<Response<DestroySnapshotReply>, Status> {
+        let req = request.into_inner().validated()?;
+        match self.service.destroy_snapshot(&req, None).await {
+            Ok(()) => Ok(Response::new(DestroySnapshotReply { error: None })),
+            Err(e) => Ok(Response::new(DestroySnapshotReply {
+                error: Some(e.into()),
+            })),
+        }
+    }
+
+    async fn get_snapshots(
+        &self,
+        request: Request<GetSnapshotsRequest>,
+    ) -> Result<Response<GetSnapshotsReply>, Status> {
+        let req = request.into_inner();
+        let ignore_notfound = req.ignore_notfound;
+        let req = req.validated()?;
+        match self
+            .service
+            .get_snapshots(&req, ignore_notfound, None)
+            .await
+        {
+            Ok(snapshots) => Ok(Response::new(GetSnapshotsReply {
+                reply: Some(get_snapshots_reply::Reply::Response(snapshots.into())),
+            })),
+            Err(err) => Ok(Response::new(GetSnapshotsReply {
+                reply: Some(get_snapshots_reply::Reply::Error(err.into())),
+            })),
+        }
+    }
 }
@@ -6,26 +6,37 @@ use crate::{
     operations::Pagination,
     replica, volume,
     volume::{
-        get_volumes_request, CreateVolumeRequest, DestroyShutdownTargetRequest,
-        DestroyVolumeRequest, PublishVolumeRequest, RegisteredTargets, RepublishVolumeRequest,
-        SetVolumeReplicaRequest, ShareVolumeRequest, UnpublishVolumeRequest, UnshareVolumeRequest,
+        get_volumes_request, CreateSnapshotRequest, CreateVolumeRequest,
+        DestroyShutdownTargetRequest, DestroySnapshotRequest, DestroyVolumeRequest,
+        GetSnapshotsRequest, PublishVolumeRequest, RegisteredTargets, RepublishVolumeRequest,
+        ResizeVolumeRequest, SetVolumeQosRequest, SetVolumeReplicaRequest, ShareVolumeRequest,
+        UnpublishVolumeRequest, UnshareVolumeRequest,
     },
 };
 use common_lib::{
-    transport_api::{v0::Volumes, ReplyError, ResourceKind},
+    transport_api::{
+        v0::{VolumeSnapshots, Volumes},
+        ReplyError, ResourceKind,
+    },
     types::v0::{
-        store::volume::{FrontendConfig, TargetConfig, VolumeSpec, VolumeTarget},
+        store::{
+            snapshot::{ReplicaSnapshot, VolumeSnapshotSpec, VolumeSnapshotSpecStatus},
+            volume::{FrontendConfig, TargetConfig, VolumeSpec, VolumeTarget},
+        },
         transport::{
-            CreateVolume, DestroyShutdownTargets, DestroyVolume, ExplicitNodeTopology, Filter,
+            CreateVolume, CreateVolumeSnapshot, DestroyShutdownTargets, DestroyVolume,
+            DestroyVolumeSnapshot, ExplicitNodeTopology, Filter, GetVolumeSnapshots,
             LabelledTopology, Nexus, NexusId, NexusNvmfConfig, NodeId, NodeTopology, PoolTopology,
             PublishVolume, ReplicaId, ReplicaStatus, ReplicaTopology, RepublishVolume,
-            SetVolumeReplica, ShareVolume, Topology, UnpublishVolume, UnshareVolume, Volume,
-            VolumeId, VolumeLabels, VolumePolicy, VolumeShareProtocol, VolumeState,
+            ResizeVolume, SetVolumeQos, SetVolumeReplica, ShareVolume, SnapshotId, Topology,
+            UnpublishVolume, UnshareVolume, Volume, VolumeAccessMode, VolumeContentSource,
+            VolumeEncryption, VolumeId, VolumeLabels, VolumePolicy, VolumeQos, VolumeShareProtocol,
+            VolumeSnapshot, VolumeSnapshotState, VolumeState,
         },
     },
     IntoOption,
 };
-use std::{borrow::Borrow, collections::HashMap, convert::TryFrom};
+use std::{borrow::Borrow, collections::HashMap, convert::TryFrom, time::SystemTime};
 
 /// All volume crud operations to be a part of the VolumeOperations trait.
 #[tonic::async_trait]
@@ -86,6 +97,18 @@ pub trait VolumeOperations: Send + Sync {
         req: &dyn SetVolumeReplicaInfo,
         ctx: Option<Context>,
     ) -> Result<Volume, ReplyError>;
+    /// Resize a volume
+    async fn resize(
+        &self,
+        req: &dyn ResizeVolumeInfo,
+        ctx: Option<Context>,
+    ) -> Result<Volume, ReplyError>;
+    /// Set the QoS limits of a volume
+    async fn set_qos(
+        &self,
+        req: &dyn SetVolumeQosInfo,
+        ctx: Option<Context>,
+    ) -> Result<Volume, ReplyError>;
     /// Liveness probe for volume service
     async fn probe(&self, ctx: Option<Context>) -> Result<bool, ReplyError>;
     /// Destroy shutdown targets
@@ -94,6 +117,25 @@ pub trait VolumeOperations: Send + Sync {
         req: &dyn DestroyShutdownTargetsInfo,
         ctx: Option<Context>,
     ) -> Result<(), ReplyError>;
+    /// Create a volume snapshot
+    async fn create_snapshot(
+        &self,
+        req: &dyn CreateVolumeSnapshotInfo,
+        ctx: Option<Context>,
+    ) -> Result<VolumeSnapshot, ReplyError>;
+    /// Destroy a volume snapshot
+    async fn destroy_snapshot(
+        &self,
+        req: &dyn DestroyVolumeSnapshotInfo,
+        ctx: Option<Context>,
+    ) -> Result<(), ReplyError>;
+    /// Get volume snapshots
+    async fn get_snapshots(
+        &self,
+        req: &dyn GetVolumeSnapshotsInfo,
+        ignore_notfound: bool,
+        ctx: Option<Context>,
+    ) -> Result<VolumeSnapshots, ReplyError>;
 }
 
 impl From<VolumeSpec> for volume::VolumeDefinition {
@@ -115,6 +157,8 @@ impl From<VolumeSpec> for volume::VolumeDefinition {
                 topology: volume_spec.topology.map(|topology| topology.into()),
                 last_nexus_id: nexus_id.map(|id| id.to_string()),
                 thin: volume_spec.thin,
+                content_source: volume_spec.content_source.map(|source| source.into()),
+                encryption: volume_spec.encryption.map(|encryption| encryption.into()),
             }),
             metadata: Some(volume::Metadata {
                 spec_status: spec_status as i32,
@@ -177,6 +221,23 @@ impl TryFrom<volume::VolumeDefinition> for VolumeSpec {
                 ))
             }
         };
+        let frontend = match volume_meta.target_config.as_ref() {
+            Some(config) => FrontendConfig::new(
+                config.frontend_nodes.clone(),
+                vec![],
+                match volume::VolumeAccessMode::from_i32(config.access_mode) {
+                    Some(access_mode) => access_mode.into(),
+                    None => {
+                        return Err(ReplyError::invalid_argument(
+                            ResourceKind::Volume,
+                            "volume.metadata.target_config.access_mode",
+                            "".to_string(),
+                        ))
+                    }
+                },
+            ),
+            None => FrontendConfig::default(),
+        };
         let volume_spec = VolumeSpec {
             uuid: VolumeId::try_from(StringValue(volume_spec.uuid))?,
             size: volume_spec.size,
@@ -198,7 +259,7 @@ impl TryFrom<volume::VolumeDefinition> for VolumeSpec {
                     Some(TargetConfig::new(
                         target,
                         NexusNvmfConfig::default(),
-                        Default::default(),
+                        frontend,
                     ))
                 }
                 None => None,
@@ -244,6 +305,13 @@ impl TryFrom<volume::VolumeDefinition> for VolumeSpec {
             publish_context: volume_meta
                 .publish_context
                 .map(|map_wrapper| map_wrapper.map),
+            content_source: match volume_spec.content_source {
+                Some(source) => Some(VolumeContentSource::try_from(source)?),
+                None => None,
+            },
+            encryption: volume_spec.encryption.map(|encryption| encryption.into()),
+            rebalance: None,
+            moved_target: None,
         };
         Ok(volume_spec)
     }
@@ -354,7 +422,13 @@ impl TryFrom<volume::ReplicaTopology> for ReplicaTopology {
                 ))
             }
         };
-        Ok(ReplicaTopology::new(node, pool, status))
+        Ok(
+            ReplicaTopology::new(node, pool, status).with_rebuild_progress(
+                replica_topology_grpc_type
+                    .rebuild_progress
+                    .map(|progress| progress as u8),
+            ),
+        )
     }
 }
 
@@ -367,6 +441,7 @@ impl From<ReplicaTopology> for volume::ReplicaTopology {
             node,
             pool,
             status: status as i32,
+            rebuild_progress: replica_topology.rebuild_progress().map(Into::into),
         }
     }
 }
@@ -415,6 +490,54 @@ impl From<Topology> for volume::Topology {
     }
 }
 
+impl TryFrom<volume::VolumeContentSource> for VolumeContentSource {
+    type Error = ReplyError;
+    fn try_from(source_grpc_type: volume::VolumeContentSource) -> Result<Self, Self::Error> {
+        match source_grpc_type.source {
+            Some(volume::volume_content_source::Source::VolumeId(id)) => {
+                Ok(Self::Volume(VolumeId::try_from(StringValue(Some(id)))?))
+            }
+            Some(volume::volume_content_source::Source::SnapshotId(id)) => {
+                Ok(Self::Snapshot(SnapshotId::try_from(StringValue(Some(id)))?))
+            }
+            None => Err(ReplyError::missing_argument(
+                ResourceKind::Volume,
+                "content_source.source",
+            )),
+        }
+    }
+}
+
+impl From<VolumeContentSource> for volume::VolumeContentSource {
+    fn from(source: VolumeContentSource) -> Self {
+        let source = match source {
+            VolumeContentSource::Volume(id) => {
+                volume::volume_content_source::Source::VolumeId(id.to_string())
+            }
+            VolumeContentSource::Snapshot(id) => {
+                volume::volume_content_source::Source::SnapshotId(id.to_string())
+            }
+        };
+        volume::VolumeContentSource {
+            source: Some(source),
+        }
+    }
+}
+
+impl From<volume::VolumeEncryption> for VolumeEncryption {
+    fn from(encryption: volume::VolumeEncryption) -> Self {
+        Self::new(encryption.key_ref)
+    }
+}
+
+impl From<VolumeEncryption> for volume::VolumeEncryption {
+    fn from(encryption: VolumeEncryption) -> Self {
+        volume::VolumeEncryption {
+            key_ref: encryption.key_ref,
+        }
+    }
+}
+
 impl TryFrom<volume::NodeTopology> for NodeTopology {
     type Error = ReplyError;
     fn try_from(node_topology_grpc_type: volume::NodeTopology) -> Result<Self, Self::Error> {
@@ -492,6 +615,7 @@ impl From<volume::LabelledTopology> for LabelledTopology {
                 Some(labels) => labels.value,
                 None => HashMap::new(),
             },
+            spread: labelled_topology_grpc_type.spread,
         }
     }
 }
@@ -505,6 +629,7 @@ impl From<LabelledTopology> for volume::LabelledTopology {
             inclusion: Some(crate::common::StringMapValue {
                 value: topo.inclusion,
             }),
+            spread: topo.spread,
         }
     }
 }
@@ -547,6 +672,8 @@ impl From<volume::VolumePolicy> for VolumePolicy {
     fn from(policy_grpc_type: volume::VolumePolicy) -> Self {
         VolumePolicy {
             self_heal: policy_grpc_type.self_heal,
+            qos: policy_grpc_type.qos.map(From::from),
+            rebuild_priority: policy_grpc_type.rebuild_priority,
         }
     }
 }
@@ -555,6 +682,30 @@ impl From<VolumePolicy> for volume::VolumePolicy {
     fn from(policy: VolumePolicy) -> Self {
         volume::VolumePolicy {
             self_heal: policy.self_heal,
+            qos: policy.qos.map(From::from),
+            rebuild_priority: policy.rebuild_priority,
+        }
+    }
+}
+
+impl From<volume::VolumeQos> for VolumeQos {
+    fn from(qos: volume::VolumeQos) -> Self {
+        VolumeQos {
+            read_iops: qos.read_iops,
+            write_iops: qos.write_iops,
+            read_mbps: qos.read_mbps,
+            write_mbps: qos.write_mbps,
+        }
+    }
+}
+
+impl From<VolumeQos> for volume::VolumeQos {
+    fn from(qos: VolumeQos) -> Self {
+        volume::VolumeQos {
+            read_iops: qos.read_iops,
+            write_iops: qos.write_iops,
+            read_mbps: qos.read_mbps,
+            write_mbps: qos.write_mbps,
         }
     }
 }
@@ -616,7 +767,20 @@ impl TryFrom<volume::TargetConfig> for TargetConfig {
                     "target_config.config",
                 )),
             }?,
-            FrontendConfig::default(),
+            FrontendConfig::new(
+                src.frontend_nodes,
+                vec![],
+                match volume::VolumeAccessMode::from_i32(src.access_mode) {
+                    Some(access_mode) => access_mode.into(),
+                    None => {
+                        return Err(ReplyError::invalid_argument(
+                            ResourceKind::Volume,
+                            "target_config.access_mode",
+                            "".to_string(),
+                        ))
+                    }
+                },
+            ),
         ))
     }
 }
@@ -625,6 +789,8 @@ impl From<TargetConfig> for volume::TargetConfig {
         volume::TargetConfig {
             target: Some(src.target().clone().into()),
             config: Some(src.config().clone().into()),
+            frontend_nodes: src.frontend().nodes(),
+            access_mode: volume::VolumeAccessMode::from(src.frontend().access_mode()) as i32,
         }
     }
 }
@@ -647,6 +813,26 @@ impl From<VolumeShareProtocol> for volume::VolumeShareProtocol {
     }
 }
 
+impl From<volume::VolumeAccessMode> for VolumeAccessMode {
+    fn from(src: volume::VolumeAccessMode) -> Self {
+        match src {
+            volume::VolumeAccessMode::SingleNodeWriter => Self::SingleNodeWriter,
+            volume::VolumeAccessMode::SingleNodeMultiWriter => Self::SingleNodeMultiWriter,
+            volume::VolumeAccessMode::MultiNodeReaderOnly => Self::MultiNodeReaderOnly,
+        }
+    }
+}
+
+impl From<VolumeAccessMode> for volume::VolumeAccessMode {
+    fn from(src: VolumeAccessMode) -> Self {
+        match src {
+            VolumeAccessMode::SingleNodeWriter => Self::SingleNodeWriter,
+            VolumeAccessMode::SingleNodeMultiWriter => Self::SingleNodeMultiWriter,
+            VolumeAccessMode::MultiNodeReaderOnly => Self::MultiNodeReaderOnly,
+        }
+    }
+}
+
 impl TryFrom<get_volumes_request::Filter> for Filter {
     type Error = ReplyError;
     fn try_from(filter: get_volumes_request::Filter) -> Result<Self, Self::Error> {
@@ -674,6 +860,10 @@ pub trait CreateVolumeInfo: Send + Sync + std::fmt::Debug {
     fn labels(&self) -> Option<VolumeLabels>;
     /// Flag indicating whether the volume should be thin provisioned
     fn thin(&self) -> bool;
+    /// Source from which the volume content should be populated
+    fn content_source(&self) -> Option<VolumeContentSource>;
+    /// Encryption at rest of the volume data
+    fn encryption(&self) -> Option<VolumeEncryption>;
 }
 
 impl CreateVolumeInfo for CreateVolume {
@@ -704,6 +894,14 @@ impl CreateVolumeInfo for CreateVolume {
     fn thin(&self) -> bool {
         self.thin
     }
+
+    fn content_source(&self) -> Option<VolumeContentSource> {
+        self.content_source.clone()
+    }
+
+    fn encryption(&self) -> Option<VolumeEncryption> {
+        self.encryption.clone()
+    }
 }
 
 /// Intermediate structure that validates the conversion to CreateVolumeRequest type.
@@ -712,6 +910,7 @@ pub struct ValidatedCreateVolumeRequest {
     inner: CreateVolumeRequest,
     uuid: VolumeId,
     topology: Option<Topology>,
+    content_source: Option<VolumeContentSource>,
 }
 
 impl CreateVolumeInfo for ValidatedCreateVolumeRequest {
@@ -748,6 +947,17 @@ impl CreateVolumeInfo for ValidatedCreateVolumeRequest {
     fn thin(&self) -> bool {
         self.inner.thin
     }
+
+    fn content_source(&self) -> Option<VolumeContentSource> {
+        self.content_source.clone()
+    }
+
+    fn encryption(&self) -> Option<VolumeEncryption> {
+        self.inner
+            .encryption
+            .clone()
+            .map(|encryption| encryption.into())
+    }
 }
 
 impl ValidateRequestTypes for CreateVolumeRequest {
@@ -768,6 +978,10 @@ impl ValidateRequestTypes for CreateVolumeRequest {
                 },
                 None => None,
             },
+            content_source: match self.content_source.clone() {
+                Some(source) => Some(VolumeContentSource::try_from(source)?),
+                None => None,
+            },
             inner: self,
         })
     }
@@ -783,6 +997,8 @@ impl From<&dyn CreateVolumeInfo> for CreateVolume {
             topology: data.topology(),
             labels: data.labels(),
             thin: data.thin(),
+            content_source: data.content_source(),
+            encryption: data.encryption(),
         }
     }
 }
@@ -799,6 +1015,8 @@ impl From<&dyn CreateVolumeInfo> for CreateVolumeRequest {
                 .labels()
                 .map(|labels| crate::common::StringMapValue { value: labels }),
             thin: data.thin(),
+            content_source: data.content_source().map(|source| source.into()),
+            encryption: data.encryption().map(|encryption| encryption.into()),
         }
     }
 }
@@ -996,6 +1214,8 @@ pub trait PublishVolumeInfo: Send + Sync + std::fmt::Debug {
     fn publish_context(&self) -> HashMap<String, String>;
     /// Hosts allowed to access the nexus.
     fn frontend_nodes(&self) -> Vec<String>;
+    /// The mode with which the volume is accessed from the frontend nodes.
+    fn access_mode(&self) -> VolumeAccessMode;
 }
 
 impl PublishVolumeInfo for PublishVolume {
@@ -1018,6 +1238,10 @@ impl PublishVolumeInfo for PublishVolume {
     fn frontend_nodes(&self) -> Vec<String> {
         self.frontend_nodes.clone()
     }
+
+    fn access_mode(&self) -> VolumeAccessMode {
+        self.access_mode
+    }
 }
 
 impl PublishVolumeInfo for RepublishVolume {
@@ -1040,6 +1264,10 @@ impl PublishVolumeInfo for RepublishVolume {
     fn frontend_nodes(&self) -> Vec<String> {
         unimplemented!()
     }
+
+    fn access_mode(&self) -> VolumeAccessMode {
+        unimplemented!()
+    }
 }
 
 /// Intermediate structure that validates the conversion to PublishVolumeRequest type.
@@ -1049,6 +1277,7 @@ pub struct ValidatedPublishVolumeRequest {
     uuid: VolumeId,
     share: Option<VolumeShareProtocol>,
     frontend_nodes: Vec<String>,
+    access_mode: VolumeAccessMode,
 }
 
 impl PublishVolumeInfo for ValidatedPublishVolumeRequest {
@@ -1074,6 +1303,10 @@ impl PublishVolumeInfo for ValidatedPublishVolumeRequest {
     fn frontend_nodes(&self) -> Vec<String> {
         self.frontend_nodes.clone()
     }
+
+    fn access_mode(&self) -> VolumeAccessMode {
+        self.access_mode
+    }
 }
 
 impl ValidateRequestTypes for PublishVolumeRequest {
@@ -1094,6 +1327,16 @@ impl ValidateRequestTypes for PublishVolumeRequest {
                 },
                 None => None,
             },
+            access_mode: match volume::VolumeAccessMode::from_i32(self.access_mode) {
+                Some(access_mode) => access_mode.into(),
+                None => {
+                    return Err(ReplyError::invalid_argument(
+                        ResourceKind::Volume,
+                        "publish_volume_request.access_mode",
+                        "".to_string(),
+                    ))
+                }
+            },
             inner: self.clone(),
             frontend_nodes: self.frontend_nodes,
         })
@@ -1108,6 +1351,7 @@ impl From<&dyn PublishVolumeInfo> for PublishVolume {
             share: data.share(),
             publish_context: data.publish_context(),
             frontend_nodes: data.frontend_nodes(),
+            access_mode: data.access_mode(),
         }
     }
 }
@@ -1127,6 +1371,7 @@ impl From<&dyn PublishVolumeInfo> for PublishVolumeRequest {
             share,
             publish_context: data.publish_context(),
             frontend_nodes: data.frontend_nodes(),
+            access_mode: volume::VolumeAccessMode::from(data.access_mode()) as i32,
         }
     }
 }
@@ -1143,6 +1388,8 @@ pub trait RepublishVolumeInfo: Send + Sync + std::fmt::Debug {
     fn share(&self) -> VolumeShareProtocol;
     /// Republish reusing current target.
     fn reuse_existing(&self) -> bool;
+    /// Defer the shutdown of the current target.
+    fn defer_shutdown(&self) -> bool;
 }
 
 impl RepublishVolumeInfo for RepublishVolume {
@@ -1165,6 +1412,10 @@ impl RepublishVolumeInfo for RepublishVolume {
     fn reuse_existing(&self) -> bool {
         self.reuse_existing
     }
+
+    fn defer_shutdown(&self) -> bool {
+        self.defer_shutdown
+    }
 }
 
 impl From<&dyn RepublishVolumeInfo> for RepublishVolume {
@@ -1175,6 +1426,7 @@ impl From<&dyn RepublishVolumeInfo> for RepublishVolume {
             frontend_node: data.frontend_node(),
             share: data.share(),
             reuse_existing: data.reuse_existing(),
+            defer_shutdown: data.defer_shutdown(),
         }
     }
 }
@@ -1188,6 +1440,7 @@ impl From<&dyn RepublishVolumeInfo> for RepublishVolumeRequest {
             share: protocol as i32,
             reuse_existing: data.reuse_existing(),
             frontend_node: data.frontend_node().to_string(),
+            defer_shutdown: data.defer_shutdown(),
         }
     }
 }
@@ -1223,6 +1476,10 @@ impl RepublishVolumeInfo for ValidatedRepublishVolumeRequest {
     fn reuse_existing(&self) -> bool {
         self.inner.reuse_existing
     }
+
+    fn defer_shutdown(&self) -> bool {
+        self.inner.defer_shutdown
+    }
 }
 
 impl ValidateRequestTypes for RepublishVolumeRequest {
@@ -1251,6 +1508,8 @@ pub trait UnpublishVolumeInfo: Send + Sync + std::fmt::Debug {
     fn uuid(&self) -> VolumeId;
     /// Force unpublish
     fn force(&self) -> bool;
+    /// The frontend node to unpublish the volume from
+    fn frontend_node(&self) -> Option<NodeId>;
 }
 
 impl UnpublishVolumeInfo for UnpublishVolume {
@@ -1261,6 +1520,10 @@ impl UnpublishVolumeInfo for UnpublishVolume {
     fn force(&self) -> bool {
         self.force()
     }
+
+    fn frontend_node(&self) -> Option<NodeId> {
+        self.frontend_node().cloned()
+    }
 }
 
 /// Intermediate structure that validates the conversion to UnpublishVolumeRequest type.
@@ -1277,6 +1540,9 @@ impl UnpublishVolumeInfo for ValidatedUnpublishVolumeRequest {
     fn force(&self) -> bool {
         self.inner.force
     }
+    fn frontend_node(&self) -> Option<NodeId> {
+        self.inner.frontend_node.clone().map(NodeId::from)
+    }
 }
 
 impl ValidateRequestTypes for UnpublishVolumeRequest {
@@ -1291,7 +1557,7 @@ impl ValidateRequestTypes for UnpublishVolumeRequest {
 
 impl From<&dyn UnpublishVolumeInfo> for UnpublishVolume {
     fn from(data: &dyn UnpublishVolumeInfo) -> Self {
-        UnpublishVolume::new(&data.uuid(), data.force())
+        UnpublishVolume::new(&data.uuid(), data.force()).with_frontend_node(data.frontend_node())
     }
 }
 
@@ -1300,6 +1566,7 @@ impl From<&dyn UnpublishVolumeInfo> for UnpublishVolumeRequest {
         Self {
             uuid: Some(data.uuid().to_string()),
             force: data.force(),
+            frontend_node: data.frontend_node().map(|node| node.to_string()),
         }
     }
 }
@@ -1366,6 +1633,130 @@ impl From<&dyn SetVolumeReplicaInfo> for SetVolumeReplicaRequest {
     }
 }
 
+/// Trait to be implemented for ResizeVolume operation.
+pub trait ResizeVolumeInfo: Send + Sync + std::fmt::Debug {
+    /// Uuid of the concerned volume
+    fn uuid(&self) -> VolumeId;
+    /// Requested new size of the volume, in bytes
+    fn requested_size(&self) -> u64;
+}
+
+impl ResizeVolumeInfo for ResizeVolume {
+    fn uuid(&self) -> VolumeId {
+        self.uuid.clone()
+    }
+
+    fn requested_size(&self) -> u64 {
+        self.requested_size
+    }
+}
+
+/// Intermediate structure that validates the conversion to ResizeVolumeRequest type.
+#[derive(Debug)]
+pub struct ValidatedResizeVolumeRequest {
+    inner: ResizeVolumeRequest,
+    uuid: VolumeId,
+}
+
+impl ResizeVolumeInfo for ValidatedResizeVolumeRequest {
+    fn uuid(&self) -> VolumeId {
+        self.uuid.clone()
+    }
+    fn requested_size(&self) -> u64 {
+        self.inner.requested_size
+    }
+}
+
+impl ValidateRequestTypes for ResizeVolumeRequest {
+    type Validated = ValidatedResizeVolumeRequest;
+    fn validated(self) -> Result<Self::Validated, ReplyError> {
+        Ok(ValidatedResizeVolumeRequest {
+            uuid: VolumeId::try_from(StringValue(self.uuid.clone()))?,
+            inner: self,
+        })
+    }
+}
+
+impl From<&dyn ResizeVolumeInfo> for ResizeVolume {
+    fn from(data: &dyn ResizeVolumeInfo) -> Self {
+        Self {
+            uuid: data.uuid(),
+            requested_size: data.requested_size(),
+        }
+    }
+}
+
+impl From<&dyn ResizeVolumeInfo> for ResizeVolumeRequest {
+    fn from(data: &dyn ResizeVolumeInfo) -> Self {
+        Self {
+            uuid: Some(data.uuid().to_string()),
+            requested_size: data.requested_size(),
+        }
+    }
+}
+
+/// Trait to be implemented for SetVolumeQos operation.
+pub trait SetVolumeQosInfo: Send + Sync + std::fmt::Debug {
+    /// Uuid of the concerned volume
+    fn uuid(&self) -> VolumeId;
+    /// The QoS limits which replace the existing ones
+    fn qos(&self) -> VolumeQos;
+}
+
+impl SetVolumeQosInfo for SetVolumeQos {
+    fn uuid(&self) -> VolumeId {
+        self.uuid.clone()
+    }
+
+    fn qos(&self) -> VolumeQos {
+        self.qos.clone()
+    }
+}
+
+/// Intermediate structure that validates the conversion to SetVolumeQosRequest type.
+#[derive(Debug)]
+pub struct ValidatedSetVolumeQosRequest {
+    inner: SetVolumeQosRequest,
+    uuid: VolumeId,
+}
+
+impl SetVolumeQosInfo for ValidatedSetVolumeQosRequest {
+    fn uuid(&self) -> VolumeId {
+        self.uuid.clone()
+    }
+    fn qos(&self) -> VolumeQos {
+        self.inner.qos.clone().map(From::from).unwrap_or_default()
+    }
+}
+
+impl ValidateRequestTypes for SetVolumeQosRequest {
+    type Validated = ValidatedSetVolumeQosRequest;
+    fn validated(self) -> Result<Self::Validated, ReplyError> {
+        Ok(ValidatedSetVolumeQosRequest {
+            uuid: VolumeId::try_from(StringValue(self.uuid.clone()))?,
+            inner: self,
+        })
+    }
+}
+
+impl From<&dyn SetVolumeQosInfo> for SetVolumeQos {
+    fn from(data: &dyn SetVolumeQosInfo) -> Self {
+        Self {
+            uuid: data.uuid(),
+            qos: data.qos(),
+        }
+    }
+}
+
+impl From<&dyn SetVolumeQosInfo> for SetVolumeQosRequest {
+    fn from(data: &dyn SetVolumeQosInfo) -> Self {
+        Self {
+            uuid: Some(data.uuid().to_string()),
+            qos: Some(data.qos().into()),
+        }
+    }
+}
+
 /// A helper to convert the replica topology map form grpc type to corresponding control plane type.
 fn to_replica_topology_map(
     map: HashMap<String, volume::ReplicaTopology>,
@@ -1483,3 +1874,400 @@ impl From<&dyn DestroyShutdownTargetsInfo> for DestroyShutdownTargets {
         Self::new(data.uuid().clone(), data.registered_targets())
     }
 }
+
+impl TryFrom<StringValue> for SnapshotId {
+    type Error = ReplyError;
+
+    fn try_from(value: StringValue) -> Result<Self, Self::Error> {
+        match value.0 {
+            Some(id) => match SnapshotId::try_from(id) {
+                Ok(snapshot_id) => Ok(snapshot_id),
+                Err(err) => Err(ReplyError::invalid_argument(
+                    ResourceKind::VolumeSnapshot,
+                    "snapshot_id",
+                    err.to_string(),
+                )),
+            },
+            None => Err(ReplyError::missing_argument(
+                ResourceKind::VolumeSnapshot,
+                "snapshot_id",
+            )),
+        }
+    }
+}
+
+impl From<common::SpecStatus> for VolumeSnapshotSpecStatus {
+    fn from(src: common::SpecStatus) -> Self {
+        match src {
+            common::SpecStatus::Created => Self::Created(()),
+            common::SpecStatus::Creating => Self::Creating,
+            common::SpecStatus::Deleted => Self::Deleted,
+            common::SpecStatus::Deleting => Self::Deleting,
+        }
+    }
+}
+
+impl From<VolumeSnapshotSpecStatus> for common::SpecStatus {
+    fn from(src: VolumeSnapshotSpecStatus) -> Self {
+        match src {
+            VolumeSnapshotSpecStatus::Creating => Self::Creating,
+            VolumeSnapshotSpecStatus::Created(_) => Self::Created,
+            VolumeSnapshotSpecStatus::Deleting => Self::Deleting,
+            VolumeSnapshotSpecStatus::Deleted => Self::Deleted,
+        }
+    }
+}
+
+/// A helper to convert an optional snapshot timestamp into the corresponding grpc type.
+fn to_grpc_timestamp<T: Into<SystemTime>>(timestamp: Option<T>) -> Option<prost_types::Timestamp> {
+    timestamp.map(|t| t.into().into())
+}
+
+/// A helper to convert an optional grpc timestamp into a snapshot timestamp.
+fn from_grpc_timestamp<T: From<SystemTime>>(
+    timestamp: Option<prost_types::Timestamp>,
+) -> Result<Option<T>, ReplyError> {
+    match timestamp {
+        Some(timestamp) => match SystemTime::try_from(timestamp) {
+            Ok(time) => Ok(Some(time.into())),
+            Err(err) => Err(ReplyError::invalid_argument(
+                ResourceKind::VolumeSnapshot,
+                "timestamp",
+                err.to_string(),
+            )),
+        },
+        None => Ok(None),
+    }
+}
+
+impl From<ReplicaSnapshot> for volume::ReplicaSnapshot {
+    fn from(src: ReplicaSnapshot) -> Self {
+        Self {
+            snapshot_id: Some(src.uuid.to_string()),
+            replica_id: Some(src.source_id.to_string()),
+            pool_id: src.pool_id.to_string(),
+        }
+    }
+}
+
+impl TryFrom<volume::ReplicaSnapshot> for ReplicaSnapshot {
+    type Error = ReplyError;
+
+    fn try_from(src: volume::ReplicaSnapshot) -> Result<Self, Self::Error> {
+        Ok(Self::new(
+            SnapshotId::try_from(StringValue(src.snapshot_id))?,
+            ReplicaId::try_from(StringValue(src.replica_id))?,
+            src.pool_id.into(),
+        ))
+    }
+}
+
+impl From<VolumeSnapshotSpec> for volume::VolumeSnapshotSpec {
+    fn from(src: VolumeSnapshotSpec) -> Self {
+        let spec_status: common::SpecStatus = src.status.into();
+        Self {
+            snapshot_id: Some(src.uuid.to_string()),
+            source_id: Some(src.source_id.to_string()),
+            spec_status: spec_status as i32,
+            timestamp: to_grpc_timestamp(src.timestamp),
+            size: src.size,
+            replica_snapshots: src.replica_snapshots.into_iter().map(Into::into).collect(),
+            encryption: src.encryption.map(|encryption| encryption.into()),
+        }
+    }
+}
+
+impl TryFrom<volume::VolumeSnapshotSpec> for VolumeSnapshotSpec {
+    type Error = ReplyError;
+
+    fn try_from(src: volume::VolumeSnapshotSpec) -> Result<Self, Self::Error> {
+        let status = match common::SpecStatus::from_i32(src.spec_status) {
+            Some(status) => status.into(),
+            None => {
+                return Err(ReplyError::invalid_argument(
+                    ResourceKind::VolumeSnapshot,
+                    "snapshot.spec.spec_status",
+                    "".to_string(),
+                ))
+            }
+        };
+        let uuid = SnapshotId::try_from(StringValue(src.snapshot_id))?;
+        Ok(Self {
+            sequencer: Default::default(),
+            uuid,
+            source_id: VolumeId::try_from(StringValue(src.source_id))?,
+            status,
+            operation: None,
+            timestamp: from_grpc_timestamp(src.timestamp)?,
+            size: src.size,
+            replica_snapshots: src
+                .replica_snapshots
+                .into_iter()
+                .map(ReplicaSnapshot::try_from)
+                .collect::<Result<_, _>>()?,
+            encryption: src.encryption.map(|encryption| encryption.into()),
+        })
+    }
+}
+
+impl From<VolumeSnapshotState> for volume::VolumeSnapshotState {
+    fn from(src: VolumeSnapshotState) -> Self {
+        Self {
+            snapshot_id: Some(src.uuid.to_string()),
+            source_id: Some(src.source_id.to_string()),
+            timestamp: to_grpc_timestamp(src.timestamp),
+            size: src.size,
+            ready_as_source: src.ready_as_source,
+        }
+    }
+}
+
+impl TryFrom<volume::VolumeSnapshotState> for VolumeSnapshotState {
+    type Error = ReplyError;
+
+    fn try_from(src: volume::VolumeSnapshotState) -> Result<Self, Self::Error> {
+        Ok(Self {
+            uuid: SnapshotId::try_from(StringValue(src.snapshot_id))?,
+            source_id: VolumeId::try_from(StringValue(src.source_id))?,
+            timestamp: from_grpc_timestamp(src.timestamp)?,
+            size: src.size,
+            ready_as_source: src.ready_as_source,
+        })
+    }
+}
+
+impl From<VolumeSnapshot> for volume::VolumeSnapshot {
+    fn from(src: VolumeSnapshot) -> Self {
+        Self {
+            spec: Some(src.spec().clone().into()),
+            state: Some(src.state().clone().into()),
+        }
+    }
+}
+
+impl TryFrom<volume::VolumeSnapshot> for VolumeSnapshot {
+    type Error = ReplyError;
+
+    fn try_from(src: volume::VolumeSnapshot) -> Result<Self, Self::Error> {
+        let spec = match src.spec {
+            Some(spec) => VolumeSnapshotSpec::try_from(spec)?,
+            None => {
+                return Err(ReplyError::missing_argument(
+                    ResourceKind::VolumeSnapshot,
+                    "snapshot.spec",
+                ))
+            }
+        };
+        let state = match src.state {
+            Some(state) => VolumeSnapshotState::try_from(state)?,
+            None => {
+                return Err(ReplyError::missing_argument(
+                    ResourceKind::VolumeSnapshot,
+                    "snapshot.state",
+                ))
+            }
+        };
+        Ok(Self::new(spec, state))
+    }
+}
+
+impl From<VolumeSnapshots> for volume::VolumeSnapshots {
+    fn from(src: VolumeSnapshots) -> Self {
+        Self {
+            entries: src.into_inner().into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl TryFrom<volume::VolumeSnapshots> for VolumeSnapshots {
+    type Error = ReplyError;
+
+    fn try_from(src: volume::VolumeSnapshots) -> Result<Self, Self::Error> {
+        Ok(Self(
+            src.entries
+                .into_iter()
+                .map(VolumeSnapshot::try_from)
+                .collect::<Result<_, _>>()?,
+        ))
+    }
+}
+
+/// Trait to be implemented for CreateVolumeSnapshot operation.
+pub trait CreateVolumeSnapshotInfo: Send + Sync + std::fmt::Debug {
+    /// Uuid of the source volume
+    fn source_id(&self) -> VolumeId;
+    /// Uuid of the snapshot
+    fn snap_id(&self) -> SnapshotId;
+}
+
+impl CreateVolumeSnapshotInfo for CreateVolumeSnapshot {
+    fn source_id(&self) -> VolumeId {
+        self.source_id.clone()
+    }
+
+    fn snap_id(&self) -> SnapshotId {
+        self.snap_id.clone()
+    }
+}
+
+/// Intermediate structure that validates the conversion to CreateSnapshotRequest type.
+#[derive(Debug)]
+pub struct ValidatedCreateSnapshotRequest {
+    source_id: VolumeId,
+    snap_id: SnapshotId,
+}
+
+impl CreateVolumeSnapshotInfo for ValidatedCreateSnapshotRequest {
+    fn source_id(&self) -> VolumeId {
+        self.source_id.clone()
+    }
+
+    fn snap_id(&self) -> SnapshotId {
+        self.snap_id.clone()
+    }
+}
+
+impl ValidateRequestTypes for CreateSnapshotRequest {
+    type Validated = ValidatedCreateSnapshotRequest;
+    fn validated(self) -> Result<Self::Validated, ReplyError> {
+        Ok(ValidatedCreateSnapshotRequest {
+            source_id: VolumeId::try_from(StringValue(self.volume_id))?,
+            snap_id: SnapshotId::try_from(StringValue(self.snapshot_id))?,
+        })
+    }
+}
+
+impl From<&dyn CreateVolumeSnapshotInfo> for CreateVolumeSnapshot {
+    fn from(data: &dyn CreateVolumeSnapshotInfo) -> Self {
+        Self::new(&data.source_id(), data.snap_id())
+    }
+}
+
+impl From<&dyn CreateVolumeSnapshotInfo> for CreateSnapshotRequest {
+    fn from(data: &dyn CreateVolumeSnapshotInfo) -> Self {
+        Self {
+            volume_id: Some(data.source_id().to_string()),
+            snapshot_id: Some(data.snap_id().to_string()),
+        }
+    }
+}
+
+/// Trait to be implemented for DestroyVolumeSnapshot operation.
+pub trait DestroyVolumeSnapshotInfo: Send + Sync + std::fmt::Debug {
+    /// Uuid of the source volume, if known
+    fn source_id(&self) -> Option<VolumeId>;
+    /// Uuid of the snapshot
+    fn snap_id(&self) -> SnapshotId;
+}
+
+impl DestroyVolumeSnapshotInfo for DestroyVolumeSnapshot {
+    fn source_id(&self) -> Option<VolumeId> {
+        self.source_id.clone()
+    }
+
+    fn snap_id(&self) -> SnapshotId {
+        self.snap_id.clone()
+    }
+}
+
+/// Intermediate structure that validates the conversion to DestroySnapshotRequest type.
+#[derive(Debug)]
+pub struct ValidatedDestroySnapshotRequest {
+    source_id: Option<VolumeId>,
+    snap_id: SnapshotId,
+}
+
+impl DestroyVolumeSnapshotInfo for ValidatedDestroySnapshotRequest {
+    fn source_id(&self) -> Option<VolumeId> {
+        self.source_id.clone()
+    }
+
+    fn snap_id(&self) -> SnapshotId {
+        self.snap_id.clone()
+    }
+}
+
+impl ValidateRequestTypes for DestroySnapshotRequest {
+    type Validated = ValidatedDestroySnapshotRequest;
+    fn validated(self) -> Result<Self::Validated, ReplyError> {
+        Ok(ValidatedDestroySnapshotRequest {
+            source_id: match self.volume_id {
+                Some(id) => Some(VolumeId::try_from(StringValue(Some(id)))?),
+                None => None,
+            },
+            snap_id: SnapshotId::try_from(StringValue(self.snapshot_id))?,
+        })
+    }
+}
+
+impl From<&dyn DestroyVolumeSnapshotInfo> for DestroyVolumeSnapshot {
+    fn from(data: &dyn DestroyVolumeSnapshotInfo) -> Self {
+        Self::new(data.source_id(), data.snap_id())
+    }
+}
+
+impl From<&dyn DestroyVolumeSnapshotInfo> for DestroySnapshotRequest {
+    fn from(data: &dyn DestroyVolumeSnapshotInfo) -> Self {
+        Self {
+            volume_id: data.source_id().map(|id| id.to_string()),
+            snapshot_id: Some(data.snap_id().to_string()),
+        }
+    }
+}
+
+/// Trait to be implemented for GetVolumeSnapshots operation.
+pub trait GetVolumeSnapshotsInfo: Send + Sync + std::fmt::Debug {
+    /// Retrieve only the snapshots of this volume
+    fn source_id(&self) -> Option<VolumeId>;
+    /// Retrieve only the snapshot with this uuid
+    fn snap_id(&self) -> Option<SnapshotId>;
+}
+
+impl GetVolumeSnapshotsInfo for GetVolumeSnapshots {
+    fn source_id(&self) -> Option<VolumeId> {
+        self.source_id.clone()
+    }
+
+    fn snap_id(&self) -> Option<SnapshotId> {
+        self.snap_id.clone()
+    }
+}
+
+/// Intermediate structure that validates the conversion to GetSnapshotsRequest type.
+#[derive(Debug)]
+pub struct ValidatedGetSnapshotsRequest {
+    source_id: Option<VolumeId>,
+    snap_id: Option<SnapshotId>,
+}
+
+impl GetVolumeSnapshotsInfo for ValidatedGetSnapshotsRequest {
+    fn source_id(&self) -> Option<VolumeId> {
+        self.source_id.clone()
+    }
+
+    fn snap_id(&self) -> Option<SnapshotId> {
+        self.snap_id.clone()
+    }
+}
+
+impl ValidateRequestTypes for GetSnapshotsRequest {
+    type Validated = ValidatedGetSnapshotsRequest;
+    fn validated(self) -> Result<Self::Validated, ReplyError> {
+        Ok(ValidatedGetSnapshotsRequest {
+            source_id: match self.volume_id {
+                Some(id) => Some(VolumeId::try_from(StringValue(Some(id)))?),
+                None => None,
+            },
+            snap_id: match self.snapshot_id {
+                Some(id) => Some(SnapshotId::try_from(StringValue(Some(id)))?),
+                None => None,
+            },
+        })
+    }
+}
+
+impl From<&dyn GetVolumeSnapshotsInfo> for GetVolumeSnapshots {
+    fn from(data: &dyn GetVolumeSnapshotsInfo) -> Self {
+        Self::new(data.source_id(), data.snap_id())
+    }
+}
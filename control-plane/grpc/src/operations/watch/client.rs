@@ -1,12 +1,15 @@
 use crate::{
     context::{Client, Context, TracedChannel},
-    operations::watch::traits::{GetWatchInfo, WatchInfo, WatchOperations},
+    operations::watch::traits::{
+        GetWatchInfo, ResourceEventStream, WatchEventsInfo, WatchInfo, WatchOperations,
+    },
     watch::{get_watches_reply, watch_grpc_client::WatchGrpcClient},
 };
 use common_lib::{
     transport_api::{v0::Watches, ReplyError, ResourceKind, TimeoutOptions},
-    types::v0::transport::MessageIdVs,
+    types::v0::transport::{MessageIdVs, ResourceEvent},
 };
+use futures::StreamExt;
 use std::{convert::TryFrom, ops::Deref};
 use tonic::transport::Uri;
 
@@ -75,4 +78,18 @@ impl WatchOperations for WatchClient {
             Some(err) => Err(err.into()),
         }
     }
+
+    async fn watch_events(
+        &self,
+        request: &dyn WatchEventsInfo,
+        ctx: Option<Context>,
+    ) -> Result<ResourceEventStream, ReplyError> {
+        let req = self.request(request, ctx, MessageIdVs::WatchEvents);
+        let stream = self.client().watch_events(req).await?.into_inner();
+        Ok(Box::pin(stream.map(|event| {
+            event
+                .map_err(ReplyError::from)
+                .and_then(ResourceEvent::try_from)
+        })))
+    }
 }
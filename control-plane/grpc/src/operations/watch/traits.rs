@@ -7,11 +7,17 @@ use crate::{
 use common_lib::{
     transport_api::{v0::Watches, ReplyError, ResourceKind},
     types::v0::transport::{
-        CreateWatch, DeleteWatch, GetWatches, NexusId, ReplicaId, VolumeId, Watch, WatchCallback,
-        WatchResourceId, WatchType,
+        CreateWatch, DeleteWatch, GetWatches, NexusId, ReplicaId, ResourceEvent,
+        ResourceEventAction, VolumeId, Watch, WatchCallback, WatchEvents, WatchResourceId,
+        WatchType,
     },
 };
-use std::convert::TryFrom;
+use futures::Stream;
+use std::{collections::HashMap, convert::TryFrom, pin::Pin, str::FromStr};
+
+/// Stream of resource change events, as returned by `WatchOperations::watch_events`
+pub type ResourceEventStream =
+    Pin<Box<dyn Stream<Item = Result<ResourceEvent, ReplyError>> + Send>>;
 
 /// All watch crud operations to be a part of the WatchOperations trait
 #[tonic::async_trait]
@@ -26,6 +32,12 @@ pub trait WatchOperations: Send + Sync {
     ) -> Result<Watches, ReplyError>;
     /// Destroy a watch
     async fn destroy(&self, req: &dyn WatchInfo, ctx: Option<Context>) -> Result<(), ReplyError>;
+    /// Stream the resource change events
+    async fn watch_events(
+        &self,
+        req: &dyn WatchEventsInfo,
+        ctx: Option<Context>,
+    ) -> Result<ResourceEventStream, ReplyError>;
 }
 
 /// WatchInfo trait for the watch creation to be implemented by entities which want to avail
@@ -39,6 +51,15 @@ pub trait WatchInfo: Send + Sync + std::fmt::Debug {
     fn watch_type(&self) -> WatchType;
 }
 
+/// WatchEventsInfo trait for the watch events operation to be implemented by entities which want
+/// to avail this operation
+pub trait WatchEventsInfo: Send + Sync + std::fmt::Debug {
+    /// only stream events for these resource kinds, or all kinds if empty
+    fn kinds(&self) -> Vec<ResourceKind>;
+    /// only stream events for resources which carry all of these labels
+    fn labels(&self) -> HashMap<String, String>;
+}
+
 /// GetWatchInfo trait for the get watch operation to be implemented by entities which want to
 /// avail this operation
 pub trait GetWatchInfo: Send + Sync + std::fmt::Debug {
@@ -402,3 +423,118 @@ impl From<&dyn GetWatchInfo> for watch::GetWatchesRequest {
         }
     }
 }
+
+impl WatchEventsInfo for WatchEvents {
+    fn kinds(&self) -> Vec<ResourceKind> {
+        self.kinds.clone()
+    }
+
+    fn labels(&self) -> HashMap<String, String> {
+        self.labels.clone()
+    }
+}
+
+/// Intermediate structure that validates the conversion to WatchEventsRequest type
+#[derive(Debug)]
+pub struct ValidatedWatchEventsRequest {
+    kinds: Vec<ResourceKind>,
+    labels: HashMap<String, String>,
+}
+
+impl WatchEventsInfo for ValidatedWatchEventsRequest {
+    fn kinds(&self) -> Vec<ResourceKind> {
+        self.kinds.clone()
+    }
+
+    fn labels(&self) -> HashMap<String, String> {
+        self.labels.clone()
+    }
+}
+
+impl ValidateRequestTypes for watch::WatchEventsRequest {
+    type Validated = ValidatedWatchEventsRequest;
+    fn validated(self) -> Result<Self::Validated, ReplyError> {
+        let kinds = self
+            .kinds
+            .iter()
+            .map(|kind| {
+                ResourceKind::from_str(kind).map_err(|error| {
+                    ReplyError::invalid_argument(ResourceKind::Watch, "kind", error.to_string())
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(ValidatedWatchEventsRequest {
+            kinds,
+            labels: self.labels,
+        })
+    }
+}
+
+impl From<&dyn WatchEventsInfo> for WatchEvents {
+    fn from(data: &dyn WatchEventsInfo) -> Self {
+        Self {
+            kinds: data.kinds(),
+            labels: data.labels(),
+        }
+    }
+}
+
+impl From<&dyn WatchEventsInfo> for watch::WatchEventsRequest {
+    fn from(data: &dyn WatchEventsInfo) -> Self {
+        Self {
+            kinds: data.kinds().iter().map(|kind| kind.to_string()).collect(),
+            labels: data.labels(),
+        }
+    }
+}
+
+impl From<ResourceEventAction> for watch::ResourceEventAction {
+    fn from(value: ResourceEventAction) -> Self {
+        match value {
+            ResourceEventAction::Created => Self::Created,
+            ResourceEventAction::Updated => Self::Updated,
+            ResourceEventAction::Deleted => Self::Deleted,
+        }
+    }
+}
+
+impl From<watch::ResourceEventAction> for ResourceEventAction {
+    fn from(value: watch::ResourceEventAction) -> Self {
+        match value {
+            watch::ResourceEventAction::Created => Self::Created,
+            watch::ResourceEventAction::Updated => Self::Updated,
+            watch::ResourceEventAction::Deleted => Self::Deleted,
+        }
+    }
+}
+
+impl From<ResourceEvent> for watch::ResourceEvent {
+    fn from(value: ResourceEvent) -> Self {
+        let action: watch::ResourceEventAction = value.action.into();
+        Self {
+            kind: value.kind.to_string(),
+            id: value.id,
+            action: action as i32,
+            labels: value.labels,
+        }
+    }
+}
+
+impl TryFrom<watch::ResourceEvent> for ResourceEvent {
+    type Error = ReplyError;
+
+    fn try_from(value: watch::ResourceEvent) -> Result<Self, Self::Error> {
+        Ok(Self {
+            kind: ResourceKind::from_str(&value.kind).map_err(|error| {
+                ReplyError::invalid_argument(ResourceKind::Watch, "kind", error.to_string())
+            })?,
+            id: value.id,
+            action: watch::ResourceEventAction::from_i32(value.action)
+                .ok_or_else(|| {
+                    ReplyError::invalid_argument(ResourceKind::Watch, "action", "".to_string())
+                })?
+                .into(),
+            labels: value.labels,
+        })
+    }
+}
@@ -3,11 +3,12 @@ use openapi::tower::client::Url;
 use opentelemetry::global;
 use plugin::{
     operations::{
-        Cordoning, Drain, Get, GetBlockDevices, List, Operations, ReplicaTopology, Scale,
+        Cordoning, Drain, Get, GetBlockDevices, Labelling, List, Operations, ReplicaTopology, Scale,
     },
     resources::{
-        blockdevice, cordon, drain, node, pool, volume, CordonResources, DrainResources,
-        GetCordonArgs, GetDrainArgs, GetResources, ScaleResources,
+        blockdevice, cordon, drain, node, pool, rebuild, switchover, volume, CordonResources,
+        DrainResources, GetCordonArgs, GetDrainArgs, GetResources, LabelResources, ScaleResources,
+        UnlabelResources,
     },
     rest_wrapper::RestClient,
 };
@@ -64,6 +65,7 @@ async fn execute(cli_args: CliArgs) {
                 node::Node::drain(
                     &drain_node_args.node_id(),
                     drain_node_args.label(),
+                    drain_node_args.evacuate(),
                     drain_node_args.drain_timeout(),
                     &cli_args.output,
                 )
@@ -100,6 +102,11 @@ async fn execute(cli_args: CliArgs) {
                 )
                 .await
             }
+            GetResources::Switchovers => switchover::SwitchOvers::list(&cli_args.output).await,
+            GetResources::VolumeRebuilds { id } => match id {
+                Some(id) => rebuild::VolumeRebuilds::get(id, &cli_args.output).await,
+                None => rebuild::VolumeRebuilds::list(&cli_args.output).await,
+            },
         },
         Operations::Scale(resource) => match resource {
             ScaleResources::Volume { id, replica_count } => {
@@ -116,5 +123,25 @@ async fn execute(cli_args: CliArgs) {
                 node::Node::uncordon(id, label, &cli_args.output).await
             }
         },
+        Operations::Label(resource) => match resource {
+            LabelResources::Node {
+                id,
+                label,
+                overwrite,
+            } => node::Node::label(id, label, *overwrite, &cli_args.output).await,
+            LabelResources::Pool {
+                id,
+                label,
+                overwrite,
+            } => pool::Pool::label(id, label, *overwrite, &cli_args.output).await,
+        },
+        Operations::Unlabel(resource) => match resource {
+            UnlabelResources::Node { id, key } => {
+                node::Node::unlabel(id, key, &cli_args.output).await
+            }
+            UnlabelResources::Pool { id, key } => {
+                pool::Pool::unlabel(id, key, &cli_args.output).await
+            }
+        },
     };
 }
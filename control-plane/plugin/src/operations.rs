@@ -1,4 +1,7 @@
-use crate::resources::{utils, CordonResources, DrainResources, GetResources, ScaleResources};
+use crate::resources::{
+    utils, CordonResources, DrainResources, GetResources, LabelResources, ScaleResources,
+    UnlabelResources,
+};
 use async_trait::async_trait;
 
 /// The types of operations that are supported.
@@ -19,6 +22,12 @@ pub enum Operations {
     /// 'Uncordon' resources.
     #[clap(subcommand)]
     Uncordon(CordonResources),
+    /// 'Label' resources.
+    #[clap(subcommand)]
+    Label(LabelResources),
+    /// 'Unlabel' resources.
+    #[clap(subcommand)]
+    Unlabel(UnlabelResources),
 }
 
 /// Drain trait.
@@ -29,6 +38,7 @@ pub trait Drain {
     async fn drain(
         id: &Self::ID,
         label: String,
+        evacuate: bool,
         drain_timeout: Option<humantime::Duration>,
         output: &utils::OutputFormat,
     );
@@ -81,3 +91,12 @@ pub trait Cordoning {
     async fn cordon(id: &Self::ID, label: &str, output: &utils::OutputFormat);
     async fn uncordon(id: &Self::ID, label: &str, output: &utils::OutputFormat);
 }
+
+/// Labelling trait.
+/// To be implemented by resources which support labelling.
+#[async_trait(?Send)]
+pub trait Labelling {
+    type ID;
+    async fn label(id: &Self::ID, label: &str, overwrite: bool, output: &utils::OutputFormat);
+    async fn unlabel(id: &Self::ID, key: &str, output: &utils::OutputFormat);
+}
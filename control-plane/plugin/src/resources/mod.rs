@@ -8,6 +8,8 @@ pub mod cordon;
 pub mod drain;
 pub mod node;
 pub mod pool;
+pub mod rebuild;
+pub mod switchover;
 pub mod utils;
 pub mod volume;
 
@@ -43,6 +45,11 @@ pub enum GetResources {
     /// Currently disks having blobstore pools not created by control-plane are also shown as
     /// usable.
     BlockDevices(BlockDeviceArgs),
+    /// Get the in-flight and recently failed volume switchovers of the HA cluster agent.
+    Switchovers,
+    /// Get the running and past replica rebuilds of all volumes, or of the volume with the given
+    /// ID.
+    VolumeRebuilds { id: Option<VolumeId> },
 }
 
 /// The types of resources that support the 'scale' operation.
@@ -64,6 +71,36 @@ pub enum CordonResources {
     Node { id: NodeId, label: String },
 }
 
+/// The types of resources that support labelling.
+#[derive(clap::Subcommand, Debug)]
+pub enum LabelResources {
+    /// Label the node with the given ID with the given label, in the key=value format.
+    Node {
+        id: NodeId,
+        label: String,
+        /// Overwrite the value of the label if the node already has it.
+        #[clap(long)]
+        overwrite: bool,
+    },
+    /// Label the pool with the given ID with the given label, in the key=value format.
+    Pool {
+        id: PoolId,
+        label: String,
+        /// Overwrite the value of the label if the pool already has it.
+        #[clap(long)]
+        overwrite: bool,
+    },
+}
+
+/// The types of resources that support unlabelling.
+#[derive(clap::Subcommand, Debug)]
+pub enum UnlabelResources {
+    /// Remove the label with the given key from the node with the given ID.
+    Node { id: NodeId, key: String },
+    /// Remove the label with the given key from the pool with the given ID.
+    Pool { id: PoolId, key: String },
+}
+
 /// The types of resources that support the 'get cordon' operation.
 #[derive(clap::Subcommand, Debug)]
 pub enum GetCordonArgs {
@@ -1,5 +1,5 @@
 use crate::{
-    operations::{Cordoning, Drain, Get, List},
+    operations::{Cordoning, Drain, Get, Labelling, List},
     resources::{
         utils,
         utils::{print_table, CreateRows, GetHeaderRow, OutputFormat},
@@ -209,6 +209,64 @@ impl Cordoning for Node {
     }
 }
 
+#[async_trait(?Send)]
+impl Labelling for Node {
+    type ID = NodeId;
+    async fn label(id: &Self::ID, label: &str, overwrite: bool, output: &OutputFormat) {
+        let (key, value) = match label.split_once('=') {
+            Some((key, value)) if !key.is_empty() => (key, value),
+            _ => {
+                println!(
+                    "Invalid label '{}', the label must be in the key=value format",
+                    label
+                );
+                return;
+            }
+        };
+        match RestClient::client()
+            .nodes_api()
+            .put_node_label(id, key, value, Some(overwrite))
+            .await
+        {
+            Ok(node) => match output {
+                OutputFormat::Yaml | OutputFormat::Json => {
+                    // Print json or yaml based on output format.
+                    utils::print_table(output, node.into_body());
+                }
+                OutputFormat::None => {
+                    // In case the output format is not specified, show a success message.
+                    println!("Node {} labelled successfully", id)
+                }
+            },
+            Err(e) => {
+                println!("Failed to label node {}. Error {}", id, e)
+            }
+        }
+    }
+
+    async fn unlabel(id: &Self::ID, key: &str, output: &OutputFormat) {
+        match RestClient::client()
+            .nodes_api()
+            .delete_node_label(id, key)
+            .await
+        {
+            Ok(node) => match output {
+                OutputFormat::Yaml | OutputFormat::Json => {
+                    // Print json or yaml based on output format.
+                    utils::print_table(output, node.into_body());
+                }
+                OutputFormat::None => {
+                    // In case the output format is not specified, show a success message.
+                    println!("Node {} unlabelled successfully", id)
+                }
+            },
+            Err(e) => {
+                println!("Failed to unlabel node {}. Error {}", id, e)
+            }
+        }
+    }
+}
+
 /// Display format options for a `Node` object.
 #[derive(Debug)]
 pub enum NodeDisplayFormat {
@@ -269,6 +327,35 @@ impl NodeDisplay {
         }
         drain_labels
     }
+    pub(crate) fn get_evacuation_list(node: &openapi::models::Node) -> Vec<String> {
+        let evacuations = match &node.spec {
+            Some(ns) => match &ns.cordondrainstate {
+                Some(CordonDrainState::drainingstate(state))
+                | Some(CordonDrainState::drainedstate(state)) => {
+                    state.evacuations.clone().unwrap_or_default()
+                }
+                _ => vec![],
+            },
+            None => vec![],
+        };
+        evacuations
+            .iter()
+            .map(|evacuation| match evacuation.rebuild_progress {
+                _ if evacuation.degraded => format!(
+                    "{} ({} replicas, degraded)",
+                    evacuation.volume_id, evacuation.replicas
+                ),
+                Some(progress) => format!(
+                    "{} ({} replicas, {}% rebuilt)",
+                    evacuation.volume_id, evacuation.replicas, progress
+                ),
+                None => format!(
+                    "{} ({} replicas)",
+                    evacuation.volume_id, evacuation.replicas
+                ),
+            })
+            .collect()
+    }
 }
 
 // Create the rows required for a `NodeDisplay` object. Nodes returned from REST call.
@@ -302,9 +389,11 @@ impl CreateRows for NodeDisplay {
                     };
 
                     let labelstring = NodeDisplay::get_drain_label_list(node).join(", ");
-                    // Add the drain labels to each row.
+                    let evacuationstring = NodeDisplay::get_evacuation_list(node).join(", ");
+                    // Add the drain labels and the evacuation progress to each row.
                     row[0].add_cell(Cell::new(drain_status_string));
                     row[0].add_cell(Cell::new(&labelstring));
+                    row[0].add_cell(Cell::new(&evacuationstring));
                     rows.push(row[0].clone());
                 }
                 rows
@@ -360,6 +449,7 @@ impl GetHeaderRow for NodeDisplay {
             NodeDisplayFormat::Drain => {
                 header.extend(vec!["DRAIN STATE"]);
                 header.extend(vec!["DRAIN LABELS"]);
+                header.extend(vec!["EVACUATION"]);
                 header
             }
         }
@@ -373,6 +463,9 @@ pub struct DrainNodeArgs {
     /// Label of the drain.
     label: String,
     #[clap(long)]
+    /// Also move the volume replicas off the node, and not only the volume targets.
+    evacuate: bool,
+    #[clap(long)]
     /// Timeout for the drain operation.
     drain_timeout: Option<humantime::Duration>,
 }
@@ -386,6 +479,10 @@ impl DrainNodeArgs {
     pub fn label(&self) -> String {
         self.label.clone()
     }
+    /// Return whether the volume replicas are also moved off the node.
+    pub fn evacuate(&self) -> bool {
+        self.evacuate
+    }
     /// Return the timeout for the drain operation.
     pub fn drain_timeout(&self) -> Option<humantime::Duration> {
         self.drain_timeout
@@ -398,6 +495,7 @@ impl Drain for Node {
     async fn drain(
         id: &Self::ID,
         label: String,
+        evacuate: bool,
         drain_timeout: Option<humantime::Duration>,
         output: &utils::OutputFormat,
     ) {
@@ -428,7 +526,7 @@ impl Drain for Node {
         if !already_has_drain_label {
             if let Err(error) = RestClient::client()
                 .nodes_api()
-                .put_node_drain(id, &label)
+                .put_node_drain(id, &label, Some(evacuate))
                 .await
             {
                 println!("Failed to put node drain {}. Error {}", id, error);
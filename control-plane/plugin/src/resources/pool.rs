@@ -1,8 +1,8 @@
 use crate::{
-    operations::{Get, List},
+    operations::{Get, Labelling, List},
     resources::{
         utils,
-        utils::{CreateRows, GetHeaderRow},
+        utils::{CreateRows, GetHeaderRow, OutputFormat},
         PoolId,
     },
     rest_wrapper::RestClient,
@@ -91,3 +91,61 @@ impl Get for Pool {
         }
     }
 }
+
+#[async_trait(?Send)]
+impl Labelling for Pool {
+    type ID = PoolId;
+    async fn label(id: &Self::ID, label: &str, overwrite: bool, output: &OutputFormat) {
+        let (key, value) = match label.split_once('=') {
+            Some((key, value)) if !key.is_empty() => (key, value),
+            _ => {
+                println!(
+                    "Invalid label '{}', the label must be in the key=value format",
+                    label
+                );
+                return;
+            }
+        };
+        match RestClient::client()
+            .pools_api()
+            .put_pool_label(id, key, value, Some(overwrite))
+            .await
+        {
+            Ok(pool) => match output {
+                OutputFormat::Yaml | OutputFormat::Json => {
+                    // Print json or yaml based on output format.
+                    utils::print_table(output, pool.into_body());
+                }
+                OutputFormat::None => {
+                    // In case the output format is not specified, show a success message.
+                    println!("Pool {} labelled successfully", id)
+                }
+            },
+            Err(e) => {
+                println!("Failed to label pool {}. Error {}", id, e)
+            }
+        }
+    }
+
+    async fn unlabel(id: &Self::ID, key: &str, output: &OutputFormat) {
+        match RestClient::client()
+            .pools_api()
+            .delete_pool_label(id, key)
+            .await
+        {
+            Ok(pool) => match output {
+                OutputFormat::Yaml | OutputFormat::Json => {
+                    // Print json or yaml based on output format.
+                    utils::print_table(output, pool.into_body());
+                }
+                OutputFormat::None => {
+                    // In case the output format is not specified, show a success message.
+                    println!("Pool {} unlabelled successfully", id)
+                }
+            },
+            Err(e) => {
+                println!("Failed to unlabel pool {}. Error {}", id, e)
+            }
+        }
+    }
+}
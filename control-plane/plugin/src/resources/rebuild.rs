@@ -0,0 +1,76 @@
+use crate::{
+    operations::{Get, List},
+    resources::{
+        utils,
+        utils::{optional_cell, CreateRows, GetHeaderRow},
+        VolumeId,
+    },
+    rest_wrapper::RestClient,
+};
+use async_trait::async_trait;
+use prettytable::Row;
+
+/// Volume replica rebuilds resource.
+#[derive(clap::Args, Debug)]
+pub struct VolumeRebuilds {}
+
+// CreateRows being trait for RebuildRecord would create the rows from the list of
+// RebuildRecords returned from REST call.
+impl CreateRows for openapi::models::RebuildRecord {
+    fn create_rows(&self) -> Vec<Row> {
+        let rows = vec![row![
+            self.volume_id,
+            self.replica_id,
+            self.reason,
+            self.outcome,
+            optional_cell(self.progress.as_ref()),
+            self.bytes,
+            self.started,
+            optional_cell(self.finished.as_ref()),
+            optional_cell(self.eta.as_ref())
+        ]];
+        rows
+    }
+}
+
+// GetHeaderRow being trait for RebuildRecord would return the Header Row for
+// RebuildRecord.
+impl GetHeaderRow for openapi::models::RebuildRecord {
+    fn get_header_row(&self) -> Row {
+        (&*utils::REBUILD_HEADERS).clone()
+    }
+}
+
+impl VolumeRebuilds {
+    /// Print the rebuilds, optionally filtered by volume.
+    async fn print(volume_id: Option<VolumeId>, output: &utils::OutputFormat) {
+        match RestClient::client()
+            .rebuild_history_api()
+            .get_rebuild_history(volume_id)
+            .await
+        {
+            Ok(rebuilds) => {
+                // Print table, json or yaml based on output format.
+                utils::print_table(output, rebuilds.into_body());
+            }
+            Err(e) => {
+                println!("Failed to list volume rebuilds. Error {}", e)
+            }
+        }
+    }
+}
+
+#[async_trait(?Send)]
+impl List for VolumeRebuilds {
+    async fn list(output: &utils::OutputFormat) {
+        Self::print(None, output).await
+    }
+}
+
+#[async_trait(?Send)]
+impl Get for VolumeRebuilds {
+    type ID = VolumeId;
+    async fn get(id: &Self::ID, output: &utils::OutputFormat) {
+        Self::print(Some(*id), output).await
+    }
+}
@@ -0,0 +1,59 @@
+use crate::{
+    operations::List,
+    resources::{
+        utils,
+        utils::{optional_cell, CreateRows, GetHeaderRow},
+    },
+    rest_wrapper::RestClient,
+};
+use async_trait::async_trait;
+use prettytable::Row;
+
+/// Volume switchovers resource.
+#[derive(clap::Args, Debug)]
+pub struct SwitchOvers {}
+
+// CreateRows being trait for SwitchOver would create the rows from the list of
+// SwitchOvers returned from REST call.
+impl CreateRows for openapi::models::SwitchOver {
+    fn create_rows(&self) -> Vec<Row> {
+        let rows = vec![row![
+            self.volume_id,
+            self.node,
+            self.stage,
+            self.retry_count,
+            self.existing_nqn,
+            optional_cell(self.new_path.as_ref()),
+            optional_cell(self.error.as_ref()),
+            self.timestamp
+        ]];
+        rows
+    }
+}
+
+// GetHeaderRow being trait for SwitchOver would return the Header Row for
+// SwitchOver.
+impl GetHeaderRow for openapi::models::SwitchOver {
+    fn get_header_row(&self) -> Row {
+        (&*utils::SWITCHOVER_HEADERS).clone()
+    }
+}
+
+#[async_trait(?Send)]
+impl List for SwitchOvers {
+    async fn list(output: &utils::OutputFormat) {
+        match RestClient::client()
+            .switchovers_api()
+            .get_switchovers(None)
+            .await
+        {
+            Ok(switchovers) => {
+                // Print table, json or yaml based on output format.
+                utils::print_table(output, switchovers.into_body());
+            }
+            Err(e) => {
+                println!("Failed to list switchovers. Error {}", e)
+            }
+        }
+    }
+}
@@ -35,6 +35,8 @@ async fn setup() {
                 topology: None,
                 labels: None,
                 thin: false,
+                content_source: None,
+                encryption: None,
             },
         )
         .await
@@ -106,6 +108,8 @@ async fn get_volumes_paginated() {
                     thin: false,
                     topology: None,
                     labels: None,
+                    content_source: None,
+                    encryption: None,
                 },
             )
             .await
@@ -32,6 +32,19 @@ lazy_static! {
     ];
     pub static ref NODE_HEADERS: Row = row!["ID", "GRPC ENDPOINT", "STATUS", "CORDONED"];
     pub static ref REPLICA_TOPOLOGY_HEADERS: Row = row!["ID", "NODE", "POOL", "STATUS"];
+    pub static ref SWITCHOVER_HEADERS: Row = row![
+        "VOLUME",
+        "NODE",
+        "STAGE",
+        "RETRIES",
+        "EXISTING NQN",
+        "NEW PATH",
+        "ERROR",
+        "TIMESTAMP"
+    ];
+    pub static ref REBUILD_HEADERS: Row = row![
+        "VOLUME", "REPLICA", "REASON", "OUTCOME", "PROGRESS", "BYTES", "STARTED", "FINISHED", "ETA"
+    ];
     pub static ref BLOCKDEVICE_HEADERS_ALL: Row = row![
         "DEVNAME",
         "DEVTYPE",
@@ -1,7 +1,7 @@
 mod authentication;
 mod v0;
 
-use crate::v0::{CORE_CLIENT, JSON_GRPC_CLIENT};
+use crate::v0::{CLUSTER_AGENT_CLIENT, CORE_CLIENT, JSON_GRPC_CLIENT};
 use actix_service::ServiceFactory;
 use actix_web::{
     body::MessageBody,
@@ -33,6 +33,10 @@ pub(crate) struct CliArgs {
     #[structopt(long, short = "J")]
     json_grpc: Option<Uri>,
 
+    /// The HA cluster agent gRPC Server URL or address to connect to the service.
+    #[structopt(long)]
+    ha_cluster_agent: Option<Uri>,
+
     /// Path to the certificate file
     #[structopt(long, short, required_unless = "dummy-certificates")]
     cert_file: Option<String>,
@@ -103,7 +107,10 @@ fn timeout_opts() -> TimeoutOptions {
 
 use actix_web_opentelemetry::RequestTracing;
 use common_lib::transport_api::{RequestMinTimeout, TimeoutOptions};
-use grpc::{client::CoreClient, operations::jsongrpc::client::JsonGrpcClient};
+use grpc::{
+    client::CoreClient,
+    operations::{ha_node::client::ClusterAgentClient, jsongrpc::client::JsonGrpcClient},
+};
 use http::Uri;
 use opentelemetry::{global, KeyValue};
 
@@ -233,6 +240,14 @@ async fn main() -> anyhow::Result<()> {
             .expect("Expect to be initialised only once");
     }
 
+    // Initialise the HA cluster agent client to be used in rest
+    if let Some(ha_cluster_agent) = CliArgs::args().ha_cluster_agent {
+        CLUSTER_AGENT_CLIENT
+            .set(ClusterAgentClient::new(ha_cluster_agent, timeout_opts()).await)
+            .ok()
+            .expect("Expect to be initialised only once");
+    }
+
     let server = HttpServer::new(app).bind_rustls(CliArgs::args().https, get_certificates()?)?;
     let result = if let Some(http) = CliArgs::args().http {
         server.bind(http).map_err(anyhow::Error::from)?
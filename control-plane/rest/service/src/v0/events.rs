@@ -0,0 +1,133 @@
+use super::*;
+use common_lib::types::v0::transport::{ResourceEvent, WatchEvents};
+use futures::StreamExt;
+use grpc::operations::watch::traits::WatchOperations;
+use std::{collections::HashMap, convert::TryFrom, str::FromStr};
+use transport_api::{ReplyError, ResourceKind};
+
+fn client() -> impl WatchOperations {
+    core_grpc().watch()
+}
+
+// todo: once the event schema is added to the spec yaml then replace this with the autogen code
+pub(super) fn configure(cfg: &mut actix_web::web::ServiceConfig) {
+    cfg.service(
+        actix_web::web::resource("/events")
+            .name("get_events")
+            .guard(actix_web::guard::Get())
+            .route(actix_web::web::get().to(get_events)),
+    );
+}
+
+/// Filters for the resource change events.
+#[derive(Deserialize, Debug, Default)]
+struct EventsQuery {
+    /// comma separated list of resource kinds, eg: `Volume,Pool`
+    kinds: Option<String>,
+    /// comma separated list of labels which the resource must carry, eg: `key1=value1,key2=value2`
+    labels: Option<String>,
+}
+
+impl TryFrom<EventsQuery> for WatchEvents {
+    type Error = ReplyError;
+
+    fn try_from(query: EventsQuery) -> Result<Self, Self::Error> {
+        let kinds = match query.kinds {
+            None => vec![],
+            Some(kinds) => kinds
+                .split(',')
+                .filter(|kind| !kind.is_empty())
+                .map(|kind| {
+                    ResourceKind::from_str(kind).map_err(|_| {
+                        ReplyError::invalid_argument(ResourceKind::Watch, "kinds", kind.to_string())
+                    })
+                })
+                .collect::<Result<Vec<_>, _>>()?,
+        };
+        let labels = match query.labels {
+            None => HashMap::new(),
+            Some(labels) => labels
+                .split(',')
+                .filter(|label| !label.is_empty())
+                .map(|label| match label.split_once('=') {
+                    Some((key, value)) => Ok((key.to_string(), value.to_string())),
+                    None => Err(ReplyError::invalid_argument(
+                        ResourceKind::Watch,
+                        "labels",
+                        label.to_string(),
+                    )),
+                })
+                .collect::<Result<HashMap<_, _>, _>>()?,
+        };
+        Ok(WatchEvents::new().with_kinds(kinds).with_labels(labels))
+    }
+}
+
+/// Format the event as a server-sent event message.
+fn sse_message(event: &ResourceEvent) -> actix_web::web::Bytes {
+    let data = serde_json::to_string(event).unwrap_or_default();
+    actix_web::web::Bytes::from(format!(
+        "event: {}\ndata: {}\n\n",
+        event.action.to_string(),
+        data
+    ))
+}
+
+/// Format the error which ended the event stream as a server-sent event message, so the client
+/// knows it has to re-list the resources rather than just reconnect.
+fn sse_error(error: &ReplyError) -> actix_web::web::Bytes {
+    let data = serde_json::to_string(error).unwrap_or_default();
+    actix_web::web::Bytes::from(format!("event: error\ndata: {}\n\n", data))
+}
+
+async fn get_events(
+    query: actix_web::web::Query<EventsQuery>,
+) -> Result<actix_web::HttpResponse, RestError<RestJsonError>> {
+    let request = WatchEvents::try_from(query.into_inner())?;
+    let stream = client().watch_events(&request, None).await?;
+    let stream = stream.map(|event| match event {
+        Ok(event) => Ok::<_, actix_web::Error>(sse_message(&event)),
+        Err(error) => Ok(sse_error(&error)),
+    });
+    Ok(actix_web::HttpResponse::Ok()
+        .content_type("text/event-stream")
+        .insert_header(("Cache-Control", "no-cache"))
+        .streaming(stream))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn query(kinds: Option<&str>, labels: Option<&str>) -> Result<WatchEvents, ReplyError> {
+        WatchEvents::try_from(EventsQuery {
+            kinds: kinds.map(ToString::to_string),
+            labels: labels.map(ToString::to_string),
+        })
+    }
+
+    #[test]
+    fn events_query() {
+        let events = query(None, None).unwrap();
+        assert!(events.kinds.is_empty());
+        assert!(events.labels.is_empty());
+
+        let events = query(Some("Volume,Pool"), None).unwrap();
+        assert_eq!(events.kinds, vec![ResourceKind::Volume, ResourceKind::Pool]);
+        // empty entries are ignored
+        let events = query(Some("Volume,,"), Some(",")).unwrap();
+        assert_eq!(events.kinds, vec![ResourceKind::Volume]);
+        assert!(events.labels.is_empty());
+        query(Some("Volume,NotAKind"), None).expect_err("Unknown resource kind");
+
+        let events = query(None, Some("app=db,tier=gold")).unwrap();
+        assert_eq!(events.labels.len(), 2);
+        assert_eq!(events.labels.get("app"), Some(&"db".to_string()));
+        assert_eq!(events.labels.get("tier"), Some(&"gold".to_string()));
+        // only the first '=' separates the key from the value
+        let events = query(None, Some("expr=a=b,empty=")).unwrap();
+        assert_eq!(events.labels.get("expr"), Some(&"a=b".to_string()));
+        assert_eq!(events.labels.get("empty"), Some(&"".to_string()));
+        query(None, Some("app=db,tier")).expect_err("Labels must be key=value pairs");
+    }
+}
@@ -0,0 +1,18 @@
+use super::*;
+use common_lib::types::v0::{openapi::apis::Uuid, transport::GetHaHistory};
+use grpc::operations::registry::traits::RegistryOperations;
+
+fn client() -> impl RegistryOperations {
+    core_grpc().registry()
+}
+
+#[async_trait::async_trait]
+impl apis::actix_server::HaHistory for RestApi {
+    async fn get_ha_history(
+        Query((volume_id, node_id)): Query<(Option<Uuid>, Option<String>)>,
+    ) -> Result<models::HaHistory, RestError<RestJsonError>> {
+        let request = GetHaHistory::new(volume_id.map(Into::into), node_id.map(Into::into));
+        let history = client().get_ha_history(&request, None).await?;
+        Ok(history.into())
+    }
+}
@@ -4,14 +4,20 @@
 
 pub mod block_devices;
 pub mod children;
+pub mod events;
+pub mod ha_history;
 pub mod jsongrpc;
 pub mod nexuses;
 pub mod nodes;
 pub mod pools;
+pub mod rebuild_history;
+pub mod rebuild_queue;
 pub mod replicas;
+pub mod snapshots;
 pub mod specs;
 pub mod states;
 pub mod swagger_ui;
+pub mod switchovers;
 pub mod volumes;
 pub mod watches;
 
@@ -30,7 +36,10 @@ pub use common_lib::{
     IntoVec,
 };
 use futures::future::Ready;
-use grpc::{client::CoreClient, operations::jsongrpc::client::JsonGrpcClient};
+use grpc::{
+    client::CoreClient,
+    operations::{ha_node::client::ClusterAgentClient, jsongrpc::client::JsonGrpcClient},
+};
 use once_cell::sync::OnceCell;
 use rest_client::versions::v0::*;
 use serde::Deserialize;
@@ -40,6 +49,9 @@ use transport_api::{ReplyError, ReplyErrorKind, ResourceKind};
 pub static CORE_CLIENT: OnceCell<CoreClient> = OnceCell::new();
 /// Once cell static variable to store the json grpc client and initialise once at startup
 pub static JSON_GRPC_CLIENT: OnceCell<JsonGrpcClient> = OnceCell::new();
+/// Once cell static variable to store the HA cluster agent grpc client and initialise once at
+/// startup
+pub static CLUSTER_AGENT_CLIENT: OnceCell<ClusterAgentClient> = OnceCell::new();
 
 /// Get Core gRPC Client
 pub(crate) fn core_grpc<'a>() -> &'a CoreClient {
@@ -61,6 +73,19 @@ pub(crate) fn json_grpc<'a>() -> Result<&'a JsonGrpcClient, ReplyError> {
     }
 }
 
+/// Get HA Cluster Agent gRPC Client
+pub(crate) fn cluster_agent<'a>() -> Result<&'a ClusterAgentClient, ReplyError> {
+    match CLUSTER_AGENT_CLIENT.get() {
+        None => Err(ReplyError {
+            kind: ReplyErrorKind::Unavailable,
+            resource: ResourceKind::SwitchOver,
+            source: "HA Cluster Agent Service is not configured/running.".to_string(),
+            extra: "".to_string(),
+        }),
+        Some(client) => Ok(client),
+    }
+}
+
 fn version() -> String {
     "v0".into()
 }
@@ -74,6 +99,8 @@ fn configure(cfg: &mut actix_web::web::ServiceConfig) {
     apis::actix_server::configure::<RestApi, BearerToken>(cfg);
     // todo: remove when the /states is added to the spec
     states::configure(cfg);
+    // todo: remove when the /events is added to the spec
+    events::configure(cfg);
 }
 
 fn json_error(err: impl std::fmt::Display, _req: &actix_web::HttpRequest) -> actix_web::Error {
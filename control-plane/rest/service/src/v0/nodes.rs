@@ -40,8 +40,28 @@ impl apis::actix_server::Nodes for RestApi {
 
     async fn put_node_drain(
         Path((id, label)): Path<(String, String)>,
+        Query(evacuate): Query<Option<bool>>,
     ) -> Result<models::Node, RestError<RestJsonError>> {
-        let node = client().drain(id.into(), label).await?;
+        let node = client()
+            .drain(id.into(), label, evacuate.unwrap_or_default())
+            .await?;
+        Ok(node.into())
+    }
+
+    async fn put_node_label(
+        Path((id, key)): Path<(String, String)>,
+        Query((value, overwrite)): Query<(String, Option<bool>)>,
+    ) -> Result<models::Node, RestError<RestJsonError>> {
+        let node = client()
+            .label(id.into(), key, value, overwrite.unwrap_or_default())
+            .await?;
+        Ok(node.into())
+    }
+
+    async fn delete_node_label(
+        Path((id, key)): Path<(String, String)>,
+    ) -> Result<models::Node, RestError<RestJsonError>> {
+        let node = client().unlabel(id.into(), key).await?;
         Ok(node.into())
     }
 }
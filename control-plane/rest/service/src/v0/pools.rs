@@ -1,5 +1,5 @@
 use super::*;
-use common_lib::types::v0::transport::{DestroyPool, Filter};
+use common_lib::types::v0::transport::{DestroyPool, ExpandPool, Filter, LabelPool, UnlabelPool};
 use grpc::operations::pool::traits::PoolOperations;
 use transport_api::{ReplyError, ReplyErrorKind, ResourceKind};
 
@@ -97,6 +97,44 @@ impl apis::actix_server::Pools for RestApi {
         let pool = client().create(&create, None).await?;
         Ok(pool.into())
     }
+
+    async fn put_node_pool_disks(
+        Path((node_id, pool_id)): Path<(String, String)>,
+        Body(expand_pool_body): Body<models::ExpandPoolBody>,
+    ) -> Result<models::Pool, RestError<RestJsonError>> {
+        let expand = ExpandPool {
+            node: node_id.into(),
+            id: pool_id.into(),
+            disks: expand_pool_body.disks.into_iter().map(From::from).collect(),
+        };
+        let pool = client().expand(&expand, None).await?;
+        Ok(pool.into())
+    }
+
+    async fn put_pool_label(
+        Path((pool_id, key)): Path<(String, String)>,
+        Query((value, overwrite)): Query<(String, Option<bool>)>,
+    ) -> Result<models::Pool, RestError<RestJsonError>> {
+        let label = LabelPool {
+            id: pool_id.into(),
+            key,
+            value,
+            overwrite: overwrite.unwrap_or_default(),
+        };
+        let pool = client().label(&label, None).await?;
+        Ok(pool.into())
+    }
+
+    async fn delete_pool_label(
+        Path((pool_id, key)): Path<(String, String)>,
+    ) -> Result<models::Pool, RestError<RestJsonError>> {
+        let unlabel = UnlabelPool {
+            id: pool_id.into(),
+            key,
+        };
+        let pool = client().unlabel(&unlabel, None).await?;
+        Ok(pool.into())
+    }
 }
 
 /// returns pool from pool option and returns an error on non existence
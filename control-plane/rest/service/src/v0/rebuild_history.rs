@@ -0,0 +1,18 @@
+use super::*;
+use common_lib::types::v0::{openapi::apis::Uuid, transport::GetRebuildHistory};
+use grpc::operations::registry::traits::RegistryOperations;
+
+fn client() -> impl RegistryOperations {
+    core_grpc().registry()
+}
+
+#[async_trait::async_trait]
+impl apis::actix_server::RebuildHistory for RestApi {
+    async fn get_rebuild_history(
+        Query(volume_id): Query<Option<Uuid>>,
+    ) -> Result<Vec<models::RebuildRecord>, RestError<RestJsonError>> {
+        let request = GetRebuildHistory::new(volume_id.map(Into::into));
+        let rebuilds = client().get_rebuild_history(&request, None).await?;
+        Ok(rebuilds.into())
+    }
+}
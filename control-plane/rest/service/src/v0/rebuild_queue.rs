@@ -0,0 +1,17 @@
+use super::*;
+use common_lib::types::v0::transport::GetRebuildQueue;
+use grpc::operations::registry::traits::RegistryOperations;
+
+fn client() -> impl RegistryOperations {
+    core_grpc().registry()
+}
+
+#[async_trait::async_trait]
+impl apis::actix_server::RebuildQueue for RestApi {
+    async fn get_rebuild_queue() -> Result<Vec<models::QueuedRebuild>, RestError<RestJsonError>> {
+        let queue = client()
+            .get_rebuild_queue(&GetRebuildQueue {}, None)
+            .await?;
+        Ok(queue.into())
+    }
+}
@@ -0,0 +1,111 @@
+use super::*;
+use common_lib::types::v0::{
+    openapi::apis::Uuid,
+    transport::{CreateVolumeSnapshot, DestroyVolumeSnapshot, GetVolumeSnapshots, VolumeSnapshot},
+};
+use grpc::operations::volume::traits::VolumeOperations;
+
+fn client() -> impl VolumeOperations {
+    core_grpc().volume()
+}
+
+#[async_trait::async_trait]
+impl apis::actix_server::Snapshots for RestApi {
+    async fn del_snapshot(Path(snapshot_id): Path<Uuid>) -> Result<(), RestError<RestJsonError>> {
+        client()
+            .destroy_snapshot(&DestroyVolumeSnapshot::new(None, snapshot_id.into()), None)
+            .await?;
+        Ok(())
+    }
+
+    async fn del_volume_snapshot(
+        Path((volume_id, snapshot_id)): Path<(Uuid, Uuid)>,
+    ) -> Result<(), RestError<RestJsonError>> {
+        client()
+            .destroy_snapshot(
+                &DestroyVolumeSnapshot::new(Some(volume_id.into()), snapshot_id.into()),
+                None,
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn get_volume_snapshot(
+        Path((volume_id, snapshot_id)): Path<(Uuid, Uuid)>,
+    ) -> Result<models::VolumeSnapshot, RestError<RestJsonError>> {
+        let request = GetVolumeSnapshots::new(Some(volume_id.into()), Some(snapshot_id.into()));
+        let snapshot = snapshot(
+            snapshot_id.to_string(),
+            client()
+                .get_snapshots(&request, false, None)
+                .await?
+                .into_inner()
+                .get(0),
+        )?;
+        Ok(snapshot.into())
+    }
+
+    async fn get_volume_snapshots(
+        Path(volume_id): Path<Uuid>,
+    ) -> Result<models::VolumeSnapshots, RestError<RestJsonError>> {
+        let request = GetVolumeSnapshots::new(Some(volume_id.into()), None);
+        let snapshots = client().get_snapshots(&request, false, None).await?;
+        Ok(models::VolumeSnapshots::new(
+            snapshots.into_inner().into_vec(),
+        ))
+    }
+
+    async fn get_volumes_snapshot(
+        Path(snapshot_id): Path<Uuid>,
+    ) -> Result<models::VolumeSnapshot, RestError<RestJsonError>> {
+        let request = GetVolumeSnapshots::new(None, Some(snapshot_id.into()));
+        let snapshot = snapshot(
+            snapshot_id.to_string(),
+            client()
+                .get_snapshots(&request, false, None)
+                .await?
+                .into_inner()
+                .get(0),
+        )?;
+        Ok(snapshot.into())
+    }
+
+    async fn get_volumes_snapshots(
+        Query((snapshot_id, volume_id)): Query<(Option<Uuid>, Option<Uuid>)>,
+    ) -> Result<models::VolumeSnapshots, RestError<RestJsonError>> {
+        let request =
+            GetVolumeSnapshots::new(volume_id.map(Into::into), snapshot_id.map(Into::into));
+        let snapshots = client().get_snapshots(&request, true, None).await?;
+        Ok(models::VolumeSnapshots::new(
+            snapshots.into_inner().into_vec(),
+        ))
+    }
+
+    async fn put_volume_snapshot(
+        Path((volume_id, snapshot_id)): Path<(Uuid, Uuid)>,
+    ) -> Result<models::VolumeSnapshot, RestError<RestJsonError>> {
+        let snapshot = client()
+            .create_snapshot(
+                &CreateVolumeSnapshot::new(&volume_id.into(), snapshot_id.into()),
+                None,
+            )
+            .await?;
+        Ok(snapshot.into())
+    }
+}
+
+/// returns snapshot from snapshot option and returns an error on non existence
+fn snapshot(
+    snapshot_id: String,
+    snapshot: Option<&VolumeSnapshot>,
+) -> Result<VolumeSnapshot, ReplyError> {
+    match snapshot {
+        Some(snapshot) => Ok(snapshot.clone()),
+        None => Err(ReplyError {
+            kind: ReplyErrorKind::NotFound,
+            resource: ResourceKind::VolumeSnapshot,
+            source: "Requested snapshot was not found".to_string(),
+            extra: format!("Snapshot id : {}", snapshot_id),
+        }),
+    }
+}
@@ -0,0 +1,34 @@
+use super::*;
+use common_lib::types::v0::{
+    openapi::apis::Uuid,
+    transport::{CancelSwitchOver, GetSwitchOvers, RetrySwitchOver},
+};
+use grpc::operations::ha_node::traits::ClusterAgentOperations;
+
+#[async_trait::async_trait]
+impl apis::actix_server::Switchovers for RestApi {
+    async fn del_switchover(Path(volume_id): Path<Uuid>) -> Result<(), RestError<RestJsonError>> {
+        cluster_agent()?
+            .cancel_switchover(&CancelSwitchOver::new(volume_id.into()), None)
+            .await?;
+        Ok(())
+    }
+
+    async fn get_switchovers(
+        Query(volume_id): Query<Option<Uuid>>,
+    ) -> Result<Vec<models::SwitchOver>, RestError<RestJsonError>> {
+        let switchovers = cluster_agent()?
+            .get_switchovers(&GetSwitchOvers::new(volume_id.map(Into::into)), None)
+            .await?;
+        Ok(switchovers.into_inner().into_vec())
+    }
+
+    async fn put_switchover_retry(
+        Path(volume_id): Path<Uuid>,
+    ) -> Result<(), RestError<RestJsonError>> {
+        cluster_agent()?
+            .retry_switchover(&RetrySwitchOver::new(volume_id.into()), None)
+            .await?;
+        Ok(())
+    }
+}
@@ -2,11 +2,15 @@ use super::*;
 use common_lib::types::v0::{
     openapi::apis::Uuid,
     transport::{
-        DestroyShutdownTargets, DestroyVolume, Filter, PublishVolume, RepublishVolume,
-        SetVolumeReplica, ShareVolume, UnpublishVolume, UnshareVolume, Volume,
+        DestroyShutdownTargets, DestroyVolume, Filter, MoveVolumeTarget, PublishVolume,
+        RepublishVolume, ResizeVolume, SetVolumeQos, SetVolumeReplica, ShareVolume,
+        UnpublishVolume, UnshareVolume, Volume,
     },
 };
-use grpc::operations::{volume::traits::VolumeOperations, MaxEntries, Pagination, StartingToken};
+use grpc::operations::{
+    ha_node::traits::ClusterAgentOperations, volume::traits::VolumeOperations, MaxEntries,
+    Pagination, StartingToken,
+};
 
 fn client() -> impl VolumeOperations {
     core_grpc().volume()
@@ -48,11 +52,12 @@ impl apis::actix_server::Volumes for RestApi {
 
     async fn del_volume_target(
         Path(volume_id): Path<Uuid>,
-        Query(force): Query<Option<bool>>,
+        Query((force, frontend_node)): Query<(Option<bool>, Option<String>)>,
     ) -> Result<models::Volume, RestError<RestJsonError>> {
         let volume = client()
             .unpublish(
-                &UnpublishVolume::new(&volume_id.into(), force.unwrap_or(false)),
+                &UnpublishVolume::new(&volume_id.into(), force.unwrap_or(false))
+                    .with_frontend_node(frontend_node.map(Into::into)),
                 None,
             )
             .await?;
@@ -107,6 +112,19 @@ impl apis::actix_server::Volumes for RestApi {
         })
     }
 
+    async fn post_volume_target_move(
+        Path(volume_id): Path<Uuid>,
+        Query(target_node): Query<Option<String>>,
+    ) -> Result<models::Volume, RestError<RestJsonError>> {
+        let volume = cluster_agent()?
+            .move_volume_target(
+                &MoveVolumeTarget::new(volume_id.into(), target_node.map(Into::into)),
+                None,
+            )
+            .await?;
+        Ok(volume.into())
+    }
+
     async fn put_volume(
         Path(volume_id): Path<Uuid>,
         Body(create_volume_body): Body<models::CreateVolumeBody>,
@@ -151,6 +169,25 @@ impl apis::actix_server::Volumes for RestApi {
         Ok(share_uri)
     }
 
+    async fn put_volume_size(
+        Path((volume_id, size)): Path<(Uuid, u64)>,
+    ) -> Result<models::Volume, RestError<RestJsonError>> {
+        let volume = client()
+            .resize(&ResizeVolume::new(volume_id.into(), size), None)
+            .await?;
+        Ok(volume.into())
+    }
+
+    async fn put_volume_qos(
+        Path(volume_id): Path<Uuid>,
+        Body(qos): Body<models::VolumeQos>,
+    ) -> Result<models::Volume, RestError<RestJsonError>> {
+        let volume = client()
+            .set_qos(&SetVolumeQos::new(volume_id.into(), qos.into()), None)
+            .await?;
+        Ok(volume.into())
+    }
+
     async fn put_volume_target(
         Path(volume_id): Path<Uuid>,
         Body(publish_volume_body): Body<models::PublishVolumeBody>,
@@ -168,6 +205,7 @@ impl apis::actix_server::Volumes for RestApi {
                                 .frontend_node
                                 .unwrap_or_default()
                                 .into(),
+                            defer_shutdown: false,
                         },
                         None,
                     )
@@ -182,6 +220,10 @@ impl apis::actix_server::Volumes for RestApi {
                             share: Some(publish_volume_body.protocol.into()),
                             publish_context: publish_volume_body.publish_context,
                             frontend_nodes: publish_volume_body.frontend_node.into_iter().collect(),
+                            access_mode: publish_volume_body
+                                .access_mode
+                                .map(Into::into)
+                                .unwrap_or_default(),
                         },
                         None,
                     )
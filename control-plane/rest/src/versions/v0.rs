@@ -14,8 +14,8 @@ pub use common_lib::{
             GetBlockDevices, JsonGrpcRequest, Nexus, NexusId, NexusShareProtocol, Node, NodeId,
             Pool, PoolDeviceUri, PoolId, Protocol, RemoveNexusChild, Replica, ReplicaId,
             ReplicaShareProtocol, ShareNexus, ShareReplica, Specs, Topology, UnshareNexus,
-            UnshareReplica, VolumeId, VolumeLabels, VolumePolicy, Watch, WatchCallback,
-            WatchResourceId,
+            UnshareReplica, VolumeContentSource, VolumeEncryption, VolumeId, VolumeLabels,
+            VolumePolicy, Watch, WatchCallback, WatchResourceId,
         },
     },
 };
@@ -128,6 +128,7 @@ impl CreateReplicaBody {
             managed: false,
             owners: Default::default(),
             allowed_hosts: self.allowed_hosts,
+            encryption: None,
         }
     }
 }
@@ -170,6 +171,7 @@ impl CreateNexusBody {
             managed: false,
             owner: None,
             config: None,
+            qos: None,
         }
     }
 }
@@ -189,6 +191,10 @@ pub struct CreateVolumeBody {
     pub labels: Option<VolumeLabels>,
     /// Flag indicating whether the volume should be thin provisioned
     pub thin: bool,
+    /// Source from which the volume content should be populated
+    pub content_source: Option<VolumeContentSource>,
+    /// Encryption at rest of the volume data
+    pub encryption: Option<VolumeEncryption>,
 }
 impl From<models::CreateVolumeBody> for CreateVolumeBody {
     fn from(src: models::CreateVolumeBody) -> Self {
@@ -199,6 +205,8 @@ impl From<models::CreateVolumeBody> for CreateVolumeBody {
             topology: src.topology.into_opt(),
             labels: src.labels,
             thin: src.thin,
+            content_source: src.content_source.into_opt(),
+            encryption: src.encryption.into_opt(),
         }
     }
 }
@@ -211,6 +219,8 @@ impl From<CreateVolume> for CreateVolumeBody {
             topology: create.topology,
             labels: create.labels,
             thin: create.thin,
+            content_source: create.content_source,
+            encryption: create.encryption,
         }
     }
 }
@@ -225,6 +235,8 @@ impl CreateVolumeBody {
             topology: self.topology.clone(),
             labels: self.labels.clone(),
             thin: self.thin,
+            content_source: self.content_source.clone(),
+            encryption: self.encryption.clone(),
         }
     }
 }
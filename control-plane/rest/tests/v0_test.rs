@@ -98,6 +98,7 @@ async fn client_test(cluster: &Cluster, auth: &bool) {
             ),
             cordondrainstate: None,
             node_nqn: Some(HostNqn::from_nodename(&io_engine1.to_string()).to_string()),
+            labels: None,
         }),
         state: Some(models::NodeState {
             id: io_engine1.to_string(),
@@ -297,6 +298,7 @@ async fn client_test(cluster: &Cluster, auth: &bool) {
                 models::VolumeShareProtocol::Nvmf,
                 None,
                 cluster.csi_node(0),
+                None,
             ),
         )
         .await
@@ -327,7 +329,7 @@ async fn client_test(cluster: &Cluster, auth: &bool) {
 
     let volume = client
         .volumes_api()
-        .del_volume_target(&volume_state.uuid, None)
+        .del_volume_target(&volume_state.uuid, None, None)
         .await
         .unwrap();
     tracing::info!("Volume: {:#?}", volume);
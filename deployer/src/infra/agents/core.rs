@@ -6,6 +6,7 @@ use common_lib::types::v0::transport::{Filter, NodeStatus};
 use composer::{Binary, ContainerSpec};
 use grpc::operations::node::traits::NodeOperations;
 use std::str::FromStr;
+use utils::DEFAULT_CLUSTER_AGENT_CLIENT_ADDR;
 
 #[async_trait]
 impl ComponentAction for CoreAgent {
@@ -64,6 +65,18 @@ impl ComponentAction for CoreAgent {
         if let Some(max_rebuilds) = &options.max_rebuilds {
             binary = binary.with_args(vec!["--max-rebuilds", &max_rebuilds.to_string()]);
         }
+        if let Some(period) = &options.faulted_child_wait_period {
+            binary = binary.with_args(vec!["--faulted-child-wait-period", &period.to_string()]);
+        }
+        if let Some(imbalance) = &options.pool_imbalance {
+            binary = binary.with_args(vec!["--pool-imbalance", &imbalance.to_string()]);
+        }
+        if cfg.container_exists("agent-ha-cluster") {
+            binary = binary.with_args(vec![
+                "--ha-cluster-agent",
+                DEFAULT_CLUSTER_AGENT_CLIENT_ADDR,
+            ]);
+        }
         Ok(cfg.add_container_spec(
             ContainerSpec::from_binary(name, binary).with_portmap("50051", "50051"),
         ))
@@ -3,6 +3,7 @@ use crate::infra::{
 };
 use composer::{Binary, ContainerSpec};
 use std::convert::TryFrom;
+use utils::DEFAULT_GRPC_CLIENT_ADDR;
 
 use tokio::time::{sleep, Duration};
 use tonic::transport::Endpoint;
@@ -32,6 +33,10 @@ impl ComponentAction for HaNodeAgent {
             spec = spec.with_args(vec!["--jaeger", &jaeger_config])
         };
 
+        if cfg.container_exists("core") {
+            spec = spec.with_args(vec!["--core-grpc", DEFAULT_GRPC_CLIENT_ADDR]);
+        }
+
         Ok(cfg.add_container_spec(spec))
     }
 
@@ -3,7 +3,7 @@ use crate::infra::{
 };
 use composer::{Binary, ContainerSpec};
 use std::time::Duration;
-use utils::DEFAULT_JSON_GRPC_CLIENT_ADDR;
+use utils::{DEFAULT_CLUSTER_AGENT_CLIENT_ADDR, DEFAULT_JSON_GRPC_CLIENT_ADDR};
 
 #[async_trait]
 impl ComponentAction for Rest {
@@ -52,6 +52,13 @@ impl ComponentAction for Rest {
                 binary = binary.with_args(vec!["--json-grpc", DEFAULT_JSON_GRPC_CLIENT_ADDR]);
             }
 
+            if cfg.container_exists("agent-ha-cluster") {
+                binary = binary.with_args(vec![
+                    "--ha-cluster-agent",
+                    DEFAULT_CLUSTER_AGENT_CLIENT_ADDR,
+                ]);
+            }
+
             if let Some(size) = &options.otel_max_batch_size {
                 binary = binary.with_env("OTEL_BSP_MAX_EXPORT_BATCH_SIZE", size);
             }
@@ -283,6 +283,16 @@ pub struct StartOptions {
     #[structopt(long)]
     max_rebuilds: Option<u32>,
 
+    /// Minimum pool usage imbalance, in percentage points, for the core agent to move the volume
+    /// replicas across the pools.
+    #[structopt(long)]
+    pool_imbalance: Option<u8>,
+
+    /// Override the core agent's period for which a faulted replica whose node is offline is
+    /// kept for a partial rebuild.
+    #[structopt(long)]
+    pub faulted_child_wait_period: Option<humantime::Duration>,
+
     /// Deploy a fio-spdk container.
     /// This can be used for userspace io against a volume's nvmf target using the spdk ioengine.
     #[structopt(long)]
@@ -455,6 +465,17 @@ impl StartOptions {
         self.max_rebuilds = max;
         self
     }
+    /// Set the pool usage imbalance which triggers the replica rebalancing.
+    #[must_use]
+    pub fn with_pool_imbalance(mut self, imbalance: Option<u8>) -> Self {
+        self.pool_imbalance = imbalance;
+        self
+    }
+    #[must_use]
+    pub fn with_faulted_child_wait_period(mut self, period: Duration) -> Self {
+        self.faulted_child_wait_period = Some(period.into());
+        self
+    }
     /// Enable/Disable the fio-spdk container.
     #[must_use]
     pub fn with_fio_spdk(mut self, fio_spdk: bool) -> Self {
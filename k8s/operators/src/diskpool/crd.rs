@@ -2,6 +2,7 @@ use kube::CustomResource;
 use openapi::models::Pool;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 #[derive(
     CustomResource, Serialize, Deserialize, Default, Debug, Eq, PartialEq, Clone, JsonSchema,
@@ -28,8 +29,14 @@ printcolumn = r#"{ "name":"available", "type":"integer", "format": "int64", "min
 pub struct DiskPoolSpec {
     /// The node the pool is placed on
     node: String,
-    /// The disk device the pool is located on
+    /// The disk devices the pool is located on
+    /// Disks may be added to an existing pool, but not removed.
     disks: Vec<String>,
+    /// Labels to be set on the pool, eg: for the labelled pool topology of the volumes.
+    /// The pool labels are kept in sync with these, and so labels which are removed from here
+    /// are also removed from the pool.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    labels: HashMap<String, String>,
 }
 
 impl DiskPoolSpec {
@@ -37,10 +44,14 @@ impl DiskPoolSpec {
     pub fn node(&self) -> String {
         self.node.clone()
     }
-    /// The disk device the pool is located on
+    /// The disk devices the pool is located on
     pub fn disks(&self) -> Vec<String> {
         self.disks.clone()
     }
+    /// Labels to be set on the pool
+    pub fn labels(&self) -> HashMap<String, String> {
+        self.labels.clone()
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq, JsonSchema)]
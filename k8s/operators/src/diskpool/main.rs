@@ -2,8 +2,11 @@
 //! There is a maximum retry limit that will put the pool into a steady error state.
 //!
 //! Successfully created pools are recreated by the control plane.
+//!
+//! Optionally, selected labels of the k8s nodes are also copied into the control plane node labels.
 
 mod crd;
+mod node_labels;
 
 use chrono::Utc;
 use clap::{App, Arg, ArgMatches};
@@ -18,19 +21,31 @@ use kube::{
     },
     Client, CustomResourceExt, Resource, ResourceExt,
 };
+use node_labels::NodeLabelSync;
 use openapi::{
     clients::{self, tower::Url},
-    models::{CreatePoolBody, Pool, RestJsonError},
+    models::{BlockDevice, CreatePoolBody, ExpandPoolBody, Pool, RestJsonError},
 };
 use opentelemetry::global;
 
 use serde_json::json;
 use snafu::Snafu;
-use std::{collections::HashMap, ops::Deref, sync::Arc, time::Duration};
+use std::{
+    collections::{BTreeSet, HashMap},
+    ops::Deref,
+    sync::Arc,
+    time::Duration,
+};
 use tracing::{debug, error, info, trace, warn};
 
 const WHO_AM_I: &str = "DiskPool Operator";
 const WHO_AM_I_SHORT: &str = "dsp-operator";
+/// The pool metadata takes up some of the disk capacity, so the disks are only considered to have
+/// grown when their size exceeds the pool capacity by more than this margin.
+const DISK_GROWTH_MARGIN: u64 = 128 * 1024 * 1024;
+/// Annotation with the comma separated keys of the pool labels which were applied by the
+/// operator from the CRD, so that the labels set by other means are never removed.
+const APPLIED_LABELS_ANNOTATION: &str = "openebs.io/applied-pool-labels";
 
 /// Errors generated during the reconciliation loop
 #[derive(Debug, Snafu)]
@@ -295,25 +310,27 @@ impl ResourceContext {
                 .await
             {
                 Ok(response) => {
-                    if !response.into_body().into_iter().any(|b| {
-                        b.devname == normalize_disk(&self.spec.disks()[0])
-                            || b.devlinks
-                                .iter()
-                                .any(|d| *d == normalize_disk(&self.spec.disks()[0]))
-                    }) {
+                    let devices = response.into_body();
+                    let missing = self
+                        .spec
+                        .disks()
+                        .into_iter()
+                        .filter(|disk| find_block_device(&devices, disk).is_none())
+                        .collect::<Vec<_>>();
+                    if !missing.is_empty() {
                         self.k8s_notify(
                             "Create or import",
                             "Missing",
                             &format!(
                                 "The block device(s): {} can not be found",
-                                &self.spec.disks()[0]
+                                missing.join(", ")
                             ),
                             "Warn",
                         )
                         .await;
 
                         return Err(Error::SpecError {
-                            value: self.spec.disks()[0].clone(),
+                            value: missing.join(", "),
                             timeout: u32::pow(2, self.num_retries),
                         });
                     }
@@ -345,7 +362,7 @@ impl ResourceContext {
                 },
             }
         }
-        let mut labels: HashMap<String, String> = HashMap::new();
+        let mut labels = self.spec.labels();
         labels.insert(
             String::from(utils::CREATED_BY_KEY),
             String::from(utils::DSP_OPERATOR),
@@ -500,10 +517,232 @@ impl ResourceContext {
                 return self.mark_unknown().await;
             }
         }.into_body();
+        let pool = self.expand_pool(pool).await;
+        let pool = self.sync_pool_labels(pool).await;
         // As pool exists, set the status based on the presence of pool state.
         self.set_status_or_unknown(pool).await
     }
 
+    /// Expand the pool if the resource spec contains disks which are not yet claimed by the pool,
+    /// or if the disks which it claims have grown beyond its capacity.
+    /// On failure the pool is left as it is and the expansion is attempted again on the next
+    /// check, unless the control-plane doesn't support growing pools at all.
+    /// Without the `pool-grow` feature the pools are never expanded.
+    #[tracing::instrument(fields(name = ?self.name_any(), status = ?self.status) skip(self, pool))]
+    async fn expand_pool(&self, pool: Pool) -> Pool {
+        if cfg!(not(feature = "pool-grow")) {
+            return pool;
+        }
+        let claimed = match &pool.spec {
+            Some(spec) => spec.disks.clone(),
+            None => return pool,
+        };
+        let new_disks = self
+            .spec
+            .disks()
+            .into_iter()
+            .filter(|disk| !claimed.contains(disk))
+            .collect::<Vec<_>>();
+        let message = if !new_disks.is_empty() {
+            format!("The pool claimed the disk(s): {}", new_disks.join(", "))
+        } else if self.disks_grown(&pool).await {
+            "The pool grew into the extra capacity of its disk(s)".to_string()
+        } else {
+            return pool;
+        };
+
+        let body = ExpandPoolBody::new(self.spec.disks());
+        match self
+            .pools_api()
+            .put_node_pool_disks(&self.spec.node(), &self.name_any(), body)
+            .await
+        {
+            Ok(response) => {
+                self.k8s_notify("Expand pool", "Expanded", &message, "Normal")
+                    .await;
+                response.into_body()
+            }
+            Err(clients::tower::Error::Response(response))
+                if response.status() == clients::tower::StatusCode::NOT_IMPLEMENTED =>
+            {
+                // there's no point in retrying, nor in raising a warning on every check
+                debug!(pool = ?self.name_any(), "the control-plane does not support growing pools");
+                pool
+            }
+            Err(error) => {
+                warn!(pool = ?self.name_any(), ?error, "failed to expand the pool");
+                self.k8s_notify(
+                    "Expand pool",
+                    "Expand",
+                    &format!("Failed to expand the pool: {:?}", error),
+                    "Warning",
+                )
+                .await;
+                pool
+            }
+        }
+    }
+
+    /// Check if the disks of the pool have grown beyond the pool capacity, by comparing the
+    /// capacity with the size of the block devices on the node.
+    /// If the block devices cannot be listed then the disks are not considered to have grown.
+    async fn disks_grown(&self, pool: &Pool) -> bool {
+        let capacity = match &pool.state {
+            Some(state) => state.capacity,
+            None => return false,
+        };
+        let devices = match self
+            .block_devices_api()
+            .get_node_block_devices(&self.spec.node(), Some(true))
+            .await
+        {
+            Ok(response) => response.into_body(),
+            Err(error) => {
+                debug!(pool = ?self.name_any(), ?error, "failed to list the block devices");
+                return false;
+            }
+        };
+        // the block device size is in 512 byte blocks
+        let size = self
+            .spec
+            .disks()
+            .iter()
+            .filter_map(|disk| find_block_device(&devices, disk))
+            .map(|device| device.size as u64 * 512)
+            .sum::<u64>();
+        size > capacity + DISK_GROWTH_MARGIN
+    }
+
+    /// Set the CRD labels on the pool and remove the pool labels which were applied from the CRD
+    /// but are no longer in it. The keys of the applied labels are recorded in the
+    /// `APPLIED_LABELS_ANNOTATION`, and so the labels set by other means are left alone.
+    /// This is best-effort, any failures are retried on the next check.
+    async fn sync_pool_labels(&self, pool: Pool) -> Pool {
+        let current = match &pool.spec {
+            Some(spec) => spec.labels.clone().unwrap_or_default(),
+            None => return pool,
+        };
+        let labels = self.spec.labels();
+        let recorded = self.applied_labels();
+        // the labels in the CRD are applied by the operator, including the ones it created the
+        // pool with
+        let mut applied = recorded
+            .iter()
+            .filter(|key| current.contains_key(*key))
+            .cloned()
+            .chain(labels.keys().cloned())
+            .collect::<BTreeSet<_>>();
+        let stale = applied
+            .iter()
+            .filter(|key| !labels.contains_key(*key))
+            .cloned()
+            .collect::<Vec<_>>();
+        let missing = labels
+            .into_iter()
+            .filter(|(key, value)| current.get(key) != Some(value))
+            .collect::<Vec<_>>();
+
+        let mut synced = pool;
+        let mut result = Ok(());
+        for (key, value) in &missing {
+            match self
+                .pools_api()
+                .put_pool_label(&self.name_any(), key, value, Some(true))
+                .await
+            {
+                Ok(response) => synced = response.into_body(),
+                Err(error) => {
+                    applied.remove(key);
+                    result = Err(error);
+                    break;
+                }
+            }
+        }
+        if result.is_ok() {
+            for key in &stale {
+                match self
+                    .pools_api()
+                    .delete_pool_label(&self.name_any(), key)
+                    .await
+                {
+                    Ok(response) => {
+                        synced = response.into_body();
+                        applied.remove(key);
+                    }
+                    Err(error) => {
+                        result = Err(error);
+                        break;
+                    }
+                }
+            }
+        }
+
+        if applied != recorded {
+            if let Err(error) = self.patch_applied_labels(&applied).await {
+                warn!(pool = ?self.name_any(), ?error, "failed to record the applied pool labels");
+            }
+        }
+        match result {
+            Err(error) => self.label_sync_failed(synced, error).await,
+            Ok(()) if missing.is_empty() && stale.is_empty() => synced,
+            Ok(()) => {
+                self.k8s_notify(
+                    "Label pool",
+                    "Labelled",
+                    "The pool labels were updated",
+                    "Normal",
+                )
+                .await;
+                synced
+            }
+        }
+    }
+
+    /// Get the keys of the pool labels which were applied by the operator from the CRD.
+    fn applied_labels(&self) -> BTreeSet<String> {
+        self.annotations()
+            .get(APPLIED_LABELS_ANNOTATION)
+            .map(|keys| {
+                keys.split(',')
+                    .filter(|key| !key.is_empty())
+                    .map(ToString::to_string)
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Record the keys of the pool labels which were applied by the operator from the CRD.
+    async fn patch_applied_labels(&self, keys: &BTreeSet<String>) -> Result<DiskPool, Error> {
+        let keys = keys.iter().cloned().collect::<Vec<_>>().join(",");
+        let annotations = json!({
+            "metadata": { "annotations": { APPLIED_LABELS_ANNOTATION: keys } }
+        });
+
+        let ps = PatchParams::apply(WHO_AM_I);
+
+        self.api()
+            .patch(&self.name_any(), &ps, &Patch::Merge(&annotations))
+            .await
+            .map_err(|source| Error::Kube { source })
+    }
+
+    /// Report the failure to update the pool labels, returning the pool as last seen.
+    async fn label_sync_failed(
+        &self,
+        pool: Pool,
+        error: clients::tower::Error<RestJsonError>,
+    ) -> Pool {
+        warn!(pool = ?self.name_any(), ?error, "failed to update the pool labels");
+        self.k8s_notify(
+            "Label pool",
+            "Label",
+            &format!("Failed to update the pool labels: {:?}", error),
+            "Warning",
+        )
+        .await;
+        pool
+    }
+
     /// If the pool, has a state we set that status to the CR and if it does not have a state
     /// we set the status as unknown so that we can try again later.
     async fn set_status_or_unknown(&self, pool: Pool) -> Result<Action, Error> {
@@ -766,8 +1005,20 @@ async fn pool_controller(args: ArgMatches<'_>) -> anyhow::Result<()> {
         namespace
     );
 
+    let context = Arc::new(context);
+    let node_labels = args
+        .values_of("node-labels")
+        .into_iter()
+        .flatten()
+        .filter(|label| !label.is_empty())
+        .map(ToString::to_string)
+        .collect::<Vec<_>>();
+    if !node_labels.is_empty() {
+        tokio::spawn(NodeLabelSync::new(context.clone(), node_labels).run());
+    }
+
     Controller::new(dsp, lp)
-        .run(reconcile, error_policy, Arc::new(context))
+        .run(reconcile, error_policy, context)
         .for_each(|res| async move {
             match res {
                 Ok(o) => {
@@ -844,6 +1095,14 @@ async fn main() -> anyhow::Result<()> {
                 .takes_value(false)
                 .help("do not attempt to validate the block device prior to pool creation"),
         )
+        .arg(
+            Arg::with_name("node-labels")
+                .long("node-labels")
+                .env("NODE_LABELS")
+                .takes_value(true)
+                .use_delimiter(true)
+                .help("the keys of the kubernetes node labels to copy into the control plane node labels, eg: topology.kubernetes.io/zone; the copied keys are recorded in an annotation of the kubernetes nodes, which requires the permission to patch them"),
+        )
         .get_matches();
 
     utils::print_package_info!();
@@ -865,6 +1124,14 @@ async fn main() -> anyhow::Result<()> {
 
 /// Normalize the disks if they have a schema, we dont want to change anything
 /// or do any error checking -- the loop will converge to the error state eventually
+/// Find the block device which the given pool disk refers to, either by its name or its links.
+fn find_block_device<'a>(devices: &'a [BlockDevice], disk: &str) -> Option<&'a BlockDevice> {
+    let disk = normalize_disk(disk);
+    devices
+        .iter()
+        .find(|b| b.devname == disk || b.devlinks.iter().any(|d| *d == disk))
+}
+
 fn normalize_disk(disk: &str) -> String {
     Url::parse(disk).map_or(disk.to_string(), |u| {
         u.to_file_path()
@@ -0,0 +1,149 @@
+//! Copies selected labels of the Kubernetes nodes, eg: the zone or the rack, into the labels of
+//! the control-plane nodes, so they can be used by the labelled node topology of the volumes.
+//! The control-plane nodes are matched with the Kubernetes nodes by name.
+
+use crate::{Error, OperatorContext};
+use k8s_openapi::api::core::v1::Node;
+use kube::{
+    api::{Api, ListParams, Patch, PatchParams},
+    ResourceExt,
+};
+use serde_json::json;
+use std::{collections::BTreeSet, sync::Arc, time::Duration};
+use tracing::{debug, error, info};
+
+/// Annotation of the Kubernetes nodes with the comma separated keys of the control-plane node
+/// labels which were copied by the operator, so that the labels set by other means are never
+/// removed.
+const APPLIED_NODE_LABELS_ANNOTATION: &str = "openebs.io/applied-node-labels";
+
+/// Periodically syncs the selected Kubernetes node labels into the control-plane node labels.
+pub(crate) struct NodeLabelSync {
+    /// Reference to the operator context.
+    ctx: Arc<OperatorContext>,
+    /// Keys of the Kubernetes node labels to copy.
+    labels: Vec<String>,
+}
+
+impl NodeLabelSync {
+    /// Return a new `Self` which copies the Kubernetes node labels with the given keys.
+    pub(crate) fn new(ctx: Arc<OperatorContext>, labels: Vec<String>) -> Self {
+        Self { ctx, labels }
+    }
+
+    /// Sync the node labels every operator interval, forever.
+    pub(crate) async fn run(self) {
+        info!(labels = ?self.labels, "Starting the node label sync");
+        loop {
+            if let Err(error) = self.sync().await {
+                error!(%error, "Failed to sync the node labels");
+            }
+            tokio::time::sleep(Duration::from_secs(self.ctx.interval)).await;
+        }
+    }
+
+    /// Set the selected labels of each Kubernetes node on the control-plane node with the same
+    /// name, and remove them from the control-plane node once they're removed from Kubernetes.
+    /// The keys of the applied labels are recorded in the `APPLIED_NODE_LABELS_ANNOTATION` of the
+    /// Kubernetes node, and so the labels set by other means are left alone.
+    async fn sync(&self) -> Result<(), Error> {
+        let k8s_api = Api::<Node>::all(self.ctx.k8s.clone());
+        let k8s_nodes = k8s_api
+            .list(&ListParams::default())
+            .await
+            .map_err(|source| Error::Kube { source })?;
+        let nodes = self.ctx.http.nodes_api().get_nodes().await?.into_body();
+
+        for node in nodes {
+            let k8s_node = match k8s_nodes.iter().find(|n| n.name_any() == node.id) {
+                Some(k8s_node) => k8s_node,
+                None => continue,
+            };
+            let labels = node.spec.and_then(|spec| spec.labels).unwrap_or_default();
+            let recorded = applied_labels(k8s_node);
+            let mut applied = recorded
+                .iter()
+                .filter(|key| labels.contains_key(*key))
+                .cloned()
+                .collect::<BTreeSet<_>>();
+            // the applied labels whose keys are no longer selected are removed as well
+            let unselected = recorded.iter().filter(|key| !self.labels.contains(key));
+
+            for key in self.labels.iter().chain(unselected) {
+                let value = match self.labels.contains(key) {
+                    true => k8s_node.labels().get(key),
+                    false => None,
+                };
+                let result = match (value, labels.get(key)) {
+                    (Some(value), Some(current)) if value == current => {
+                        applied.insert(key.clone());
+                        continue;
+                    }
+                    (Some(value), _) => {
+                        debug!(node = %node.id, %key, %value, "Setting the node label");
+                        self.ctx
+                            .http
+                            .nodes_api()
+                            .put_node_label(&node.id, key, value, Some(true))
+                            .await
+                            .map(|_| applied.insert(key.clone()))
+                    }
+                    (None, Some(_)) if recorded.contains(key) => {
+                        debug!(node = %node.id, %key, "Removing the node label");
+                        self.ctx
+                            .http
+                            .nodes_api()
+                            .delete_node_label(&node.id, key)
+                            .await
+                            .map(|_| applied.remove(key))
+                    }
+                    _ => continue,
+                };
+                if let Err(error) = result {
+                    error!(node = %node.id, %key, %error, "Failed to sync the node label");
+                }
+            }
+
+            if applied != recorded {
+                if let Err(error) = patch_applied_labels(&k8s_api, k8s_node, &applied).await {
+                    error!(node = %node.id, %error, "Failed to record the applied node labels");
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Get the keys of the node labels which were applied by the operator from the Kubernetes node.
+fn applied_labels(k8s_node: &Node) -> BTreeSet<String> {
+    k8s_node
+        .annotations()
+        .get(APPLIED_NODE_LABELS_ANNOTATION)
+        .map(|keys| {
+            keys.split(',')
+                .filter(|key| !key.is_empty())
+                .map(ToString::to_string)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Record the keys of the node labels which were applied by the operator on the Kubernetes node.
+async fn patch_applied_labels(
+    api: &Api<Node>,
+    k8s_node: &Node,
+    keys: &BTreeSet<String>,
+) -> Result<Node, Error> {
+    let keys = keys.iter().cloned().collect::<Vec<_>>().join(",");
+    let annotations = json!({
+        "metadata": { "annotations": { APPLIED_NODE_LABELS_ANNOTATION: keys } }
+    });
+
+    api.patch(
+        &k8s_node.name_any(),
+        &PatchParams::default(),
+        &Patch::Merge(&annotations),
+    )
+    .await
+    .map_err(|source| Error::Kube { source })
+}
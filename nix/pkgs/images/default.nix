@@ -2,7 +2,7 @@
 # avoid dependency on docker tool chain. Though the maturity of OCI
 # builder in nixpkgs is questionable which is why we postpone this step.
 
-{ busybox, dockerTools, lib, xfsprogs, e2fsprogs, utillinux, fetchurl, control-plane, tini, img_tag ? "" }:
+{ busybox, dockerTools, lib, xfsprogs, e2fsprogs, btrfs-progs, utillinux, fetchurl, control-plane, tini, img_tag ? "" }:
 let
   e2fsprogs_1_46_2 = (e2fsprogs.overrideAttrs (oldAttrs: rec {
     version = "1.46.2";
@@ -95,7 +95,7 @@ let
       inherit buildType;
       name = "node";
       config = {
-        Env = [ "PATH=${lib.makeBinPath [ "/" xfsprogs e2fsprogs_1_46_2 utillinux ]}" ];
+        Env = [ "PATH=${lib.makeBinPath [ "/" xfsprogs e2fsprogs_1_46_2 btrfs-progs utillinux ]}" ];
       };
     };
   };
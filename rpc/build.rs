@@ -36,6 +36,7 @@ fn main() {
                 "api/protobuf/v1/nexus.proto",
                 "api/protobuf/v1/pool.proto",
                 "api/protobuf/v1/json.proto",
+                "api/protobuf/v1/snapshot.proto",
             ],
             &["api/protobuf/v1"],
         )
@@ -398,19 +398,25 @@ pub mod v1 {
     pub mod replica {
         pub use super::pb::{
             replica_rpc_client, CreateReplicaRequest, DestroyReplicaRequest, ListReplicaOptions,
-            ListReplicasResponse, Replica, ShareReplicaRequest, UnshareReplicaRequest,
+            ListReplicasResponse, Replica, ResizeReplicaRequest, ShareReplicaRequest,
+            UnshareReplicaRequest,
         };
     }
 
     /// V1 Nexus autogenerated grpc code.
     pub mod nexus {
+        #[cfg(feature = "nexus-qos")]
+        pub use super::pb::SetNexusQosRequest;
+        #[cfg(feature = "rebuild-bandwidth")]
+        pub use super::pb::SetRebuildBandwidthRequest;
         pub use super::pb::{
             nexus_rpc_client, AddChildNexusRequest, AddChildNexusResponse, Child, ChildState,
             ChildStateReason, CreateNexusRequest, CreateNexusResponse, DestroyNexusRequest,
             FaultNexusChildRequest, ListNexusOptions, ListNexusResponse, Nexus,
             NexusNvmePreemption, NexusState, NvmeAnaState, NvmeReservation, PublishNexusRequest,
             PublishNexusResponse, RemoveChildNexusRequest, RemoveChildNexusResponse,
-            ShutdownNexusRequest, UnpublishNexusRequest, UnpublishNexusResponse,
+            ResizeNexusRequest, ResizeNexusResponse, ShutdownNexusRequest, UnpublishNexusRequest,
+            UnpublishNexusResponse,
         };
     }
 
@@ -420,6 +426,19 @@ pub mod v1 {
             pool_rpc_client, CreatePoolRequest, DestroyPoolRequest, ListPoolOptions,
             ListPoolsResponse, Pool, PoolType,
         };
+        #[cfg(feature = "pool-grow")]
+        pub use super::pb::{GrowPoolRequest, GrowPoolResponse};
+    }
+
+    /// V1 Snapshot autogenerated grpc code.
+    pub mod snapshot {
+        pub use super::pb::{
+            snapshot_rpc_client, CreateReplicaSnapshotRequest, CreateReplicaSnapshotResponse,
+            CreateSnapshotCloneRequest, DestroySnapshotRequest, ListSnapshotsRequest,
+            ListSnapshotsResponse, NexusCreateSnapshotReplicaDescriptor,
+            NexusCreateSnapshotReplicaStatus, NexusCreateSnapshotRequest,
+            NexusCreateSnapshotResponse, SnapshotInfo,
+        };
     }
 
     /// V1 JsonRpc autogenerated grpc code.
@@ -0,0 +1,17 @@
+Feature: Planned volume target move
+
+  Background:
+    Given a control plane, 3 Io-Engine instances, a ha node agent and a published volume
+
+  Scenario: moving the target of a healthy volume
+    Given a client connected to the volume target
+    And fio client is running against the volume
+    When the volume target is moved to another node
+    Then the volume target should be on the other node
+    And the older target should be destroyed
+    And fio client should successfully complete with the moved target
+
+  Scenario: moving the target of an unpublished volume
+    Given an unpublished volume
+    When the volume target is moved to another node
+    Then the move should fail with a precondition error
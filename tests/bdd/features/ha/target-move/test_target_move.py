@@ -0,0 +1,173 @@
+"""Planned volume target move feature tests."""
+from pytest_bdd import (
+    given,
+    scenario,
+    then,
+    when,
+)
+import pytest
+import subprocess
+from time import sleep
+
+from common.deployer import Deployer
+from common.apiclient import ApiClient
+from common.fio import Fio
+from common.nvme import (
+    nvme_connect,
+    nvme_disconnect,
+    nvme_list_subsystems,
+)
+
+from openapi.exceptions import ApiException
+from openapi.model.create_pool_body import CreatePoolBody
+from openapi.model.create_volume_body import CreateVolumeBody
+from openapi.model.volume_policy import VolumePolicy
+from openapi.model.protocol import Protocol
+from openapi.model.publish_volume_body import PublishVolumeBody
+
+VOLUME_UUID = "5cd5378e-3f05-47f1-a830-a0f5873a1789"
+VOLUME_SIZE = 10485761
+POOL_UUID = "4cc6ee64-7232-497d-a26f-38284a444789"
+POOL_NODE = "io-engine-3"
+TARGET_NODE_1 = "io-engine-1"
+TARGET_NODE_2 = "io-engine-2"
+FRONTEND_NODE = "app-node-1"
+# FIO should be active long enough to outlive the move.
+FIO_RUNTIME = 20
+
+
+@scenario("target_move.feature", "moving the target of a healthy volume")
+def test_moving_the_target_of_a_healthy_volume():
+    """moving the target of a healthy volume."""
+
+
+@scenario("target_move.feature", "moving the target of an unpublished volume")
+def test_moving_the_target_of_an_unpublished_volume():
+    """moving the target of an unpublished volume."""
+
+
+@given(
+    "a control plane, 3 Io-Engine instances, a ha node agent and a published volume"
+)
+def a_control_plane_3_ioengine_instances_a_ha_node_agent_and_a_published_volume(
+    background,
+):
+    """a control plane, 3 Io-Engine instances, a ha node agent and a published volume."""
+    volume = background
+    assert hasattr(volume.state, "target")
+
+
+@given("a client connected to the volume target")
+def a_client_connected_to_the_volume_target(connect_to_target):
+    """a client connected to the volume target."""
+    pass
+
+
+@given("fio client is running against the volume")
+def fio_client_is_running_against_the_volume(run_fio):
+    """fio client is running against the volume."""
+    pass
+
+
+@given("an unpublished volume")
+def an_unpublished_volume():
+    """an unpublished volume."""
+    ApiClient.volumes_api().del_volume_target(VOLUME_UUID)
+
+
+@when("the volume target is moved to another node", target_fixture="moved")
+def the_volume_target_is_moved_to_another_node():
+    """the volume target is moved to another node."""
+    try:
+        return ApiClient.volumes_api().post_volume_target_move(
+            VOLUME_UUID, target_node=TARGET_NODE_2
+        )
+    except ApiException as e:
+        return e
+
+
+@then("the volume target should be on the other node")
+def the_volume_target_should_be_on_the_other_node(moved):
+    """the volume target should be on the other node."""
+    assert not isinstance(moved, ApiException), "The target move failed: %s" % moved
+    volume = ApiClient.volumes_api().get_volume(VOLUME_UUID)
+    assert volume["state"]["target"]["node"] == TARGET_NODE_2
+    assert volume["spec"]["target"]["node"] == TARGET_NODE_2
+
+
+@then("the older target should be destroyed")
+def the_older_target_should_be_destroyed(background):
+    """the older target should be destroyed."""
+    older_target = background["state"]["target"]["uuid"]
+    for _ in range(10):
+        nexuses = ApiClient.nexuses_api().get_nexuses()
+        if all(nexus["uuid"] != older_target for nexus in nexuses):
+            return
+        sleep(1)
+    pytest.fail("The older target was not destroyed")
+
+
+@then("fio client should successfully complete with the moved target")
+def fio_client_should_successfully_complete_with_the_moved_target(run_fio):
+    """fio client should successfully complete with the moved target."""
+    try:
+        code = run_fio.wait(timeout=FIO_RUNTIME * 2)
+    except subprocess.TimeoutExpired:
+        assert False, "FIO timed out"
+    assert code == 0, "FIO failed, exit code: %d" % code
+
+
+@then("the move should fail with a precondition error")
+def the_move_should_fail_with_a_precondition_error(moved):
+    """the move should fail with a precondition error."""
+    assert isinstance(moved, ApiException), "The target move should have failed"
+    assert ApiClient.exception_to_error(moved).kind == "FailedPrecondition"
+
+
+"""" FixTure Implementations """
+
+
+@pytest.fixture
+def background():
+    Deployer.start(
+        3,
+        node_agent=True,
+        cluster_agent=True,
+        csi_node=True,
+        cache_period="1s",
+    )
+
+    ApiClient.pools_api().put_node_pool(
+        POOL_NODE, POOL_UUID, CreatePoolBody(["malloc:///disk?size_mb=100"])
+    )
+    ApiClient.volumes_api().put_volume(
+        VOLUME_UUID, CreateVolumeBody(VolumePolicy(False), 1, VOLUME_SIZE, False)
+    )
+    volume = ApiClient.volumes_api().put_volume_target(
+        VOLUME_UUID,
+        publish_volume_body=PublishVolumeBody(
+            {}, Protocol("nvmf"), node=TARGET_NODE_1, frontend_node=FRONTEND_NODE
+        ),
+    )
+    yield volume
+    Deployer.stop()
+
+
+@pytest.fixture
+def connect_to_target(background):
+    volume = background
+    device_uri = volume.state["target"]["deviceUri"]
+    yield nvme_connect(device_uri)
+    nvme_disconnect(device_uri)
+
+
+@pytest.fixture
+def run_fio(connect_to_target):
+    device = connect_to_target
+    desc = nvme_list_subsystems(device)
+    assert (
+        len(desc["Subsystems"]) == 1
+    ), "Must be exactly one NVMe subsystem for target nexus"
+    # Launch fio in background and let it run along with the move.
+    fio = Fio("job", "randrw", device, runtime=FIO_RUNTIME).build()
+    return subprocess.Popen(fio, shell=True)
@@ -10,6 +10,10 @@ Feature: Pool creation
 
   Scenario: creating a pool with multiple disks
     When the user attempts to create a pool specifying multiple disks
+    Then the pool should be created successfully
+
+  Scenario: creating a pool without disks
+    When the user attempts to create a pool without specifying any disks
     Then the pool creation should fail with error kind "InvalidArgument"
 
   Scenario: creating a pool with an AIO disk
@@ -38,6 +38,11 @@ def test_creating_a_pool_with_multiple_disks():
     """creating a pool with multiple disks."""
 
 
+@scenario("disks.feature", "creating a pool without disks")
+def test_creating_a_pool_without_disks():
+    """creating a pool without disks."""
+
+
 @given("a control plane and Io-Engine instances")
 def a_control_plane_and_io_engine_instances(background):
     """a control plane and Io-Engine instances."""
@@ -64,6 +69,13 @@ def the_user_attempts_to_create_a_pool_specifying_multiple_disks(
     """the user attempts to create a pool specifying multiple disks."""
 
 
+@when("the user attempts to create a pool without specifying any disks")
+def the_user_attempts_to_create_a_pool_without_specifying_any_disks(
+    attempt_create_pool_no_disks,
+):
+    """the user attempts to create a pool without specifying any disks."""
+
+
 @when("the user attempts to create a pool with a non-existent disk")
 def the_user_attempts_to_create_a_pool_with_a_nonexistent_disk(
     attempt_create_pool_nonexistent_disk,
@@ -164,6 +176,11 @@ def attempt_create_pool_multiple_disk(context, disks, attempt_create_pool):
     context["attempt"] = attempt_create_pool(POOL_NAME, POOL_NODE, disks)
 
 
+@pytest.fixture
+def attempt_create_pool_no_disks(context, attempt_create_pool):
+    context["attempt"] = attempt_create_pool(POOL_NAME, POOL_NODE, [])
+
+
 @pytest.fixture
 def attempt_create_pool():
     def _method(name, node, disks):
@@ -57,6 +57,7 @@ async fn concurrent_rebuilds() {
                     models::VolumeShareProtocol::Nvmf,
                     None,
                     "".to_string(),
+                    None,
                 ),
             )
             .await
@@ -91,6 +91,7 @@ impl ResourceUpdates for Vec<models::Volume> {
                         models::VolumeShareProtocol::Nvmf,
                         None,
                         "".to_string(),
+                        None,
                     ),
                 )
                 .await?;
@@ -98,7 +99,7 @@ impl ResourceUpdates for Vec<models::Volume> {
 
             client
                 .volumes_api()
-                .del_volume_target(&volume.spec.uuid, Some(true))
+                .del_volume_target(&volume.spec.uuid, Some(true), None)
                 .await?;
         }
         Ok(())
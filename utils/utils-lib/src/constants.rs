@@ -113,5 +113,26 @@ pub const NVME_PATH_RETRANSMISSION_PERIOD: &str = "10s";
 /// Period for aggregating multiple failed paths before reporting them.
 pub const NVME_PATH_AGGREGATION_PERIOD: &str = "1s";
 
+/// Period for refreshing the node-agent's registration with the cluster-agent.
+pub const NODE_AGENT_REGISTRATION_PERIOD: &str = "30s";
+
+/// Time for which an NVMe path must remain suspected before it's considered failed.
+pub const NVME_PATH_SUSPECT_GRACE_PERIOD: &str = "0s";
+
+/// Minimum time between two consecutive reports of the same failed NVMe path.
+pub const NVME_PATH_MIN_REPORT_INTERVAL: &str = "0s";
+
+/// Volume label which, when set to "false", stops failed paths of the volume from being reported.
+pub const HA_FAILOVER_LABEL: &str = "openebs.io/ha-failover";
+
+/// Volume label overriding the suspected path grace period of the volume.
+pub const HA_GRACE_PERIOD_LABEL: &str = "openebs.io/ha-grace-period";
+
+/// Volume label overriding the minimum time between reports of a failed path of the volume.
+pub const HA_MIN_REPORT_INTERVAL_LABEL: &str = "openebs.io/ha-min-report-interval";
+
+/// Volume label overriding whether the ANA state of the paths of the volume is considered.
+pub const HA_ANA_AWARE_LABEL: &str = "openebs.io/ha-ana-aware";
+
 /// Period for aggregating multiple failed paths before reporting them.
 pub const DEFAULT_HOST_ACCESS_CONTROL: &str = "nexuses,replicas";